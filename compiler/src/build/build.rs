@@ -1,4 +1,6 @@
 use crate::build::InstructionMetadata;
+use crate::build::local::{Locals, resolve_locals};
+use crate::build::pattern::compile_pattern;
 use crate::error::CompilerError;
 use crate::parse::{Definition, ParseNode};
 use garnish_lang_traits::{GarnishData, GarnishDataFactory, Instruction, TypeConstants};
@@ -76,6 +78,8 @@ struct BuildNode<Data: GarnishData> {
     root_end_instruction: Option<Vec<(Instruction, Option<Data::Size>)>>,
     conditional_parent: Option<usize>,
     conditional_items: Vec<ConditionItem<Data>>,
+    // jump table index of the catch block, set when a catch node emits its start instruction
+    catch_jump_index: Option<Data::Size>,
 }
 
 impl<Data: GarnishData> BuildNode<Data> {
@@ -91,6 +95,7 @@ impl<Data: GarnishData> BuildNode<Data> {
             root_end_instruction: None,
             conditional_parent: None,
             conditional_items: vec![],
+            catch_jump_index: None,
         }
     }
 
@@ -106,6 +111,7 @@ impl<Data: GarnishData> BuildNode<Data> {
             root_end_instruction: None,
             conditional_parent: None,
             conditional_items: vec![],
+            catch_jump_index: None,
         }
     }
 
@@ -121,6 +127,7 @@ impl<Data: GarnishData> BuildNode<Data> {
             root_end_instruction: None,
             conditional_parent: Some(conditional_parent),
             conditional_items: vec![],
+            catch_jump_index: None,
         }
     }
 
@@ -136,6 +143,7 @@ impl<Data: GarnishData> BuildNode<Data> {
             root_end_instruction: None,
             conditional_parent: None,
             conditional_items: vec![],
+            catch_jump_index: None,
         }
     }
 
@@ -151,6 +159,7 @@ impl<Data: GarnishData> BuildNode<Data> {
             root_end_instruction: Some(end_instruction),
             conditional_parent: None,
             conditional_items: vec![],
+            catch_jump_index: None,
         }
     }
 }
@@ -176,6 +185,8 @@ pub fn build<Data: GarnishData>(parse_root: usize, parse_tree: Vec<ParseNode>, d
     nodes[parse_root] = Some(BuildNode::new(parse_root, tree_root_jump.clone()));
 
     let mut instruction_metadata = vec![];
+    // local bindings and the identifiers that refer to them
+    let locals = resolve_locals::<Data>(&parse_tree, parse_root)?;
 
     let mut root_stack = vec![parse_root];
 
@@ -220,6 +231,8 @@ pub fn build<Data: GarnishData>(parse_root: usize, parse_tree: Vec<ParseNode>, d
                 &mut stack,
                 node_index,
                 parse_node,
+                &parse_tree,
+                &locals,
             )?;
 
             match nodes.get_mut(node_index) {
@@ -268,6 +281,8 @@ fn handle_parse_node<Data: GarnishData>(
     mut stack: &mut Vec<usize>,
     node_index: usize,
     parse_node: &ParseNode,
+    parse_tree: &[ParseNode],
+    locals: &Locals<Data::Size>,
 ) -> Result<(), CompilerError<<Data as GarnishData>::Error>> {
     match parse_node.get_definition() {
         Definition::Unit => handle_value_primitive(|data, _| data.add_unit(), &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata)?,
@@ -310,6 +325,16 @@ fn handle_parse_node<Data: GarnishData>(
             &mut instruction_metadata,
         )?,
         Definition::Value => handle_value_like(|_, _| Ok(None), Instruction::PutValue, &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata)?,
+        Definition::Identifier if locals.slot(node_index).is_some() => handle_value_like(
+            |_, _| Ok(locals.slot(node_index).cloned()),
+            Instruction::PutLocal,
+            nodes,
+            node_index,
+            stack,
+            parse_node,
+            data,
+            instruction_metadata,
+        )?,
         Definition::Identifier => handle_value_like(
             |data, node| Ok(Some(data.parse_add_symbol(node.text())?)),
             Instruction::Resolve,
@@ -385,6 +410,13 @@ fn handle_parse_node<Data: GarnishData>(
         Definition::ApplyTo => handle_binary_operation_with_push(Instruction::Apply, &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata, |left, right| {
             (left, right)
         })?,
+        Definition::Map => handle_iteration(Instruction::Map, &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata)?,
+        Definition::Filter => handle_iteration(Instruction::Filter, &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata)?,
+        Definition::Fold => handle_iteration(Instruction::Fold, &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata)?,
+        Definition::Any => handle_iteration(Instruction::Any, &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata)?,
+        Definition::All => handle_iteration(Instruction::All, &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata)?,
+        Definition::Find => handle_iteration(Instruction::Find, &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata)?,
+        Definition::Sort => handle_iteration(Instruction::Sort, &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata)?,
         Definition::CommaList => handle_list(Definition::CommaList, &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata)?,
         Definition::List => handle_list(Definition::List, &mut nodes, node_index, &mut stack, parse_node, data, &mut instruction_metadata)?,
         Definition::Or => handle_logical_binary(Instruction::Or, &mut nodes, node_index, &mut stack, &mut root_stack, parse_node, data, &mut instruction_metadata)?,
@@ -443,6 +475,23 @@ fn handle_parse_node<Data: GarnishData>(
                 data.push_instruction(Instruction::Put, Some(addr))?;
                 instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
 
+                // pair expression with the enclosing locals it refers to
+                if let Some(captures) = locals.captures(node_index) {
+                    let mut count = Data::Size::zero();
+                    for slot in captures {
+                        data.push_instruction(Instruction::PutLocal, Some(slot.clone()))?;
+                        instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+                        count += Data::Size::one();
+                    }
+
+                    if count > Data::Size::zero() {
+                        data.push_instruction(Instruction::MakeList, Some(count))?;
+                        instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+                        data.push_instruction(Instruction::MakeClosure, None)?;
+                        instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+                    }
+                }
+
                 nodes[right] = Some(BuildNode::new_with_jump(right, jump_index.clone(), jump_index));
                 root_stack.push(right);
             }
@@ -467,6 +516,8 @@ fn handle_parse_node<Data: GarnishData>(
             data,
             &mut instruction_metadata,
         )?,
+        Definition::PatternMatch => handle_pattern_match(nodes, node_index, root_stack, parse_tree, data, instruction_metadata, locals)?,
+        Definition::Catch => handle_catch(nodes, node_index, stack, root_stack, parse_node, data, instruction_metadata)?,
         Definition::ElseJump => {
             let node = match nodes.get_mut(node_index) {
                 Some(Some(node)) => node,
@@ -627,6 +678,7 @@ fn handle_parse_node<Data: GarnishData>(
                 }
             }
         }
+        Definition::LocalBinding => handle_local_binding(locals, nodes, node_index, stack, parse_node, data, instruction_metadata)?,
         Definition::Drop => Err(CompilerError::new_message("Cannot build a Drop definition".to_string()))?,
     }
     Ok(())
@@ -736,6 +788,151 @@ fn handle_jump_if<Data: GarnishData>(
     Ok(())
 }
 
+fn handle_catch<Data: GarnishData>(
+    nodes: &mut Vec<Option<BuildNode<Data>>>,
+    node_index: usize,
+    stack: &mut Vec<usize>,
+    root_stack: &mut Vec<usize>,
+    parse_node: &ParseNode,
+    data: &mut Data,
+    instruction_metadata: &mut Vec<InstructionMetadata>,
+) -> Result<(), CompilerError<Data::Error>> {
+    let node = match nodes.get_mut(node_index) {
+        Some(Some(node)) => node,
+        _ => Err(CompilerError::new_message(format!("No build node at index {}", node_index)))?,
+    };
+
+    match node.state {
+        BuildNodeState::Uninitialized => {
+            node.state = BuildNodeState::Initialized;
+
+            // handler is registered before the guarded left side runs
+            let jump_index = data.get_jump_table_len();
+            data.push_to_jump_table(Data::Size::zero())?;
+            data.push_instruction(Instruction::StartTry, Some(jump_index.clone()))?;
+            instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+            node.catch_jump_index = Some(jump_index);
+
+            stack.push(node.parse_node_index);
+            let left = parse_node.get_left().ok_or(CompilerError::new_message("No left on Catch definition".to_string()))?;
+            stack.push(left);
+
+            nodes[left] = Some(BuildNode::new(left, node.containing_expression_jump.clone()));
+        }
+        BuildNodeState::Initialized => {
+            let jump_index = node.catch_jump_index.clone().ok_or(CompilerError::new_message("No catch jump index on Catch definition".to_string()))?;
+
+            data.push_instruction(Instruction::EndTry, None)?;
+            instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+
+            let right = parse_node.get_right().ok_or(CompilerError::new_message("No right on Catch definition".to_string()))?;
+
+            root_stack.push(right);
+
+            let jump_to_index = data.get_jump_table_len();
+            data.push_to_jump_table(data.get_instruction_len())?;
+
+            // catch block runs with the error as its value, then continues after the guarded expression
+            nodes[right] = Some(BuildNode::new_with_jump_and_end(
+                right,
+                node.containing_expression_jump.clone(),
+                jump_index,
+                vec![(Instruction::EndCatch, None), (Instruction::JumpTo, Some(jump_to_index))],
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_pattern_match<Data: GarnishData>(
+    nodes: &mut Vec<Option<BuildNode<Data>>>,
+    node_index: usize,
+    root_stack: &mut Vec<usize>,
+    parse_tree: &[ParseNode],
+    data: &mut Data,
+    instruction_metadata: &mut Vec<InstructionMetadata>,
+    locals: &Locals<Data::Size>,
+) -> Result<(), CompilerError<Data::Error>> {
+    let node = nodes.get_mut_or_error(node_index)?;
+    let (conditional_parent, containing) = (node.conditional_parent, node.containing_expression_jump.clone());
+    let parse_node = &parse_tree[node_index];
+
+    let left = parse_node.get_left().ok_or(CompilerError::new_message("No left on PatternMatch definition".to_string()))?;
+    let right = parse_node.get_right().ok_or(CompilerError::new_message("No right on PatternMatch definition".to_string()))?;
+
+    let pattern = compile_pattern(data, instruction_metadata, parse_tree, left)?;
+    pattern.store_bindings(data, instruction_metadata, locals)?;
+
+    // all checks passed, jump to arm's body
+    let jump_index = data.get_jump_table_len();
+    data.push_to_jump_table(Data::Size::zero())?;
+    data.push_instruction(Instruction::JumpTo, Some(jump_index.clone()))?;
+    instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+
+    pattern.end_checks(data)?;
+
+    match conditional_parent {
+        Some(conditional_parent) => {
+            let parent = nodes.get_mut_or_error(conditional_parent)?;
+            parent.conditional_items.push(ConditionItem {
+                node_index: right,
+                jump_index_to_update: jump_index,
+                root_end_instruction: (Instruction::Invalid, None),
+            });
+        }
+        None => {
+            data.push_instruction(Instruction::PutValue, None)?;
+            instruction_metadata.push(InstructionMetadata::new(None));
+
+            root_stack.push(right);
+
+            let jump_to_index = data.get_jump_table_len();
+            data.push_to_jump_table(data.get_instruction_len())?;
+
+            nodes[right] = Some(BuildNode::new_with_jump_and_end(right, containing, jump_index, vec![(Instruction::JumpTo, Some(jump_to_index))]));
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_local_binding<Data: GarnishData>(
+    locals: &Locals<Data::Size>,
+    nodes: &mut Vec<Option<BuildNode<Data>>>,
+    node_index: usize,
+    stack: &mut Vec<usize>,
+    parse_node: &ParseNode,
+    data: &mut Data,
+    instruction_metadata: &mut Vec<InstructionMetadata>,
+) -> Result<(), CompilerError<Data::Error>> {
+    let node = nodes.get_mut_or_error(node_index)?;
+
+    match node.state {
+        BuildNodeState::Uninitialized => {
+            node.state = BuildNodeState::Initialized;
+
+            let containing = node.containing_expression_jump.clone();
+            let right = parse_node.get_right().ok_or(CompilerError::new_message("No right on LocalBinding definition".to_string()))?;
+
+            stack.push(node_index);
+            stack.push(right);
+            nodes[right] = Some(BuildNode::new(right, containing));
+        }
+        BuildNodeState::Initialized => {
+            let slot = locals.slot(node_index).ok_or(CompilerError::new_message(format!("No local slot for node at index {}", node_index)))?;
+
+            // binding's value is the bound value
+            data.push_instruction(Instruction::UpdateLocal, Some(slot.clone()))?;
+            instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+            data.push_instruction(Instruction::PutLocal, Some(slot.clone()))?;
+            instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+        }
+    }
+
+    Ok(())
+}
+
 fn handle_logical_binary<Data: GarnishData>(
     instruction: Instruction,
     nodes: &mut Vec<Option<BuildNode<Data>>>,
@@ -978,6 +1175,29 @@ where
     Ok(())
 }
 
+// iteration instructions step through the items collected by the StartIteration instruction before them
+fn handle_iteration<Data: GarnishData>(
+    instruction: Instruction,
+    nodes: &mut Vec<Option<BuildNode<Data>>>,
+    node_index: usize,
+    stack: &mut Vec<usize>,
+    parse_node: &ParseNode,
+    data: &mut Data,
+    instruction_metadata: &mut Vec<InstructionMetadata>,
+) -> Result<(), CompilerError<Data::Error>> {
+    let node = match nodes.get(node_index) {
+        Some(Some(node)) => node,
+        _ => Err(CompilerError::new_message(format!("No build node at index {}", node_index)))?,
+    };
+
+    if let BuildNodeState::Initialized = node.state {
+        data.push_instruction(Instruction::StartIteration, None)?;
+        instruction_metadata.push(InstructionMetadata::new(Some(node.parse_node_index)));
+    }
+
+    handle_binary_operation(instruction, nodes, node_index, stack, parse_node, data, instruction_metadata)
+}
+
 fn handle_list<Data: GarnishData>(
     definition: Definition,
     nodes: &mut Vec<Option<BuildNode<Data>>>,
@@ -1293,6 +1513,39 @@ mod binary_operations {
         concatenation: "value1 <> value2", Instruction::Concat,
     }
 
+    macro_rules! iteration_tests {
+        ($($name:ident: $input:expr, $instruction:expr,)*) => {
+            $(
+                #[test]
+                fn $name() {
+                    let (data, _build_data) = build_input($input);
+
+                    assert_eq!(
+                        data.get_instructions(),
+                        &vec![
+                            SimpleInstruction::new(Instruction::Resolve, Some(3)),
+                            SimpleInstruction::new(Instruction::Resolve, Some(4)),
+                            SimpleInstruction::new(Instruction::StartIteration, None),
+                            SimpleInstruction::new($instruction, None),
+                            SimpleInstruction::new(Instruction::EndExpression, None)
+                        ]
+                    );
+                    assert_eq!(data.get_data(), &SimpleDataList::default().append_symbol("value1").append_symbol("value2"));
+                }
+            )*
+        }
+    }
+
+    iteration_tests! {
+        map: "value1 ~* value2", Instruction::Map,
+        filter: "value1 ~? value2", Instruction::Filter,
+        fold: "value1 ~+ value2", Instruction::Fold,
+        any: "value1 ~| value2", Instruction::Any,
+        all: "value1 ~& value2", Instruction::All,
+        find: "value1 ~@ value2", Instruction::Find,
+        sort: "value1 ~< value2", Instruction::Sort,
+    }
+
     #[test]
     fn apply_to() {
         let (data, _build_data) = build_input("5 ~> 10");
@@ -1907,6 +2160,364 @@ mod jumps {
     }
 }
 
+#[cfg(test)]
+mod pattern_match {
+    use crate::build::InstructionMetadata;
+    use crate::build::build::build;
+    use crate::build::build::tests::build_input;
+    use crate::lex::lex;
+    use crate::parse::parse;
+    use garnish_lang_simple_data::{SimpleData, SimpleDataList, SimpleGarnishData, SimpleInstruction};
+    use garnish_lang_traits::{GarnishDataType, Instruction};
+
+    #[test]
+    fn literal_pattern() {
+        let (data, build_data) = build_input("5 => 10");
+
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::PutValue, None),
+                SimpleInstruction::new(Instruction::Put, Some(3)),
+                SimpleInstruction::new(Instruction::Equal, None),
+                SimpleInstruction::new(Instruction::JumpIfFalse, Some(1)),
+                SimpleInstruction::new(Instruction::JumpTo, Some(2)),
+                SimpleInstruction::new(Instruction::PutValue, None),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+                SimpleInstruction::new(Instruction::Put, Some(4)),
+                SimpleInstruction::new(Instruction::JumpTo, Some(3)),
+            ]
+        );
+        assert_eq!(data.get_jump_points(), &vec![0, 5, 7, 6]);
+        assert_eq!(
+            data.get_data(),
+            &SimpleDataList::default().append(SimpleData::Number(5.into())).append(SimpleData::Number(10.into()))
+        );
+        assert_eq!(
+            build_data.instruction_metadata,
+            vec![
+                InstructionMetadata::new(Some(0)),
+                InstructionMetadata::new(Some(0)),
+                InstructionMetadata::new(Some(0)),
+                InstructionMetadata::new(Some(0)),
+                InstructionMetadata::new(Some(1)),
+                InstructionMetadata::new(None),
+                InstructionMetadata::new(None),
+                InstructionMetadata::new(Some(2)),
+                InstructionMetadata::new(None),
+            ]
+        )
+    }
+
+    #[test]
+    fn bound_pattern_with_else() {
+        let (data, build_data) = build_input("a = b => b |> 0");
+
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::PutValue, None),
+                SimpleInstruction::new(Instruction::Put, Some(3)),
+                SimpleInstruction::new(Instruction::TypeEqual, None),
+                SimpleInstruction::new(Instruction::JumpIfFalse, Some(1)),
+                SimpleInstruction::new(Instruction::PutValue, None),
+                SimpleInstruction::new(Instruction::AccessLeftInternal, None),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(0)),
+                SimpleInstruction::new(Instruction::PutValue, None),
+                SimpleInstruction::new(Instruction::AccessRightInternal, None),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(1)),
+                SimpleInstruction::new(Instruction::JumpTo, Some(2)),
+                SimpleInstruction::new(Instruction::Put, Some(4)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+                SimpleInstruction::new(Instruction::PutLocal, Some(1)),
+                SimpleInstruction::new(Instruction::JumpTo, Some(3)),
+            ]
+        );
+        assert_eq!(data.get_jump_points(), &vec![0, 11, 13, 12]);
+        assert_eq!(
+            data.get_data(),
+            &SimpleDataList::default().append(SimpleData::Type(GarnishDataType::Pair)).append(SimpleData::Number(0.into()))
+        );
+        assert_eq!(
+            build_data.instruction_metadata,
+            vec![
+                InstructionMetadata::new(Some(1)),
+                InstructionMetadata::new(Some(1)),
+                InstructionMetadata::new(Some(1)),
+                InstructionMetadata::new(Some(1)),
+                InstructionMetadata::new(Some(0)),
+                InstructionMetadata::new(Some(0)),
+                InstructionMetadata::new(Some(0)),
+                InstructionMetadata::new(Some(2)),
+                InstructionMetadata::new(Some(2)),
+                InstructionMetadata::new(Some(2)),
+                InstructionMetadata::new(Some(3)),
+                InstructionMetadata::new(Some(6)),
+                InstructionMetadata::new(None),
+                InstructionMetadata::new(Some(4)),
+                InstructionMetadata::new(None),
+            ]
+        )
+    }
+
+    #[test]
+    fn name_bound_twice_is_error() {
+        let tokens = lex("a = a => a").unwrap();
+        let parsed = parse(&tokens).unwrap();
+        let mut data = SimpleGarnishData::new();
+
+        let result = build(parsed.get_root(), parsed.get_nodes_owned(), &mut data);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn unsupported_pattern_is_error() {
+        let tokens = lex("a + 1 => a").unwrap();
+        let parsed = parse(&tokens).unwrap();
+        let mut data = SimpleGarnishData::new();
+
+        let result = build(parsed.get_root(), parsed.get_nodes_owned(), &mut data);
+
+        assert!(result.is_err());
+    }
+}
+
+#[cfg(test)]
+mod locals {
+    use crate::build::InstructionMetadata;
+    use crate::build::build::build;
+    use crate::build::build::tests::build_input;
+    use crate::lex::lex;
+    use crate::parse::parse;
+    use garnish_lang_simple_data::{SimpleData, SimpleDataList, SimpleGarnishData, SimpleInstruction};
+    use garnish_lang_traits::Instruction;
+
+    #[test]
+    fn binding_then_reference() {
+        let (data, build_data) = build_input("a := 5; a");
+
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::Put, Some(3)),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(0)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::UpdateValue, None),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+            ]
+        );
+        assert_eq!(data.get_data(), &SimpleDataList::default().append(SimpleData::Number(5.into())));
+        assert_eq!(
+            build_data.instruction_metadata,
+            vec![
+                InstructionMetadata::new(Some(2)),
+                InstructionMetadata::new(Some(1)),
+                InstructionMetadata::new(Some(1)),
+                InstructionMetadata::new(Some(3)),
+                InstructionMetadata::new(Some(4)),
+                InstructionMetadata::new(None),
+            ]
+        )
+    }
+
+    #[test]
+    fn reference_before_binding_resolves() {
+        let (data, _) = build_input("a; a := 5");
+
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::Resolve, Some(3)),
+                SimpleInstruction::new(Instruction::UpdateValue, None),
+                SimpleInstruction::new(Instruction::Put, Some(4)),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(0)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn rebinding_uses_new_slot() {
+        let (data, _) = build_input("a := 5; a := a + 1; a");
+
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::Put, Some(3)),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(0)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::UpdateValue, None),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::Put, Some(4)),
+                SimpleInstruction::new(Instruction::Add, None),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(1)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(1)),
+                SimpleInstruction::new(Instruction::UpdateValue, None),
+                SimpleInstruction::new(Instruction::PutLocal, Some(1)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_expression_has_own_slots() {
+        let (data, _) = build_input("a := 5; { b := 10; b }");
+
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::Put, Some(3)),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(0)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::UpdateValue, None),
+                SimpleInstruction::new(Instruction::Put, Some(4)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+                SimpleInstruction::new(Instruction::Put, Some(5)),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(0)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::UpdateValue, None),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn nested_expression_captures_outer_local() {
+        let (data, build_data) = build_input("a := 5; { b := a; b }");
+
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::Put, Some(3)),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(0)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::UpdateValue, None),
+                SimpleInstruction::new(Instruction::Put, Some(4)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::MakeList, Some(1)),
+                SimpleInstruction::new(Instruction::MakeClosure, None),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(1)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(1)),
+                SimpleInstruction::new(Instruction::UpdateValue, None),
+                SimpleInstruction::new(Instruction::PutLocal, Some(1)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+            ]
+        );
+        assert_eq!(
+            build_data.instruction_metadata[4..8],
+            vec![
+                InstructionMetadata::new(Some(4)),
+                InstructionMetadata::new(Some(4)),
+                InstructionMetadata::new(Some(4)),
+                InstructionMetadata::new(Some(4)),
+            ]
+        );
+    }
+
+    #[test]
+    fn capture_passes_through_expressions() {
+        let (data, _) = build_input("a := 5; { { a } }");
+
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::Put, Some(3)),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(0)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::UpdateValue, None),
+                SimpleInstruction::new(Instruction::Put, Some(4)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::MakeList, Some(1)),
+                SimpleInstruction::new(Instruction::MakeClosure, None),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+                SimpleInstruction::new(Instruction::Put, Some(5)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::MakeList, Some(1)),
+                SimpleInstruction::new(Instruction::MakeClosure, None),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn binding_after_expression_is_not_captured() {
+        let (data, _) = build_input("{ a }; a := 5");
+
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::Put, Some(3)),
+                SimpleInstruction::new(Instruction::UpdateValue, None),
+                SimpleInstruction::new(Instruction::Put, Some(4)),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(0)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+                SimpleInstruction::new(Instruction::Resolve, Some(5)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn pattern_binding_is_captured_by_nested_expression() {
+        let (data, _) = build_input("x => { x }");
+
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::PutValue, None),
+                SimpleInstruction::new(Instruction::UpdateLocal, Some(0)),
+                SimpleInstruction::new(Instruction::JumpTo, Some(1)),
+                SimpleInstruction::new(Instruction::PutValue, None),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+                SimpleInstruction::new(Instruction::Put, Some(3)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::MakeList, Some(1)),
+                SimpleInstruction::new(Instruction::MakeClosure, None),
+                SimpleInstruction::new(Instruction::JumpTo, Some(2)),
+                SimpleInstruction::new(Instruction::PutLocal, Some(0)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+            ]
+        );
+    }
+
+    #[test]
+    fn pattern_binding_is_only_visible_in_arm() {
+        let (data, _) = build_input("(a = b => b |> 0); b");
+
+        assert_eq!(
+            data.get_instructions()[11..],
+            vec![
+                SimpleInstruction::new(Instruction::Put, Some(4)),
+                SimpleInstruction::new(Instruction::UpdateValue, None),
+                SimpleInstruction::new(Instruction::Resolve, Some(5)),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+                SimpleInstruction::new(Instruction::PutLocal, Some(1)),
+                SimpleInstruction::new(Instruction::JumpTo, Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn binding_non_identifier_is_error() {
+        let tokens = lex("5 := 5").unwrap();
+        let parsed = parse(&tokens).unwrap();
+        let mut data = SimpleGarnishData::new();
+
+        let result = build(parsed.get_root(), parsed.get_nodes_owned(), &mut data);
+
+        assert!(result.is_err());
+    }
+}
+
 #[cfg(test)]
 mod logical {
     use crate::build::build::tests::build_input;
@@ -2014,6 +2625,55 @@ mod logical {
     }
 }
 
+#[cfg(test)]
+mod catch {
+    use crate::build::build::tests::build_input;
+    use garnish_lang_simple_data::{SimpleData, SimpleDataList, SimpleInstruction};
+    use garnish_lang_traits::Instruction;
+
+    #[test]
+    fn catch() {
+        let (data, build_data) = build_input("5 !? 10");
+
+        assert_eq!(build_data.jump_index, 0);
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::StartTry, Some(1)),
+                SimpleInstruction::new(Instruction::Put, Some(3)),
+                SimpleInstruction::new(Instruction::EndTry, None),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+                SimpleInstruction::new(Instruction::Put, Some(4)),
+                SimpleInstruction::new(Instruction::EndCatch, None),
+                SimpleInstruction::new(Instruction::JumpTo, Some(2)),
+            ]
+        );
+        assert_eq!(data.get_jump_points(), &vec![0, 4, 3]);
+        assert_eq!(data.get_data(), &SimpleDataList::default().append(SimpleData::Number(5.into())).append(SimpleData::Number(10.into())));
+    }
+
+    #[test]
+    fn catch_guards_operation() {
+        let (data, _) = build_input("5 + 10 !? $");
+
+        assert_eq!(
+            data.get_instructions(),
+            &vec![
+                SimpleInstruction::new(Instruction::StartTry, Some(1)),
+                SimpleInstruction::new(Instruction::Put, Some(3)),
+                SimpleInstruction::new(Instruction::Put, Some(4)),
+                SimpleInstruction::new(Instruction::Add, None),
+                SimpleInstruction::new(Instruction::EndTry, None),
+                SimpleInstruction::new(Instruction::EndExpression, None),
+                SimpleInstruction::new(Instruction::PutValue, None),
+                SimpleInstruction::new(Instruction::EndCatch, None),
+                SimpleInstruction::new(Instruction::JumpTo, Some(2)),
+            ]
+        );
+        assert_eq!(data.get_jump_points(), &vec![0, 6, 5]);
+    }
+}
+
 #[cfg(test)]
 mod reapply {
     use crate::build::build::tests::build_input;
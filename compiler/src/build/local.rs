@@ -0,0 +1,230 @@
+use std::collections::HashMap;
+
+use garnish_lang_traits::{GarnishData, TypeConstants};
+
+use crate::build::pattern::pattern_names;
+use crate::error::CompilerError;
+use crate::parse::{Definition, ParseNode};
+
+/// A `name := value` binding or a name bound by a pattern match arm within an expression.
+struct LocalBinding {
+    index: usize,
+    name: String,
+    /// First and last parse node index of the identifiers that can see the bound name.
+    visible: (usize, usize),
+}
+
+impl LocalBinding {
+    fn is_visible(&self, position: usize) -> bool {
+        self.visible.0 <= position && position <= self.visible.1
+    }
+}
+
+/// Local slots for bindings and the identifiers that refer to them.
+pub(crate) struct Locals<Size> {
+    slots: HashMap<usize, Size>,
+    captures: HashMap<usize, Vec<Size>>,
+}
+
+impl<Size> Locals<Size> {
+    /// Slot a binding stores into or an identifier reads from.
+    pub(crate) fn slot(&self, node_index: usize) -> Option<&Size> {
+        self.slots.get(&node_index)
+    }
+
+    /// Slots of the enclosing expression a nested expression captures, in the order they're restored when applied.
+    pub(crate) fn captures(&self, node_index: usize) -> Option<&Vec<Size>> {
+        self.captures.get(&node_index)
+    }
+}
+
+/// Assigns local slots to each binding and to the identifiers that refer to them.
+///
+/// Slots are numbered from zero per `{ }` expression, since each gets its own frame when applied.
+/// A binding is visible to identifiers that come after it in the same expression, including inside nested expressions,
+/// which capture the bound value into their first slots when created. Names bound by a pattern are only visible in the arm's body.
+/// When a name is bound more than once the latest binding before an identifier is used.
+pub(crate) fn resolve_locals<Data: GarnishData>(
+    parse_tree: &[ParseNode],
+    parse_root: usize,
+) -> Result<Locals<Data::Size>, CompilerError<Data::Error>> {
+    let mut locals = Locals {
+        slots: HashMap::new(),
+        captures: HashMap::new(),
+    };
+
+    resolve_scope::<Data>(parse_tree, parse_root, &[], &mut locals)?;
+
+    Ok(locals)
+}
+
+/// Resolves the locals of one expression, returning the names it captures from enclosing expressions.
+fn resolve_scope<Data: GarnishData>(
+    parse_tree: &[ParseNode],
+    scope: usize,
+    visible: &[String],
+    locals: &mut Locals<Data::Size>,
+) -> Result<Vec<String>, CompilerError<Data::Error>> {
+    let mut bindings: Vec<LocalBinding> = vec![];
+    let mut identifiers = vec![];
+    let mut nested = vec![];
+    let mut stack = vec![scope];
+
+    while let Some(index) = stack.pop() {
+        let node = get_node(parse_tree, index)?;
+
+        match node.get_definition() {
+            Definition::NestedExpression => {
+                if let Some(right) = node.get_right() {
+                    nested.push((index, right));
+                }
+                continue;
+            }
+            Definition::LocalBinding => {
+                let name = match node.get_left().map(|left| get_node(parse_tree, left)).transpose()? {
+                    Some(left) if left.get_definition() == Definition::Identifier => left.text().to_string(),
+                    Some(left) => Err(CompilerError::new_message(format!(
+                        "Syntax Error: {:?} cannot be bound to a local",
+                        left.get_definition()
+                    )))?,
+                    None => Err(CompilerError::new_message("Syntax Error: LocalBinding is missing a name".to_string()))?,
+                };
+
+                bindings.push(LocalBinding {
+                    index,
+                    name,
+                    visible: (subtree_range(parse_tree, index)?.1 + 1, usize::MAX),
+                });
+
+                // the bound name itself isn't a reference
+                stack.extend(node.get_right());
+                continue;
+            }
+            Definition::PatternMatch => {
+                if let Some(body) = node.get_right() {
+                    let visible = subtree_range(parse_tree, body)?;
+                    for (index, name) in node.get_left().map(|pattern| pattern_names(parse_tree, pattern)).unwrap_or_default() {
+                        bindings.push(LocalBinding { index, name, visible });
+                    }
+
+                    // names in the pattern are bound rather than referenced
+                    stack.push(body);
+                }
+                continue;
+            }
+            Definition::Identifier => identifiers.push((index, node.text().to_string())),
+            _ => {}
+        }
+
+        // left first so slots are numbered in source order
+        stack.extend(node.get_right());
+        stack.extend(node.get_left());
+    }
+
+    let latest_binding = |position: usize, name: &str| {
+        bindings
+            .iter()
+            .enumerate()
+            .filter(|(_, binding)| binding.is_visible(position) && binding.name == name)
+            .max_by_key(|(_, binding)| binding.visible.0)
+            .map(|(i, _)| i)
+    };
+
+    // captured names take the first slots, followed by this expression's bindings
+    let mut captures: Vec<String> = vec![];
+    let mut resolved = vec![];
+    let mut nested_captures = vec![];
+
+    for (index, name) in identifiers {
+        match latest_binding(index, &name) {
+            Some(binding) => resolved.push((index, Slot::Binding(binding))),
+            None if visible.contains(&name) => resolved.push((index, Slot::Capture(capture_index(&mut captures, name)))),
+            None => {}
+        }
+    }
+
+    for (index, right) in nested {
+        let mut inner_visible = visible.to_vec();
+        inner_visible.extend(
+            bindings
+                .iter()
+                .filter(|binding| binding.is_visible(index))
+                .map(|binding| binding.name.clone()),
+        );
+
+        let names = resolve_scope::<Data>(parse_tree, right, &inner_visible, locals)?;
+        let slots = names
+            .into_iter()
+            .map(|name| match latest_binding(index, &name) {
+                Some(binding) => Slot::Binding(binding),
+                None => Slot::Capture(capture_index(&mut captures, name)),
+            })
+            .collect::<Vec<_>>();
+
+        nested_captures.push((index, slots));
+    }
+
+    let slot_number = |slot: &Slot| {
+        let position = match slot {
+            Slot::Capture(i) => *i,
+            Slot::Binding(i) => captures.len() + i,
+        };
+
+        let mut size = Data::Size::zero();
+        for _ in 0..position {
+            size += Data::Size::one();
+        }
+        size
+    };
+
+    for (i, binding) in bindings.iter().enumerate() {
+        locals.slots.insert(binding.index, slot_number(&Slot::Binding(i)));
+    }
+
+    for (index, slot) in resolved {
+        locals.slots.insert(index, slot_number(&slot));
+    }
+
+    for (index, slots) in nested_captures {
+        locals.captures.insert(index, slots.iter().map(slot_number).collect());
+    }
+
+    Ok(captures)
+}
+
+enum Slot {
+    Capture(usize),
+    Binding(usize),
+}
+
+fn capture_index(captures: &mut Vec<String>, name: String) -> usize {
+    match captures.iter().position(|captured| *captured == name) {
+        Some(i) => i,
+        None => {
+            captures.push(name);
+            captures.len() - 1
+        }
+    }
+}
+
+fn get_node<Error: std::error::Error + 'static>(parse_tree: &[ParseNode], index: usize) -> Result<&ParseNode, CompilerError<Error>> {
+    parse_tree
+        .get(index)
+        .ok_or(CompilerError::new_message(format!("No parse node at index {}", index)))
+}
+
+/// Smallest and largest parse node index within the node's subtree, nodes are ordered by source position.
+fn subtree_range<Error: std::error::Error + 'static>(parse_tree: &[ParseNode], root: usize) -> Result<(usize, usize), CompilerError<Error>> {
+    let mut range = (root, root);
+    let mut stack = vec![root];
+
+    while let Some(index) = stack.pop() {
+        let node = get_node(parse_tree, index)?;
+        range = (range.0.min(index), range.1.max(index));
+
+        stack.extend(node.get_left());
+        stack.extend(node.get_right());
+    }
+
+    Ok(range)
+}
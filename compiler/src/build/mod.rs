@@ -1,4 +1,6 @@
 mod build;
+mod local;
+mod pattern;
 
 pub use build::*;
 
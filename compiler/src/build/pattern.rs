@@ -0,0 +1,407 @@
+use std::collections::HashSet;
+
+use garnish_lang_traits::{GarnishData, GarnishDataType, Instruction, TypeConstants};
+
+use crate::build::InstructionMetadata;
+use crate::build::local::Locals;
+use crate::error::CompilerError;
+use crate::parse::{Definition, ParseNode};
+
+/// One step from a matched value to one of its parts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum PatternAccess<Size> {
+    Left,
+    Right,
+    /// Access with the key or index stored at the given data address.
+    Key(Size),
+}
+
+/// Steps from the matched value (`$`) to a bound part of it.
+pub(crate) type PatternPath<Size> = Vec<PatternAccess<Size>>;
+
+/// Instructions created for an arm's pattern and the identifiers it bound, by parse node index.
+pub(crate) struct CompiledPattern<Data: GarnishData> {
+    fail_jump: Option<Data::Size>,
+    bindings: Vec<(usize, PatternPath<Data::Size>)>,
+}
+
+impl<Data: GarnishData> CompiledPattern<Data> {
+    /// Points failed checks at the next instruction to be created, where the next arm starts.
+    pub(crate) fn end_checks(&self, data: &mut Data) -> Result<(), CompilerError<Data::Error>> {
+        match &self.fail_jump {
+            None => Ok(()),
+            Some(index) => {
+                let instruction_len = data.get_instruction_len();
+                match data.get_from_jump_table_mut(index.clone()) {
+                    Some(point) => *point = instruction_len,
+                    None => Err(CompilerError::new_message(format!("No jump point at {} when ending pattern", index)))?,
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Stores each bound part of `$` into the local slot of its name, once all checks have passed.
+    pub(crate) fn store_bindings(
+        &self,
+        data: &mut Data,
+        instruction_metadata: &mut Vec<InstructionMetadata>,
+        locals: &Locals<Data::Size>,
+    ) -> Result<(), CompilerError<Data::Error>> {
+        for (node_index, path) in &self.bindings {
+            let slot = locals
+                .slot(*node_index)
+                .ok_or(CompilerError::new_message(format!("No local slot for node at index {}", node_index)))?;
+
+            push_pattern_path(data, instruction_metadata, path, *node_index)?;
+            data.push_instruction(Instruction::UpdateLocal, Some(slot.clone()))?;
+            instruction_metadata.push(InstructionMetadata::new(Some(*node_index)));
+        }
+
+        Ok(())
+    }
+}
+
+/// Creates the checks for a pattern against the current value, each jumping past the arm when it fails.
+///
+/// Supported patterns are literal values, `$` to match anything, identifiers which bind the matched part,
+/// `pattern #= Type` for type checks, `pattern = pattern` for pairs, lists of patterns matching by length and position,
+/// and lists of `:key = pattern` matching associative keys.
+pub(crate) fn compile_pattern<Data: GarnishData>(
+    data: &mut Data,
+    instruction_metadata: &mut Vec<InstructionMetadata>,
+    parse_tree: &[ParseNode],
+    pattern_root: usize,
+) -> Result<CompiledPattern<Data>, CompilerError<Data::Error>> {
+    let mut builder = PatternBuilder {
+        data,
+        instruction_metadata,
+        parse_tree,
+        fail_jump: None,
+        bindings: vec![],
+        names: HashSet::new(),
+    };
+
+    builder.pattern(pattern_root, vec![])?;
+
+    Ok(CompiledPattern {
+        fail_jump: builder.fail_jump,
+        bindings: builder.bindings,
+    })
+}
+
+/// Pushes the instructions that put the part of `$` at the end of `path` into the register.
+pub(crate) fn push_pattern_path<Data: GarnishData>(
+    data: &mut Data,
+    instruction_metadata: &mut Vec<InstructionMetadata>,
+    path: &[PatternAccess<Data::Size>],
+    node_index: usize,
+) -> Result<(), CompilerError<Data::Error>> {
+    data.push_instruction(Instruction::PutValue, None)?;
+    instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+
+    for access in path {
+        match access {
+            PatternAccess::Left => {
+                data.push_instruction(Instruction::AccessLeftInternal, None)?;
+                instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+            }
+            PatternAccess::Right => {
+                data.push_instruction(Instruction::AccessRightInternal, None)?;
+                instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+            }
+            PatternAccess::Key(addr) => {
+                data.push_instruction(Instruction::Put, Some(addr.clone()))?;
+                instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+                data.push_instruction(Instruction::Access, None)?;
+                instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Identifiers a pattern binds and their parse node indices, in source order.
+///
+/// Follows the structure [`compile_pattern`] accepts, anything it would reject binds nothing.
+pub(crate) fn pattern_names(parse_tree: &[ParseNode], pattern_root: usize) -> Vec<(usize, String)> {
+    let mut names = vec![];
+    let mut stack = vec![pattern_root];
+
+    while let Some(index) = stack.pop() {
+        let node = match parse_tree.get(index) {
+            Some(node) => node,
+            None => continue,
+        };
+
+        match node.get_definition() {
+            Definition::Identifier => names.push((index, node.text().to_string())),
+            // right side is a type name
+            Definition::TypeEqual => stack.extend(node.get_left()),
+            Definition::Group => stack.extend(node.get_right()),
+            Definition::Pair | Definition::List | Definition::CommaList => {
+                stack.extend(node.get_right());
+                stack.extend(node.get_left());
+            }
+            _ => {}
+        }
+    }
+
+    names
+}
+
+fn type_from_name(name: &str) -> Option<GarnishDataType> {
+    Some(match name {
+        "Unit" => GarnishDataType::Unit,
+        "Number" => GarnishDataType::Number,
+        "Type" => GarnishDataType::Type,
+        "Char" => GarnishDataType::Char,
+        "CharList" => GarnishDataType::CharList,
+        "Byte" => GarnishDataType::Byte,
+        "ByteList" => GarnishDataType::ByteList,
+        "Symbol" => GarnishDataType::Symbol,
+        "SymbolList" => GarnishDataType::SymbolList,
+        "Pair" => GarnishDataType::Pair,
+        "Range" => GarnishDataType::Range,
+        "Concatenation" => GarnishDataType::Concatenation,
+        "Slice" => GarnishDataType::Slice,
+        "Partial" => GarnishDataType::Partial,
+        "List" => GarnishDataType::List,
+        "Expression" => GarnishDataType::Expression,
+        "External" => GarnishDataType::External,
+        "True" => GarnishDataType::True,
+        "False" => GarnishDataType::False,
+        "Custom" => GarnishDataType::Custom,
+        "Error" => GarnishDataType::Error,
+        _ => return None,
+    })
+}
+
+struct PatternBuilder<'a, Data: GarnishData> {
+    data: &'a mut Data,
+    instruction_metadata: &'a mut Vec<InstructionMetadata>,
+    parse_tree: &'a [ParseNode],
+    fail_jump: Option<Data::Size>,
+    bindings: Vec<(usize, PatternPath<Data::Size>)>,
+    names: HashSet<&'a str>,
+}
+
+impl<'a, Data: GarnishData> PatternBuilder<'a, Data> {
+    fn node(&self, index: usize) -> Result<&'a ParseNode, CompilerError<Data::Error>> {
+        let parse_tree = self.parse_tree;
+        parse_tree
+            .get(index)
+            .ok_or(CompilerError::new_message(format!("No parse node at index {}", index)))
+    }
+
+    fn pattern(&mut self, index: usize, path: PatternPath<Data::Size>) -> Result<(), CompilerError<Data::Error>> {
+        let node = self.node(index)?;
+
+        match node.get_definition() {
+            Definition::Group => match node.get_right() {
+                Some(right) => self.pattern(right, path)?,
+                None => {
+                    let addr = self.data.add_unit()?;
+                    self.check(&path, None, addr, Instruction::Equal, index)?;
+                }
+            },
+            Definition::Value => {}
+            Definition::Identifier => {
+                self.bindings.push((index, path));
+                if !self.names.insert(node.text()) {
+                    Err(
+                        CompilerError::new_message(format!("Name '{}' bound more than once in pattern", node.text()))
+                            .append_token_details(&node.get_lex_token()),
+                    )?;
+                }
+            }
+            Definition::Unit => {
+                let addr = self.data.add_unit()?;
+                self.check(&path, None, addr, Instruction::Equal, index)?;
+            }
+            Definition::True => {
+                let addr = self.data.add_true()?;
+                self.check(&path, None, addr, Instruction::Equal, index)?;
+            }
+            Definition::False => {
+                let addr = self.data.add_false()?;
+                self.check(&path, None, addr, Instruction::Equal, index)?;
+            }
+            Definition::Number => {
+                let addr = self.data.parse_add_number(node.text())?;
+                self.check(&path, None, addr, Instruction::Equal, index)?;
+            }
+            Definition::CharList => {
+                let addr = self.data.parse_add_char_list(node.text())?;
+                self.check(&path, None, addr, Instruction::Equal, index)?;
+            }
+            Definition::ByteList => {
+                let addr = self.data.parse_add_byte_list(node.text())?;
+                self.check(&path, None, addr, Instruction::Equal, index)?;
+            }
+            Definition::Symbol => {
+                let addr = self.data.parse_add_symbol(&node.text()[1..])?;
+                self.check(&path, None, addr, Instruction::Equal, index)?;
+            }
+            Definition::TypeEqual => {
+                let (left, right) = self.children(node)?;
+                let data_type = self.pattern_type(right)?;
+                self.type_check(&path, data_type, index)?;
+                self.pattern(left, path)?;
+            }
+            Definition::Pair => {
+                let (left, right) = self.children(node)?;
+                self.type_check(&path, GarnishDataType::Pair, index)?;
+                self.pattern(left, extend(&path, PatternAccess::Left))?;
+                self.pattern(right, extend(&path, PatternAccess::Right))?;
+            }
+            Definition::List | Definition::CommaList => {
+                let mut items = vec![];
+                self.list_items(index, node.get_definition(), &mut items)?;
+                self.type_check(&path, GarnishDataType::List, index)?;
+
+                let keys = items.iter().map(|item| self.associative_key(*item)).collect::<Result<Vec<_>, _>>()?;
+                if keys.iter().all(Option::is_some) {
+                    for (item, key) in items.iter().zip(keys) {
+                        let (_, value) = self.children(self.node(*item)?)?;
+                        let key = self.data.parse_add_symbol(key.unwrap_or_default())?;
+                        let item_path = extend(&path, PatternAccess::Key(key));
+
+                        let addr = self.data.add_unit()?;
+                        self.check(&item_path, None, addr, Instruction::NotEqual, *item)?;
+                        self.pattern(value, item_path)?;
+                    }
+                } else {
+                    let len = self.data.parse_add_number(&items.len().to_string())?;
+                    self.check(&path, Some(Instruction::AccessLengthInternal), len, Instruction::Equal, index)?;
+
+                    for (i, item) in items.iter().enumerate() {
+                        let position = self.data.parse_add_number(&i.to_string())?;
+                        self.pattern(*item, extend(&path, PatternAccess::Key(position)))?;
+                    }
+                }
+            }
+            d => {
+                Err(CompilerError::new_message(format!("Syntax Error: {:?} cannot be used in a pattern", d))
+                    .append_token_details(&node.get_lex_token()))?
+            }
+        }
+
+        Ok(())
+    }
+
+    fn children(&self, node: &ParseNode) -> Result<(usize, usize), CompilerError<Data::Error>> {
+        match (node.get_left(), node.get_right()) {
+            (Some(left), Some(right)) => Ok((left, right)),
+            _ => Err(
+                CompilerError::new_message(format!("Syntax Error: {:?} in pattern is missing a side", node.get_definition()))
+                    .append_token_details(&node.get_lex_token()),
+            ),
+        }
+    }
+
+    /// Items of a list pattern, nested lists of the same kind are part of the same list.
+    fn list_items(&self, index: usize, definition: Definition, items: &mut Vec<usize>) -> Result<(), CompilerError<Data::Error>> {
+        let node = self.node(index)?;
+        if node.get_definition() != definition {
+            items.push(index);
+            return Ok(());
+        }
+
+        for child in [node.get_left(), node.get_right()].into_iter().flatten() {
+            self.list_items(child, definition, items)?;
+        }
+
+        Ok(())
+    }
+
+    /// Key of a `:key = pattern` list item.
+    fn associative_key(&self, index: usize) -> Result<Option<&'a str>, CompilerError<Data::Error>> {
+        let node = self.node(index)?;
+        if node.get_definition() != Definition::Pair {
+            return Ok(None);
+        }
+
+        let (left, _) = self.children(node)?;
+        let left = self.node(left)?;
+        Ok(match left.get_definition() {
+            Definition::Symbol => Some(&left.text()[1..]),
+            _ => None,
+        })
+    }
+
+    /// Type named by the right side of `#=`, either a type name or a literal value of that type.
+    fn pattern_type(&self, index: usize) -> Result<GarnishDataType, CompilerError<Data::Error>> {
+        let node = self.node(index)?;
+        let data_type = match node.get_definition() {
+            Definition::Identifier => type_from_name(node.text()),
+            Definition::Unit => Some(GarnishDataType::Unit),
+            Definition::True => Some(GarnishDataType::True),
+            Definition::False => Some(GarnishDataType::False),
+            Definition::Number => Some(GarnishDataType::Number),
+            Definition::CharList => Some(GarnishDataType::CharList),
+            Definition::ByteList => Some(GarnishDataType::ByteList),
+            Definition::Symbol => Some(GarnishDataType::Symbol),
+            _ => None,
+        };
+
+        data_type.ok_or(
+            CompilerError::new_message(format!("Syntax Error: Unknown type '{}' in pattern", node.text()))
+                .append_token_details(&node.get_lex_token()),
+        )
+    }
+
+    fn type_check(
+        &mut self,
+        path: &[PatternAccess<Data::Size>],
+        data_type: GarnishDataType,
+        node_index: usize,
+    ) -> Result<(), CompilerError<Data::Error>> {
+        let addr = self.data.add_type(data_type)?;
+        self.check(path, None, addr, Instruction::TypeEqual, node_index)
+    }
+
+    /// Compares the part at `path`, optionally passed through `operation`, with the value at `addr` and jumps to the fail point if false.
+    fn check(
+        &mut self,
+        path: &[PatternAccess<Data::Size>],
+        operation: Option<Instruction>,
+        addr: Data::Size,
+        comparison: Instruction,
+        node_index: usize,
+    ) -> Result<(), CompilerError<Data::Error>> {
+        let fail_jump = match &self.fail_jump {
+            Some(index) => index.clone(),
+            None => {
+                let index = self.data.get_jump_table_len();
+                self.data.push_to_jump_table(Data::Size::zero())?;
+                self.fail_jump = Some(index.clone());
+                index
+            }
+        };
+
+        push_pattern_path(self.data, self.instruction_metadata, path, node_index)?;
+
+        let mut instructions = vec![];
+        instructions.extend(operation.map(|operation| (operation, None)));
+        instructions.extend([
+            (Instruction::Put, Some(addr)),
+            (comparison, None),
+            (Instruction::JumpIfFalse, Some(fail_jump)),
+        ]);
+
+        for (instruction, instruction_data) in instructions {
+            self.data.push_instruction(instruction, instruction_data)?;
+            self.instruction_metadata.push(InstructionMetadata::new(Some(node_index)));
+        }
+
+        Ok(())
+    }
+}
+
+fn extend<Size: Clone>(path: &[PatternAccess<Size>], access: PatternAccess<Size>) -> PatternPath<Size> {
+    let mut path = path.to_vec();
+    path.push(access);
+    path
+}
@@ -47,6 +47,15 @@ impl<Source: 'static + Error> CompilerError<Source> {
         self
     }
 
+    /// Moves an error's position from the start of an embedded input to where that input starts in the enclosing input.
+    pub(crate) fn offset_position(mut self, line: usize, column: usize) -> Self {
+        if self.line == 0 {
+            self.column += column;
+        }
+        self.line += line;
+        self
+    }
+
     pub fn get_message(&self) -> &String {
         &self.message
     }
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::str::Chars;
 
 use log::trace;
@@ -54,11 +54,20 @@ pub enum TokenType {
     JumpIfFalse,
     JumpIfTrue,
     ElseJump,
+    PatternMatch,
+    Catch,
     TypeOf,
     Apply,
     ApplyTo,
     PartialApply,
     Reapply,
+    Map,
+    Filter,
+    Fold,
+    Any,
+    All,
+    Find,
+    Sort,
     EmptyApply,
     TypeCast,
     TypeEqual,
@@ -83,6 +92,7 @@ pub enum TokenType {
     PrefixIdentifier,
     SuffixIdentifier,
     InfixIdentifier,
+    LocalBinding,
 }
 
 #[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
@@ -168,6 +178,9 @@ pub struct Lexer<'a> {
     start_quote_count: usize,
     end_quote_count: usize,
     could_be_sub_expression: bool,
+    char_list_escape: bool,
+    interpolation: InterpolationScan,
+    pending: VecDeque<LexerToken>,
     result: Result<(), CompilerError>,
     characters_lexed: usize,
     at_end: bool,
@@ -210,11 +223,20 @@ impl<'a> Lexer<'a> {
             ("!>", TokenType::JumpIfFalse),
             ("?>", TokenType::JumpIfTrue),
             ("|>", TokenType::ElseJump),
+            ("=>", TokenType::PatternMatch),
+            ("!?", TokenType::Catch),
             ("<~", TokenType::Apply),
             ("~>", TokenType::ApplyTo),
             ("~", TokenType::PartialApply),
             ("^~", TokenType::Reapply),
             ("~~", TokenType::EmptyApply),
+            ("~*", TokenType::Map),
+            ("~?", TokenType::Filter),
+            ("~+", TokenType::Fold),
+            ("~|", TokenType::Any),
+            ("~&", TokenType::All),
+            ("~@", TokenType::Find),
+            ("~<", TokenType::Sort),
             ("#", TokenType::TypeOf),
             ("~#", TokenType::TypeCast),
             ("#=", TokenType::TypeEqual),
@@ -269,6 +291,9 @@ impl<'a> Lexer<'a> {
             start_quote_count,
             end_quote_count,
             could_be_sub_expression,
+            char_list_escape: false,
+            interpolation: InterpolationScan::default(),
+            pending: VecDeque::new(),
             result: Ok(()),
             characters_lexed: 0,
             at_end: false,
@@ -515,6 +540,13 @@ impl<'a> Lexer<'a> {
                         TokenType::PrefixIdentifier
                     });
 
+                    true
+                } else if c == '=' && self.current_characters == ":" {
+                    trace!("Switching to local binding");
+                    self.current_characters.push(c);
+                    self.should_create = false;
+                    self.current_token_type = Some(TokenType::LocalBinding);
+
                     true
                 } else {
                     trace!("Ending identifier");
@@ -537,6 +569,7 @@ impl<'a> Lexer<'a> {
                     } else {
                         self.start_quote_count = self.current_characters.len();
                         self.state = LexingState::CharList;
+                        self.char_list_escape = c == '\\';
 
                         false
                     }
@@ -552,8 +585,24 @@ impl<'a> Lexer<'a> {
 
                 end
             }
+            LexingState::CharList if self.interpolation.is_open() => {
+                // inside an embedded expression, only track nesting so quotes and braces of the expression don't end the char list
+                // the expression is lexed separately once the char list ends
+                self.current_characters.push(c);
+                self.end_quote_count = 0;
+                self.interpolation.update(c);
+                false
+            }
             LexingState::CharList => {
-                if c == '"' {
+                let escaped = self.char_list_escape;
+                self.char_list_escape = c == '\\' && !escaped;
+
+                if c == '{' && escaped {
+                    trace!("Starting interpolation");
+                    self.interpolation = InterpolationScan::start();
+                }
+
+                if c == '"' && !escaped {
                     self.end_quote_count += 1;
                     self.current_characters.push(c);
 
@@ -797,6 +846,8 @@ impl<'a> Lexer<'a> {
             self.start_quote_count = 0;
             self.end_quote_count = 0;
             self.could_be_sub_expression = false;
+            self.char_list_escape = false;
+            self.interpolation = InterpolationScan::default();
 
             if self.should_create {
                 trace!("Starting new token");
@@ -868,8 +919,260 @@ impl<'a> Iterator for Lexer<'a> {
     type Item = LexerToken;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.internal_next()
+        if let Some(token) = self.pending.pop_front() {
+            return Some(token);
+        }
+
+        let token = self.internal_next()?;
+        if token.get_token_type() != TokenType::CharList {
+            return Some(token);
+        }
+
+        match expand_interpolations(&token) {
+            Ok(None) => Some(token),
+            Ok(Some(tokens)) => {
+                self.pending.extend(tokens);
+                self.pending.pop_front()
+            }
+            Err(e) => {
+                self.result = Err(e);
+                None
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum InterpolationNesting {
+    Expression(usize),
+    CharList,
+    ByteList,
+}
+
+/// Follows an embedded expression to its closing brace using the lexer's quoting rules.
+///
+/// Braces inside nested char lists and byte lists are ignored, escaped quotes don't end a nested char list
+/// and interpolations inside nested char lists are followed to their own closing brace.
+#[derive(Debug, Clone, Default)]
+struct InterpolationScan {
+    nesting: Vec<InterpolationNesting>,
+    escaped: bool,
+}
+
+impl InterpolationScan {
+    fn start() -> Self {
+        Self {
+            nesting: vec![InterpolationNesting::Expression(1)],
+            escaped: false,
+        }
+    }
+
+    fn is_open(&self) -> bool {
+        !self.nesting.is_empty()
+    }
+
+    fn update(&mut self, c: char) {
+        match self.nesting.last_mut() {
+            None => (),
+            Some(InterpolationNesting::Expression(depth)) => match c {
+                '"' => self.nesting.push(InterpolationNesting::CharList),
+                '\'' => self.nesting.push(InterpolationNesting::ByteList),
+                '{' => *depth += 1,
+                '}' if *depth == 1 => {
+                    self.nesting.pop();
+                }
+                '}' => *depth -= 1,
+                _ => (),
+            },
+            Some(InterpolationNesting::CharList) => {
+                let escaped = self.escaped;
+                self.escaped = c == '\\' && !escaped;
+                match c {
+                    '{' if escaped => self.nesting.push(InterpolationNesting::Expression(1)),
+                    '"' if !escaped => {
+                        self.nesting.pop();
+                    }
+                    _ => (),
+                }
+            }
+            Some(InterpolationNesting::ByteList) if c == '\'' => {
+                self.nesting.pop();
+            }
+            Some(InterpolationNesting::ByteList) => (),
+        }
+    }
+}
+
+enum InterpolationPart {
+    Text(String),
+    Expression(String, usize, usize),
+}
+
+/// Splits a char list token's text on `\{ expression }` interpolations, `None` if it has none.
+fn split_interpolations(token: &LexerToken, quote_count: usize) -> Result<Option<Vec<InterpolationPart>>, CompilerError> {
+    let chars = token.get_text().chars().collect::<Vec<_>>();
+    let inner = &chars[quote_count..chars.len() - quote_count];
+
+    // position of each character in the original input
+    let mut positions = Vec::with_capacity(inner.len() + 1);
+    let (mut row, mut column) = (token.get_line(), token.get_column() + quote_count);
+    for c in inner {
+        positions.push((row, column));
+        if *c == '\n' {
+            row += 1;
+            column = 0;
+        } else {
+            column += 1;
+        }
+    }
+    positions.push((row, column));
+
+    let mut parts = vec![];
+    let mut text = String::new();
+    let mut escaped = false;
+    let mut i = 0;
+
+    while i < inner.len() {
+        let c = inner[i];
+        if c != '{' || !escaped {
+            escaped = c == '\\' && !escaped;
+            text.push(c);
+            i += 1;
+            continue;
+        }
+
+        // drop the backslash already added to the text
+        text.pop();
+        parts.push(InterpolationPart::Text(std::mem::take(&mut text)));
+
+        let (interpolation_row, interpolation_column) = positions[i - 1];
+        let start = i + 1;
+        let mut scan = InterpolationScan::start();
+        loop {
+            i += 1;
+            match inner.get(i) {
+                None => {
+                    return Err(CompilerError::new(
+                        "Unclosed interpolation in char list",
+                        interpolation_row,
+                        interpolation_column,
+                    ));
+                }
+                Some(c) => scan.update(*c),
+            }
+            if !scan.is_open() {
+                break;
+            }
+        }
+
+        let source = inner[start..i].iter().collect::<String>();
+        if source.trim().is_empty() {
+            return Err(CompilerError::new(
+                "Empty interpolation in char list",
+                interpolation_row,
+                interpolation_column,
+            ));
+        }
+
+        let (start_row, start_column) = positions[start];
+        parts.push(InterpolationPart::Expression(source, start_row, start_column));
+        escaped = false;
+        i += 1;
     }
+
+    if parts.is_empty() {
+        return Ok(None);
+    }
+
+    parts.push(InterpolationPart::Text(text));
+    Ok(Some(parts))
+}
+
+/// Replaces an interpolated char list with the tokens of `((text <> ((expression) ~# "") <> text) ~# "")`.
+///
+/// Embedded expressions are lexed with their own [`Lexer`] and keep their position in the original input.
+fn expand_interpolations(token: &LexerToken) -> Result<Option<Vec<LexerToken>>, CompilerError> {
+    let quote_count = token.get_text().chars().take_while(|c| *c == '"').count();
+    if quote_count * 2 >= token.get_text().chars().count() {
+        return Ok(None);
+    }
+
+    let parts = match split_interpolations(token, quote_count)? {
+        None => return Ok(None),
+        Some(parts) => parts,
+    };
+
+    let quotes = "\"".repeat(quote_count);
+    let synthetic = |text: &str, token_type: TokenType, row: usize, column: usize| LexerToken::new(text.to_string(), token_type, row, column);
+    let (row, column) = (token.get_line(), token.get_column());
+
+    let mut tokens = vec![
+        synthetic("(", TokenType::StartGroup, row, column),
+        synthetic("(", TokenType::StartGroup, row, column),
+    ];
+    let mut first = true;
+
+    for part in parts {
+        let part_tokens = match part {
+            InterpolationPart::Text(text) if text.is_empty() => continue,
+            InterpolationPart::Text(text) => {
+                // a leading quote would be read as part of the surrounding quotes, escape it instead
+                let text = match text.strip_prefix('"') {
+                    Some(rest) => format!("\\\"{}", rest),
+                    None => text,
+                };
+                vec![synthetic(&format!("{}{}{}", quotes, text, quotes), TokenType::CharList, row, column)]
+            }
+            InterpolationPart::Expression(source, start_row, start_column) => {
+                let inner = lex(&source).map_err(|e| e.offset_position(start_row, start_column))?;
+                let inner = inner
+                    .into_iter()
+                    .map(|t| {
+                        let inner_column = if t.get_line() == 0 {
+                            t.get_column() + start_column
+                        } else {
+                            t.get_column()
+                        };
+                        LexerToken::new(t.get_text().clone(), t.get_token_type(), t.get_line() + start_row, inner_column)
+                    })
+                    .collect::<Vec<_>>();
+                let start = inner.iter().position(|t| !is_space_token(t)).unwrap_or(inner.len());
+                let end = inner.iter().rposition(|t| !is_space_token(t)).map_or(start, |i| i + 1);
+
+                let mut part_tokens = vec![
+                    synthetic("(", TokenType::StartGroup, start_row, start_column),
+                    synthetic("(", TokenType::StartGroup, start_row, start_column),
+                ];
+                part_tokens.extend_from_slice(&inner[start..end]);
+                part_tokens.extend([
+                    synthetic(")", TokenType::EndGroup, start_row, start_column),
+                    synthetic("~#", TokenType::TypeCast, start_row, start_column),
+                    synthetic("\"\"", TokenType::CharList, start_row, start_column),
+                    synthetic(")", TokenType::EndGroup, start_row, start_column),
+                ]);
+                part_tokens
+            }
+        };
+
+        if !first {
+            tokens.push(synthetic("<>", TokenType::Concatenation, row, column));
+        }
+        first = false;
+        tokens.extend(part_tokens);
+    }
+
+    tokens.extend([
+        synthetic(")", TokenType::EndGroup, row, column),
+        synthetic("~#", TokenType::TypeCast, row, column),
+        synthetic("\"\"", TokenType::CharList, row, column),
+        synthetic(")", TokenType::EndGroup, row, column),
+    ]);
+
+    Ok(Some(tokens))
+}
+
+fn is_space_token(token: &LexerToken) -> bool {
+    token.get_token_type() == TokenType::Whitespace || token.get_token_type() == TokenType::Subexpression
 }
 
 pub fn create_operator_tree(symbol_list: Vec<(&str, TokenType)>) -> LexerOperatorNode {
@@ -1423,6 +1726,61 @@ mod tests {
         )
     }
 
+    #[test]
+    fn pattern_match_symbol() {
+        let result = lex("=>").unwrap();
+
+        assert_eq!(
+            result,
+            vec![LexerToken {
+                text: "=>".to_string(),
+                token_type: TokenType::PatternMatch,
+                column: 0,
+                row: 0
+            }]
+        )
+    }
+
+    #[test]
+    fn iteration_symbols() {
+        for (text, token_type) in [
+            ("~*", TokenType::Map),
+            ("~?", TokenType::Filter),
+            ("~+", TokenType::Fold),
+            ("~|", TokenType::Any),
+            ("~&", TokenType::All),
+            ("~@", TokenType::Find),
+            ("~<", TokenType::Sort),
+        ] {
+            let result = lex(text).unwrap();
+
+            assert_eq!(
+                result,
+                vec![LexerToken {
+                    text: text.to_string(),
+                    token_type,
+                    column: 0,
+                    row: 0
+                }]
+            )
+        }
+    }
+
+    #[test]
+    fn catch_symbol() {
+        let result = lex("!?").unwrap();
+
+        assert_eq!(
+            result,
+            vec![LexerToken {
+                text: "!?".to_string(),
+                token_type: TokenType::Catch,
+                column: 0,
+                row: 0
+            }]
+        )
+    }
+
     #[test]
     fn apply_if_false_symbol() {
         let result = lex(&"!>".to_string()).unwrap();
@@ -2635,6 +2993,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn local_binding() {
+        let result = lex(&"value := 5".to_string()).unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                LexerToken {
+                    text: "value".to_string(),
+                    token_type: TokenType::Identifier,
+                    column: 0,
+                    row: 0
+                },
+                LexerToken {
+                    text: " ".to_string(),
+                    token_type: TokenType::Whitespace,
+                    column: 5,
+                    row: 0
+                },
+                LexerToken {
+                    text: ":=".to_string(),
+                    token_type: TokenType::LocalBinding,
+                    column: 6,
+                    row: 0
+                },
+                LexerToken {
+                    text: " ".to_string(),
+                    token_type: TokenType::Whitespace,
+                    column: 8,
+                    row: 0
+                },
+                LexerToken {
+                    text: "5".to_string(),
+                    token_type: TokenType::Number,
+                    column: 9,
+                    row: 0
+                },
+            ]
+        );
+    }
+
     #[test]
     fn annotation() {
         let result = lex(&"@annotation".to_string()).unwrap();
@@ -2886,6 +3285,21 @@ mod numbers {
         )
     }
 
+    #[test]
+    fn decimal_suffix() {
+        let result = lex("19.99d").unwrap();
+
+        assert_eq!(
+            result,
+            vec![LexerToken {
+                text: "19.99d".to_string(),
+                token_type: TokenType::Number,
+                column: 0,
+                row: 0
+            }]
+        )
+    }
+
     #[test]
     fn float_end_with_period() {
         let result = lex("3.").unwrap();
@@ -2906,6 +3320,7 @@ mod numbers {
 mod chars_and_bytes {
     use std::vec;
 
+    use crate::error::CompilerError;
     use crate::lex::*;
 
     #[test]
@@ -3192,4 +3607,140 @@ mod chars_and_bytes {
             ]
         );
     }
+
+    #[test]
+    fn character_list_interpolation() {
+        let result = lex("\"a\\{1}b\"").unwrap();
+
+        assert_eq!(
+            result.iter().map(|t| (t.get_text().as_str(), t.get_token_type())).collect::<Vec<_>>(),
+            vec![
+                ("(", TokenType::StartGroup),
+                ("(", TokenType::StartGroup),
+                ("\"a\"", TokenType::CharList),
+                ("<>", TokenType::Concatenation),
+                ("(", TokenType::StartGroup),
+                ("(", TokenType::StartGroup),
+                ("1", TokenType::Number),
+                (")", TokenType::EndGroup),
+                ("~#", TokenType::TypeCast),
+                ("\"\"", TokenType::CharList),
+                (")", TokenType::EndGroup),
+                ("<>", TokenType::Concatenation),
+                ("\"b\"", TokenType::CharList),
+                (")", TokenType::EndGroup),
+                ("~#", TokenType::TypeCast),
+                ("\"\"", TokenType::CharList),
+                (")", TokenType::EndGroup),
+            ]
+        );
+        assert_eq!((result[6].get_line(), result[6].get_column()), (0, 4));
+    }
+
+    #[test]
+    fn character_list_interpolation_at_start() {
+        let result = lex("\"\\{1}b\"").unwrap();
+
+        assert_eq!(
+            result.iter().map(|t| t.get_text().as_str()).collect::<Vec<_>>(),
+            vec!["(", "(", "(", "(", "1", ")", "~#", "\"\"", ")", "<>", "\"b\"", ")", "~#", "\"\"", ")"]
+        );
+    }
+
+    #[test]
+    fn character_list_interpolation_with_nested_character_list() {
+        let result = lex("\"a\\{ \"}\" }b\"").unwrap();
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|t| (t.get_text().as_str(), t.get_token_type()))
+                .filter(|(_, t)| *t == TokenType::CharList)
+                .collect::<Vec<_>>(),
+            vec![
+                ("\"a\"", TokenType::CharList),
+                ("\"}\"", TokenType::CharList),
+                ("\"\"", TokenType::CharList),
+                ("\"b\"", TokenType::CharList),
+                ("\"\"", TokenType::CharList),
+            ]
+        );
+    }
+
+    #[test]
+    fn character_list_interpolation_with_byte_list() {
+        let result = lex("\"a\\{ '}' }\"").unwrap();
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|t| (t.get_text().as_str(), t.get_token_type()))
+                .filter(|(_, t)| *t == TokenType::ByteList)
+                .collect::<Vec<_>>(),
+            vec![("'}'", TokenType::ByteList)]
+        );
+    }
+
+    #[test]
+    fn character_list_interpolation_with_escaped_quote() {
+        let result = lex("\"a\\{\"b\\\"c\"}\"").unwrap();
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|t| (t.get_text().as_str(), t.get_token_type()))
+                .filter(|(_, t)| *t == TokenType::CharList)
+                .collect::<Vec<_>>(),
+            vec![
+                ("\"a\"", TokenType::CharList),
+                ("\"b\\\"c\"", TokenType::CharList),
+                ("\"\"", TokenType::CharList),
+                ("\"\"", TokenType::CharList),
+            ]
+        );
+    }
+
+    #[test]
+    fn character_list_escaped_quote() {
+        let result = lex("\"a\\\"b\"").unwrap();
+
+        assert_eq!(
+            result,
+            vec![LexerToken {
+                text: "\"a\\\"b\"".to_string(),
+                token_type: TokenType::CharList,
+                column: 0,
+                row: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn character_list_escaped_interpolation() {
+        let result = lex("\"a\\\\{1}b\"").unwrap();
+
+        assert_eq!(
+            result,
+            vec![LexerToken {
+                text: "\"a\\\\{1}b\"".to_string(),
+                token_type: TokenType::CharList,
+                column: 0,
+                row: 0
+            }]
+        );
+    }
+
+    #[test]
+    fn character_list_empty_interpolation() {
+        let result = lex("\"a\\{ }b\"");
+
+        assert_eq!(result, Err(CompilerError::new("Empty interpolation in char list", 0, 2)));
+    }
+
+    #[test]
+    fn character_list_interpolation_error_position() {
+        let result = lex("\"ab\\{ _ }\"");
+
+        assert_eq!(result, Err(CompilerError::new("No token", 0, 6)));
+    }
 }
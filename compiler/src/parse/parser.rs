@@ -74,14 +74,24 @@ pub enum Definition {
     ApplyTo,
     PartialApply,
     Reapply,
+    Map,
+    Filter,
+    Fold,
+    Any,
+    All,
+    Find,
+    Sort,
     JumpIfTrue,
     JumpIfFalse,
     ElseJump,
+    PatternMatch,
+    Catch,
     True,
     False,
     PrefixApply,
     SuffixApply,
     InfixApply,
+    LocalBinding,
 }
 
 impl Definition {
@@ -104,7 +114,7 @@ impl Definition {
     }
 
     pub fn is_conditional(self) -> bool {
-        self == Definition::JumpIfFalse || self == Definition::JumpIfTrue || self == Definition::ElseJump
+        self == Definition::JumpIfFalse || self == Definition::JumpIfTrue || self == Definition::ElseJump || self == Definition::PatternMatch
     }
 
     pub fn is_optional(self) -> bool {
@@ -204,6 +214,13 @@ fn get_definition(token_type: TokenType) -> (Definition, SecondaryDefinition) {
         TokenType::ApplyTo => (Definition::ApplyTo, SecondaryDefinition::BinaryLeftToRight),
         TokenType::PartialApply => (Definition::PartialApply, SecondaryDefinition::BinaryLeftToRight),
         TokenType::Concatenation => (Definition::Concatenation, SecondaryDefinition::BinaryLeftToRight),
+        TokenType::Map => (Definition::Map, SecondaryDefinition::BinaryLeftToRight),
+        TokenType::Filter => (Definition::Filter, SecondaryDefinition::BinaryLeftToRight),
+        TokenType::Fold => (Definition::Fold, SecondaryDefinition::BinaryLeftToRight),
+        TokenType::Any => (Definition::Any, SecondaryDefinition::BinaryLeftToRight),
+        TokenType::All => (Definition::All, SecondaryDefinition::BinaryLeftToRight),
+        TokenType::Find => (Definition::Find, SecondaryDefinition::BinaryLeftToRight),
+        TokenType::Sort => (Definition::Sort, SecondaryDefinition::BinaryLeftToRight),
         TokenType::LeftInternal => (Definition::AccessLeftInternal, SecondaryDefinition::UnaryPrefix),
         TokenType::RightInternal => (Definition::AccessRightInternal, SecondaryDefinition::UnarySuffix),
         TokenType::LengthInternal => (Definition::AccessLengthInternal, SecondaryDefinition::UnarySuffix),
@@ -217,6 +234,11 @@ fn get_definition(token_type: TokenType) -> (Definition, SecondaryDefinition) {
         TokenType::JumpIfFalse => (Definition::JumpIfFalse, SecondaryDefinition::BinaryLeftToRight),
         TokenType::JumpIfTrue => (Definition::JumpIfTrue, SecondaryDefinition::BinaryLeftToRight),
         TokenType::ElseJump => (Definition::ElseJump, SecondaryDefinition::BinaryLeftToRight),
+        TokenType::PatternMatch => (Definition::PatternMatch, SecondaryDefinition::BinaryLeftToRight),
+        TokenType::Catch => (Definition::Catch, SecondaryDefinition::BinaryLeftToRight),
+
+        // Bindings
+        TokenType::LocalBinding => (Definition::LocalBinding, SecondaryDefinition::BinaryRightToLeft),
     }
 }
 
@@ -448,15 +470,28 @@ fn make_priority_map() -> HashMap<Definition, usize> {
     map.insert(Definition::Apply, 550);
     map.insert(Definition::ApplyTo, 550);
 
+    map.insert(Definition::Map, 560);
+    map.insert(Definition::Filter, 560);
+    map.insert(Definition::Fold, 560);
+    map.insert(Definition::Any, 560);
+    map.insert(Definition::All, 560);
+    map.insert(Definition::Find, 560);
+    map.insert(Definition::Sort, 560);
+
     map.insert(Definition::Reapply, 600);
 
+    map.insert(Definition::Catch, 650);
+
     map.insert(Definition::JumpIfTrue, 700);
     map.insert(Definition::JumpIfFalse, 700);
+    map.insert(Definition::PatternMatch, 700);
 
     map.insert(Definition::ElseJump, 800);
 
     map.insert(Definition::CommaList, 900);
 
+    map.insert(Definition::LocalBinding, 950);
+
     map.insert(Definition::ExpressionSeparator, 990);
     map.insert(Definition::Subexpression, 1000);
 
@@ -2064,7 +2099,7 @@ mod tests {
 
     #[test]
     fn conditional_definitions() {
-        let value_like = [Definition::JumpIfTrue, Definition::JumpIfFalse];
+        let value_like = [Definition::JumpIfTrue, Definition::JumpIfFalse, Definition::PatternMatch];
 
         for def in value_like {
             assert!(def.is_conditional());
@@ -3114,6 +3149,81 @@ mod tests {
         );
     }
 
+    #[test]
+    fn map_binds_after_apply() {
+        let tokens = vec![
+            LexerToken::new("a".to_string(), TokenType::Identifier, 0, 0),
+            LexerToken::new("~>".to_string(), TokenType::ApplyTo, 0, 0),
+            LexerToken::new("b".to_string(), TokenType::Identifier, 0, 0),
+            LexerToken::new("~*".to_string(), TokenType::Map, 0, 0),
+            LexerToken::new("c".to_string(), TokenType::Identifier, 0, 0),
+        ];
+
+        let result = parse(&tokens).unwrap();
+
+        assert_result(
+            &result,
+            3,
+            &[
+                (0, Definition::Identifier, Some(1), None, None),
+                (1, Definition::ApplyTo, Some(3), Some(0), Some(2)),
+                (2, Definition::Identifier, Some(1), None, None),
+                (3, Definition::Map, None, Some(1), Some(4)),
+                (4, Definition::Identifier, Some(3), None, None),
+            ],
+        );
+    }
+
+    #[test]
+    fn catch() {
+        let tokens = vec![
+            LexerToken::new("5".to_string(), TokenType::Number, 0, 0),
+            LexerToken::new("!?".to_string(), TokenType::Catch, 0, 0),
+            LexerToken::new("5".to_string(), TokenType::Number, 0, 0),
+        ];
+
+        let result = parse(&tokens).unwrap();
+
+        assert_result(
+            &result,
+            1,
+            &[
+                (0, Definition::Number, Some(1), None, None),
+                (1, Definition::Catch, None, Some(0), Some(2)),
+                (2, Definition::Number, Some(1), None, None),
+            ],
+        );
+    }
+
+    #[test]
+    fn catch_guards_apply_inside_conditional() {
+        let tokens = vec![
+            LexerToken::new("a".to_string(), TokenType::Identifier, 0, 0),
+            LexerToken::new("?>".to_string(), TokenType::JumpIfTrue, 0, 0),
+            LexerToken::new("b".to_string(), TokenType::Identifier, 0, 0),
+            LexerToken::new("<~".to_string(), TokenType::Apply, 0, 0),
+            LexerToken::new("c".to_string(), TokenType::Identifier, 0, 0),
+            LexerToken::new("!?".to_string(), TokenType::Catch, 0, 0),
+            LexerToken::new("d".to_string(), TokenType::Identifier, 0, 0),
+        ];
+
+        let result = parse(&tokens).unwrap();
+
+        assert_result(
+            &result,
+            1,
+            &[
+                (0, Definition::Identifier, Some(1), None, None),
+                (1, Definition::JumpIfTrue, None, Some(0), Some(5)),
+                (2, Definition::Identifier, Some(3), None, None),
+                (3, Definition::Apply, Some(5), Some(2), Some(4)),
+                (4, Definition::Identifier, Some(3), None, None),
+                (5, Definition::Catch, Some(1), Some(3), Some(6)),
+                (6, Definition::Identifier, Some(5), None, None),
+            ],
+        );
+    }
+
     #[test]
     fn reapply() {
         let tokens = vec![
@@ -5096,6 +5206,93 @@ mod conditionals {
             ],
         );
     }
+
+    #[test]
+    fn pattern_match_chain() {
+        let tokens = vec![
+            LexerToken::new("a".to_string(), TokenType::Identifier, 0, 0),
+            LexerToken::new("=".to_string(), TokenType::Pair, 0, 0),
+            LexerToken::new("b".to_string(), TokenType::Identifier, 0, 0),
+            LexerToken::new("=>".to_string(), TokenType::PatternMatch, 0, 0),
+            LexerToken::new("a".to_string(), TokenType::Identifier, 0, 0),
+            LexerToken::new("|>".to_string(), TokenType::ElseJump, 0, 0),
+            LexerToken::new("5".to_string(), TokenType::Number, 0, 0),
+            LexerToken::new("=>".to_string(), TokenType::PatternMatch, 0, 0),
+            LexerToken::new("10".to_string(), TokenType::Number, 0, 0),
+        ];
+
+        let result = parse(&tokens).unwrap();
+
+        assert_result(
+            &result,
+            5,
+            &[
+                (0, Definition::Identifier, Some(1), None, None),
+                (1, Definition::Pair, Some(3), Some(0), Some(2)),
+                (2, Definition::Identifier, Some(1), None, None),
+                (3, Definition::PatternMatch, Some(5), Some(1), Some(4)),
+                (4, Definition::Identifier, Some(3), None, None),
+                (5, Definition::ElseJump, None, Some(3), Some(7)),
+                (6, Definition::Number, Some(7), None, None),
+                (7, Definition::PatternMatch, Some(5), Some(6), Some(8)),
+                (8, Definition::Number, Some(7), None, None),
+            ],
+        );
+    }
+
+    #[test]
+    fn local_binding() {
+        let tokens = vec![
+            LexerToken::new("a".to_string(), TokenType::Identifier, 0, 0),
+            LexerToken::new(":=".to_string(), TokenType::LocalBinding, 0, 0),
+            LexerToken::new("5".to_string(), TokenType::Number, 0, 0),
+            LexerToken::new("+".to_string(), TokenType::PlusSign, 0, 0),
+            LexerToken::new("5".to_string(), TokenType::Number, 0, 0),
+            LexerToken::new(";".to_string(), TokenType::ExpressionSeparator, 0, 0),
+            LexerToken::new("a".to_string(), TokenType::Identifier, 0, 0),
+        ];
+
+        let result = parse(&tokens).unwrap();
+
+        assert_result(
+            &result,
+            5,
+            &[
+                (0, Definition::Identifier, Some(1), None, None),
+                (1, Definition::LocalBinding, Some(5), Some(0), Some(3)),
+                (2, Definition::Number, Some(3), None, None),
+                (3, Definition::Addition, Some(1), Some(2), Some(4)),
+                (4, Definition::Number, Some(3), None, None),
+                (5, Definition::ExpressionSeparator, None, Some(1), Some(6)),
+                (6, Definition::Identifier, Some(5), None, None),
+            ],
+        );
+    }
+
+    #[test]
+    fn local_binding_of_list() {
+        let tokens = vec![
+            LexerToken::new("a".to_string(), TokenType::Identifier, 0, 0),
+            LexerToken::new(":=".to_string(), TokenType::LocalBinding, 0, 0),
+            LexerToken::new("5".to_string(), TokenType::Number, 0, 0),
+            LexerToken::new(",".to_string(), TokenType::Comma, 0, 0),
+            LexerToken::new("5".to_string(), TokenType::Number, 0, 0),
+        ];
+
+        let result = parse(&tokens).unwrap();
+
+        assert_result(
+            &result,
+            1,
+            &[
+                (0, Definition::Identifier, Some(1), None, None),
+                (1, Definition::LocalBinding, None, Some(0), Some(3)),
+                (2, Definition::Number, Some(3), None, None),
+                (3, Definition::CommaList, Some(1), Some(2), Some(4)),
+                (4, Definition::Number, Some(3), None, None),
+            ],
+        );
+    }
 }
 
 #[cfg(test)]
@@ -1,5 +1,6 @@
 use std::cmp::Ordering;
 use std::fmt::Debug;
+use std::sync::atomic::Ordering as AtomicOrdering;
 use std::usize;
 
 use garnish_lang_traits::GarnishDataType;
@@ -8,10 +9,17 @@ use garnish_lang_traits::Instruction;
 use crate::ConversionDelegate;
 use crate::basic::clone::CloneDelegate;
 use crate::basic::companion::BasicDataCompanion;
+use crate::basic::gc::Collector;
+use crate::basic::handle::PinnedData;
 use crate::basic::ordering::OrderingDelegate;
-use crate::basic::search::{search_for_associative_item, search_for_associative_item_index};
+use crate::basic::packed::PACKED_LIST_THRESHOLD;
+use crate::basic::search::{
+    build_hash_index, hash_index_size, list_allocation_size, search_for_associative_item, search_for_associative_item_index, search_hash_index,
+};
+use crate::basic::snapshot::{SnapshotReader, SnapshotWriter};
 use crate::basic::storage::{StorageBlock, StorageSettings};
-use crate::{BasicData, DataError, SimpleNumber};
+use crate::error::DataErrorType;
+use crate::{BasicData, DataError, OverflowPolicy, SimpleNumber};
 
 pub type BasicNumber = SimpleNumber;
 
@@ -36,6 +44,14 @@ pub trait BasicDataCustom: Clone + Debug + PartialEq + Eq + PartialOrd {
     {
         Ok(value.clone())
     }
+
+    fn write_snapshot(&self, _writer: &mut SnapshotWriter) -> Result<(), DataError> {
+        Err(DataError::new("Custom data does not support snapshots", DataErrorType::CustomDataNotSnapshotable))
+    }
+
+    fn read_snapshot(_reader: &mut SnapshotReader) -> Result<Self, DataError> {
+        Err(DataError::new("Custom data does not support snapshots", DataErrorType::CustomDataNotSnapshotable))
+    }
 }
 
 impl BasicDataCustom for () {
@@ -47,6 +63,14 @@ impl BasicDataCustom for () {
         delegate.push_char(')')?;
         Ok(())
     }
+
+    fn write_snapshot(&self, _writer: &mut SnapshotWriter) -> Result<(), DataError> {
+        Ok(())
+    }
+
+    fn read_snapshot(_reader: &mut SnapshotReader) -> Result<Self, DataError> {
+        Ok(())
+    }
 } 
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd)]
@@ -75,6 +99,16 @@ impl BasicDataCompanion<()> for NoOpCompanion {
 }
 
 
+/// Heads of the value, register, frame and local chains when a handler was pushed, restored when unwinding to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BasicHandler {
+    pub(crate) jump_index: usize,
+    pub(crate) value: Option<usize>,
+    pub(crate) register: Option<usize>,
+    pub(crate) frame: Option<usize>,
+    pub(crate) local: Option<usize>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct BasicGarnishData<T = (), Companion = NoOpCompanion>
 where 
@@ -85,6 +119,11 @@ where
     current_register: Option<usize>,
     instruction_pointer: usize,
     current_frame: Option<usize>,
+    current_local: Option<usize>,
+    handlers: Vec<BasicHandler>,
+    pub(crate) error_values: bool,
+    pub(crate) strict_mode: bool,
+    pub(crate) overflow_policy: OverflowPolicy,
     data_retention_count: usize,
     data: Vec<BasicData<T>>,
     instruction_block: StorageBlock,
@@ -93,6 +132,8 @@ where
     expression_symbol_block: StorageBlock,
     data_block: StorageBlock,
     custom_data_block: StorageBlock,
+    collector: Collector,
+    pinned: PinnedData,
     pub(crate) companion: Companion, 
 }
 
@@ -129,6 +170,11 @@ where
             current_register: None,
             instruction_pointer: 0,
             current_frame: None,
+            current_local: None,
+            handlers: Vec::new(),
+            error_values: false,
+            strict_mode: false,
+            overflow_policy: OverflowPolicy::Error,
             data_retention_count: 0,
             data: Vec::new(),
             instruction_block: StorageBlock::new(instruction_settings.initial_size(), instruction_settings.clone()),
@@ -137,6 +183,8 @@ where
             expression_symbol_block: StorageBlock::new(expression_symbol_settings.initial_size(), expression_symbol_settings.clone()),
             data_block: StorageBlock::new(data_settings.initial_size(), data_settings.clone()),
             custom_data_block: StorageBlock::new(custom_data_settings.initial_size(), custom_data_settings.clone()),
+            collector: Collector::default(),
+            pinned: PinnedData::default(),
             companion,
         };
 
@@ -164,6 +212,25 @@ where
         self.companion = companion;
     }
 
+    /// When set, failed casts and accesses produce [`GarnishDataType::Error`] values instead of Unit.
+    pub fn set_error_values(&mut self, enabled: bool) {
+        self.error_values = enabled;
+    }
+
+    /// When set, operations that would produce Unit from unsupported operands or missing values raise errors instead.
+    pub fn set_strict_mode(&mut self, enabled: bool) {
+        self.strict_mode = enabled;
+    }
+
+    /// How number operations and parsing handle integers that don't fit in 64 bits, [`OverflowPolicy::Error`] by default.
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.overflow_policy = policy;
+    }
+
+    pub fn overflow_policy(&self) -> OverflowPolicy {
+        self.overflow_policy
+    }
+
     pub fn data_size(&self) -> usize {
         self.data_block.cursor
     }
@@ -232,8 +299,56 @@ where
         self.data_retention_count = count;
     }
 
+    /// Removes data that is not reachable from the value stack, registers, frames, symbol table, pinned handles or `additional_data_retentions`.
+    ///
+    /// Returns the new indexes of `additional_data_retentions` in the same order, [`DataHandle`](crate::DataHandle)s are updated in place.
     pub fn optimize(&mut self, additional_data_retentions: &[usize]) -> Result<Vec<usize>, DataError> {
-        self.optimize_data_block_and_retain(additional_data_retentions)
+        let handles = self.pinned_mut().live_handles();
+        let mut retentions = additional_data_retentions.to_vec();
+        retentions.extend(handles.iter().map(|handle| handle.load(AtomicOrdering::Acquire)));
+
+        let mut mapped = self.optimize_data_block_and_retain(&retentions)?;
+        for (handle, index) in handles.iter().zip(mapped.split_off(additional_data_retentions.len())) {
+            handle.store(index, AtomicOrdering::Release);
+        }
+
+        self.reset_collection();
+        self.apply_shrink_policies()?;
+        Ok(mapped)
+    }
+
+    /// Reallocates every block down to its used size, never going below a block's initial size.
+    pub fn compact(&mut self) -> Result<(), DataError> {
+        self.resize_blocks(StorageBlock::fitted_size)
+    }
+
+    /// Shrinks blocks according to their [`ShrinkPolicy`](crate::ShrinkPolicy), called after data is discarded.
+    pub(crate) fn apply_shrink_policies(&mut self) -> Result<(), DataError> {
+        self.resize_blocks(StorageBlock::shrunk_size)
+    }
+
+    fn resize_blocks(&mut self, size: fn(&StorageBlock) -> usize) -> Result<(), DataError> {
+        let sizes = [
+            size(&self.instruction_block),
+            size(&self.jump_table_block),
+            size(&self.symbol_table_block),
+            size(&self.expression_symbol_block),
+            size(&self.data_block),
+            size(&self.custom_data_block),
+        ];
+        let current = [
+            self.instruction_block.size,
+            self.jump_table_block.size,
+            self.symbol_table_block.size,
+            self.expression_symbol_block.size,
+            self.data_block.size,
+            self.custom_data_block.size,
+        ];
+        if sizes == current {
+            return Ok(());
+        }
+
+        self.reallocate_heap(sizes[0], sizes[1], sizes[2], sizes[3], sizes[4], sizes[5])
     }
 
     pub fn clone_data(&mut self, index: usize) -> Result<usize, DataError> {
@@ -256,7 +371,9 @@ where
             Some(data_index) => BasicData::InstructionWithData(instruction, data_index),
             None => BasicData::Instruction(instruction),
         };
-        Ok(Self::push_to_block(&mut self.data, &mut self.instruction_block, instruction_data))
+        let index = Self::push_to_block(&mut self.data, &mut self.instruction_block, instruction_data);
+        self.instruction_push_barrier(index, instruction, data);
+        Ok(index)
     }
 
     pub fn push_to_jump_table_block(&mut self, index: usize) -> Result<usize, DataError> {
@@ -273,7 +390,23 @@ where
         Ok(Self::push_to_block(&mut self.data, &mut self.jump_table_block, BasicData::JumpPoint(index)))
     }
 
+    /// Registers `value`, the index of a char list holding the symbol's name, for `symbol`.
+    ///
+    /// Fails with [`DataErrorType::SymbolHashCollision`] when `symbol` is already registered for a different name.
     pub fn push_to_symbol_table_block(&mut self, symbol: u64, value: usize) -> Result<(), DataError> {
+        let search_slice = &self.data()[self.symbol_table_block().start..self.symbol_table_block().start + self.symbol_table_block().cursor];
+        if let Some(item) = search_for_associative_item(search_slice, symbol)? {
+            let (_, existing) = item.as_associative_item()?;
+            if let (Ok(existing_name), Ok(name)) = (self.char_list_string_at(existing), self.char_list_string_at(value))
+                && existing_name != name
+            {
+                return Err(DataError::new(
+                    &format!("Symbol for \"{}\" is already registered for \"{}\"", name, existing_name),
+                    DataErrorType::SymbolHashCollision(symbol),
+                ));
+            }
+        }
+
         if self.symbol_table_block.cursor >= self.symbol_table_block.size {
             self.reallocate_heap(
                 self.instruction_block.size,
@@ -285,6 +418,7 @@ where
             )?;
         }
         Self::push_to_block(&mut self.data, &mut self.symbol_table_block, BasicData::AssociativeItem(symbol, value));
+        self.symbol_push_barrier(symbol, value);
         let sort_range = &mut self.data[self.symbol_table_block.start..self.symbol_table_block.start + self.symbol_table_block.cursor];
         sort_range.sort_by(|a, b| match (a, b) {
             (BasicData::AssociativeItem(sym1, _), BasicData::AssociativeItem(sym2, _)) => sym1.cmp(sym2),
@@ -330,7 +464,9 @@ where
                 self.custom_data_block.size,
             )?;
         }
-        Ok(Self::push_to_block(&mut self.data, &mut self.data_block, data))
+        let index = Self::push_to_block(&mut self.data, &mut self.data_block, data);
+        self.data_push_barrier(index)?;
+        Ok(index)
     }
 
     pub fn push_to_custom_data_block(&mut self, value: T) -> Result<usize, DataError> {
@@ -356,16 +492,17 @@ where
     }
 
     pub fn get_symbol_string(&self, symbol: u64) -> Result<Option<String>, DataError> {
+        match self.get_symbol_name_index(symbol)? {
+            Some(index) => Ok(Some(self.char_list_string_at(index)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Index of the char list holding the name of `symbol` in the symbol table.
+    pub(crate) fn get_symbol_name_index(&self, symbol: u64) -> Result<Option<usize>, DataError> {
         let search_slice = &self.data()[self.symbol_table_block().start..self.symbol_table_block().start + self.symbol_table_block().cursor];
         match search_for_associative_item(search_slice, symbol)? {
-            Some(item) => {
-                let (_, index) = item.as_associative_item()?;
-                let list_length = self.get_from_data_block_ensure_index(index)?.as_char_list()?;
-                let start = self.data_block().start + index + 1;
-                let slice = &self.data()[start..start + list_length];
-                let result = slice.iter().map(|data| data.as_char().unwrap()).collect::<String>();
-                Ok(Some(result))
-            }
+            Some(item) => Ok(Some(item.as_associative_item()?.1)),
             None => Ok(None),
         }
     }
@@ -391,48 +528,97 @@ where
         Ok(None)
     }
 
-    pub fn get_associative_item_with_symbol(&self, list_index: usize, symbol: u64) -> Result<Option<&BasicData<T>>, DataError> {
+    /// Absolute index of the associative item for `symbol` in the list at `list_index`.
+    ///
+    /// Lists with at least [`HASHED_LIST_THRESHOLD`](crate::basic::HASHED_LIST_THRESHOLD) associations are searched through their hash index, lists with fewer with a binary search.
+    pub(crate) fn find_list_association(&self, list_index: usize, symbol: u64) -> Result<Option<usize>, DataError> {
         let (len, associations_len) = self.get_from_data_block_ensure_index(list_index)?.as_list()?;
 
         let association_start = self.data_block().start + list_index + len + 1;
-        let association_range = association_start..association_start + associations_len;
-        let association_slice = &self.data()[association_range];
+        let association_slice = &self.data()[association_start..association_start + associations_len];
 
-        match search_for_associative_item_index(association_slice, symbol)? {
-            Some(index) => Ok(Some(&self.data()[association_start + index])),
-            None => Ok(None),
+        let position = match hash_index_size(associations_len) {
+            0 => search_for_associative_item_index(association_slice, symbol)?,
+            size => {
+                let index_start = association_start + len;
+                search_hash_index(association_slice, &self.data()[index_start..index_start + size], symbol)?
+            }
+        };
+
+        Ok(position.map(|position| association_start + position))
+    }
+
+    /// Adds a hash index of `index_size` slots directly after the associations of the list at `list_index`, returning where the list now starts.
+    ///
+    /// When data was added after the list was started the list is first copied to the end of the data block, leaving the original allocation as garbage.
+    pub(crate) fn add_list_hash_index(
+        &mut self,
+        list_index: usize,
+        len: usize,
+        associations_count: usize,
+        index_size: usize,
+    ) -> Result<usize, DataError> {
+        let list_end = list_index + 1 + list_allocation_size(len, 0);
+        let list_index = match list_end == self.data_block().cursor {
+            true => list_index,
+            false => {
+                let moved = self.push_to_data_block(self.get_from_data_block_ensure_index(list_index)?.clone())?;
+                for i in list_index + 1..list_end {
+                    self.push_to_data_block(self.get_from_data_block_ensure_index(i)?.clone())?;
+                }
+                moved
+            }
+        };
+
+        for _ in 0..index_size {
+            self.push_to_data_block(BasicData::Empty)?;
         }
-    }
 
-    pub fn get_associative_item_with_symbol_mut(&mut self, list_index: usize, symbol: u64) -> Result<Option<&mut BasicData<T>>, DataError> {
-        let (len, associations_len) = self.get_from_data_block_ensure_index(list_index)?.as_list()?;
+        let start = self.data_block().start + list_index + 1 + len;
+        let (associations, index) = self.data_mut()[start..start + len + index_size].split_at_mut(len);
+        build_hash_index(&associations[..associations_count], index)?;
 
-        let association_start = self.data_block().start + list_index + len + 1;
-        let association_range = association_start..association_start + associations_len;
-        
-        let search_index = {
-            let association_slice = &self.data()[association_range.clone()];
-            search_for_associative_item_index(association_slice, symbol)?
-        };
+        Ok(list_index)
+    }
 
-        match search_index {
+    pub fn get_associative_item_with_symbol(&self, list_index: usize, symbol: u64) -> Result<Option<&BasicData<T>>, DataError> {
+        Ok(self.find_list_association(list_index, symbol)?.map(|index| &self.data()[index]))
+    }
+
+    pub fn get_associative_item_with_symbol_mut(&mut self, list_index: usize, symbol: u64) -> Result<Option<&mut BasicData<T>>, DataError> {
+        match self.find_list_association(list_index, symbol)? {
             Some(index) => {
-                let true_index = association_start + index;
-                Ok(Some(&mut self.data_mut()[true_index]))
-            },
+                self.data_write_barrier(index - self.data_block().start)?;
+                Ok(Some(&mut self.data_mut()[index]))
+            }
             None => Ok(None),
         }
     }
 
+    /// Adds `string` as a char list, packed when it has at least [`PACKED_LIST_THRESHOLD`](crate::basic::PACKED_LIST_THRESHOLD) characters.
     pub fn add_string(&mut self, string: &str) -> Result<usize, DataError> {
-        let start = self.push_to_data_block(BasicData::CharList(string.len()))?;
+        let len = string.chars().count();
+        if len >= PACKED_LIST_THRESHOLD {
+            let start = self.push_to_data_block(BasicData::PackedCharList(len, string.len()))?;
+            self.push_packed_text(string)?;
+            return Ok(start);
+        }
+
+        let start = self.push_to_data_block(BasicData::CharList(len))?;
         for c in string.chars() {
             self.push_to_data_block(BasicData::Char(c))?;
         }
         Ok(start)
     }
 
+    /// Adds `slice` as a byte list, packed when it has at least [`PACKED_LIST_THRESHOLD`](crate::basic::PACKED_LIST_THRESHOLD) bytes.
     pub fn add_byte_slice(&mut self, slice: &[u8]) -> Result<usize, DataError> {
+        if slice.len() >= PACKED_LIST_THRESHOLD {
+            let start = self.push_to_data_block(BasicData::PackedByteList(slice.len()))?;
+            self.push_packed_bytes(slice)?;
+            return Ok(start);
+        }
+
         let start = self.push_to_data_block(BasicData::ByteList(slice.len()))?;
         for b in slice {
             self.push_to_data_block(BasicData::Byte(*b))?;
@@ -496,6 +682,22 @@ where
         &mut self.custom_data_block
     }
 
+    pub(crate) fn collector(&self) -> &Collector {
+        &self.collector
+    }
+
+    pub(crate) fn collector_mut(&mut self) -> &mut Collector {
+        &mut self.collector
+    }
+
+    pub(crate) fn pinned(&self) -> &PinnedData {
+        &self.pinned
+    }
+
+    pub(crate) fn pinned_mut(&mut self) -> &mut PinnedData {
+        &mut self.pinned
+    }
+
     pub(crate) fn current_value(&self) -> Option<usize> {
         self.current_value
     }
@@ -528,6 +730,52 @@ where
         self.current_frame = value;
     }
 
+    pub(crate) fn current_local(&self) -> Option<usize> {
+        self.current_local
+    }
+
+    pub(crate) fn set_current_local(&mut self, value: Option<usize>) {
+        self.current_local = value;
+    }
+
+    pub(crate) fn handlers(&self) -> &Vec<BasicHandler> {
+        &self.handlers
+    }
+
+    pub(crate) fn handlers_mut(&mut self) -> &mut Vec<BasicHandler> {
+        &mut self.handlers
+    }
+
+    /// Indexes of the nodes holding the current frame's locals, last slot first.
+    ///
+    /// Locals are kept in their own register chain so they survive the operand registers being popped.
+    pub(crate) fn local_nodes(&self) -> Result<Vec<usize>, DataError> {
+        let mut nodes = vec![];
+        let mut current = self.current_local();
+        while let Some(index) = current {
+            nodes.push(index);
+            current = match self.get_from_data_block_ensure_index(index)? {
+                BasicData::Register(previous, _) => Some(*previous),
+                BasicData::RegisterRoot(_) => None,
+                _ => return Err(DataError::not_basic_type_error()),
+            };
+        }
+        Ok(nodes)
+    }
+
+    /// Locals of the caller, stored right before the jump point of the frame at `frame_index` when the caller had any.
+    pub(crate) fn frame_locals(&self, frame_index: usize) -> Option<usize> {
+        match self.get_from_data_block_ensure_index(frame_index) {
+            Ok(BasicData::Frame(_, _) | BasicData::FrameIndex(_) | BasicData::FrameRegister(_) | BasicData::FrameRoot) => {}
+            _ => return None,
+        }
+
+        match frame_index.checked_sub(2).map(|index| self.get_from_data_block_ensure_index(index)) {
+            Some(Ok(BasicData::FrameLocals(locals))) => Some(*locals),
+            _ => None,
+        }
+    }
+
 }
 
 #[cfg(test)]
@@ -575,11 +823,11 @@ pub mod utilities {
 
 #[cfg(test)]
 mod tests {
-    use garnish_lang_traits::{GarnishDataType, Instruction};
+    use garnish_lang_traits::{GarnishData, GarnishDataType, Instruction};
 
     use crate::{
-        BasicData, BasicDataCustom, BasicGarnishData, BasicGarnishDataUnit, DataError, NoOpCompanion, basic::{
-            basic::utilities::test_data, object::BasicObject, storage::{ReallocationStrategy, StorageBlock, StorageSettings}
+        BasicData, BasicDataCustom, BasicGarnishData, BasicGarnishDataUnit, DataError, NoOpCompanion, OverflowPolicy, basic::{
+            basic::utilities::test_data, gc::Collector, handle::PinnedData, object::BasicObject, storage::{ReallocationStrategy, ShrinkPolicy, StorageBlock, StorageSettings}
             
         }, error::DataErrorType
     };
@@ -628,6 +876,11 @@ mod tests {
                 current_value: None,
                 current_register: None,
                 current_frame: None,
+                current_local: None,
+                handlers: Vec::new(),
+                error_values: false,
+                strict_mode: false,
+                overflow_policy: OverflowPolicy::Error,
                 data_retention_count: 0,
                 data: expected_data,
                 instruction_block: expected_instruction_block,
@@ -636,6 +889,8 @@ mod tests {
                 expression_symbol_block: expected_expression_symbol_block,
                 data_block: expected_data_block,
                 custom_data_block: expected_custom_data_block,
+                collector: Collector::default(),
+                pinned: PinnedData::default(),
                 companion: crate::basic::NoOpCompanion::default(),
             })
         );
@@ -1069,6 +1324,39 @@ mod tests {
         assert_eq!(result, Some("second symbol".to_string()));
     }
 
+    #[test]
+    fn push_to_symbol_table_block_same_name() {
+        let mut data = test_data();
+        let first = data.push_object_to_data_block(BasicObject::CharList("symbol".to_string())).unwrap();
+        let second = data.push_object_to_data_block(BasicObject::CharList("symbol".to_string())).unwrap();
+
+        data.push_to_symbol_table_block(100, first).unwrap();
+        let result = data.push_to_symbol_table_block(100, second);
+
+        assert_eq!(result, Ok(()));
+        assert_eq!(data.get_symbol_string(100).unwrap(), Some("symbol".to_string()));
+    }
+
+    #[test]
+    fn push_to_symbol_table_block_collision() {
+        let mut data = test_data();
+        let first = data.push_object_to_data_block(BasicObject::CharList("first".to_string())).unwrap();
+        let second = data.push_object_to_data_block(BasicObject::CharList("second".to_string())).unwrap();
+
+        data.push_to_symbol_table_block(100, first).unwrap();
+        let result = data.push_to_symbol_table_block(100, second);
+
+        assert_eq!(
+            result,
+            Err(DataError::new(
+                "Symbol for \"second\" is already registered for \"first\"",
+                DataErrorType::SymbolHashCollision(100)
+            ))
+        );
+        assert_eq!(data.symbol_table_block.cursor, 1);
+        assert_eq!(data.get_symbol_string(100).unwrap(), Some("first".to_string()));
+    }
+
     #[test]
     fn get_symbol_string_not_found() {
         let mut data = BasicGarnishDataUnit::new_with_settings(
@@ -1399,4 +1687,73 @@ mod tests {
         assert_eq!(data.custom_data_size(), 1);
         assert_eq!(data.allocated_custom_data_size(), 10);
     }
+
+    fn shrinking_data(shrink_policy: ShrinkPolicy) -> BasicGarnishDataUnit {
+        let mut data = BasicGarnishDataUnit::new_with_settings(
+            StorageSettings::new(0, usize::MAX, ReallocationStrategy::FixedSize(10)),
+            StorageSettings::new(0, usize::MAX, ReallocationStrategy::FixedSize(10)),
+            StorageSettings::new(0, usize::MAX, ReallocationStrategy::FixedSize(10)),
+            StorageSettings::new(0, usize::MAX, ReallocationStrategy::FixedSize(10)),
+            StorageSettings::new_with_shrink_policy(10, usize::MAX, ReallocationStrategy::FixedSize(10), shrink_policy),
+            StorageSettings::new(0, usize::MAX, ReallocationStrategy::FixedSize(10)),
+            NoOpCompanion::default(),
+        )
+        .unwrap();
+
+        for i in 0..50 {
+            data.push_to_data_block(BasicData::Number(i.into())).unwrap();
+        }
+        data.push_to_data_block(BasicData::ValueRoot(49)).unwrap();
+        data.set_current_value(Some(50));
+
+        data
+    }
+
+    #[test]
+    fn optimize_does_not_shrink_by_default() {
+        let mut data = shrinking_data(ShrinkPolicy::Never);
+        data.optimize(&[]).unwrap();
+
+        assert_eq!(data.data_size(), 2);
+        assert_eq!(data.allocated_data_size(), 60);
+    }
+
+    #[test]
+    fn optimize_shrinks_to_fit() {
+        let mut data = shrinking_data(ShrinkPolicy::ToFit);
+        data.optimize(&[]).unwrap();
+
+        assert_eq!(data.data_size(), 2);
+        assert_eq!(data.allocated_data_size(), 10);
+        assert_eq!(data.get_from_data_block_ensure_index(0), Ok(&BasicData::Number(49.into())));
+    }
+
+    #[test]
+    fn optimize_shrinks_past_threshold() {
+        let mut data = shrinking_data(ShrinkPolicy::Threshold { usage_percent: 50, headroom_percent: 100 });
+        data.push_value_stack(10).unwrap();
+        data.push_value_stack(20).unwrap();
+        data.push_value_stack(30).unwrap();
+        data.push_value_stack(40).unwrap();
+        data.optimize(&[]).unwrap();
+
+        assert_eq!(data.data_size(), 10);
+        assert_eq!(data.allocated_data_size(), 20);
+    }
+
+    #[test]
+    fn compact_reallocates_to_used_size() {
+        let mut data = shrinking_data(ShrinkPolicy::Never);
+        data.push_to_instruction_block(Instruction::Add, None).unwrap();
+        data.optimize(&[]).unwrap();
+        data.compact().unwrap();
+
+        assert_eq!(data.allocated_data_size(), 10);
+        assert_eq!(data.allocated_instruction_size(), 1);
+        assert_eq!(data.total_allocated_size(), 11);
+        assert_eq!(data.get_from_data_block_ensure_index(1), Ok(&BasicData::ValueRoot(0)));
+
+        data.push_to_data_block(BasicData::Unit).unwrap();
+        assert_eq!(data.data_size(), 3);
+    }
 }
@@ -1,3 +1,5 @@
+use crate::basic::packed::{packed_char_slot_count, packed_slot_count};
+use crate::basic::search::list_allocation_size;
 use crate::{BasicData, BasicDataCustom, BasicGarnishData, DataError, error::DataErrorType, basic::companion::BasicDataCompanion};
 
 pub trait CloneDelegate<Companion> {
@@ -98,6 +100,16 @@ where
                             }
                             list_index
                         }
+                        BasicData::PackedCharList(len, byte_len) => {
+                            let list_index = self.push_to_data_block(BasicData::PackedCharList(len, byte_len))?;
+                            self.clone_packed_slots(index, packed_char_slot_count(len, byte_len))?;
+                            list_index
+                        }
+                        BasicData::PackedByteList(len) => {
+                            let list_index = self.push_to_data_block(BasicData::PackedByteList(len))?;
+                            self.clone_packed_slots(index, packed_slot_count(len))?;
+                            list_index
+                        }
                         BasicData::Pair(left, right) => {
                             let left = self.lookup_in_data_slice(lookup_start, lookup_end, left)?;
                             let right = self.lookup_in_data_slice(lookup_start, lookup_end, right)?;
@@ -122,9 +134,21 @@ where
 
                             self.push_to_data_block(BasicData::Partial(left, right))?
                         }
+                        BasicData::Closure(expression, captures) => {
+                            let expression = self.lookup_in_data_slice(lookup_start, lookup_end, expression)?;
+                            let captures = self.lookup_in_data_slice(lookup_start, lookup_end, captures)?;
+
+                            self.push_to_data_block(BasicData::Closure(expression, captures))?
+                        }
+                        BasicData::Error(message, code) => {
+                            let message = self.lookup_in_data_slice(lookup_start, lookup_end, message)?;
+                            let code = self.lookup_in_data_slice(lookup_start, lookup_end, code)?;
+
+                            self.push_to_data_block(BasicData::Error(message, code))?
+                        }
                         BasicData::List(length, association_length) => {
                             let start = index + 1;
-                            let end = start + length * 2;
+                            let end = start + list_allocation_size(length, association_length);
 
                             let list_index = self.push_to_data_block(BasicData::List(length, association_length))?;
 
@@ -138,6 +162,10 @@ where
                                         let item = self.lookup_in_data_slice(lookup_start, lookup_end, item.clone())?;
                                         self.push_to_data_block(BasicData::AssociativeItem(symbol.clone(), item))?;
                                     }
+                                    BasicData::AssociativeIndex(position) => {
+                                        let position = *position;
+                                        self.push_to_data_block(BasicData::AssociativeIndex(position))?;
+                                    }
                                     BasicData::Empty => {
                                         self.push_to_data_block(BasicData::Empty)?;
                                     }
@@ -164,7 +192,7 @@ where
                         BasicData::Empty => self.push_to_data_block(BasicData::Empty)?,
                         BasicData::UninitializedList(length, count) => {
                             let start = index + 1;
-                            let end = start + length * 2;
+                            let end = start + list_allocation_size(length, 0);
 
                             let list_index = self.push_to_data_block(BasicData::UninitializedList(length, count))?;
 
@@ -178,6 +206,10 @@ where
                                         let item = self.lookup_in_data_slice(lookup_start, lookup_end, item.clone())?;
                                         self.push_to_data_block(BasicData::AssociativeItem(symbol.clone(), item))?;
                                     }
+                                    BasicData::AssociativeIndex(position) => {
+                                        let position = *position;
+                                        self.push_to_data_block(BasicData::AssociativeIndex(position))?;
+                                    }
                                     BasicData::Empty => {
                                         self.push_to_data_block(BasicData::Empty)?;
                                     }
@@ -192,6 +224,8 @@ where
                         }
                         BasicData::ListItem(_) => Err(DataError::new("Cannot clone", DataErrorType::CannotClone))?,
                         BasicData::AssociativeItem(_, _) => Err(DataError::new("Cannot clone", DataErrorType::CannotClone))?,
+                        BasicData::AssociativeIndex(_) => Err(DataError::new("Cannot clone", DataErrorType::CannotClone))?,
+                        BasicData::PackedBytes(_) => Err(DataError::new("Cannot clone", DataErrorType::CannotClone))?,
                         BasicData::Value(previous, value) => {
                             let previous = self.lookup_in_data_slice(lookup_start, lookup_end, previous)?;
                             let value = self.lookup_in_data_slice(lookup_start, lookup_end, value)?;
@@ -218,6 +252,7 @@ where
                         BasicData::JumpPoint(point) => self.push_to_data_block(BasicData::JumpPoint(point))?,
                         BasicData::Frame(previous, register) => {
                             let point = self.get_from_data_block_ensure_index(index - 1)?.as_jump_point()?;
+                            self.clone_frame_locals(index, lookup_start, lookup_end)?;
                             let previous = self.lookup_in_data_slice(lookup_start, lookup_end, previous)?;
                             let register = self.lookup_in_data_slice(lookup_start, lookup_end, register)?;
                             self.push_to_data_block(BasicData::JumpPoint(point))?;
@@ -225,21 +260,25 @@ where
                         }
                         BasicData::FrameIndex(previous) => {
                             let point = self.get_from_data_block_ensure_index(index - 1)?.as_jump_point()?;
+                            self.clone_frame_locals(index, lookup_start, lookup_end)?;
                             let previous = self.lookup_in_data_slice(lookup_start, lookup_end, previous)?;
                             self.push_to_data_block(BasicData::JumpPoint(point))?;
                             self.push_to_data_block(BasicData::FrameIndex(previous))?
                         }
                         BasicData::FrameRegister(register) => {
                             let point = self.get_from_data_block_ensure_index(index - 1)?.as_jump_point()?;
+                            self.clone_frame_locals(index, lookup_start, lookup_end)?;
                             let register = self.lookup_in_data_slice(lookup_start, lookup_end, register)?;
                             self.push_to_data_block(BasicData::JumpPoint(point))?;
                             self.push_to_data_block(BasicData::FrameRegister(register))?
                         }
                         BasicData::FrameRoot => {
                             let point = self.get_from_data_block_ensure_index(index - 1)?.as_jump_point()?;
+                            self.clone_frame_locals(index, lookup_start, lookup_end)?;
                             self.push_to_data_block(BasicData::JumpPoint(point))?;
                             self.push_to_data_block(BasicData::FrameRoot)?
                         }
+                        BasicData::FrameLocals(_) => Err(DataError::new("Cannot clone", DataErrorType::CannotClone))?,
                         BasicData::CloneItem(_) => Err(DataError::new("Cannot clone", DataErrorType::CannotClone))?,
                         BasicData::CloneIndexMap(_, _) => Err(DataError::new("Cannot clone", DataErrorType::CannotClone))?,
                     };
@@ -262,6 +301,22 @@ where
         Ok(new)
     }
 
+    fn clone_packed_slots(&mut self, list_index: usize, slot_count: usize) -> Result<(), DataError> {
+        for i in 0..slot_count {
+            let item = self.get_from_data_block_ensure_index(list_index + 1 + i)?.clone();
+            self.push_to_data_block(item)?;
+        }
+        Ok(())
+    }
+
+    fn clone_frame_locals(&mut self, frame_index: usize, lookup_start: usize, lookup_end: usize) -> Result<(), DataError> {
+        if let Some(locals) = self.frame_locals(frame_index) {
+            let locals = self.lookup_in_data_slice(lookup_start, lookup_end, locals)?;
+            self.push_to_data_block(BasicData::FrameLocals(locals))?;
+        }
+        Ok(())
+    }
+
     pub(crate) fn lookup_in_data_slice(&self, start: usize, end: usize, lookup_index: usize) -> Result<usize, DataError> {
         let index = match self.lookup_in_data_slice_optional(start, end, lookup_index)? {
             Some(value) => value,
@@ -577,6 +632,26 @@ mod clone {
         assert_eq!(data, expected_data);
     }
 
+    #[test]
+    fn packed_lists() {
+        let mut data = BasicGarnishData::<(), NoOpCompanion>::new(NoOpCompanion::new()).unwrap();
+        let text = "packed é ".repeat(10);
+        let bytes = (0..50).collect::<Vec<u8>>();
+        let chars = data.add_string(&text).unwrap();
+        let byte_list = data.add_byte_slice(&bytes).unwrap();
+        let index = data.push_to_data_block(BasicData::Pair(chars, byte_list)).unwrap();
+        let index_stack_start = data.create_index_stack(index).unwrap();
+        let index = data.clone_index_stack(index_stack_start, 0).unwrap();
+
+        let (left, right) = data.get_from_data_block_ensure_index(index).unwrap().as_pair().unwrap();
+        assert_ne!(left, chars);
+        assert_ne!(right, byte_list);
+        assert_eq!(data.get_from_data_block_ensure_index(left), Ok(&BasicData::PackedCharList(90, 100)));
+        assert_eq!(data.char_list_item_at(left, 79), Ok(Some('é')));
+        assert_eq!(data.char_list_string_at(left), Ok(text));
+        assert_eq!(data.byte_list_bytes_at(right, 0, 50), Ok(bytes));
+    }
+
     #[test]
     fn byte_list() {
         let mut data = BasicGarnishData::<(), NoOpCompanion>::new(NoOpCompanion::new()).unwrap();
@@ -1,7 +1,7 @@
 use std::fmt::Display;
 
 use garnish_lang_traits::{GarnishDataType, Instruction};
-use crate::{BasicDataCustom, DataError};
+use crate::{BasicDataCustom, DataError, basic::PACKED_CHUNK_SIZE};
 
 type BasicNumber = crate::data::SimpleNumber;
 
@@ -21,10 +21,14 @@ where T: BasicDataCustom {
     External(usize),
     CharList(usize),
     ByteList(usize),
+    PackedCharList(usize, usize),
+    PackedByteList(usize),
     Pair(usize, usize),
     Range(usize, usize),
     Slice(usize, usize),
     Partial(usize, usize),
+    Closure(usize, usize),
+    Error(usize, usize),
     List(usize, usize),
     Concatenation(usize, usize),
     Custom(T),
@@ -33,6 +37,8 @@ where T: BasicDataCustom {
     UninitializedList(usize, usize),
     ListItem(usize),
     AssociativeItem(u64, usize),
+    AssociativeIndex(usize),
+    PackedBytes([u8; PACKED_CHUNK_SIZE]),
     Value(usize, usize),
     ValueRoot(usize),
     Register(usize, usize),
@@ -44,6 +50,7 @@ where T: BasicDataCustom {
     FrameIndex(usize),
     FrameRegister(usize),
     FrameRoot,
+    FrameLocals(usize),
     CloneItem(usize),
     CloneIndexMap(usize, usize),
 }
@@ -65,10 +72,14 @@ where T: BasicDataCustom {
             BasicData::External(_) => GarnishDataType::External,
             BasicData::CharList(_) => GarnishDataType::CharList,
             BasicData::ByteList(_) => GarnishDataType::ByteList,
+            BasicData::PackedCharList(_, _) => GarnishDataType::CharList,
+            BasicData::PackedByteList(_) => GarnishDataType::ByteList,
             BasicData::Pair(_, _) => GarnishDataType::Pair,
             BasicData::Range(_, _) => GarnishDataType::Range,
             BasicData::Slice(_, _) => GarnishDataType::Slice,
             BasicData::Partial(_, _) => GarnishDataType::Partial,
+            BasicData::Closure(_, _) => GarnishDataType::Closure,
+            BasicData::Error(_, _) => GarnishDataType::Error,
             BasicData::List(_, _) => GarnishDataType::List,
             BasicData::Concatenation(_, _) => GarnishDataType::Concatenation,
             BasicData::Custom(_) => GarnishDataType::Custom,
@@ -77,6 +88,8 @@ where T: BasicDataCustom {
             BasicData::UninitializedList(_, _) => GarnishDataType::Invalid,
             BasicData::ListItem(_) => GarnishDataType::Invalid,
             BasicData::AssociativeItem(_, _) => GarnishDataType::Invalid,
+            BasicData::AssociativeIndex(_) => GarnishDataType::Invalid,
+            BasicData::PackedBytes(_) => GarnishDataType::Invalid,
             BasicData::Value(_, _) => GarnishDataType::Invalid,
             BasicData::ValueRoot(_) => GarnishDataType::Invalid,
             BasicData::Register(_, _) => GarnishDataType::Invalid,
@@ -88,6 +101,7 @@ where T: BasicDataCustom {
             BasicData::FrameIndex(_) => GarnishDataType::Invalid,
             BasicData::FrameRegister(_) => GarnishDataType::Invalid,
             BasicData::FrameRoot => GarnishDataType::Invalid,
+            BasicData::FrameLocals(_) => GarnishDataType::Invalid,
             BasicData::CloneItem(_) => GarnishDataType::Invalid,
             BasicData::CloneIndexMap(_, _) => GarnishDataType::Invalid,
         }
@@ -233,6 +247,20 @@ where T: BasicDataCustom {
         }
     }
 
+    pub fn as_packed_char_list(&self) -> Result<(usize, usize), DataError> {
+        match self {
+            BasicData::PackedCharList(len, byte_len) => Ok((*len, *byte_len)),
+            _ => Err(DataError::not_type_error(GarnishDataType::CharList, self.get_data_type())),
+        }
+    }
+
+    pub fn as_packed_byte_list(&self) -> Result<usize, DataError> {
+        match self {
+            BasicData::PackedByteList(l) => Ok(*l),
+            _ => Err(DataError::not_type_error(GarnishDataType::ByteList, self.get_data_type())),
+        }
+    }
+
     pub fn as_pair(&self) -> Result<(usize, usize), DataError> {
         match self {
             BasicData::Pair(left, right) => Ok((*left, *right)),
@@ -261,6 +289,34 @@ where T: BasicDataCustom {
         }
     }
 
+    pub fn as_closure(&self) -> Result<(usize, usize), DataError> {
+        match self {
+            BasicData::Closure(expression, captures) => Ok((*expression, *captures)),
+            _ => Err(DataError::not_type_error(GarnishDataType::Closure, self.get_data_type())),
+        }
+    }
+
+    pub fn as_closure_mut(&mut self) -> Result<(&mut usize, &mut usize), DataError> {
+        match self {
+            BasicData::Closure(expression, captures) => Ok((expression, captures)),
+            _ => Err(DataError::not_type_error(GarnishDataType::Closure, self.get_data_type())),
+        }
+    }
+
+    pub fn as_error(&self) -> Result<(usize, usize), DataError> {
+        match self {
+            BasicData::Error(message, code) => Ok((*message, *code)),
+            _ => Err(DataError::not_type_error(GarnishDataType::Error, self.get_data_type())),
+        }
+    }
+
+    pub fn as_error_mut(&mut self) -> Result<(&mut usize, &mut usize), DataError> {
+        match self {
+            BasicData::Error(message, code) => Ok((message, code)),
+            _ => Err(DataError::not_type_error(GarnishDataType::Error, self.get_data_type())),
+        }
+    }
+
     pub fn as_concatenation(&self) -> Result<(usize, usize), DataError> {
         match self {
             BasicData::Concatenation(left, right) => Ok((*left, *right)),
@@ -359,6 +415,13 @@ where T: BasicDataCustom {
         }
     }
 
+    pub fn as_packed_bytes(&self) -> Result<&[u8; PACKED_CHUNK_SIZE], DataError> {
+        match self {
+            BasicData::PackedBytes(bytes) => Ok(bytes),
+            _ => Err(DataError::not_basic_type_error()),
+        }
+    }
+
     pub fn as_list_item(&self) -> Result<usize, DataError> {
         match self {
             BasicData::ListItem(item) => Ok(*item),
@@ -586,10 +649,14 @@ mod tests {
             (BasicDataUnitCustom::External(100), GarnishDataType::External),
             (BasicDataUnitCustom::CharList(3), GarnishDataType::CharList),
             (BasicDataUnitCustom::ByteList(3), GarnishDataType::ByteList),
+            (BasicDataUnitCustom::PackedCharList(3, 4), GarnishDataType::CharList),
+            (BasicDataUnitCustom::PackedByteList(3), GarnishDataType::ByteList),
             (BasicDataUnitCustom::Pair(100, 200), GarnishDataType::Pair),
             (BasicDataUnitCustom::Range(100, 200), GarnishDataType::Range),
             (BasicDataUnitCustom::Slice(100, 200), GarnishDataType::Slice),
             (BasicDataUnitCustom::Partial(100, 200), GarnishDataType::Partial),
+            (BasicDataUnitCustom::Closure(100, 200), GarnishDataType::Closure),
+            (BasicDataUnitCustom::Error(100, 200), GarnishDataType::Error),
             (BasicDataUnitCustom::List(100, 200), GarnishDataType::List),
             (BasicDataUnitCustom::Concatenation(100, 200), GarnishDataType::Concatenation),
             (BasicDataUnitCustom::Custom(()), GarnishDataType::Custom),
@@ -598,6 +665,8 @@ mod tests {
             (BasicDataUnitCustom::UninitializedList(100, 200), GarnishDataType::Invalid),
             (BasicDataUnitCustom::ListItem(100), GarnishDataType::Invalid),
             (BasicDataUnitCustom::AssociativeItem(100, 200), GarnishDataType::Invalid),
+            (BasicDataUnitCustom::AssociativeIndex(100), GarnishDataType::Invalid),
+            (BasicDataUnitCustom::PackedBytes([0; PACKED_CHUNK_SIZE]), GarnishDataType::Invalid),
             (BasicDataUnitCustom::Value(100, 200), GarnishDataType::Invalid),
             (BasicDataUnitCustom::ValueRoot(100), GarnishDataType::Invalid),
             (BasicDataUnitCustom::Register(100, 200), GarnishDataType::Invalid),
@@ -859,6 +928,54 @@ mod tests {
         assert_eq!(data.as_partial_mut(), Err(DataError::not_type_error(GarnishDataType::Partial, GarnishDataType::Number)));
     }
 
+    #[test]
+    fn as_closure() {
+        let data = BasicDataUnitCustom::Closure(100, 200);
+        assert_eq!(data.as_closure(), Ok((100, 200)));
+    }
+
+    #[test]
+    fn as_closure_not_closure() {
+        let data = BasicDataUnitCustom::Number(100.into());
+        assert_eq!(data.as_closure(), Err(DataError::not_type_error(GarnishDataType::Closure, GarnishDataType::Number)));
+    }
+
+    #[test]
+    fn as_closure_mut() {
+        let mut data = BasicDataUnitCustom::Closure(100, 200);
+        let (expression, captures) = data.as_closure_mut().unwrap();
+        *expression = 300;
+        *captures = 400;
+        assert_eq!(data.as_closure(), Ok((300, 400)));
+    }
+
+    #[test]
+    fn as_closure_mut_not_closure() {
+        let mut data = BasicDataUnitCustom::Number(100.into());
+        assert_eq!(data.as_closure_mut(), Err(DataError::not_type_error(GarnishDataType::Closure, GarnishDataType::Number)));
+    }
+
+    #[test]
+    fn as_error() {
+        let data = BasicDataUnitCustom::Error(100, 200);
+        assert_eq!(data.as_error(), Ok((100, 200)));
+    }
+
+    #[test]
+    fn as_error_not_error() {
+        let data = BasicDataUnitCustom::Number(100.into());
+        assert_eq!(data.as_error(), Err(DataError::not_type_error(GarnishDataType::Error, GarnishDataType::Number)));
+    }
+
+    #[test]
+    fn as_error_mut() {
+        let mut data = BasicDataUnitCustom::Error(100, 200);
+        let (message, code) = data.as_error_mut().unwrap();
+        *message = 300;
+        *code = 400;
+        assert_eq!(data.as_error(), Ok((300, 400)));
+    }
+
     #[test]
     fn as_concatenation() {
         let data = BasicDataUnitCustom::Concatenation(100, 200);
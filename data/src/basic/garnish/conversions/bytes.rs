@@ -1,4 +1,4 @@
-use crate::{BasicData, BasicDataCustom, BasicGarnishData, BasicNumber, DataError, basic::companion::BasicDataCompanion};
+use crate::{BasicData, BasicDataCustom, BasicGarnishData, BasicNumber, DataError, Decimal, basic::companion::BasicDataCompanion};
 
 impl<T, Companion> BasicGarnishData<T, Companion>
 where
@@ -13,23 +13,12 @@ where
             BasicData::Byte(value) => value.to_le_bytes().to_vec(),
             BasicData::Char(value) => u32::from(value.clone()).to_le_bytes().to_vec(),
             BasicData::Symbol(value) => value.to_le_bytes().to_vec(),
-            BasicData::ByteList(length) => {
-                let start = from + 1;
-                let end = start + length;
-                self.data()[start..end]
-                    .iter()
-                    .map(|c| c.as_byte())
-                    .collect::<Result<Vec<u8>, DataError>>()?
-            }
-            BasicData::CharList(length) => {
-                let start = from + 1;
-                let end = start + length;
-                self.data()[start..end]
-                    .iter()
-                    .map(|c| c.as_char().map(|c| u32::from(c).to_le_bytes().to_vec()).unwrap_or(vec![]))
-                    .flatten()
-                    .collect::<Vec<u8>>()
-            }
+            BasicData::ByteList(length) | BasicData::PackedByteList(length) => self.byte_list_bytes_at(from, 0, *length)?,
+            BasicData::CharList(_) | BasicData::PackedCharList(_, _) => self
+                .char_list_string_at(from)?
+                .chars()
+                .flat_map(|c| u32::from(c).to_le_bytes())
+                .collect::<Vec<u8>>(),
             BasicData::SymbolList(length) => {
                 let start = from + 1;
                 let end = start + length;
@@ -40,6 +29,7 @@ where
                         BasicData::Number(value) => match value {
                             BasicNumber::Integer(value) => value.to_le_bytes().to_vec(),
                             BasicNumber::Float(value) => value.to_le_bytes().to_vec(),
+                            BasicNumber::Decimal(value) => decimal_bytes(value),
                         },
                         _ => vec![],
                     })
@@ -49,6 +39,7 @@ where
             BasicData::Number(value) => match value {
                 BasicNumber::Integer(value) => value.to_le_bytes().to_vec(),
                 BasicNumber::Float(value) => value.to_le_bytes().to_vec(),
+                BasicNumber::Decimal(value) => decimal_bytes(value),
             },
             BasicData::List(length, _) => {
                 let start = from + 1;
@@ -70,12 +61,16 @@ where
             | BasicData::Range(_, _)
             | BasicData::Slice(_, _)
             | BasicData::Partial(_, _)
+            | BasicData::Closure(_, _)
+            | BasicData::Error(_, _)
             | BasicData::Concatenation(_, _)
             | BasicData::Custom(_)
             | BasicData::Empty
             | BasicData::UninitializedList(_, _)
             | BasicData::ListItem(_)
             | BasicData::AssociativeItem(_, _)
+            | BasicData::AssociativeIndex(_)
+            | BasicData::PackedBytes(_)
             | BasicData::Value(_, _)
             | BasicData::ValueRoot(_)
             | BasicData::Register(_, _)
@@ -87,12 +82,18 @@ where
             | BasicData::FrameIndex(_)
             | BasicData::FrameRegister(_)
             | BasicData::FrameRoot
+            | BasicData::FrameLocals(_)
             | BasicData::CloneItem(_)
             | BasicData::CloneIndexMap(_, _) => vec![],
         })
     }
 }
 
+// units followed by scale, both little endian
+fn decimal_bytes(value: &Decimal) -> Vec<u8> {
+    value.units().to_le_bytes().into_iter().chain(value.scale().to_le_bytes()).collect()
+}
+
 #[cfg(test)]
 mod tests {
     use crate::{basic::utilities::test_data, basic_object};
@@ -134,7 +135,7 @@ mod tests {
 
         let result = data.convert_basic_data_at_to_bytes(index).unwrap();
 
-        assert_eq!(result, vec![57, 48, 0, 0])
+        assert_eq!(result, vec![57, 48, 0, 0, 0, 0, 0, 0])
     }
 
     #[test]
@@ -219,7 +220,7 @@ mod tests {
         assert_eq!(
             result,
             vec![
-                100, 0, 0, 0, 83, 0, 0, 0, 111, 0, 0, 0, 109, 0, 0, 0, 101, 0, 0, 0, 32, 0, 0, 0, 84, 0, 0, 0, 101, 0, 0, 0, 120, 0, 0, 0, 116, 0, 0,
+                100, 0, 0, 0, 0, 0, 0, 0, 83, 0, 0, 0, 111, 0, 0, 0, 109, 0, 0, 0, 101, 0, 0, 0, 32, 0, 0, 0, 84, 0, 0, 0, 101, 0, 0, 0, 120, 0, 0, 0, 116, 0, 0,
                 0
             ]
         )
@@ -1,4 +1,4 @@
-use crate::{BasicData, BasicDataCustom, BasicGarnishData, BasicNumber, DataError, basic::companion::BasicDataCompanion};
+use crate::{BasicData, BasicDataCustom, BasicGarnishData, BasicNumber, DataError, Decimal, basic::companion::BasicDataCompanion};
 
 impl<T, Companion> BasicGarnishData<T, Companion>
 where
@@ -10,40 +10,28 @@ where
             BasicData::Number(number) => Some(number.clone()),
             BasicData::Char(value) => {
                 match value.to_digit(10) {
-                    Some(value) => Some(BasicNumber::Integer(value as i32)),
+                    Some(value) => Some(BasicNumber::Integer(value as i64)),
                     None => None,
                 }
             },
-            BasicData::Byte(value) => Some(BasicNumber::Integer(value.clone() as i32)),
-            BasicData::CharList(value) => {
-                let start = from + 1;
-                let end = start + value;
-                let mut chars = String::new();
-                for i in start..end {
-                    let char = self.get_from_data_block_ensure_index(i)?.as_char()?;
-                    chars.push(char);
-                }
-                match chars.parse::<i32>() {
+            BasicData::Byte(value) => Some(BasicNumber::Integer(value.clone() as i64)),
+            BasicData::CharList(_) | BasicData::PackedCharList(_, _) => {
+                let chars = self.char_list_string_at(from)?;
+                match chars.parse::<i64>() {
                     Ok(value) => Some(BasicNumber::Integer(value)),
-                    Err(_) => None,
+                    Err(_) => chars.parse::<Decimal>().ok().map(BasicNumber::Decimal),
                 }
             },
-            BasicData::ByteList(value) => {
-                let start = from + 1;
-                let end = start + value;
-                let mut bytes = vec![];
-                for i in start..end {
-                    let byte = self.get_from_data_block_ensure_index(i)?.as_byte()?;
-                    bytes.push(byte);
-                }
-                if bytes.len() > 4 {
+            BasicData::ByteList(length) | BasicData::PackedByteList(length) => {
+                let bytes = self.byte_list_bytes_at(from, 0, *length)?;
+                if bytes.len() > 8 {
                     return Ok(None);
                 }
-                let mut conversion_bytes = [0; 4];
+                let mut conversion_bytes = [0; 8];
                 for (i, byte) in bytes.iter().enumerate() {
                     conversion_bytes[i] = *byte;
                 }
-                let num = i32::from_le_bytes(conversion_bytes);
+                let num = i64::from_le_bytes(conversion_bytes);
                 Some(BasicNumber::Integer(num))
             }
             BasicData::Unit
@@ -58,6 +46,8 @@ where
             | BasicData::Range(_, _)
             | BasicData::Slice(_, _)
             | BasicData::Partial(_, _)
+            | BasicData::Closure(_, _)
+            | BasicData::Error(_, _)
             | BasicData::List(_, _)
             | BasicData::Concatenation(_, _)
             | BasicData::Custom(_)
@@ -65,6 +55,8 @@ where
             | BasicData::UninitializedList(_, _)
             | BasicData::ListItem(_)
             | BasicData::AssociativeItem(_, _)
+            | BasicData::AssociativeIndex(_)
+            | BasicData::PackedBytes(_)
             | BasicData::Value(_, _)
             | BasicData::ValueRoot(_)
             | BasicData::Register(_, _)
@@ -76,6 +68,7 @@ where
             | BasicData::FrameIndex(_)
             | BasicData::FrameRegister(_)
             | BasicData::FrameRoot
+            | BasicData::FrameLocals(_)
             | BasicData::CloneItem(_)
             | BasicData::CloneIndexMap(_, _) => None,
         })
@@ -84,7 +77,7 @@ where
 
 #[cfg(test)]
 mod tests {
-    use crate::{BasicNumber, basic::utilities::test_data, basic_object};
+    use crate::{BasicNumber, Decimal, basic::utilities::test_data, basic_object};
 
     #[test]
     fn convert_number() {
@@ -118,6 +111,15 @@ mod tests {
         assert_eq!(result, Some(BasicNumber::Integer(123456789)));
     }
 
+    #[test]
+    fn convert_char_list_with_decimal() {
+        let mut data = test_data();
+        let index = data.push_object_to_data_block(basic_object!(CharList "-12.50")).unwrap();
+        let result = data.convert_basic_data_at_to_number(index).unwrap();
+        assert_eq!(result, Some(BasicNumber::Decimal(Decimal::new(-1250, 2).unwrap())));
+        assert_eq!(result.unwrap().to_string(), "-12.50");
+    }
+
     #[test]
     fn convert_char_list_with_non_numeric_char_list() {
         let mut data = test_data();
@@ -145,7 +147,7 @@ mod tests {
     #[test]
     fn convert_byte_list_with_too_many_bytes() {
         let mut data = test_data();
-        let index = data.push_object_to_data_block(basic_object!(ByteList 100, 200, 250, 100, 100)).unwrap();
+        let index = data.push_object_to_data_block(basic_object!(ByteList 100, 200, 250, 100, 100, 100, 100, 100, 100)).unwrap();
         let result = data.convert_basic_data_at_to_number(index).unwrap();
         assert_eq!(result, None);
     }
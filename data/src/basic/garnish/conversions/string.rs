@@ -5,8 +5,7 @@ where
     T: BasicDataCustom,
     Companion: BasicDataCompanion<T>,
 {
-    list_index: usize,
-    length: usize,
+    string: String,
     data: &'a mut BasicGarnishData<T, Companion>,
 }
 
@@ -16,11 +15,7 @@ where
     Companion: BasicDataCompanion<T>,
 {
     fn new(data: &'a mut BasicGarnishData<T, Companion>) -> Self {
-        Self {
-            list_index: 0,
-            length: 0,
-            data,
-        }
+        Self { string: String::new(), data }
     }
 }
 
@@ -32,13 +27,11 @@ where
     type Output = usize;
 
     fn init(&mut self) -> Result<(), DataError> {
-        self.list_index = self.data.push_to_data_block(BasicData::CharList(0))?;
         Ok(())
     }
 
     fn push_char(&mut self, c: char) -> Result<(), DataError> {
-        self.data.push_to_data_block(BasicData::Char(c))?;
-        self.length += 1;
+        self.string.push(c);
         Ok(())
     }
 
@@ -47,9 +40,7 @@ where
     }
 
     fn end(self) -> Result<Self::Output, DataError> {
-        let list_length = self.data.get_from_data_block_ensure_index_mut(self.list_index)?.as_char_list_mut()?;
-        *list_length = self.length;
-        Ok(self.list_index)
+        self.data.add_string(&self.string)
     }
 
     fn data(&self) -> &BasicGarnishData<T, Companion> {
@@ -219,25 +210,42 @@ where
                 delegate.push_char(c)?;
             }
         }
+        BasicData::Closure(expression, _) => {
+            let jump_table_index = delegate.get_data_at(expression.clone())?.as_expression()?;
+            let s = format!("[Closure {}]", jump_table_index);
+            for c in s.chars() {
+                delegate.push_char(c)?;
+            }
+        }
+        BasicData::Error(message, code) => {
+            let (message, code) = (*message, *code);
+            for c in "[Error ".chars() {
+                delegate.push_char(c)?;
+            }
+            convert_with_delegate(delegate, code, depth + 1)?;
+            delegate.push_char(' ')?;
+            convert_with_delegate(delegate, message, depth + 1)?;
+            delegate.push_char(']')?;
+        }
         BasicData::External(value) => {
             let s = format!("[External {}]", value);
             for c in s.chars() {
                 delegate.push_char(c)?;
             }
         }
-        BasicData::ByteList(length) => {
-            let length = length.clone();
-            let end = from + 1 + length;
-            let range = from + 1..end;
+        BasicData::ByteList(length) | BasicData::PackedByteList(length) => {
+            let bytes = delegate.data().byte_list_bytes_at(from, 0, *length)?;
 
             if depth > 0 {
                 delegate.push_char('(')?;
             }
 
-            for i in range {
-                convert_with_delegate(delegate, i, depth + 1)?;
+            for (i, b) in bytes.iter().enumerate() {
+                for c in b.to_string().chars() {
+                    delegate.push_char(c)?;
+                }
 
-                if i < end - 1 {
+                if i < bytes.len() - 1 {
                     delegate.push_char(' ')?;
                 }
             }
@@ -246,11 +254,8 @@ where
                 delegate.push_char(')')?;
             }
         }
-        BasicData::CharList(length) => {
-            let start = from + 1;
-            let length = length.clone();
-            for i in start..start + length {
-                let c = delegate.get_data_at(i)?.as_char()?;
+        BasicData::CharList(_) | BasicData::PackedCharList(_, _) => {
+            for c in delegate.data().char_list_string_at(from)?.chars() {
                 delegate.push_char(c)?;
             }
         }
@@ -360,6 +365,8 @@ where
         | BasicData::UninitializedList(_, _)
         | BasicData::ListItem(_)
         | BasicData::AssociativeItem(_, _)
+        | BasicData::AssociativeIndex(_)
+        | BasicData::PackedBytes(_)
         | BasicData::Value(_, _)
         | BasicData::ValueRoot(_)
         | BasicData::Register(_, _)
@@ -371,6 +378,7 @@ where
         | BasicData::FrameIndex(_)
         | BasicData::FrameRegister(_)
         | BasicData::FrameRoot
+        | BasicData::FrameLocals(_)
         | BasicData::CloneItem(_)
         | BasicData::CloneIndexMap(_, _) => {}
     })
@@ -391,11 +399,7 @@ mod tests {
                 let index = data.push_object_to_data_block($object).unwrap();
                 let char_list = data.convert_basic_data_at_to_char_list(index).unwrap();
 
-                let length = data.get_from_data_block_ensure_index(char_list).unwrap().as_char_list().unwrap();
-
-                let start = data.data_block().start + char_list + 1;
-                let slice = &data.data()[start..start + length];
-                let result = slice.iter().map(|data| data.as_char().unwrap()).collect::<String>();
+                let result = data.char_list_string_at(char_list).unwrap();
                 assert_eq!(result, $output);
 
                 let string = data.string_from_basic_data_at(index).unwrap();
@@ -412,11 +416,7 @@ mod tests {
                 let index = data.push_to_data_block($object).unwrap();
                 let char_list = data.convert_basic_data_at_to_char_list(index).unwrap();
 
-                let length = data.get_from_data_block_ensure_index(char_list).unwrap().as_char_list().unwrap();
-
-                let start = char_list + 1;
-                let slice = &data.data()[start..start + length];
-                let result = slice.iter().map(|data| data.as_char().unwrap()).collect::<String>();
+                let result = data.char_list_string_at(char_list).unwrap();
                 assert_eq!(result, $output);
 
                 let string = data.string_from_basic_data_at(index).unwrap();
@@ -19,7 +19,7 @@ impl GarnishDataFactory<usize, BasicNumber, char, u8, u64, DataError, SizeIterat
                 Ok(c) => Some(c),
                 Err(_) => None,
             },
-            BasicNumber::Float(_) => None,
+            BasicNumber::Float(_) | BasicNumber::Decimal(_) => None,
         }
     }
 
@@ -29,7 +29,7 @@ impl GarnishDataFactory<usize, BasicNumber, char, u8, u64, DataError, SizeIterat
                 Ok(b) => Some(b),
                 Err(_) => None,
             },
-            BasicNumber::Float(_) => None,
+            BasicNumber::Float(_) | BasicNumber::Decimal(_) => None,
         }
     }
 
@@ -179,13 +179,13 @@ mod tests {
     #[test]
     fn parse_symbol() {
         let result = BasicDataFactory::parse_symbol("my_symbol").unwrap();
-        assert_eq!(result, 8904929874702161741);
+        assert_eq!(result, 7248772672978976256);
     }
 
     #[test]
     fn parse_symbol_trims_semi_coloon() {
         let result = BasicDataFactory::parse_symbol(":my_symbol").unwrap();
-        assert_eq!(result, 8904929874702161741);
+        assert_eq!(result, 7248772672978976256);
     }
 
     #[test]
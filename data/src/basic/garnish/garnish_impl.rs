@@ -2,13 +2,17 @@ use std::cmp::Ordering;
 
 use crate::{
     BasicData, BasicDataCustom, ByteListIterator, CharListIterator, DataError, DataIndexIterator, NumberIterator, SizeIterator,
+    parse_simple_number_with,
     SymbolListPartIterator,
     basic::{
-        BasicGarnishData, BasicNumber,
+        BasicGarnishData, BasicHandler, BasicNumber,
         companion::BasicDataCompanion,
-        garnish::{factory::BasicDataFactory, utils::extents_to_start_end},
+        garnish::{
+            factory::BasicDataFactory,
+            utils::{extents_to_item_range, extents_to_start_end},
+        },
         merge_to_symbol_list::merge_to_symbol_list,
-        search::search_for_associative_item,
+        search::{hash_index_size, list_allocation_size},
     },
     error::DataErrorType,
 };
@@ -131,6 +135,15 @@ where
         self.get_from_data_block_ensure_index(addr)?.as_symbol()
     }
 
+    fn cmp_symbol_names(&self, left: Self::Symbol, right: Self::Symbol) -> Result<Ordering, Self::Error> {
+        match (self.get_symbol_name_index(left)?, self.get_symbol_name_index(right)?) {
+            (Some(left), Some(right)) => self.char_list_cmp_at(left, right),
+            (Some(_), None) => Ok(Ordering::Less),
+            (None, Some(_)) => Ok(Ordering::Greater),
+            (None, None) => Ok(left.cmp(&right)),
+        }
+    }
+
     fn get_expression(&self, addr: Self::Size) -> Result<Self::Size, Self::Error> {
         self.get_from_data_block_ensure_index(addr)?.as_expression()
     }
@@ -159,6 +172,14 @@ where
         self.get_from_data_block_ensure_index(addr)?.as_partial()
     }
 
+    fn get_closure(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error> {
+        self.get_from_data_block_ensure_index(addr)?.as_closure()
+    }
+
+    fn get_error(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error> {
+        self.get_from_data_block_ensure_index(addr)?.as_error()
+    }
+
     fn get_list_len(&self, addr: Self::Size) -> Result<Self::Size, Self::Error> {
         Ok(self.get_from_data_block_ensure_index(addr)?.as_list()?.0)
     }
@@ -176,60 +197,40 @@ where
     }
 
     fn get_list_item_with_symbol(&self, list_index: Self::Size, sym: Self::Symbol) -> Result<Option<Self::Size>, Self::Error> {
-        let (len, associations_len) = self.get_from_data_block_ensure_index(list_index)?.as_list()?;
-
-        let association_start = self.data_block().start + list_index + len + 1;
-        let association_range = association_start..association_start + associations_len;
-        let association_slice = &self.data()[association_range.clone()];
-
-        match search_for_associative_item(association_slice, sym)? {
-            Some(item) => Ok(Some(item.as_associative_item()?.1)),
+        match self.find_list_association(list_index, sym)? {
+            Some(index) => Ok(Some(self.data()[index].as_associative_item()?.1)),
             None => Ok(None),
         }
     }
 
     fn get_char_list_len(&self, list_index: Self::Size) -> Result<Self::Size, Self::Error> {
-        Ok(self.get_from_data_block_ensure_index(list_index)?.as_char_list()?)
+        self.char_list_len_at(list_index)
     }
 
     fn get_char_list_item(&self, list_index: Self::Size, item_index: Self::Number) -> Result<Option<Self::Char>, Self::Error> {
-        let len = self.get_from_data_block_ensure_index(list_index)?.as_char_list()?;
-        let index: usize = item_index.into();
-        if index >= len {
-            return Ok(None);
-        }
-        Ok(Some(self.get_from_data_block_ensure_index(list_index + 1 + index)?.as_char()?))
+        self.char_list_item_at(list_index, item_index.into())
     }
 
     fn get_char_list_iter(&self, list_index: Self::Size, extents: Extents<Self::Number>) -> Result<Self::CharIterator, Self::Error> {
-        let len = self.get_from_data_block_ensure_index(list_index)?.as_char_list()?;
-        let (start, end) = extents_to_start_end(extents, self.data_block().start + list_index, len);
+        let len = self.char_list_len_at(list_index)?;
+        let (start, end) = extents_to_item_range(extents, len);
 
-        Ok(CharListIterator::new(
-            self.data()[start..end].iter().map(|c| c.as_char().unwrap()).collect(),
-        ))
+        Ok(CharListIterator::new(self.char_list_chars_at(list_index, start, end)?))
     }
 
     fn get_byte_list_len(&self, addr: Self::Size) -> Result<Self::Size, Self::Error> {
-        Ok(self.get_from_data_block_ensure_index(addr)?.as_byte_list()?)
+        self.byte_list_len_at(addr)
     }
 
     fn get_byte_list_item(&self, addr: Self::Size, item_index: Self::Number) -> Result<Option<Self::Byte>, Self::Error> {
-        let len = self.get_from_data_block_ensure_index(addr)?.as_byte_list()?;
-        let index: usize = item_index.into();
-        if index >= len {
-            return Ok(None);
-        }
-        Ok(Some(self.get_from_data_block_ensure_index(addr + 1 + index)?.as_byte()?))
+        self.byte_list_item_at(addr, item_index.into())
     }
 
     fn get_byte_list_iter(&self, list_index: Self::Size, extents: Extents<Self::Number>) -> Result<Self::ByteIterator, Self::Error> {
-        let len = self.get_from_data_block_ensure_index(list_index)?.as_byte_list()?;
-        let (start, end) = extents_to_start_end(extents, self.data_block().start + list_index, len);
+        let len = self.byte_list_len_at(list_index)?;
+        let (start, end) = extents_to_item_range(extents, len);
 
-        Ok(ByteListIterator::new(
-            self.data()[start..end].iter().map(|c| c.as_byte().unwrap()).collect(),
-        ))
+        Ok(ByteListIterator::new(self.byte_list_bytes_at(list_index, start, end)?))
     }
 
     fn get_symbol_list_len(&self, addr: Self::Size) -> Result<Self::Size, Self::Error> {
@@ -387,12 +388,29 @@ where
         self.push_to_data_block(BasicData::Partial(reciever, input))
     }
 
+    fn add_closure(&mut self, expression: Self::Size, captures: Self::Size) -> Result<Self::Size, Self::Error> {
+        self.push_to_data_block(BasicData::Closure(expression, captures))
+    }
+
+    fn add_error(&mut self, message: Self::Size, code: Self::Size) -> Result<Self::Size, Self::Error> {
+        self.push_to_data_block(BasicData::Error(message, code))
+    }
+
+    fn add_char_list(&mut self, chars: Vec<Self::Char>) -> Result<Self::Size, Self::Error> {
+        self.add_string(&chars.into_iter().collect::<String>())
+    }
+
+    fn add_byte_list(&mut self, bytes: Vec<Self::Byte>) -> Result<Self::Size, Self::Error> {
+        self.add_byte_slice(&bytes)
+    }
+
     fn merge_to_symbol_list(&mut self, first: Self::Size, second: Self::Size) -> Result<Self::Size, Self::Error> {
         merge_to_symbol_list(self, first, second)
     }
 
     fn start_list(&mut self, len: Self::Size) -> Result<Self::Size, Self::Error> {
-        let allocation_size = len * 2;
+        // the hash index, if needed, is only added by end_list once the associations are known
+        let allocation_size = list_allocation_size(len, 0);
         let list_index = self.push_to_data_block(BasicData::UninitializedList(len, 0))?;
         for _ in 0..allocation_size {
             self.push_to_data_block(BasicData::Empty)?;
@@ -438,6 +456,10 @@ where
         }
 
         let len = len.clone();
+        // sorting moves associations between slots
+        for i in list_index + 1 + len..list_index + 1 + len * 2 {
+            self.data_write_barrier(i)?;
+        }
         let start = self.data_block().start + list_index + 1 + len;
         let associations_end = start + len;
         let associations_range = start..associations_end;
@@ -458,6 +480,11 @@ where
             _ => Ordering::Equal,
         });
 
+        let list_index = match hash_index_size(associations_count) {
+            0 => list_index,
+            index_size => self.add_list_hash_index(list_index, len, associations_count, index_size)?,
+        };
+
         let list_item = BasicData::List(len, associations_count);
 
         let item = self.get_from_data_block_ensure_index_mut(list_index)?;
@@ -565,6 +592,10 @@ where
         Ok(())
     }
 
+    fn safe_point(&mut self) -> Result<(), Self::Error> {
+        self.collection_safe_point()
+    }
+
     fn get_jump_table_len(&self) -> Self::Size {
         self.jump_table_block().cursor
     }
@@ -589,6 +620,10 @@ where
     }
 
     fn push_frame(&mut self, index: Self::Size) -> Result<(), Self::Error> {
+        if let Some(locals) = self.current_local() {
+            self.push_to_data_block(BasicData::FrameLocals(locals))?;
+            self.set_current_local(None);
+        }
         self.push_to_data_block(BasicData::JumpPoint(index))?;
         let frame_data = match (self.current_frame(), self.current_register()) {
             (Some(frame), Some(register)) => BasicData::Frame(frame, register),
@@ -613,24 +648,81 @@ where
                     BasicData::FrameRoot => (None, None),
                     _ => return Err(DataError::not_basic_type_error()),
                 };
+                let locals = self.frame_locals(index);
                 self.set_current_frame(previous_opt);
                 self.set_current_register(register_opt);
+                self.set_current_local(locals);
                 Some(return_index)
             }
             None => None,
         })
     }
 
+    fn get_local(&self, index: Self::Size) -> Option<Self::Size> {
+        let nodes = self.local_nodes().ok()?;
+        let node = nodes.len().checked_sub(index + 1).map(|i| nodes[i])?;
+        match self.get_from_data_block_ensure_index(node) {
+            Ok(BasicData::Register(_, value)) | Ok(BasicData::RegisterRoot(value)) => Some(*value),
+            _ => None,
+        }
+    }
+
+    fn set_local(&mut self, index: Self::Size, addr: Self::Size) -> Result<(), Self::Error> {
+        let nodes = self.local_nodes()?;
+        if let Some(node) = nodes.len().checked_sub(index + 1).map(|i| nodes[i]) {
+            match self.get_from_data_block_ensure_index_mut(node)? {
+                BasicData::Register(_, value) | BasicData::RegisterRoot(value) => *value = addr,
+                _ => return Err(DataError::not_basic_type_error()),
+            }
+            return Ok(());
+        }
+
+        for slot in nodes.len()..=index {
+            let value = if slot == index { addr } else { self.add_unit()? };
+            let node = match self.current_local() {
+                Some(previous) => self.push_to_data_block(BasicData::Register(previous, value))?,
+                None => self.push_to_data_block(BasicData::RegisterRoot(value))?,
+            };
+            self.set_current_local(Some(node));
+        }
+
+        Ok(())
+    }
+
+    fn push_handler(&mut self, index: Self::Size) -> Result<(), Self::Error> {
+        let handler = BasicHandler {
+            jump_index: index,
+            value: self.current_value(),
+            register: self.current_register(),
+            frame: self.current_frame(),
+            local: self.current_local(),
+        };
+        self.handlers_mut().push(handler);
+        self.handler_push_barrier(self.handlers().len() - 1);
+        Ok(())
+    }
+
+    fn pop_handler(&mut self) -> Result<Option<Self::Size>, Self::Error> {
+        Ok(self.handlers_mut().pop().map(|handler| handler.jump_index))
+    }
+
+    fn unwind_handler(&mut self) -> Result<Option<Self::Size>, Self::Error> {
+        Ok(self.handlers_mut().pop().map(|handler| {
+            self.set_current_value(handler.value);
+            self.set_current_register(handler.register);
+            self.set_current_frame(handler.frame);
+            self.set_current_local(handler.local);
+            handler.jump_index
+        }))
+    }
+
     fn add_char_list_from(&mut self, from: Self::Size) -> Result<Self::Size, Self::Error> {
         self.convert_basic_data_at_to_char_list(from)
     }
 
     fn add_byte_list_from(&mut self, from: Self::Size) -> Result<Self::Size, Self::Error> {
         let bytes = self.convert_basic_data_at_to_bytes(from)?;
-        self.push_to_data_block(BasicData::ByteList(bytes.len()))?;
-        for byte in bytes {
-            self.push_to_data_block(BasicData::Byte(byte))?;
-        }
+        self.add_byte_slice(&bytes)?;
         Ok(from)
     }
 
@@ -648,31 +740,23 @@ where
 
     fn parse_add_char_list(&mut self, from: &str) -> Result<Self::Size, Self::Error> {
         let chars = Self::DataFactory::parse_char_list(from)?;
-        let len = chars.len();
-        let list_index = self.push_to_data_block(BasicData::CharList(len))?;
-        for c in chars {
-            self.push_to_data_block(BasicData::Char(c))?;
-        }
-        Ok(list_index)
+        self.add_char_list(chars)
     }
 
     fn parse_add_byte_list(&mut self, from: &str) -> Result<Self::Size, Self::Error> {
         let bytes = Self::DataFactory::parse_byte_list(from)?;
-        let len = bytes.len();
-        let list_index = self.push_to_data_block(BasicData::ByteList(len))?;
-        for b in bytes {
-            self.push_to_data_block(BasicData::Byte(b))?;
-        }
-        Ok(list_index)
+        self.add_byte_slice(&bytes)
+    }
+
+    fn parse_add_number(&mut self, from: &str) -> Result<Self::Size, Self::Error> {
+        let number = parse_simple_number_with(from, self.overflow_policy)?;
+        self.add_number(number)
     }
 
     fn parse_add_symbol(&mut self, from: &str) -> Result<Self::Size, Self::Error> {
         let symbol = Self::DataFactory::parse_symbol(from)?;
         let symbol_index = self.push_to_data_block(BasicData::Symbol(symbol))?;
-        let list_index = self.push_to_data_block(BasicData::CharList(from.len()))?;
-        for c in from.chars() {
-            self.push_to_data_block(BasicData::Char(c))?;
-        }
+        let list_index = self.add_string(from)?;
         self.push_to_symbol_table_block(symbol, list_index)?;
         Ok(symbol_index)
     }
@@ -693,6 +777,22 @@ where
     fn resolve(&mut self, symbol: Self::Symbol) -> Result<bool, Self::Error> {
         Companion::resolve(self, symbol)
     }
+
+    fn error_values(&self) -> bool {
+        self.error_values
+    }
+
+    fn strict_mode(&self) -> bool {
+        self.strict_mode
+    }
+
+    fn number_op(&self, operation: Instruction, left: Self::Number, right: Self::Number) -> Option<Self::Number> {
+        self.overflow_policy.apply(operation, left, right)
+    }
+
+    fn unary_number_op(&self, operation: Instruction, value: Self::Number) -> Option<Self::Number> {
+        self.overflow_policy.apply_unary(operation, value)
+    }
 }
 
 #[cfg(test)]
@@ -700,8 +800,9 @@ mod tests {
     use garnish_lang_traits::Instruction;
 
     use crate::{
-        BasicData, BasicDataCustom, basic::{
+        BasicData, BasicDataCustom, BasicGarnishDataUnit, HASHED_LIST_THRESHOLD, PACKED_CHUNK_SIZE, SimpleNumber, basic::{
             companion::BasicDataCompanion,
+            search::search_for_associative_item_index,
             storage::{ReallocationStrategy, StorageSettings}, utilities::{instruction_test_data, jump_table_test_data, test_data}
         }, basic_object, error::DataErrorType, DataError
     };
@@ -914,6 +1015,30 @@ mod tests {
         assert_eq!(data, expected_data);
     }
 
+    #[test]
+    fn add_closure() {
+        let mut data = test_data();
+        data.add_closure(100, 200).unwrap();
+
+        let mut expected_data = test_data();
+        expected_data.data_mut()[0] = BasicData::Closure(100, 200);
+        expected_data.data_block_mut().cursor = 1;
+
+        assert_eq!(data, expected_data);
+    }
+
+    #[test]
+    fn add_error() {
+        let mut data = test_data();
+        data.add_error(100, 200).unwrap();
+
+        let mut expected_data = test_data();
+        expected_data.data_mut()[0] = BasicData::Error(100, 200);
+        expected_data.data_block_mut().cursor = 1;
+
+        assert_eq!(data, expected_data);
+    }
+
     #[test]
     fn get_data_type_ok() {
         let mut data = test_data();
@@ -1058,6 +1183,26 @@ mod tests {
         assert_eq!(result, Ok(100));
     }
 
+    #[test]
+    fn cmp_symbol_names_orders_by_name_without_adding_data() {
+        let mut data = test_data();
+        let mut add_symbol = |name: &str| {
+            let addr = data.parse_add_symbol(name).unwrap();
+            data.get_symbol(addr).unwrap()
+        };
+        let apple = add_symbol("apple");
+        let banana = add_symbol("banana");
+        let long_name = add_symbol(&"a".repeat(PACKED_CHUNK_SIZE * 2));
+        let cursor = data.data_block().cursor;
+
+        assert_eq!(data.cmp_symbol_names(apple, banana), Ok(Ordering::Less));
+        assert_eq!(data.cmp_symbol_names(long_name, apple), Ok(Ordering::Less));
+        assert_eq!(data.cmp_symbol_names(banana, banana), Ok(Ordering::Equal));
+        assert_eq!(data.cmp_symbol_names(1, apple), Ok(Ordering::Greater));
+        assert_eq!(data.cmp_symbol_names(1, 2), Ok(Ordering::Less));
+        assert_eq!(data.data_block().cursor, cursor);
+    }
+
     #[test]
     fn get_symbol_not_symbol() {
         let mut data = test_data();
@@ -1200,6 +1345,50 @@ mod tests {
         assert_eq!(result, Err(DataError::new("Invalid data index", DataErrorType::InvalidDataIndex(1))));
     }
 
+    #[test]
+    fn get_closure_ok() {
+        let mut data = test_data();
+        data.push_to_data_block(BasicData::Closure(100, 200)).unwrap();
+        let result = data.get_closure(0);
+        assert_eq!(result, Ok((100, 200)));
+    }
+
+    #[test]
+    fn get_closure_not_closure() {
+        let mut data = test_data();
+        data.push_to_data_block(BasicData::Number(100.into())).unwrap();
+        let result = data.get_closure(0);
+        assert_eq!(
+            result,
+            Err(DataError::new(
+                "Not of type",
+                DataErrorType::NotType(GarnishDataType::Closure, GarnishDataType::Number)
+            ))
+        );
+    }
+
+    #[test]
+    fn get_error_ok() {
+        let mut data = test_data();
+        data.push_to_data_block(BasicData::Error(100, 200)).unwrap();
+        let result = data.get_error(0);
+        assert_eq!(result, Ok((100, 200)));
+    }
+
+    #[test]
+    fn get_error_not_error() {
+        let mut data = test_data();
+        data.push_to_data_block(BasicData::Number(100.into())).unwrap();
+        let result = data.get_error(0);
+        assert_eq!(
+            result,
+            Err(DataError::new(
+                "Not of type",
+                DataErrorType::NotType(GarnishDataType::Error, GarnishDataType::Number)
+            ))
+        );
+    }
+
     #[test]
     fn get_concatenation_ok() {
         let mut data = test_data();
@@ -1603,6 +1792,128 @@ mod tests {
         assert_eq!(result, Err(DataError::new("Invalid data index", DataErrorType::InvalidDataIndex(100))));
     }
 
+    fn hashed_list_data() -> (BasicGarnishDataUnit, usize) {
+        let mut data = test_data();
+        let len = HASHED_LIST_THRESHOLD + 10;
+        let items = (0..len)
+            .map(|i| {
+                let sym = data.add_symbol(i as u64 * 7 + 3).unwrap();
+                let value = data.add_number(SimpleNumber::Integer(i as i64)).unwrap();
+                data.add_pair((sym, value)).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let list = data.start_list(len).unwrap();
+        for item in items.iter().rev() {
+            data.add_to_list(list, *item).unwrap();
+        }
+        let list = data.end_list(list).unwrap();
+        (data, list)
+    }
+
+    fn assert_hashed_list_lookups(data: &BasicGarnishDataUnit, list: usize) {
+        for i in 0..HASHED_LIST_THRESHOLD + 10 {
+            let value = data.get_list_item_with_symbol(list, i as u64 * 7 + 3).unwrap().unwrap();
+            assert_eq!(data.get_number(value), Ok(SimpleNumber::Integer(i as i64)));
+        }
+        assert_eq!(data.get_list_item_with_symbol(list, 1), Ok(None));
+        assert_eq!(data.get_list_item_with_symbol(list, 7 * 1000 + 3), Ok(None));
+    }
+
+    #[test]
+    fn get_list_item_with_symbol_hashed_list() {
+        let (data, list) = hashed_list_data();
+
+        assert_eq!(data.get_from_data_block_ensure_index(list), Ok(&BasicData::List(HASHED_LIST_THRESHOLD + 10, HASHED_LIST_THRESHOLD + 10)));
+        assert_hashed_list_lookups(&data, list);
+    }
+
+    #[test]
+    fn get_list_item_with_symbol_hashed_list_keeps_order() {
+        let (data, list) = hashed_list_data();
+        let len = HASHED_LIST_THRESHOLD + 10;
+
+        for i in 0..len {
+            let item = data.get_list_item(list, SimpleNumber::Integer(i as i64)).unwrap().unwrap();
+            let (left, _) = data.get_pair(item).unwrap();
+            assert_eq!(data.get_symbol(left), Ok((len - 1 - i) as u64 * 7 + 3));
+        }
+    }
+
+    #[test]
+    fn get_list_item_with_symbol_hashed_list_after_clone_and_optimize() {
+        let (mut data, list) = hashed_list_data();
+
+        let cloned = data.clone_data(list).unwrap();
+        assert_hashed_list_lookups(&data, cloned);
+
+        let retained = data.optimize(&[list]).unwrap();
+        assert_hashed_list_lookups(&data, retained[0]);
+    }
+
+    #[test]
+    fn get_list_item_with_symbol_hashed_list_built_while_adding_data() {
+        let mut data = test_data();
+        let len = HASHED_LIST_THRESHOLD + 10;
+
+        let list = data.start_list(len).unwrap();
+        for i in 0..len {
+            let sym = data.add_symbol(i as u64 * 7 + 3).unwrap();
+            let value = data.add_number(SimpleNumber::Integer(i as i64)).unwrap();
+            let pair = data.add_pair((sym, value)).unwrap();
+            data.add_to_list(list, pair).unwrap();
+        }
+        let moved = data.end_list(list).unwrap();
+
+        assert_ne!(moved, list);
+        assert_eq!(data.get_from_data_block_ensure_index(moved), Ok(&BasicData::List(len, len)));
+        assert_hashed_list_lookups(&data, moved);
+        assert_eq!(data.data_block().cursor, moved + 1 + list_allocation_size(len, len));
+    }
+
+    #[test]
+    fn long_list_with_few_associations_has_no_hash_index() {
+        let mut data = test_data();
+        let len = HASHED_LIST_THRESHOLD + 10;
+        let items = (0..len).map(|i| data.add_number(SimpleNumber::Integer(i as i64)).unwrap()).collect::<Vec<_>>();
+        let sym = data.add_symbol(5).unwrap();
+        let value = data.add_number(SimpleNumber::Integer(50)).unwrap();
+        let pair = data.add_pair((sym, value)).unwrap();
+
+        let list = data.start_list(len + 1).unwrap();
+        for item in items.iter().chain([&pair]) {
+            data.add_to_list(list, *item).unwrap();
+        }
+        let list = data.end_list(list).unwrap();
+
+        assert_eq!(data.data_block().cursor, list + 1 + (len + 1) * 2);
+        assert_eq!(data.get_list_item_with_symbol(list, 5), Ok(Some(value)));
+    }
+
+    #[test]
+    fn get_list_item_with_symbol_hashed_list_repeated_symbol_uses_last() {
+        let mut data = test_data();
+        let len = HASHED_LIST_THRESHOLD + 10;
+        let items = (0..len)
+            .map(|i| {
+                let sym = data.add_symbol((i % 2) as u64 + 3).unwrap();
+                let value = data.add_number(SimpleNumber::Integer(i as i64)).unwrap();
+                data.add_pair((sym, value)).unwrap()
+            })
+            .collect::<Vec<_>>();
+
+        let list = data.start_list(len).unwrap();
+        for item in items.iter() {
+            data.add_to_list(list, *item).unwrap();
+        }
+        let list = data.end_list(list).unwrap();
+
+        let found = data.find_list_association(list, 3).unwrap().unwrap();
+        let start = data.data_block().start + list + 1 + len;
+        let binary = search_for_associative_item_index(&data.data()[start..start + len], 3).unwrap().unwrap();
+        assert_eq!(found, start + binary);
+    }
+
     #[test]
     fn get_char_list_len_ok() {
         let mut data = test_data();
@@ -1749,6 +2060,37 @@ mod tests {
         assert_eq!(result, vec!['a', 'b', 'c', 'd', 'e']);
     }
 
+    #[test]
+    fn get_char_list_packed() {
+        let mut data = test_data();
+        let text = "abcdefghijklmnopqrstuvwxyzéabcdefghijklmnopqrstuvwxyz";
+        data.push_object_to_data_block(basic_object!(CharList text)).unwrap();
+
+        assert_eq!(data.get_char_list_len(0), Ok(53));
+        assert_eq!(data.get_char_list_item(0, 26.into()), Ok(Some('é')));
+        assert_eq!(data.get_char_list_item(0, 27.into()), Ok(Some('a')));
+        assert_eq!(data.get_char_list_item(0, 53.into()), Ok(None));
+        assert_eq!(
+            data.get_char_list_iter(0, Extents::new(24.into(), 29.into())).unwrap().collect::<String>(),
+            "yzéab"
+        );
+    }
+
+    #[test]
+    fn get_byte_list_packed() {
+        let mut data = test_data();
+        let bytes = (0..40).collect::<Vec<u8>>();
+        data.add_byte_slice(&bytes).unwrap();
+
+        assert_eq!(data.get_byte_list_len(0), Ok(40));
+        assert_eq!(data.get_byte_list_item(0, 33.into()), Ok(Some(33)));
+        assert_eq!(data.get_byte_list_item(0, 40.into()), Ok(None));
+        assert_eq!(
+            data.get_byte_list_iter(0, Extents::new(30.into(), 34.into())).unwrap().collect::<Vec<u8>>(),
+            vec![30, 31, 32, 33]
+        );
+    }
+
     #[test]
     fn get_byte_list_len_ok() {
         let mut data = test_data();
@@ -2725,6 +3067,83 @@ mod tests {
         assert_eq!(jump_path, None);
     }
 
+    #[test]
+    fn set_local() {
+        let mut data = test_data();
+        data.set_local(0, 5).unwrap();
+        data.set_local(1, 6).unwrap();
+        data.set_local(0, 7).unwrap();
+
+        let mut expected_data = test_data();
+        expected_data.data_mut()[0] = BasicData::RegisterRoot(7);
+        expected_data.data_mut()[1] = BasicData::Register(0, 6);
+        expected_data.set_current_local(Some(1));
+        expected_data.data_block_mut().cursor = 2;
+        assert_eq!(data, expected_data);
+
+        assert_eq!(data.get_local(0), Some(7));
+        assert_eq!(data.get_local(1), Some(6));
+        assert_eq!(data.get_local(2), None);
+    }
+
+    #[test]
+    fn set_local_past_end_fills_unit() {
+        let mut data = test_data();
+        data.set_local(1, 6).unwrap();
+
+        let unit = data.get_local(0).unwrap();
+        assert_eq!(data.get_data_type(unit), Ok(GarnishDataType::Unit));
+        assert_eq!(data.get_local(1), Some(6));
+    }
+
+    #[test]
+    fn frame_restores_locals() {
+        let mut data = test_data();
+        data.set_local(0, 5).unwrap();
+        data.push_frame(100).unwrap();
+
+        assert_eq!(data.get_local(0), None);
+        data.set_local(0, 6).unwrap();
+        assert_eq!(data.get_local(0), Some(6));
+
+        let jump_path = data.pop_frame().unwrap();
+        assert_eq!(jump_path, Some(100));
+        assert_eq!(data.get_local(0), Some(5));
+    }
+
+    #[test]
+    fn pop_handler_keeps_state() {
+        let mut data = test_data();
+        data.push_handler(3).unwrap();
+        data.push_register(5).unwrap();
+
+        assert_eq!(data.pop_handler(), Ok(Some(3)));
+        assert_eq!(data.pop_handler(), Ok(None));
+        assert_eq!(data.get_register_len(), 1);
+    }
+
+    #[test]
+    fn unwind_handler_restores_state() {
+        let mut data = test_data();
+        data.push_value_stack(1).unwrap();
+        data.push_register(2).unwrap();
+        data.set_local(0, 3).unwrap();
+        data.push_handler(4).unwrap();
+
+        data.push_value_stack(5).unwrap();
+        data.push_register(6).unwrap();
+        data.set_local(1, 7).unwrap();
+        data.push_frame(100).unwrap();
+
+        assert_eq!(data.unwind_handler(), Ok(Some(4)));
+        assert_eq!(data.unwind_handler(), Ok(None));
+        assert_eq!(data.get_current_value(), Some(1));
+        assert_eq!(data.get_register_len(), 1);
+        assert_eq!(data.pop_frame(), Ok(None));
+        assert_eq!(data.get_local(0), Some(3));
+        assert_eq!(data.get_local(1), None);
+    }
+
     #[test]
     fn parse_add_symbol() {
         let mut data = test_data();
@@ -2732,8 +3151,8 @@ mod tests {
 
         let mut expected_data = test_data();
         expected_data.data_mut().resize(30, BasicData::Empty);
-        expected_data.data_mut()[0] = BasicData::AssociativeItem(8904929874702161741, 1);
-        expected_data.data_mut()[10] = BasicData::Symbol(8904929874702161741);
+        expected_data.data_mut()[0] = BasicData::AssociativeItem(7248772672978976256, 1);
+        expected_data.data_mut()[10] = BasicData::Symbol(7248772672978976256);
         expected_data.data_mut()[11] = BasicData::CharList(9);
         expected_data.data_mut()[12] = BasicData::Char('m');
         expected_data.data_mut()[13] = BasicData::Char('y');
@@ -2763,7 +3182,7 @@ mod tests {
         data.add_byte_list_from(index).unwrap();
 
         let mut expected_data = test_data();
-        expected_data.data_mut().resize(60, BasicData::Empty);
+        expected_data.data_mut().resize(20, BasicData::Empty);
         expected_data.data_mut()[0] = BasicData::Number(100.into());
         expected_data.data_mut()[1] = BasicData::CharList(9);
         expected_data.data_mut()[2] = BasicData::Char('S');
@@ -2780,52 +3199,20 @@ mod tests {
         expected_data.data_mut()[13] = BasicData::ListItem(1);
         expected_data.data_mut()[14] = BasicData::Empty;
         expected_data.data_mut()[15] = BasicData::Empty;
-        expected_data.data_mut()[16] = BasicData::ByteList(40);
-        expected_data.data_mut()[17] = BasicData::Byte(100);
-        expected_data.data_mut()[18] = BasicData::Byte(0);
-        expected_data.data_mut()[19] = BasicData::Byte(0);
-        expected_data.data_mut()[20] = BasicData::Byte(0);
-        expected_data.data_mut()[21] = BasicData::Byte(83);
-        expected_data.data_mut()[22] = BasicData::Byte(0);
-        expected_data.data_mut()[23] = BasicData::Byte(0);
-        expected_data.data_mut()[24] = BasicData::Byte(0);
-        expected_data.data_mut()[25] = BasicData::Byte(111);
-        expected_data.data_mut()[26] = BasicData::Byte(0);
-        expected_data.data_mut()[27] = BasicData::Byte(0);
-        expected_data.data_mut()[28] = BasicData::Byte(0);
-        expected_data.data_mut()[29] = BasicData::Byte(109);
-        expected_data.data_mut()[30] = BasicData::Byte(0);
-        expected_data.data_mut()[31] = BasicData::Byte(0);
-        expected_data.data_mut()[32] = BasicData::Byte(0);
-        expected_data.data_mut()[33] = BasicData::Byte(101);
-        expected_data.data_mut()[34] = BasicData::Byte(0);
-        expected_data.data_mut()[35] = BasicData::Byte(0);
-        expected_data.data_mut()[36] = BasicData::Byte(0);
-        expected_data.data_mut()[37] = BasicData::Byte(32);
-        expected_data.data_mut()[38] = BasicData::Byte(0);
-        expected_data.data_mut()[39] = BasicData::Byte(0);
-        expected_data.data_mut()[40] = BasicData::Byte(0);
-        expected_data.data_mut()[41] = BasicData::Byte(84);
-        expected_data.data_mut()[42] = BasicData::Byte(0);
-        expected_data.data_mut()[43] = BasicData::Byte(0);
-        expected_data.data_mut()[44] = BasicData::Byte(0);
-        expected_data.data_mut()[45] = BasicData::Byte(101);
-        expected_data.data_mut()[46] = BasicData::Byte(0);
-        expected_data.data_mut()[47] = BasicData::Byte(0);
-        expected_data.data_mut()[48] = BasicData::Byte(0);
-        expected_data.data_mut()[49] = BasicData::Byte(120);
-        expected_data.data_mut()[50] = BasicData::Byte(0);
-        expected_data.data_mut()[51] = BasicData::Byte(0);
-        expected_data.data_mut()[52] = BasicData::Byte(0);
-        expected_data.data_mut()[53] = BasicData::Byte(116);
-        expected_data.data_mut()[54] = BasicData::Byte(0);
-        expected_data.data_mut()[55] = BasicData::Byte(0);
-        expected_data.data_mut()[56] = BasicData::Byte(0);
-
-        expected_data.data_block_mut().cursor = 57;
-        expected_data.data_block_mut().size = 60;
+        let mut bytes = vec![100, 0, 0, 0, 0, 0, 0, 0];
+        bytes.extend("Some Text".chars().flat_map(|c| u32::from(c).to_le_bytes()));
+        let mut first = [0; PACKED_CHUNK_SIZE];
+        first.copy_from_slice(&bytes[..PACKED_CHUNK_SIZE]);
+        let mut second = [0; PACKED_CHUNK_SIZE];
+        second[..12].copy_from_slice(&bytes[PACKED_CHUNK_SIZE..]);
+        expected_data.data_mut()[16] = BasicData::PackedByteList(44);
+        expected_data.data_mut()[17] = BasicData::PackedBytes(first);
+        expected_data.data_mut()[18] = BasicData::PackedBytes(second);
+
+        expected_data.data_block_mut().cursor = 19;
+        expected_data.data_block_mut().size = 20;
         expected_data.data_block_mut().start = 0;
-        expected_data.custom_data_block_mut().start = 60;
+        expected_data.custom_data_block_mut().start = 20;
 
         assert_eq!(data, expected_data);
     }
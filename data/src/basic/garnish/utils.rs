@@ -3,7 +3,10 @@ use garnish_lang_traits::Extents;
 use crate::BasicNumber;
 
 pub(crate) fn extents_to_start_end(extents: Extents<BasicNumber>, base: usize, len: usize) -> (usize, usize) {
-    let start: usize = base + 1 + usize::from(extents.start()).min(len);
-    let end: usize = base + 1 + (usize::from(extents.end())).min(len);
-    (start, end)
+    let (start, end) = extents_to_item_range(extents, len);
+    (base + 1 + start, base + 1 + end)
+}
+
+pub(crate) fn extents_to_item_range(extents: Extents<BasicNumber>, len: usize) -> (usize, usize) {
+    (usize::from(extents.start()).min(len), usize::from(extents.end()).min(len))
 }
\ No newline at end of file
@@ -0,0 +1,1240 @@
+use std::collections::HashMap;
+use std::sync::atomic::Ordering as AtomicOrdering;
+
+use garnish_lang_traits::Instruction;
+
+use crate::basic::clone::CloneDelegate;
+use crate::basic::ordering::OrderingDelegate;
+use crate::basic::packed::{packed_char_slot_count, packed_slot_count};
+use crate::basic::search::list_allocation_size;
+use crate::{BasicData, BasicDataCustom, BasicGarnishData, DataError, basic::companion::BasicDataCompanion, error::DataErrorType};
+
+/// Controls the automatic collector of a [`BasicGarnishData`].
+///
+/// When enabled, a collection cycle starts once the data block has grown by `trigger_growth` items since the end of the
+/// previous cycle. Each [`GarnishData::safe_point`](garnish_lang_traits::GarnishData::safe_point), which the runtime reaches after every
+/// instruction, then does about `step_size` items of work, first marking reachable data, then sweeping to find where it will move and
+/// finally sliding it down.
+///
+/// Data allocated during a cycle is kept until the next one but still has to move, so once marking is over a step also takes on the
+/// work the program added since the previous step, the slots it allocated and the references it recorded, and a cycle always
+/// finishes. A step also finishes the allocation it is working on, going over by the size of that allocation and the references to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CollectionSettings {
+    pub(crate) enabled: bool,
+    pub(crate) trigger_growth: usize,
+    pub(crate) step_size: usize,
+}
+
+impl Default for CollectionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            trigger_growth: 1024,
+            step_size: 256,
+        }
+    }
+}
+
+impl CollectionSettings {
+    /// Settings with automatic collection enabled.
+    pub fn new(trigger_growth: usize, step_size: usize) -> Self {
+        Self {
+            enabled: true,
+            trigger_growth,
+            step_size,
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn trigger_growth(&self) -> usize {
+        self.trigger_growth
+    }
+
+    pub fn step_size(&self) -> usize {
+        self.step_size
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) struct Collector {
+    pub(crate) settings: CollectionSettings,
+    pub(crate) phase: CollectionPhase,
+    pub(crate) collected_size: usize,
+    pub(crate) longest_step: usize,
+    pub(crate) cycle_longest_step: usize,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub(crate) enum CollectionPhase {
+    #[default]
+    Idle,
+    Marking(MarkState),
+    Sweeping(ReclaimState),
+    Moving(ReclaimState),
+}
+
+/// Marking state for data in `retained..boundary`, data allocated at or after `boundary` is live for the current cycle.
+///
+/// Marking works from a snapshot of the roots taken when the cycle started. Instructions and symbols are only ever added, so they
+/// are scanned a step at a time, and the write barrier marks whatever a slot referenced before it is changed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MarkState {
+    retained: usize,
+    boundary: usize,
+    marks: Vec<bool>,
+    gray: Vec<usize>,
+    instruction_roots: usize,
+    symbol_roots: usize,
+}
+
+impl MarkState {
+    fn is_marked(&self, index: usize) -> bool {
+        index < self.retained || index >= self.boundary || self.marks[index - self.retained]
+    }
+}
+
+/// Where an allocation that is going to move is referenced from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Reference {
+    /// A data slot that had not moved yet when it was recorded, by its index before moving.
+    Slot(usize),
+    /// A data slot that had already moved when it was recorded.
+    MovedSlot(usize),
+    Instruction(usize),
+    Symbol(u64),
+    Handler(usize),
+    Pinned(usize),
+    Retention(usize),
+}
+
+/// Sweeping and moving state for data in `retained..`, with `retained..boundary` marked and everything after live.
+///
+/// Sweeping walks `retained..limit`, the data block when marking finished, recording where each live slot will move to and every
+/// reference to data that may move. Data allocated after that is recorded as it is pushed. Moving then slides allocations down one
+/// at a time, rewriting only the recorded references to each one. At any point an index below `to` is final and an index at or
+/// after `scan` hasn't moved, so a reference equal to the index being moved can only mean that allocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ReclaimState {
+    retained: usize,
+    boundary: usize,
+    marks: Vec<bool>,
+    limit: usize,
+    swept: usize,
+    reclaimed: usize,
+    forward: Vec<Option<usize>>,
+    instruction_roots: usize,
+    symbol_roots: usize,
+    references: HashMap<usize, Vec<Reference>>,
+    dirty: Vec<usize>,
+    debt: usize,
+    scan: usize,
+    to: usize,
+    retentions: Vec<usize>,
+}
+
+impl ReclaimState {
+    fn is_live(&self, index: usize) -> bool {
+        index < self.retained || index >= self.boundary || self.marks[index - self.retained]
+    }
+
+    /// New position of the live slot at `index`, data after `swept` moves down by everything reclaimed before it.
+    fn forward(&self, index: usize) -> Option<usize> {
+        if index < self.retained {
+            Some(index)
+        } else if index < self.swept {
+            self.forward[index - self.retained]
+        } else {
+            Some(index - self.reclaimed)
+        }
+    }
+
+    fn slot_position(&self, reference: &Reference) -> Option<usize> {
+        match reference {
+            Reference::Slot(index) if *index < self.scan => self.forward(*index),
+            Reference::Slot(index) | Reference::MovedSlot(index) => Some(*index),
+            _ => None,
+        }
+    }
+
+    /// Records a reference to `target` when it hasn't moved yet, returning whether it was recorded.
+    fn record(&mut self, target: usize, reference: Reference) -> bool {
+        if target < self.scan {
+            return false;
+        }
+        self.references.entry(target).or_default().push(reference);
+        true
+    }
+
+    /// Records the references held by the slot at `index`, returning how many were recorded.
+    fn record_slot<T: BasicDataCustom, Companion: BasicDataCompanion<T>>(&mut self, index: usize, slot: &BasicData<T>) -> Result<usize, DataError> {
+        let reference = match index {
+            index if index < self.retained => return Ok(0),
+            index if index < self.to => Reference::MovedSlot(index),
+            index if index < self.scan => return Ok(0),
+            index => Reference::Slot(index),
+        };
+
+        let mut targets = vec![];
+        slot_references::<T, Companion>(slot, &mut targets)?;
+        Ok(targets.into_iter().filter(|target| self.record(*target, reference.clone())).count())
+    }
+}
+
+/// Number of data block slots taken by the value at the start of an allocation.
+pub(crate) fn data_extent<T: BasicDataCustom>(data: &BasicData<T>) -> usize {
+    match data {
+        BasicData::CharList(len) | BasicData::ByteList(len) | BasicData::SymbolList(len) => 1 + len,
+        BasicData::PackedCharList(len, byte_len) => 1 + packed_char_slot_count(*len, *byte_len),
+        BasicData::PackedByteList(len) => 1 + packed_slot_count(*len),
+        BasicData::List(len, associations_count) => 1 + list_allocation_size(*len, *associations_count),
+        BasicData::UninitializedList(len, _) => 1 + list_allocation_size(*len, 0),
+        _ => 1,
+    }
+}
+
+/// Pushes the data indexes held by a single slot, a list's items are held by the slots after it.
+fn slot_references<T: BasicDataCustom, Companion: BasicDataCompanion<T>>(slot: &BasicData<T>, references: &mut Vec<usize>) -> Result<(), DataError> {
+    match slot {
+        BasicData::Pair(left, right)
+        | BasicData::Range(left, right)
+        | BasicData::Slice(left, right)
+        | BasicData::Partial(left, right)
+        | BasicData::Closure(left, right)
+        | BasicData::Error(left, right)
+        | BasicData::Concatenation(left, right)
+        | BasicData::Value(left, right)
+        | BasicData::Register(left, right)
+        | BasicData::Frame(left, right) => {
+            references.push(*right);
+            references.push(*left);
+        }
+        BasicData::ValueRoot(value)
+        | BasicData::RegisterRoot(value)
+        | BasicData::FrameIndex(value)
+        | BasicData::FrameRegister(value)
+        | BasicData::FrameLocals(value)
+        | BasicData::InstructionWithData(_, value)
+        | BasicData::ListItem(value)
+        | BasicData::AssociativeItem(_, value) => references.push(*value),
+        BasicData::Custom(custom) => {
+            let mut delegate = GrayDelegate { gray: references };
+            T::push_clone_items_for_custom_data::<Companion>(&mut delegate, custom.clone())?;
+        }
+        _ => {}
+    }
+
+    Ok(())
+}
+
+struct GrayDelegate<'a> {
+    gray: &'a mut Vec<usize>,
+}
+
+impl<'a, Companion> OrderingDelegate<Companion> for GrayDelegate<'a> {
+    fn push_clone_items(&mut self, value: usize) -> Result<(), DataError> {
+        self.gray.push(value);
+        Ok(())
+    }
+}
+
+/// Points references to the allocation at `from` to `to`, leaving every other reference alone.
+struct Relocation {
+    from: usize,
+    to: usize,
+}
+
+impl Relocation {
+    fn lookup(&self, index: usize) -> usize {
+        if index == self.from { self.to } else { index }
+    }
+
+    fn remap<T: BasicDataCustom, Companion: BasicDataCompanion<T>>(&mut self, data: BasicData<T>) -> Result<BasicData<T>, DataError> {
+        Ok(match data {
+            BasicData::Pair(left, right) => BasicData::Pair(self.lookup(left), self.lookup(right)),
+            BasicData::Range(left, right) => BasicData::Range(self.lookup(left), self.lookup(right)),
+            BasicData::Slice(left, right) => BasicData::Slice(self.lookup(left), self.lookup(right)),
+            BasicData::Partial(left, right) => BasicData::Partial(self.lookup(left), self.lookup(right)),
+            BasicData::Closure(expression, captures) => BasicData::Closure(self.lookup(expression), self.lookup(captures)),
+            BasicData::Error(message, code) => BasicData::Error(self.lookup(message), self.lookup(code)),
+            BasicData::Concatenation(left, right) => BasicData::Concatenation(self.lookup(left), self.lookup(right)),
+            BasicData::ListItem(item) => BasicData::ListItem(self.lookup(item)),
+            BasicData::AssociativeItem(symbol, item) => BasicData::AssociativeItem(symbol, self.lookup(item)),
+            BasicData::Value(previous, value) => BasicData::Value(self.lookup(previous), self.lookup(value)),
+            BasicData::ValueRoot(value) => BasicData::ValueRoot(self.lookup(value)),
+            BasicData::Register(previous, value) => BasicData::Register(self.lookup(previous), self.lookup(value)),
+            BasicData::RegisterRoot(value) => BasicData::RegisterRoot(self.lookup(value)),
+            BasicData::InstructionWithData(instruction, data) => BasicData::InstructionWithData(instruction, self.lookup(data)),
+            BasicData::Frame(previous, register) => BasicData::Frame(self.lookup(previous), self.lookup(register)),
+            BasicData::FrameIndex(previous) => BasicData::FrameIndex(self.lookup(previous)),
+            BasicData::FrameRegister(register) => BasicData::FrameRegister(self.lookup(register)),
+            BasicData::FrameLocals(locals) => BasicData::FrameLocals(self.lookup(locals)),
+            BasicData::Custom(custom) => BasicData::Custom(T::create_cloned_custom_data::<Companion>(self, custom)?),
+            data => data,
+        })
+    }
+}
+
+impl<Companion> CloneDelegate<Companion> for Relocation {
+    fn lookup_value(&mut self, value: usize) -> Result<usize, DataError> {
+        Ok(self.lookup(value))
+    }
+}
+
+/// Only `Put` and `Resolve` carry a data index, other instruction data refers to jump table entries or counts.
+fn instruction_references_data(instruction: Instruction) -> bool {
+    matches!(instruction, Instruction::Put | Instruction::Resolve)
+}
+
+fn collected_data_error(index: usize) -> DataError {
+    DataError::new("Collected data is still referenced", DataErrorType::CollectedDataReferenced(index))
+}
+
+impl<T, Companion> BasicGarnishData<T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    pub fn collection_settings(&self) -> &CollectionSettings {
+        &self.collector().settings
+    }
+
+    pub fn set_collection_settings(&mut self, settings: CollectionSettings) {
+        self.collector_mut().settings = settings;
+    }
+
+    /// Most work done by a single safe point during the last cycle finished by safe points.
+    ///
+    /// Work is counted in items marked, slots swept or moved and references rewritten, the same units as
+    /// [`CollectionSettings::step_size`] and including the work taken on for data the program added during the step before.
+    pub fn longest_collection_step(&self) -> usize {
+        self.collector().longest_step
+    }
+
+    /// Whether a collection cycle has started and not yet moved its live data down.
+    pub fn collection_in_progress(&self) -> bool {
+        !matches!(self.collector().phase, CollectionPhase::Idle)
+    }
+
+    /// Runs a full collection immediately, finishing any cycle already in progress.
+    ///
+    /// Roots are the value stack, registers, frames, locals, symbol table, pinned [`DataHandle`](crate::DataHandle)s and the data used by `Put` and `Resolve` instructions.
+    /// Data below [`BasicGarnishData::data_retention_count`] is never moved or collected.
+    /// Any other index the host keeps must be passed in `additional_data_retentions`, the new indexes are returned in the same order.
+    pub fn collect_garbage(&mut self, additional_data_retentions: &[usize]) -> Result<Vec<usize>, DataError> {
+        let mut retentions = additional_data_retentions.to_vec();
+        if matches!(self.collector().phase, CollectionPhase::Sweeping(_) | CollectionPhase::Moving(_)) {
+            // marking is already over, so reclaim what it found before marking again with the retentions
+            retentions = self.finish_collection(&retentions)?;
+        }
+
+        if !self.collection_in_progress() {
+            self.start_collection()?;
+        }
+        self.finish_collection(&retentions)
+    }
+
+    /// Advances automatic collection by one step, called from the safe point the runtime reaches between instructions.
+    pub(crate) fn collection_safe_point(&mut self) -> Result<(), DataError> {
+        if !self.collector().settings.enabled {
+            return Ok(());
+        }
+
+        let retained = match &self.collector().phase {
+            CollectionPhase::Idle => None,
+            CollectionPhase::Marking(state) => Some(state.retained),
+            CollectionPhase::Sweeping(state) | CollectionPhase::Moving(state) => Some(state.retained),
+        };
+        if retained.is_some_and(|retained| retained != self.data_retention_count()) {
+            // anything moved so far is already referenced from its new index, the rest stays where it is
+            self.collector_mut().phase = CollectionPhase::Idle;
+        }
+
+        if !self.collection_in_progress() {
+            let threshold = self.collector().collected_size.max(self.data_retention_count()) + self.collector().settings.trigger_growth;
+            if self.data_block().cursor < threshold {
+                return Ok(());
+            }
+            self.start_collection()?;
+        }
+
+        let work = self.collection_step(self.collector().settings.step_size.max(1))?;
+        let collector = self.collector_mut();
+        collector.cycle_longest_step = collector.cycle_longest_step.max(work);
+        if matches!(collector.phase, CollectionPhase::Idle) {
+            collector.longest_step = collector.cycle_longest_step;
+        }
+
+        Ok(())
+    }
+
+    /// Drops any in progress cycle, used when data is moved by something other than the collector.
+    pub(crate) fn reset_collection(&mut self) {
+        self.collector_mut().phase = CollectionPhase::Idle;
+        self.collector_mut().collected_size = self.data_block().cursor;
+    }
+
+    /// Write barrier for the data slot at `index`, called before the slot is changed in place.
+    ///
+    /// While marking, whatever the slot references now is marked, so everything reachable when the cycle started stays reachable.
+    /// Afterwards the slot is recorded and its new references are picked up at the start of the next step.
+    pub(crate) fn data_write_barrier(&mut self, index: usize) -> Result<(), DataError> {
+        match &self.collector().phase {
+            CollectionPhase::Idle => {}
+            CollectionPhase::Marking(_) => {
+                let mut references = vec![];
+                slot_references::<T, Companion>(self.get_from_data_block_ensure_index(index)?, &mut references)?;
+                if let CollectionPhase::Marking(state) = &mut self.collector_mut().phase {
+                    state.gray.extend(references);
+                }
+            }
+            CollectionPhase::Sweeping(_) | CollectionPhase::Moving(_) => {
+                if let CollectionPhase::Sweeping(state) | CollectionPhase::Moving(state) = &mut self.collector_mut().phase {
+                    state.dirty.push(index);
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the references of a slot pushed after marking finished, data pushed while marking is live for the cycle.
+    pub(crate) fn data_push_barrier(&mut self, index: usize) -> Result<(), DataError> {
+        if !matches!(self.collector().phase, CollectionPhase::Sweeping(_) | CollectionPhase::Moving(_)) {
+            return Ok(());
+        }
+
+        let slot = self.get_from_data_block_ensure_index(index)?.clone();
+        if let CollectionPhase::Sweeping(state) | CollectionPhase::Moving(state) = &mut self.collector_mut().phase {
+            state.debt += 1 + state.record_slot::<T, Companion>(index, &slot)?;
+        }
+        Ok(())
+    }
+
+    /// Keeps the data used by an instruction added during a cycle.
+    pub(crate) fn instruction_push_barrier(&mut self, instruction_index: usize, instruction: Instruction, data: Option<usize>) {
+        if let Some(data) = data
+            && instruction_references_data(instruction)
+        {
+            self.root_barrier(data, Reference::Instruction(instruction_index));
+        }
+    }
+
+    /// Keeps the name of a symbol added during a cycle.
+    pub(crate) fn symbol_push_barrier(&mut self, symbol: u64, value: usize) {
+        self.root_barrier(value, Reference::Symbol(symbol));
+    }
+
+    /// Keeps the data pinned during a cycle.
+    pub(crate) fn pin_barrier(&mut self, position: usize, index: usize) {
+        self.root_barrier(index, Reference::Pinned(position));
+    }
+
+    /// Records the state saved by a handler pushed after marking finished, it was reachable from the current roots when saved.
+    pub(crate) fn handler_push_barrier(&mut self, position: usize) {
+        let handler = self.handlers()[position];
+        if let CollectionPhase::Sweeping(state) | CollectionPhase::Moving(state) = &mut self.collector_mut().phase {
+            for index in [handler.value, handler.register, handler.frame, handler.local].into_iter().flatten() {
+                if state.record(index, Reference::Handler(position)) {
+                    state.debt += 1;
+                }
+            }
+        }
+    }
+
+    fn root_barrier(&mut self, index: usize, reference: Reference) {
+        match &mut self.collector_mut().phase {
+            CollectionPhase::Idle => {}
+            CollectionPhase::Marking(state) => state.gray.push(index),
+            CollectionPhase::Sweeping(state) | CollectionPhase::Moving(state) => {
+                if state.record(index, reference) {
+                    state.debt += 1;
+                }
+            }
+        }
+    }
+
+    fn start_collection(&mut self) -> Result<(), DataError> {
+        let retained = self.data_retention_count();
+        let boundary = self.data_block().cursor;
+        let mut gray = vec![];
+        gray.extend(self.current_value());
+        gray.extend(self.current_register());
+        gray.extend(self.current_frame());
+        gray.extend(self.current_local());
+
+        for handler in self.handlers() {
+            gray.extend(handler.value);
+            gray.extend(handler.register);
+            gray.extend(handler.frame);
+            gray.extend(handler.local);
+        }
+
+        gray.extend(self.pinned().indexes());
+
+        let collector = self.collector_mut();
+        collector.cycle_longest_step = 0;
+        collector.phase = CollectionPhase::Marking(MarkState {
+            retained,
+            boundary,
+            marks: vec![false; boundary.saturating_sub(retained)],
+            gray,
+            instruction_roots: 0,
+            symbol_roots: 0,
+        });
+
+        Ok(())
+    }
+
+    /// Advances the cycle in progress by about `budget` items of work, returning the work done.
+    fn collection_step(&mut self, budget: usize) -> Result<usize, DataError> {
+        let mut spent = 0;
+        let mut work = 0;
+        loop {
+            match std::mem::take(&mut self.collector_mut().phase) {
+                CollectionPhase::Idle => return Ok(work),
+                CollectionPhase::Marking(mut state) => {
+                    let (done, marked) = self.mark_step(&mut state, budget - spent)?;
+                    spent = (spent + marked).min(budget);
+                    work += marked;
+                    if !done {
+                        self.collector_mut().phase = CollectionPhase::Marking(state);
+                        return Ok(work);
+                    }
+                    let state = self.start_sweep(state);
+                    work += state.references.values().map(Vec::len).sum::<usize>();
+                    self.collector_mut().phase = CollectionPhase::Sweeping(state);
+                }
+                CollectionPhase::Sweeping(mut state) => {
+                    // sweeping stops at the end of the data marked, anything added since is paid for while moving
+                    work += self.record_dirty_slots(&mut state)?;
+                    state.debt = 0;
+                    let (done, swept) = self.sweep_step(&mut state, budget - spent)?;
+                    spent = (spent + swept).min(budget);
+                    work += swept;
+                    if !done {
+                        self.collector_mut().phase = CollectionPhase::Sweeping(state);
+                        return Ok(work);
+                    }
+                    self.collector_mut().phase = CollectionPhase::Moving(state);
+                }
+                CollectionPhase::Moving(mut state) => {
+                    work += self.record_dirty_slots(&mut state)?;
+                    let debt = std::mem::take(&mut state.debt);
+                    let (done, moved) = self.move_step(&mut state, budget - spent + debt)?;
+                    work += moved;
+                    if !done {
+                        self.collector_mut().phase = CollectionPhase::Moving(state);
+                        return Ok(work);
+                    }
+                    self.finish_reclaim(state)?;
+                    return Ok(work);
+                }
+            }
+        }
+    }
+
+    /// Finishes the cycle in progress without a budget, returning the new indexes of `additional_data_retentions`.
+    fn finish_collection(&mut self, additional_data_retentions: &[usize]) -> Result<Vec<usize>, DataError> {
+        let mut state = match std::mem::take(&mut self.collector_mut().phase) {
+            CollectionPhase::Idle => return Ok(additional_data_retentions.to_vec()),
+            CollectionPhase::Marking(mut state) => {
+                state.gray.extend_from_slice(additional_data_retentions);
+                self.mark_step(&mut state, usize::MAX)?;
+                self.start_sweep(state)
+            }
+            CollectionPhase::Sweeping(state) | CollectionPhase::Moving(state) => state,
+        };
+
+        self.retain_during_reclaim(&mut state, additional_data_retentions)?;
+        self.record_dirty_slots(&mut state)?;
+        self.sweep_step(&mut state, usize::MAX)?;
+        self.move_step(&mut state, usize::MAX)?;
+        self.finish_reclaim(state)
+    }
+
+    /// Marks up to `budget` items, returning whether marking is done and how many items were taken.
+    fn mark_step(&mut self, state: &mut MarkState, budget: usize) -> Result<(bool, usize), DataError> {
+        let mut taken = 0;
+        while taken < budget && state.instruction_roots < self.instruction_block().cursor {
+            if let (instruction, Some(data)) = self.get_from_instruction_block_ensure_index(state.instruction_roots)?
+                && instruction_references_data(instruction)
+            {
+                state.gray.push(data);
+            }
+            state.instruction_roots += 1;
+            taken += 1;
+        }
+
+        while taken < budget && state.symbol_roots < self.symbol_table_block().cursor {
+            state.gray.push(self.get_from_symbol_table_block_ensure_index(state.symbol_roots)?.1);
+            state.symbol_roots += 1;
+            taken += 1;
+        }
+
+        taken += self.drain_gray(state, budget - taken)?;
+        let done = state.gray.is_empty()
+            && state.instruction_roots >= self.instruction_block().cursor
+            && state.symbol_roots >= self.symbol_table_block().cursor;
+
+        Ok((done, taken))
+    }
+
+    /// Marks up to `budget` gray items, returning how many were taken.
+    fn drain_gray(&self, state: &mut MarkState, budget: usize) -> Result<usize, DataError> {
+        let mut remaining = budget;
+        while remaining > 0
+            && let Some(index) = state.gray.pop()
+        {
+            remaining -= 1;
+            if state.is_marked(index) {
+                continue;
+            }
+
+            state.marks[index - state.retained] = true;
+            let data = self.get_from_data_block_ensure_index(index)?;
+            if matches!(
+                data,
+                BasicData::Frame(_, _) | BasicData::FrameIndex(_) | BasicData::FrameRegister(_) | BasicData::FrameRoot
+            ) && index > state.retained
+            {
+                // pop_frame reads the jump point stored right before the frame
+                state.marks[index - 1 - state.retained] = true;
+                if self.frame_locals(index).is_some() {
+                    state.gray.push(index - 2);
+                }
+            }
+            self.push_data_children(index, &mut state.gray)?;
+        }
+
+        Ok(budget - remaining)
+    }
+
+    fn push_data_children(&self, index: usize, gray: &mut Vec<usize>) -> Result<(), DataError> {
+        match self.get_from_data_block_ensure_index(index)? {
+            BasicData::List(len, _) | BasicData::UninitializedList(len, _) => {
+                // items and associations hold the references, the hash index only holds positions
+                for i in index + 1..index + 1 + list_allocation_size(*len, 0) {
+                    slot_references::<T, Companion>(self.get_from_data_block_ensure_index(i)?, gray)?;
+                }
+            }
+            data => slot_references::<T, Companion>(data, gray)?,
+        }
+
+        Ok(())
+    }
+
+    /// Ends marking, recording the handlers and pins that reference data which may move.
+    fn start_sweep(&self, state: MarkState) -> ReclaimState {
+        let limit = self.data_block().cursor;
+        let mut reclaim = ReclaimState {
+            retained: state.retained,
+            boundary: state.boundary,
+            marks: state.marks,
+            limit,
+            swept: state.retained,
+            reclaimed: 0,
+            forward: vec![None; limit.saturating_sub(state.retained)],
+            instruction_roots: 0,
+            symbol_roots: 0,
+            references: HashMap::new(),
+            dirty: vec![],
+            debt: 0,
+            scan: state.retained,
+            to: state.retained,
+            retentions: vec![],
+        };
+
+        for (position, handler) in self.handlers().iter().enumerate() {
+            for index in [handler.value, handler.register, handler.frame, handler.local].into_iter().flatten() {
+                reclaim.record(index, Reference::Handler(position));
+            }
+        }
+
+        for position in 0..self.pinned().handle_count() {
+            if let Some(handle) = self.pinned().handle(position) {
+                reclaim.record(handle.load(AtomicOrdering::Acquire), Reference::Pinned(position));
+            }
+        }
+
+        reclaim
+    }
+
+    /// Records host retentions once marking is over, failing for any index that has already been found unreachable.
+    fn retain_during_reclaim(&self, state: &mut ReclaimState, retentions: &[usize]) -> Result<(), DataError> {
+        for (position, index) in retentions.iter().enumerate() {
+            if *index >= self.data_block().cursor {
+                return Err(DataError::new("Invalid data index", DataErrorType::InvalidDataIndex(*index)));
+            }
+            if *index >= state.retained && *index >= state.to && (*index < state.scan || !state.is_live(*index)) {
+                return Err(collected_data_error(*index));
+            }
+            state.record(*index, Reference::Retention(position));
+        }
+
+        state.retentions = retentions.to_vec();
+        Ok(())
+    }
+
+    /// Records the references of slots changed in place since the last step, returning how many slots there were.
+    fn record_dirty_slots(&self, state: &mut ReclaimState) -> Result<usize, DataError> {
+        let dirty = std::mem::take(&mut state.dirty);
+        for index in &dirty {
+            if *index < self.data_block().cursor {
+                state.debt += state.record_slot::<T, Companion>(*index, self.get_from_data_block_ensure_index(*index)?)?;
+            }
+        }
+
+        Ok(dirty.len())
+    }
+
+    /// Sweeps up to `budget` roots and slots, returning whether sweeping is done and how much was visited.
+    fn sweep_step(&self, state: &mut ReclaimState, budget: usize) -> Result<(bool, usize), DataError> {
+        let mut visited = 0;
+        while visited < budget && state.instruction_roots < self.instruction_block().cursor {
+            if let (instruction, Some(data)) = self.get_from_instruction_block_ensure_index(state.instruction_roots)?
+                && instruction_references_data(instruction)
+            {
+                state.record(data, Reference::Instruction(state.instruction_roots));
+            }
+            state.instruction_roots += 1;
+            visited += 1;
+        }
+
+        while visited < budget && state.symbol_roots < self.symbol_table_block().cursor {
+            let (symbol, data) = self.get_from_symbol_table_block_ensure_index(state.symbol_roots)?;
+            state.record(data, Reference::Symbol(symbol));
+            state.symbol_roots += 1;
+            visited += 1;
+        }
+
+        while visited < budget && state.swept < state.limit {
+            let index = state.swept;
+            let extent = data_extent(self.get_from_data_block_ensure_index(index)?);
+            if state.is_live(index) {
+                for slot in index..index + extent {
+                    state.forward[slot - state.retained] = Some(slot - state.reclaimed);
+                    state.record_slot::<T, Companion>(slot, self.get_from_data_block_ensure_index(slot)?)?;
+                }
+                visited += extent;
+            } else {
+                state.reclaimed += extent;
+                visited += 1;
+            }
+            state.swept += extent;
+        }
+
+        let done = state.swept >= state.limit
+            && state.instruction_roots >= self.instruction_block().cursor
+            && state.symbol_roots >= self.symbol_table_block().cursor;
+        Ok((done, visited))
+    }
+
+    /// Slides allocations down until `budget` is spent, returning whether everything has moved and the work done.
+    ///
+    /// Each allocation costs its size plus the references rewritten to it, garbage costs its size to clear.
+    fn move_step(&mut self, state: &mut ReclaimState, budget: usize) -> Result<(bool, usize), DataError> {
+        let block_start = self.data_block().start;
+        let mut work = 0;
+        while work < budget && state.scan < self.data_block().cursor {
+            let from = state.scan;
+            let extent = data_extent(self.get_from_data_block_ensure_index(from)?);
+            state.scan += extent;
+
+            if !state.is_live(from) {
+                for i in block_start + from..block_start + from + extent {
+                    self.data_mut()[i] = BasicData::Empty;
+                }
+                work += extent;
+                continue;
+            }
+
+            let to = state.to;
+            state.to += extent;
+            if to == from {
+                state.references.remove(&from);
+                work += 1;
+                continue;
+            }
+
+            for offset in 0..extent {
+                self.data_mut().swap(block_start + to + offset, block_start + from + offset);
+            }
+            for i in block_start + from.max(to + extent)..block_start + from + extent {
+                self.data_mut()[i] = BasicData::Empty;
+            }
+
+            work += extent + self.relocate_references(state, from, to)?;
+        }
+
+        Ok((state.scan >= self.data_block().cursor, work))
+    }
+
+    /// Rewrites the recorded references to the allocation moved from `from` to `to`, returning how many were recorded.
+    fn relocate_references(&mut self, state: &mut ReclaimState, from: usize, to: usize) -> Result<usize, DataError> {
+        let mut relocation = Relocation { from, to };
+        let references = state.references.remove(&from).unwrap_or_default();
+        for reference in &references {
+            match reference {
+                Reference::Slot(_) | Reference::MovedSlot(_) => {
+                    if let Some(index) = state.slot_position(reference)
+                        && index < self.data_block().cursor
+                    {
+                        let true_index = self.data_block().start + index;
+                        let slot = std::mem::replace(&mut self.data_mut()[true_index], BasicData::Empty);
+                        self.data_mut()[true_index] = relocation.remap::<T, Companion>(slot)?;
+                    }
+                }
+                Reference::Instruction(position) => {
+                    if let Ok((instruction, Some(data))) = self.get_from_instruction_block_ensure_index(*position)
+                        && data == from
+                    {
+                        let true_index = self.instruction_block().start + position;
+                        self.data_mut()[true_index] = BasicData::InstructionWithData(instruction, to);
+                    }
+                }
+                Reference::Symbol(symbol) => {
+                    let start = self.symbol_table_block().start;
+                    let table = &self.data()[start..start + self.symbol_table_block().cursor];
+                    if let Ok(position) = table.binary_search_by(|item| match item {
+                        BasicData::AssociativeItem(item_symbol, _) => item_symbol.cmp(symbol),
+                        _ => std::cmp::Ordering::Greater,
+                    }) && let BasicData::AssociativeItem(_, value) = &mut self.data_mut()[start + position]
+                    {
+                        *value = relocation.lookup(*value);
+                    }
+                }
+                Reference::Handler(position) => {
+                    if let Some(handler) = self.handlers_mut().get_mut(*position) {
+                        handler.value = handler.value.map(|i| relocation.lookup(i));
+                        handler.register = handler.register.map(|i| relocation.lookup(i));
+                        handler.frame = handler.frame.map(|i| relocation.lookup(i));
+                        handler.local = handler.local.map(|i| relocation.lookup(i));
+                    }
+                }
+                Reference::Pinned(position) => {
+                    if let Some(handle) = self.pinned().handle(*position) {
+                        let _ = handle.compare_exchange(from, to, AtomicOrdering::AcqRel, AtomicOrdering::Acquire);
+                    }
+                }
+                Reference::Retention(position) => {
+                    state.retentions[*position] = relocation.lookup(state.retentions[*position]);
+                }
+            }
+        }
+
+        self.set_current_value(self.current_value().map(|i| relocation.lookup(i)));
+        self.set_current_register(self.current_register().map(|i| relocation.lookup(i)));
+        self.set_current_frame(self.current_frame().map(|i| relocation.lookup(i)));
+        self.set_current_local(self.current_local().map(|i| relocation.lookup(i)));
+
+        Ok(references.len())
+    }
+
+    /// Drops the moved out tail of the data block, returning the new indexes of the retentions.
+    fn finish_reclaim(&mut self, state: ReclaimState) -> Result<Vec<usize>, DataError> {
+        self.data_block_mut().cursor = state.to;
+        self.collector_mut().phase = CollectionPhase::Idle;
+        self.collector_mut().collected_size = state.to;
+        self.apply_shrink_policies()?;
+
+        Ok(state.retentions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use garnish_lang_traits::{GarnishData, Instruction};
+
+    use crate::{
+        BasicData, CollectionSettings, DataError, SimpleNumber,
+        basic::{
+            gc::CollectionPhase,
+            utilities::{instruction_test_data, test_data},
+        },
+        error::DataErrorType,
+        symbol_value,
+    };
+
+    #[test]
+    fn disabled_by_default() {
+        let mut data = test_data();
+        for _ in 0..2000 {
+            data.add_number(1.into()).unwrap();
+        }
+        data.safe_point().unwrap();
+
+        assert!(!data.collection_settings().enabled());
+        assert!(!data.collection_in_progress());
+        assert_eq!(data.data_size(), 2000);
+    }
+
+    #[test]
+    fn unreachable_data_is_reclaimed() {
+        let mut data = test_data();
+        data.add_number(100.into()).unwrap();
+        let kept = data.add_number(200.into()).unwrap();
+        data.add_char_list_from(kept).unwrap();
+        data.push_value_stack(kept).unwrap();
+
+        data.collect_garbage(&[]).unwrap();
+
+        assert_eq!(data.data_size(), 2);
+        assert_eq!(data.get_from_data_block_ensure_index(0), Ok(&BasicData::Number(200.into())));
+        assert_eq!(data.get_from_data_block_ensure_index(1), Ok(&BasicData::ValueRoot(0)));
+        assert_eq!(data.get_current_value(), Some(0));
+    }
+
+    #[test]
+    fn nested_values_are_remapped() {
+        let mut data = test_data();
+        data.add_number(1.into()).unwrap();
+        let symbol = data.add_symbol(symbol_value("key")).unwrap();
+        data.add_number(2.into()).unwrap();
+        let value = data.add_char_list(vec!['a', 'b']).unwrap();
+        let pair = data.add_pair((symbol, value)).unwrap();
+        let list = data.start_list(1).unwrap();
+        data.add_to_list(list, pair).unwrap();
+        data.end_list(list).unwrap();
+        data.add_number(3.into()).unwrap();
+        data.push_register(list).unwrap();
+
+        data.collect_garbage(&[]).unwrap();
+
+        assert_eq!(data.data_size(), 1 + 3 + 1 + 3 + 1);
+        let list = data.get_register(0).unwrap();
+        let pair = data.get_list_item(list, 0.into()).unwrap().unwrap();
+        let (left, right) = data.get_pair(pair).unwrap();
+        assert_eq!(data.get_symbol(left), Ok(symbol_value("key")));
+        assert_eq!(data.get_char_list_item(right, 1.into()), Ok(Some('b')));
+        assert_eq!(data.get_list_item_with_symbol(list, symbol_value("key")), Ok(Some(right)));
+    }
+
+    #[test]
+    fn symbol_table_and_instruction_data_are_roots() {
+        let mut data = instruction_test_data();
+        data.add_number(1.into()).unwrap();
+        let put = data.add_number(2.into()).unwrap();
+        data.add_number(3.into()).unwrap();
+        data.parse_add_symbol("name").unwrap();
+        data.push_instruction(Instruction::Put, Some(put)).unwrap();
+        data.push_instruction(Instruction::JumpTo, Some(0)).unwrap();
+
+        data.collect_garbage(&[]).unwrap();
+
+        assert_eq!(data.data_size(), 1 + 5);
+        assert_eq!(data.get_instruction(0), Some((Instruction::Put, Some(0))));
+        assert_eq!(data.get_instruction(1), Some((Instruction::JumpTo, Some(0))));
+        assert_eq!(data.get_number(0), Ok(2.into()));
+        assert_eq!(data.get_symbol_string(symbol_value("name")), Ok(Some("name".to_string())));
+    }
+
+    #[test]
+    fn retained_data_is_not_moved() {
+        let mut data = test_data();
+        data.add_number(1.into()).unwrap();
+        data.retain_all_current_data();
+        data.add_number(2.into()).unwrap();
+        let kept = data.add_number(3.into()).unwrap();
+
+        let mapped = data.collect_garbage(&[kept]).unwrap();
+
+        assert_eq!(mapped, vec![1]);
+        assert_eq!(data.data_size(), 2);
+        assert_eq!(data.get_number(0), Ok(1.into()));
+        assert_eq!(data.get_number(1), Ok(3.into()));
+    }
+
+    #[test]
+    fn frames_keep_jump_points() {
+        let mut data = test_data();
+        data.add_number(1.into()).unwrap();
+        let register = data.add_number(2.into()).unwrap();
+        data.push_register(register).unwrap();
+        data.push_frame(10).unwrap();
+        data.add_number(3.into()).unwrap();
+
+        data.collect_garbage(&[]).unwrap();
+
+        assert_eq!(data.data_size(), 4);
+        assert_eq!(data.get_from_data_block_ensure_index(2), Ok(&BasicData::JumpPoint(10)));
+        assert_eq!(data.pop_frame(), Ok(Some(10)));
+        assert_eq!(data.get_register(0), Some(0));
+        assert_eq!(data.get_number(0), Ok(2.into()));
+    }
+
+    #[test]
+    fn frames_keep_caller_locals() {
+        let mut data = test_data();
+        data.add_number(1.into()).unwrap();
+        let local = data.add_number(2.into()).unwrap();
+        data.set_local(0, local).unwrap();
+        data.push_frame(10).unwrap();
+        data.add_number(3.into()).unwrap();
+        let inner = data.add_number(4.into()).unwrap();
+        data.set_local(0, inner).unwrap();
+
+        data.collect_garbage(&[]).unwrap();
+
+        assert_eq!(data.data_size(), 7);
+        let inner = data.get_local(0).unwrap();
+        assert_eq!(data.get_number(inner), Ok(4.into()));
+        assert_eq!(data.pop_frame(), Ok(Some(10)));
+        let local = data.get_local(0).unwrap();
+        assert_eq!(data.get_number(local), Ok(2.into()));
+    }
+
+    #[test]
+    fn errors_keep_message_and_code() {
+        let mut data = test_data();
+        data.add_number(1.into()).unwrap();
+        let message = data.add_string("failed").unwrap();
+        let code = data.add_symbol(10).unwrap();
+        let error = data.add_error(message, code).unwrap();
+        data.push_register(error).unwrap();
+
+        data.collect_garbage(&[]).unwrap();
+
+        let error = data.get_register(0).unwrap();
+        let (message, code) = data.get_error(error).unwrap();
+        assert_eq!(data.get_string_for_data_at(message), Ok("failed".to_string()));
+        assert_eq!(data.get_symbol(code), Ok(10));
+    }
+
+    #[test]
+    fn closures_keep_captures() {
+        let mut data = test_data();
+        data.add_number(1.into()).unwrap();
+        let expression = data.add_expression(5).unwrap();
+        let captured = data.add_number(2.into()).unwrap();
+        let list = data.start_list(1).unwrap();
+        data.add_to_list(list, captured).unwrap();
+        let captures = data.end_list(list).unwrap();
+        let closure = data.add_closure(expression, captures).unwrap();
+        data.push_register(closure).unwrap();
+
+        data.collect_garbage(&[]).unwrap();
+
+        let closure = data.get_register(0).unwrap();
+        let (expression, captures) = data.get_closure(closure).unwrap();
+        assert_eq!(data.get_expression(expression), Ok(5));
+        let captured = data.get_list_item(captures, 0.into()).unwrap().unwrap();
+        assert_eq!(data.get_number(captured), Ok(2.into()));
+    }
+
+    #[test]
+    fn handlers_keep_recorded_state() {
+        let mut data = test_data();
+        data.add_number(1.into()).unwrap();
+        let value = data.add_number(2.into()).unwrap();
+        data.push_register(value).unwrap();
+        data.push_handler(3).unwrap();
+        data.pop_register().unwrap();
+
+        data.collect_garbage(&[]).unwrap();
+
+        assert_eq!(data.unwind_handler(), Ok(Some(3)));
+        let value = data.get_register(0).unwrap();
+        assert_eq!(data.get_number(value), Ok(2.into()));
+    }
+
+    #[test]
+    fn incremental_steps() {
+        let mut data = test_data();
+        data.set_collection_settings(CollectionSettings::new(30, 2));
+        for i in 0..10 {
+            let value = data.add_number(i.into()).unwrap();
+            data.push_value_stack(value).unwrap();
+        }
+        for i in 0..10 {
+            data.add_number((i + 100).into()).unwrap();
+        }
+
+        data.safe_point().unwrap();
+        assert!(data.collection_in_progress());
+
+        let mut steps = 0;
+        while data.collection_in_progress() {
+            let value = data.add_number(50.into()).unwrap();
+            data.push_value_stack(value).unwrap();
+            data.safe_point().unwrap();
+            steps += 1;
+        }
+
+        assert!(steps > 1);
+        assert_eq!(data.data_size(), 20 + steps * 2);
+
+        let mut values = vec![];
+        while let Some(value) = data.pop_value_stack() {
+            values.push(data.get_number(value).unwrap());
+        }
+        let mut expected = vec![SimpleNumber::Integer(50); steps];
+        expected.extend((0..10).rev().map(SimpleNumber::Integer));
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn cycle_starts_after_growth() {
+        let mut data = test_data();
+        data.set_collection_settings(CollectionSettings::new(10, 100));
+        for i in 0..9 {
+            data.add_number(i.into()).unwrap();
+        }
+
+        data.safe_point().unwrap();
+        assert_eq!(data.data_size(), 9);
+
+        data.add_number(9.into()).unwrap();
+        data.safe_point().unwrap();
+        assert!(!data.collection_in_progress());
+        assert_eq!(data.data_size(), 0);
+    }
+
+    #[test]
+    fn retention_change_restarts_cycle() {
+        let mut data = test_data();
+        data.set_collection_settings(CollectionSettings::new(4, 1));
+        for i in 0..4 {
+            let value = data.add_number(i.into()).unwrap();
+            data.push_value_stack(value).unwrap();
+        }
+
+        data.safe_point().unwrap();
+        assert!(data.collection_in_progress());
+
+        data.retain_all_current_data();
+        data.safe_point().unwrap();
+
+        assert!(!data.collection_in_progress());
+        assert_eq!(data.data_size(), 8);
+    }
+
+    #[test]
+    fn steps_are_bounded_by_step_size() {
+        let mut data = test_data();
+        data.set_collection_settings(CollectionSettings::new(100, 10));
+        for i in 0..50 {
+            data.add_number((i + 100).into()).unwrap();
+            let value = data.add_number(i.into()).unwrap();
+            data.push_value_stack(value).unwrap();
+        }
+
+        let mut steps = 0;
+        data.safe_point().unwrap();
+        while data.collection_in_progress() {
+            data.safe_point().unwrap();
+            steps += 1;
+        }
+
+        // a step can finish one value node and the two references to it
+        assert!(steps > 20);
+        assert!(data.longest_collection_step() >= 10);
+        assert!(data.longest_collection_step() <= 10 + 3);
+        assert_eq!(data.data_size(), 100);
+
+        let mut values = vec![];
+        while let Some(value) = data.pop_value_stack() {
+            values.push(data.get_number(value).unwrap());
+        }
+        assert_eq!(values, (0..50).rev().map(SimpleNumber::Integer).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn overwritten_reference_is_kept_while_marking() {
+        let mut data = test_data();
+        data.set_collection_settings(CollectionSettings::new(20, 1));
+        let kept = data.add_number(7.into()).unwrap();
+        data.set_local(0, kept).unwrap();
+        for i in 1..5 {
+            let value = data.add_number(i.into()).unwrap();
+            data.set_local(i, value).unwrap();
+        }
+        for i in 0..10 {
+            data.add_number((i + 100).into()).unwrap();
+        }
+
+        data.safe_point().unwrap();
+        assert!(matches!(data.collector().phase, CollectionPhase::Marking(_)));
+
+        // the only reference to kept moves from a local the marker hasn't reached to a register allocated during the cycle
+        data.push_register(kept).unwrap();
+        let replacement = data.add_number(8.into()).unwrap();
+        data.set_local(0, replacement).unwrap();
+
+        while data.collection_in_progress() {
+            data.safe_point().unwrap();
+        }
+
+        let kept = data.get_register(0).unwrap();
+        assert_eq!(data.get_number(kept), Ok(7.into()));
+        let local = data.get_local(0).unwrap();
+        assert_eq!(data.get_number(local), Ok(8.into()));
+    }
+
+    #[test]
+    fn data_changed_while_moving_is_remapped() {
+        let mut data = test_data();
+        data.set_collection_settings(CollectionSettings::new(10, 2));
+        for i in 0..10 {
+            data.add_number((i + 100).into()).unwrap();
+        }
+        let first = data.add_number(1.into()).unwrap();
+        data.set_local(0, first).unwrap();
+        let second = data.add_number(2.into()).unwrap();
+        data.set_local(1, second).unwrap();
+        data.push_value_stack(second).unwrap();
+        data.add_number(200.into()).unwrap();
+
+        while !matches!(data.collector().phase, CollectionPhase::Moving(_)) {
+            data.safe_point().unwrap();
+        }
+
+        let mut count = 2;
+        while data.collection_in_progress() {
+            let local = data.get_local(1).unwrap();
+            count += 1;
+            let value = data.add_number(count.into()).unwrap();
+            data.set_local(0, local).unwrap();
+            data.set_local(1, value).unwrap();
+            *data.get_current_value_mut().unwrap() = local;
+            data.safe_point().unwrap();
+        }
+
+        assert!(count > 3);
+        let local = data.get_local(1).unwrap();
+        assert_eq!(data.get_number(local), Ok(count.into()));
+        let previous = data.get_local(0).unwrap();
+        assert_eq!(data.get_number(previous), Ok((count - 1).into()));
+        let current = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(current), Ok((count - 1).into()));
+    }
+
+    #[test]
+    fn collect_garbage_finishes_reclaiming_in_progress() {
+        let mut data = instruction_test_data();
+        data.set_collection_settings(CollectionSettings::new(10, 1));
+        for i in 0..10 {
+            data.add_number((i + 100).into()).unwrap();
+        }
+        let put = data.add_number(1.into()).unwrap();
+        data.push_instruction(Instruction::Put, Some(put)).unwrap();
+        let kept = data.add_number(2.into()).unwrap();
+
+        while !matches!(data.collector().phase, CollectionPhase::Moving(_)) {
+            data.safe_point().unwrap();
+        }
+        let mapped = data.collect_garbage(&[put]).unwrap();
+
+        assert!(!data.collection_in_progress());
+        assert_eq!(mapped, vec![0]);
+        assert_eq!(data.data_size(), 1);
+        assert_eq!(data.get_instruction(0), Some((Instruction::Put, Some(0))));
+        assert_eq!(
+            data.collect_garbage(&[kept]),
+            Err(DataError::new("Invalid data index", DataErrorType::InvalidDataIndex(kept)))
+        );
+    }
+}
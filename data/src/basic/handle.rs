@@ -0,0 +1,193 @@
+use std::fmt::{Debug, Formatter};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Weak};
+
+use crate::{BasicDataCustom, BasicGarnishData, DataError, basic::companion::BasicDataCompanion, error::DataErrorType};
+
+/// A data index pinned by the host, created with [`BasicGarnishData::pin_data`].
+///
+/// The pinned data is kept alive by [`BasicGarnishData::optimize`] and garbage collection, and the handle's index is updated
+/// whenever the data is moved. Dropping every clone of the handle releases the pin.
+#[derive(Clone)]
+pub struct DataHandle {
+    index: Arc<AtomicUsize>,
+}
+
+impl DataHandle {
+    /// Current data index of the pinned data.
+    pub fn index(&self) -> usize {
+        self.index.load(Ordering::Acquire)
+    }
+}
+
+impl Debug for DataHandle {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("DataHandle").field("index", &self.index()).finish()
+    }
+}
+
+impl PartialEq for DataHandle {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.index, &other.index)
+    }
+}
+
+impl Eq for DataHandle {}
+
+/// Handles registered with a [`BasicGarnishData`], released handles are dropped the next time the data is moved.
+///
+/// Pins belong to the data they were created by, a cloned [`BasicGarnishData`] starts without any.
+#[derive(Default)]
+pub(crate) struct PinnedData {
+    handles: Vec<Weak<AtomicUsize>>,
+}
+
+impl PinnedData {
+    pub(crate) fn indexes(&self) -> Vec<usize> {
+        self.handles
+            .iter()
+            .filter_map(Weak::upgrade)
+            .map(|handle| handle.load(Ordering::Acquire))
+            .collect()
+    }
+
+    pub(crate) fn handle_count(&self) -> usize {
+        self.handles.len()
+    }
+
+    pub(crate) fn handle(&self, position: usize) -> Option<Arc<AtomicUsize>> {
+        self.handles.get(position).and_then(Weak::upgrade)
+    }
+
+    /// Drops released handles, returning the live ones so they stay alive while their data is moved.
+    pub(crate) fn live_handles(&mut self) -> Vec<Arc<AtomicUsize>> {
+        let live = self.handles.iter().filter_map(Weak::upgrade).collect::<Vec<_>>();
+        self.handles = live.iter().map(Arc::downgrade).collect();
+        live
+    }
+}
+
+impl Clone for PinnedData {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl Debug for PinnedData {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_list().entries(self.indexes()).finish()
+    }
+}
+
+/// Pins are host bookkeeping and not part of the data's value.
+impl PartialEq for PinnedData {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
+}
+
+impl Eq for PinnedData {}
+
+impl<T, Companion> BasicGarnishData<T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    /// Pins the data at `index`, keeping it alive and its index current until the returned handle is dropped.
+    pub fn pin_data(&mut self, index: usize) -> Result<DataHandle, DataError> {
+        if index >= self.data_block().cursor {
+            return Err(DataError::new("Invalid data index", DataErrorType::InvalidDataIndex(index)));
+        }
+
+        let handle = Arc::new(AtomicUsize::new(index));
+        self.pinned_mut().handles.push(Arc::downgrade(&handle));
+        self.pin_barrier(self.pinned().handles.len() - 1, index);
+        Ok(DataHandle { index: handle })
+    }
+
+    /// Number of handles that are still pinning data.
+    pub fn pinned_count(&self) -> usize {
+        self.pinned().handles.iter().filter(|handle| handle.strong_count() > 0).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use garnish_lang_traits::GarnishData;
+
+    use crate::{BasicData, CollectionSettings, DataError, basic::utilities::test_data, error::DataErrorType};
+
+    #[test]
+    fn pin_invalid_index() {
+        let mut data = test_data();
+        data.add_number(1.into()).unwrap();
+
+        assert_eq!(
+            data.pin_data(1).map(|_| ()),
+            Err(DataError::new("Invalid data index", DataErrorType::InvalidDataIndex(1)))
+        );
+    }
+
+    #[test]
+    fn pinned_data_survives_optimize() {
+        let mut data = test_data();
+        data.add_number(1.into()).unwrap();
+        let number = data.add_number(2.into()).unwrap();
+        let pair = data.add_pair((number, number)).unwrap();
+        let pair = data.pin_data(pair).unwrap();
+        let number = data.pin_data(number).unwrap();
+
+        data.optimize(&[]).unwrap();
+
+        assert_eq!(data.data_size(), 2);
+        assert_eq!(number.index(), 0);
+        assert_eq!(pair.index(), 1);
+        assert_eq!(data.get_pair(pair.index()), Ok((0, 0)));
+    }
+
+    #[test]
+    fn pinned_data_survives_collection() {
+        let mut data = test_data();
+        data.set_collection_settings(CollectionSettings::new(3, 1));
+        data.add_number(1.into()).unwrap();
+        data.add_number(2.into()).unwrap();
+        let kept = data.add_number(3.into()).unwrap();
+        let handle = data.pin_data(kept).unwrap();
+
+        while data.data_size() > 1 {
+            data.safe_point().unwrap();
+        }
+
+        assert_eq!(handle.index(), 0);
+        assert_eq!(data.get_from_data_block_ensure_index(handle.index()), Ok(&BasicData::Number(3.into())));
+    }
+
+    #[test]
+    fn dropped_handle_releases_data() {
+        let mut data = test_data();
+        let number = data.add_number(1.into()).unwrap();
+        let handle = data.pin_data(number).unwrap();
+        let copy = handle.clone();
+        drop(handle);
+
+        assert_eq!(data.pinned_count(), 1);
+
+        drop(copy);
+        data.optimize(&[]).unwrap();
+
+        assert_eq!(data.pinned_count(), 0);
+        assert_eq!(data.data_size(), 0);
+    }
+
+    #[test]
+    fn clone_starts_without_pins() {
+        let mut data = test_data();
+        let number = data.add_number(1.into()).unwrap();
+        let _handle = data.pin_data(number).unwrap();
+
+        let cloned = data.clone();
+
+        assert_eq!(data.pinned_count(), 1);
+        assert_eq!(cloned.pinned_count(), 0);
+    }
+}
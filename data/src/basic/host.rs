@@ -0,0 +1,1041 @@
+use std::collections::HashMap;
+use std::fmt::{Debug, Display, Formatter};
+use std::sync::Arc;
+
+use garnish_lang_traits::{Extents, GarnishData, GarnishDataType, Instruction, TypeConstants};
+
+use crate::basic::companion::BasicDataCompanion;
+use crate::error::DataErrorType;
+use crate::{BasicData, BasicDataCustom, BasicGarnishData, BasicNumber, DataError, symbol_value};
+
+/// Conversion from a value in the data block to a host function argument.
+pub trait HostArgument: Sized {
+    /// Name of the expected type, used in error messages.
+    fn type_name() -> String;
+
+    /// Returns `Ok(None)` when the value at `index` cannot be converted.
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>;
+
+    /// Value used when the argument was not provided. `None` means the argument is required.
+    fn missing() -> Option<Self> {
+        None
+    }
+}
+
+/// Conversion from a host function's return value to a value in the data block.
+pub trait HostResult {
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>;
+}
+
+type BoxedHostFunction<T> = Arc<dyn Fn(&mut BasicGarnishData<T, HostFunctionCompanion<T>>, usize) -> Result<usize, DataError>>;
+
+/// Rust function that can be registered with a [`HostFunctionCompanion`].
+///
+/// Implemented for closures of up to six arguments, where each argument is a [`HostArgument`] and the return value is a [`HostResult`].
+pub trait HostFunction<T, Args>: 'static
+where
+    T: BasicDataCustom,
+{
+    fn into_boxed(self, name: String) -> BoxedHostFunction<T>;
+}
+
+struct RegisteredFunction<T>
+where
+    T: BasicDataCustom,
+{
+    name: String,
+    symbol: u64,
+    function: BoxedHostFunction<T>,
+}
+
+impl<T> Clone for RegisteredFunction<T>
+where
+    T: BasicDataCustom,
+{
+    fn clone(&self) -> Self {
+        Self {
+            name: self.name.clone(),
+            symbol: self.symbol,
+            function: self.function.clone(),
+        }
+    }
+}
+
+/// Companion that exposes registered Rust functions to scripts.
+///
+/// Each function is resolved by name to a [`GarnishDataType::External`] value. Applying that value decodes the input into the function's arguments and
+/// encodes the return value back into the data block.
+///
+/// A function with one argument receives the applied value directly. A function with more arguments receives the items of an applied list in order.
+/// Trailing [`Option`] arguments may be omitted.
+///
+/// Companions compare equal when they have registered the same function names.
+pub struct HostFunctionCompanion<T = ()>
+where
+    T: BasicDataCustom,
+{
+    functions: Vec<RegisteredFunction<T>>,
+}
+
+impl<T> HostFunctionCompanion<T>
+where
+    T: BasicDataCustom,
+{
+    pub fn new() -> Self {
+        Self { functions: vec![] }
+    }
+
+    /// Registers a function under the given name, returning the value that will be stored in its [`GarnishDataType::External`] data.
+    ///
+    /// Registering a name a second time replaces the previous function.
+    pub fn register<Args, F>(&mut self, name: &str, function: F) -> usize
+    where
+        F: HostFunction<T, Args>,
+    {
+        let symbol = symbol_value(name);
+        let registered = RegisteredFunction {
+            name: name.to_string(),
+            symbol,
+            function: function.into_boxed(name.to_string()),
+        };
+        match self.functions.iter().position(|f| f.symbol == symbol) {
+            Some(index) => {
+                self.functions[index] = registered;
+                index
+            }
+            None => {
+                self.functions.push(registered);
+                self.functions.len() - 1
+            }
+        }
+    }
+
+    pub fn function_names(&self) -> Vec<&str> {
+        self.functions.iter().map(|f| f.name.as_str()).collect()
+    }
+
+    pub fn get_external_value(&self, name: &str) -> Option<usize> {
+        let symbol = symbol_value(name);
+        self.functions.iter().position(|f| f.symbol == symbol)
+    }
+}
+
+impl<T> Default for HostFunctionCompanion<T>
+where
+    T: BasicDataCustom,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> Clone for HostFunctionCompanion<T>
+where
+    T: BasicDataCustom,
+{
+    fn clone(&self) -> Self {
+        Self {
+            functions: self.functions.clone(),
+        }
+    }
+}
+
+impl<T> Debug for HostFunctionCompanion<T>
+where
+    T: BasicDataCustom,
+{
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("HostFunctionCompanion")
+            .field("functions", &self.function_names())
+            .finish()
+    }
+}
+
+impl<T> PartialEq for HostFunctionCompanion<T>
+where
+    T: BasicDataCustom,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.function_names() == other.function_names()
+    }
+}
+
+impl<T> Eq for HostFunctionCompanion<T> where T: BasicDataCustom {}
+
+impl<T> PartialOrd for HostFunctionCompanion<T>
+where
+    T: BasicDataCustom,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.function_names().partial_cmp(&other.function_names())
+    }
+}
+
+impl<T> BasicDataCompanion<T> for HostFunctionCompanion<T>
+where
+    T: BasicDataCustom,
+{
+    fn resolve(data: &mut BasicGarnishData<T, Self>, symbol: u64) -> Result<bool, DataError> {
+        match data.companion().functions.iter().position(|f| f.symbol == symbol) {
+            Some(index) => {
+                let external = data.push_to_data_block(BasicData::External(index))?;
+                data.push_register(external)?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn apply(data: &mut BasicGarnishData<T, Self>, external_value: usize, input_addr: usize) -> Result<bool, DataError> {
+        let function = match data.companion().functions.get(external_value) {
+            Some(f) => f.function.clone(),
+            None => return Ok(false),
+        };
+
+        let result = function(data, input_addr)?;
+        data.push_register(result)?;
+        Ok(true)
+    }
+
+    fn defer_op(
+        _data: &mut BasicGarnishData<T, Self>,
+        _operation: Instruction,
+        _left: (GarnishDataType, usize),
+        _right: (GarnishDataType, usize),
+    ) -> Result<bool, DataError> {
+        Ok(false)
+    }
+}
+
+// number of arguments a host function accepts and was given
+struct ArgumentCount {
+    required: usize,
+    arity: usize,
+    provided: usize,
+}
+
+impl ArgumentCount {
+    fn error_type(&self) -> DataErrorType {
+        DataErrorType::HostFunctionArgumentCount(self.required, self.arity, self.provided)
+    }
+}
+
+fn collect_arguments<T, Companion>(
+    data: &BasicGarnishData<T, Companion>,
+    name: &str,
+    input: usize,
+    required: usize,
+    arity: usize,
+) -> Result<(Vec<Option<usize>>, ArgumentCount), DataError>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    let mut arguments: Vec<Option<usize>> = match arity {
+        0 => vec![],
+        1 => vec![Some(input)],
+        _ => match data.get_data_type(input)? {
+            GarnishDataType::List => data
+                .get_list_item_iter(input, Extents::new(0.into(), BasicNumber::max_value()))?
+                .map(Some)
+                .collect(),
+            GarnishDataType::Unit => vec![],
+            _ => vec![Some(input)],
+        },
+    };
+
+    let count = ArgumentCount {
+        required,
+        arity,
+        provided: arguments.len(),
+    };
+
+    if count.provided < required || count.provided > arity {
+        return Err(DataError::new(
+            &format!(
+                "Host function '{}' expected between {} and {} arguments, got {}",
+                name, required, arity, count.provided
+            ),
+            count.error_type(),
+        ));
+    }
+
+    arguments.resize(arity, None);
+    Ok((arguments, count))
+}
+
+// position is 1-based, matching the order arguments are written in
+fn decode_argument<A, T, Companion>(
+    data: &BasicGarnishData<T, Companion>,
+    name: &str,
+    count: &ArgumentCount,
+    position: usize,
+    index: Option<usize>,
+) -> Result<A, DataError>
+where
+    A: HostArgument,
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    let index = match index {
+        Some(index) => index,
+        None => {
+            return A::missing().ok_or_else(|| {
+                DataError::new(
+                    &format!(
+                        "Host function '{}' missing argument {}, expected between {} and {} arguments, got {}",
+                        name, position, count.required, count.arity, count.provided
+                    ),
+                    count.error_type(),
+                )
+            });
+        }
+    };
+
+    match A::from_data(data, index)? {
+        Some(value) => Ok(value),
+        None => {
+            let got = data.get_data_type(index)?;
+            Err(DataError::new(
+                &format!(
+                    "Host function '{}' expected {} for argument {}, got {}",
+                    name,
+                    A::type_name(),
+                    position,
+                    got
+                ),
+                DataErrorType::HostFunctionArgumentType(position, got),
+            ))
+        }
+    }
+}
+
+macro_rules! count_arguments {
+    () => { 0 };
+    ($head:ident $($tail:ident)*) => { 1 + count_arguments!($($tail)*) };
+}
+
+macro_rules! impl_host_function {
+    ($($arg:ident),*) => {
+        impl<T, F, R, $($arg),*> HostFunction<T, ($($arg,)*)> for F
+        where
+            T: BasicDataCustom,
+            F: Fn($($arg),*) -> R + 'static,
+            R: HostResult,
+            $($arg: HostArgument + 'static,)*
+        {
+            #[allow(non_snake_case, unused_mut, unused_variables)]
+            fn into_boxed(self, name: String) -> BoxedHostFunction<T> {
+                Arc::new(move |data, input| {
+                    let optional: [bool; count_arguments!($($arg)*)] = [$($arg::missing().is_some()),*];
+                    let required = optional.iter().rposition(|o| !o).map(|i| i + 1).unwrap_or(0);
+                    let (arguments, count) = collect_arguments(data, &name, input, required, count_arguments!($($arg)*))?;
+                    let mut arguments = arguments.into_iter().enumerate();
+                    $(
+                        let (position, index) = arguments.next().unwrap_or_default();
+                        let $arg = decode_argument::<$arg, _, _>(data, &name, &count, position + 1, index)?;
+                    )*
+                    (self)($($arg),*).push_to_data(data)
+                })
+            }
+        }
+    };
+}
+
+impl_host_function!();
+impl_host_function!(A1);
+impl_host_function!(A1, A2);
+impl_host_function!(A1, A2, A3);
+impl_host_function!(A1, A2, A3, A4);
+impl_host_function!(A1, A2, A3, A4, A5);
+impl_host_function!(A1, A2, A3, A4, A5, A6);
+
+impl HostArgument for BasicNumber {
+    fn type_name() -> String {
+        "Number".to_string()
+    }
+
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        Ok(data.get_from_data_block_ensure_index(index)?.as_number().ok())
+    }
+}
+
+impl HostArgument for i32 {
+    fn type_name() -> String {
+        "integer Number".to_string()
+    }
+
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        Ok(match data.get_from_data_block_ensure_index(index)? {
+            BasicData::Number(BasicNumber::Integer(v)) => i32::try_from(*v).ok(),
+            _ => None,
+        })
+    }
+}
+
+impl HostArgument for i64 {
+    fn type_name() -> String {
+        "integer Number".to_string()
+    }
+
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        Ok(match data.get_from_data_block_ensure_index(index)? {
+            BasicData::Number(BasicNumber::Integer(v)) => Some(*v),
+            _ => None,
+        })
+    }
+}
+
+impl HostArgument for f64 {
+    fn type_name() -> String {
+        "Number".to_string()
+    }
+
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        Ok(BasicNumber::from_data(data, index)?.map(f64::from))
+    }
+}
+
+impl HostArgument for bool {
+    fn type_name() -> String {
+        "True or False".to_string()
+    }
+
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        Ok(match data.get_from_data_block_ensure_index(index)? {
+            BasicData::True => Some(true),
+            BasicData::False => Some(false),
+            _ => None,
+        })
+    }
+}
+
+impl HostArgument for char {
+    fn type_name() -> String {
+        "Char".to_string()
+    }
+
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        Ok(data.get_from_data_block_ensure_index(index)?.as_char().ok())
+    }
+}
+
+impl HostArgument for u8 {
+    fn type_name() -> String {
+        "Byte".to_string()
+    }
+
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        Ok(data.get_from_data_block_ensure_index(index)?.as_byte().ok())
+    }
+}
+
+impl HostArgument for String {
+    fn type_name() -> String {
+        "CharList".to_string()
+    }
+
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        match data.get_data_type(index)? {
+            GarnishDataType::CharList => Ok(Some(
+                data.get_char_list_iter(index, Extents::new(0.into(), BasicNumber::max_value()))?
+                    .collect(),
+            )),
+            _ => Ok(None),
+        }
+    }
+}
+
+impl<A> HostArgument for Vec<A>
+where
+    A: HostArgument,
+{
+    fn type_name() -> String {
+        format!("List of {}", A::type_name())
+    }
+
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        if data.get_data_type(index)? != GarnishDataType::List {
+            return Ok(None);
+        }
+
+        let mut items = vec![];
+        for item in data.get_list_item_iter(index, Extents::new(0.into(), BasicNumber::max_value()))? {
+            match A::from_data(data, item)? {
+                Some(value) => items.push(value),
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(items))
+    }
+}
+
+impl<A> HostArgument for HashMap<String, A>
+where
+    A: HostArgument,
+{
+    fn type_name() -> String {
+        format!("associative List of {}", A::type_name())
+    }
+
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        if data.get_data_type(index)? != GarnishDataType::List {
+            return Ok(None);
+        }
+
+        let mut map = HashMap::new();
+        for item in data.get_list_item_iter(index, Extents::new(0.into(), BasicNumber::max_value()))? {
+            let (key, value) = match data.get_from_data_block_ensure_index(item)? {
+                BasicData::Pair(left, right) => (*left, *right),
+                _ => return Ok(None),
+            };
+            let key = match data.get_from_data_block_ensure_index(key)? {
+                BasicData::Symbol(sym) => match data.get_symbol_string(*sym)? {
+                    Some(key) => key,
+                    None => return Ok(None),
+                },
+                _ => return Ok(None),
+            };
+            match A::from_data(data, value)? {
+                Some(value) => map.insert(key, value),
+                None => return Ok(None),
+            };
+        }
+
+        Ok(Some(map))
+    }
+}
+
+impl<A> HostArgument for Option<A>
+where
+    A: HostArgument,
+{
+    fn type_name() -> String {
+        format!("optional {}", A::type_name())
+    }
+
+    fn from_data<T, Companion>(data: &BasicGarnishData<T, Companion>, index: usize) -> Result<Option<Self>, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        match data.get_data_type(index)? {
+            GarnishDataType::Unit => Ok(Some(None)),
+            _ => Ok(A::from_data(data, index)?.map(Some)),
+        }
+    }
+
+    fn missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl HostResult for () {
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        data.add_unit()
+    }
+}
+
+impl HostResult for bool {
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        match self {
+            true => data.add_true(),
+            false => data.add_false(),
+        }
+    }
+}
+
+impl HostResult for BasicNumber {
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        data.add_number(self)
+    }
+}
+
+impl HostResult for i32 {
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        data.add_number(BasicNumber::Integer(self.into()))
+    }
+}
+
+impl HostResult for i64 {
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        data.add_number(BasicNumber::Integer(self))
+    }
+}
+
+impl HostResult for f64 {
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        data.add_number(BasicNumber::Float(self))
+    }
+}
+
+impl HostResult for char {
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        data.add_char(self)
+    }
+}
+
+impl HostResult for u8 {
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        data.add_byte(self)
+    }
+}
+
+impl HostResult for String {
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        data.add_string(&self)
+    }
+}
+
+impl HostResult for &str {
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        data.add_string(self)
+    }
+}
+
+impl<R> HostResult for Vec<R>
+where
+    R: HostResult,
+{
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        let items = self
+            .into_iter()
+            .map(|item| item.push_to_data(data))
+            .collect::<Result<Vec<usize>, DataError>>()?;
+        let mut list = data.start_list(items.len())?;
+        for item in items {
+            list = data.add_to_list(list, item)?;
+        }
+        data.end_list(list)
+    }
+}
+
+impl<R> HostResult for HashMap<String, R>
+where
+    R: HostResult,
+{
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        let mut entries = self.into_iter().collect::<Vec<(String, R)>>();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut pairs = vec![];
+        for (key, value) in entries {
+            let key = data.parse_add_symbol(&key)?;
+            let value = value.push_to_data(data)?;
+            pairs.push(data.add_pair((key, value))?);
+        }
+
+        let mut list = data.start_list(pairs.len())?;
+        for pair in pairs {
+            list = data.add_to_list(list, pair)?;
+        }
+        data.end_list(list)
+    }
+}
+
+impl<R> HostResult for Option<R>
+where
+    R: HostResult,
+{
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        match self {
+            Some(value) => value.push_to_data(data),
+            None => data.add_unit(),
+        }
+    }
+}
+
+impl<R, E> HostResult for Result<R, E>
+where
+    R: HostResult,
+    E: Display,
+{
+    fn push_to_data<T, Companion>(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        match self {
+            Ok(value) => value.push_to_data(data),
+            Err(e) => Err(DataError::new(&e.to_string(), DataErrorType::HostFunctionFailed)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use garnish_lang_traits::GarnishData;
+
+    use crate::basic::companion::BasicDataCompanion;
+    use crate::error::DataErrorType;
+    use crate::{BasicData, BasicGarnishData, BasicNumber, DataError, HostFunctionCompanion, basic_object, symbol_value};
+
+    fn data_with(setup: impl FnOnce(&mut HostFunctionCompanion)) -> BasicGarnishData<(), HostFunctionCompanion> {
+        let mut companion = HostFunctionCompanion::new();
+        setup(&mut companion);
+        BasicGarnishData::new(companion).unwrap()
+    }
+
+    fn call(data: &mut BasicGarnishData<(), HostFunctionCompanion>, name: &str, input: usize) -> Result<usize, DataError> {
+        let external = data.companion().get_external_value(name).unwrap();
+        assert!(HostFunctionCompanion::apply(data, external, input)?);
+        Ok(data.pop_register()?.unwrap())
+    }
+
+    #[test]
+    fn resolve_registered_function() {
+        let mut data = data_with(|c| {
+            c.register("first", || 1);
+            c.register("second", || 2);
+        });
+
+        let resolved = HostFunctionCompanion::resolve(&mut data, symbol_value("second")).unwrap();
+        let register = data.pop_register().unwrap().unwrap();
+
+        assert!(resolved);
+        assert_eq!(data.get_from_data_block_ensure_index(register).unwrap(), &BasicData::External(1));
+    }
+
+    #[test]
+    fn resolve_unknown_symbol() {
+        let mut data = data_with(|c| {
+            c.register("first", || 1);
+        });
+
+        let resolved = HostFunctionCompanion::resolve(&mut data, symbol_value("other")).unwrap();
+
+        assert!(!resolved);
+        assert_eq!(data.get_register_len(), 0);
+    }
+
+    #[test]
+    fn apply_unknown_external() {
+        let mut data = data_with(|_| {});
+        let input = data.add_unit().unwrap();
+
+        assert!(!HostFunctionCompanion::apply(&mut data, 10, input).unwrap());
+    }
+
+    #[test]
+    fn register_same_name_replaces() {
+        let mut companion = HostFunctionCompanion::<()>::new();
+        let first = companion.register("value", || 1);
+        let second = companion.register("value", || 2);
+
+        assert_eq!(first, second);
+        assert_eq!(companion.function_names(), vec!["value"]);
+    }
+
+    #[test]
+    fn single_argument_is_input() {
+        let mut data = data_with(|c| {
+            c.register("double", |v: i32| v * 2);
+        });
+        let input = data.add_number(21.into()).unwrap();
+
+        let result = call(&mut data, "double", input).unwrap();
+
+        assert_eq!(data.get_number(result).unwrap(), 42.into());
+    }
+
+    #[test]
+    fn multiple_arguments_from_list() {
+        let mut data = data_with(|c| {
+            c.register("add", |a: f64, b: f64| a + b);
+        });
+        let input = data.push_object_to_data_block(basic_object!((Number 1), (Number 2.5))).unwrap();
+
+        let result = call(&mut data, "add", input).unwrap();
+
+        assert_eq!(data.get_number(result).unwrap(), BasicNumber::Float(3.5));
+    }
+
+    #[test]
+    fn string_arguments_and_result() {
+        let mut data = data_with(|c| {
+            c.register("greet", |name: String, punctuation: char| format!("Hello, {}{}", name, punctuation));
+        });
+        let input = data.push_object_to_data_block(basic_object!((CharList "World"), (Char '!'))).unwrap();
+
+        let result = call(&mut data, "greet", input).unwrap();
+
+        assert_eq!(data.get_string_for_data_at(result).unwrap(), "Hello, World!");
+    }
+
+    #[test]
+    fn list_argument_and_result() {
+        let mut data = data_with(|c| {
+            c.register("reverse", |mut items: Vec<i32>| {
+                items.reverse();
+                items
+            });
+        });
+        let input = data.push_object_to_data_block(basic_object!((Number 1), (Number 2), (Number 3))).unwrap();
+
+        let result = call(&mut data, "reverse", input).unwrap();
+
+        let items = data
+            .get_list_item_iter(result, garnish_lang_traits::Extents::new(0.into(), 10.into()))
+            .unwrap()
+            .collect::<Vec<usize>>();
+        let numbers = items.iter().map(|i| data.get_number(*i).unwrap()).collect::<Vec<BasicNumber>>();
+        assert_eq!(numbers, vec![3.into(), 2.into(), 1.into()]);
+    }
+
+    #[test]
+    fn associative_argument_and_result() {
+        let mut data = data_with(|c| {
+            c.register("scale", |values: HashMap<String, i32>, factor: i32| {
+                values.into_iter().map(|(k, v)| (k, v * factor)).collect::<HashMap<String, i32>>()
+            });
+        });
+        let a = data.parse_add_symbol("a").unwrap();
+        let a_value = data.add_number(1.into()).unwrap();
+        let a_pair = data.add_pair((a, a_value)).unwrap();
+        let b = data.parse_add_symbol("b").unwrap();
+        let b_value = data.add_number(2.into()).unwrap();
+        let b_pair = data.add_pair((b, b_value)).unwrap();
+        let values = data.start_list(2).unwrap();
+        data.add_to_list(values, a_pair).unwrap();
+        data.add_to_list(values, b_pair).unwrap();
+        let values = data.end_list(values).unwrap();
+        let factor = data.add_number(10.into()).unwrap();
+        let input = data.start_list(2).unwrap();
+        data.add_to_list(input, values).unwrap();
+        data.add_to_list(input, factor).unwrap();
+        let input = data.end_list(input).unwrap();
+
+        let result = call(&mut data, "scale", input).unwrap();
+
+        let a = data.get_list_item_with_symbol(result, symbol_value("a")).unwrap().unwrap();
+        let b = data.get_list_item_with_symbol(result, symbol_value("b")).unwrap().unwrap();
+        assert_eq!(data.get_number(a).unwrap(), 10.into());
+        assert_eq!(data.get_number(b).unwrap(), 20.into());
+    }
+
+    #[test]
+    fn optional_arguments_may_be_omitted() {
+        let mut data = data_with(|c| {
+            c.register("offset", |value: i32, by: Option<i32>, extra: Option<i32>| {
+                value + by.unwrap_or(1) + extra.unwrap_or(0)
+            });
+        });
+        let short = data.push_object_to_data_block(basic_object!((Number 10), (Number 5))).unwrap();
+        let single = data.add_number(10.into()).unwrap();
+
+        let short_result = call(&mut data, "offset", short).unwrap();
+        let single_result = call(&mut data, "offset", single).unwrap();
+
+        assert_eq!(data.get_number(short_result).unwrap(), 15.into());
+        assert_eq!(data.get_number(single_result).unwrap(), 11.into());
+    }
+
+    #[test]
+    fn optional_single_argument_from_unit() {
+        let mut data = data_with(|c| {
+            c.register("or_zero", |value: Option<i32>| value.unwrap_or(0));
+        });
+        let input = data.add_unit().unwrap();
+
+        let result = call(&mut data, "or_zero", input).unwrap();
+
+        assert_eq!(data.get_number(result).unwrap(), 0.into());
+    }
+
+    #[test]
+    fn none_result_is_unit() {
+        let mut data = data_with(|c| {
+            c.register("nothing", || None::<i32>);
+        });
+        let input = data.add_unit().unwrap();
+
+        let result = call(&mut data, "nothing", input).unwrap();
+
+        assert_eq!(data.get_data_type(result).unwrap(), garnish_lang_traits::GarnishDataType::Unit);
+    }
+
+    #[test]
+    fn too_few_arguments() {
+        let mut data = data_with(|c| {
+            c.register("add", |a: i32, b: i32, _c: Option<i32>| a + b);
+        });
+        let input = data.push_object_to_data_block(basic_object!(Number 1)).unwrap();
+
+        let result = call(&mut data, "add", input);
+
+        assert_eq!(
+            result,
+            Err(DataError::new(
+                "Host function 'add' expected between 2 and 3 arguments, got 1",
+                DataErrorType::HostFunctionArgumentCount(2, 3, 1)
+            ))
+        );
+    }
+
+    #[test]
+    fn too_many_arguments() {
+        let mut data = data_with(|c| {
+            c.register("add", |a: i32, b: i32| a + b);
+        });
+        let input = data.push_object_to_data_block(basic_object!((Number 1), (Number 2), (Number 3))).unwrap();
+
+        let result = call(&mut data, "add", input);
+
+        assert_eq!(
+            result,
+            Err(DataError::new(
+                "Host function 'add' expected between 2 and 2 arguments, got 3",
+                DataErrorType::HostFunctionArgumentCount(2, 2, 3)
+            ))
+        );
+    }
+
+    #[test]
+    fn wrong_argument_type() {
+        let mut data = data_with(|c| {
+            c.register("add", |a: i32, b: i32| a + b);
+        });
+        let input = data.push_object_to_data_block(basic_object!((Number 1), (CharList "two"))).unwrap();
+
+        let result = call(&mut data, "add", input);
+
+        assert_eq!(
+            result,
+            Err(DataError::new(
+                "Host function 'add' expected integer Number for argument 2, got CharList",
+                DataErrorType::HostFunctionArgumentType(2, garnish_lang_traits::GarnishDataType::CharList)
+            ))
+        );
+    }
+
+    #[test]
+    fn wrong_list_item_type() {
+        let mut data = data_with(|c| {
+            c.register("sum", |items: Vec<i32>| items.iter().sum::<i32>());
+        });
+        let input = data.push_object_to_data_block(basic_object!((Number 1), True)).unwrap();
+
+        let result = call(&mut data, "sum", input);
+
+        assert_eq!(
+            result,
+            Err(DataError::new(
+                "Host function 'sum' expected List of integer Number for argument 1, got List",
+                DataErrorType::HostFunctionArgumentType(1, garnish_lang_traits::GarnishDataType::List)
+            ))
+        );
+    }
+
+    #[test]
+    fn error_result() {
+        let mut data = data_with(|c| {
+            c.register("fail", |value: i32| if value > 0 { Ok(value) } else { Err("value must be positive") });
+        });
+        let input = data.add_number(0.into()).unwrap();
+
+        let result = call(&mut data, "fail", input);
+
+        assert_eq!(result, Err(DataError::new("value must be positive", DataErrorType::HostFunctionFailed)));
+    }
+}
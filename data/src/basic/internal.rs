@@ -21,6 +21,7 @@ where
         if index >= self.data_block().cursor {
             return Err(DataError::new("Invalid data index", DataErrorType::InvalidDataIndex(index)));
         }
+        self.data_write_barrier(index)?;
         let true_index = self.data_block().start + index;
         Ok(&mut self.data_mut()[true_index])
     }
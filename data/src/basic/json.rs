@@ -0,0 +1,602 @@
+use std::iter::Peekable;
+use std::str::CharIndices;
+
+use garnish_lang_traits::{GarnishData, GarnishDataType};
+
+use crate::basic::companion::BasicDataCompanion;
+use crate::error::DataErrorType;
+use crate::{BasicData, BasicDataCustom, BasicGarnishData, BasicObject, DataError, SimpleNumber};
+
+/// Deepest nesting of arrays and objects accepted by [`BasicGarnishData::add_json`].
+pub const MAX_JSON_DEPTH: usize = 128;
+
+impl<T, Companion> BasicGarnishData<T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    /// Parses JSON text into the data block, returning the index of the created value.
+    ///
+    /// Objects become associative lists of pairs keyed by symbols, arrays become lists, strings become char lists and `null` becomes Unit.
+    /// Numbers without a fraction or exponent that fit an integer become integers, all others become floats.
+    /// Arrays and objects nested deeper than [`MAX_JSON_DEPTH`] produce an error.
+    pub fn add_json(&mut self, json: &str) -> Result<usize, DataError> {
+        let mut parser = JsonParser {
+            chars: json.char_indices().peekable(),
+            len: json.len(),
+            depth: 0,
+        };
+        let index = parser.parse_value(self)?;
+        parser.skip_whitespace();
+        match parser.chars.peek() {
+            None => Ok(index),
+            Some((position, _)) => Err(json_error("Unexpected trailing characters", *position)),
+        }
+    }
+
+    /// Renders the value at `index` as JSON text.
+    ///
+    /// Associative lists, where every item is a pair keyed by a symbol, become objects and other lists become arrays.
+    /// Concatenations and slices are rendered as the flat array of their items. Symbols render as their name.
+    /// Ranges render as an array of their start and end, followed by the step for stepped ranges.
+    /// Expressions, externals, partials and other values without a JSON representation produce an error.
+    pub fn to_json(&self, index: usize) -> Result<String, DataError> {
+        let mut json = String::new();
+        self.write_json(&self.get_object_from_data_block(index)?, &mut json)?;
+        Ok(json)
+    }
+
+    fn write_json(&self, object: &BasicObject<T>, json: &mut String) -> Result<(), DataError> {
+        match object {
+            BasicObject::Unit => json.push_str("null"),
+            BasicObject::True => json.push_str("true"),
+            BasicObject::False => json.push_str("false"),
+            BasicObject::Number(SimpleNumber::Integer(v)) => json.push_str(&v.to_string()),
+            BasicObject::Number(SimpleNumber::Float(v)) if v.is_finite() => json.push_str(&format!("{:?}", v)),
+            BasicObject::Number(SimpleNumber::Decimal(v)) => json.push_str(&v.to_string()),
+            BasicObject::Byte(b) => json.push_str(&b.to_string()),
+            BasicObject::Char(c) => write_json_string(&c.to_string(), json),
+            BasicObject::CharList(s) => write_json_string(s, json),
+            BasicObject::Symbol(symbol) => write_json_string(&self.json_symbol_name(*symbol)?, json),
+            BasicObject::ByteList(bytes) => {
+                json.push('[');
+                json.push_str(&bytes.iter().map(|b| b.to_string()).collect::<Vec<_>>().join(","));
+                json.push(']');
+            }
+            BasicObject::List(items) if !items.is_empty() && items.iter().all(|item| json_field(item).is_some()) => {
+                json.push('{');
+                for (i, (symbol, value)) in items.iter().filter_map(|item| json_field(item)).enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    write_json_string(&self.json_symbol_name(symbol)?, json);
+                    json.push(':');
+                    self.write_json(value, json)?;
+                }
+                json.push('}');
+            }
+            BasicObject::List(items) => {
+                json.push('[');
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        json.push(',');
+                    }
+                    self.write_json(item, json)?;
+                }
+                json.push(']');
+            }
+            BasicObject::Range(left, right) => match left.as_ref() {
+                // stepped ranges are stored with the contiguous range as their start
+                BasicObject::Range(start, end) => self.write_json_array(&[start, end, right], json)?,
+                _ => self.write_json_array(&[left, right], json)?,
+            },
+            BasicObject::Pair(left, right) => self.write_json_array(&[left, right], json)?,
+            object => {
+                let data_type = match object {
+                    BasicObject::Number(_) => GarnishDataType::Number,
+                    BasicObject::Type(_) => GarnishDataType::Type,
+                    BasicObject::SymbolList(_) => GarnishDataType::SymbolList,
+                    BasicObject::Expression(_) => GarnishDataType::Expression,
+                    BasicObject::External(_) => GarnishDataType::External,
+                    BasicObject::Partial(_, _) => GarnishDataType::Partial,
+                    BasicObject::Custom(_) => GarnishDataType::Custom,
+                    _ => GarnishDataType::Invalid,
+                };
+                return Err(DataError::new(
+                    "Value cannot be represented as JSON",
+                    DataErrorType::NotJsonRepresentable(data_type),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn write_json_array(&self, items: &[&BasicObject<T>], json: &mut String) -> Result<(), DataError> {
+        json.push('[');
+        for (i, item) in items.iter().enumerate() {
+            if i > 0 {
+                json.push(',');
+            }
+            self.write_json(item, json)?;
+        }
+        json.push(']');
+        Ok(())
+    }
+
+    fn json_symbol_name(&self, symbol: u64) -> Result<String, DataError> {
+        self.get_symbol_string(symbol)?.ok_or_else(|| {
+            DataError::new(
+                "Symbol has no registered name",
+                DataErrorType::NotJsonRepresentable(GarnishDataType::Symbol),
+            )
+        })
+    }
+}
+
+fn json_field<T: BasicDataCustom>(item: &BasicObject<T>) -> Option<(u64, &BasicObject<T>)> {
+    match item {
+        BasicObject::Pair(left, right) => match left.as_ref() {
+            BasicObject::Symbol(symbol) => Some((*symbol, right.as_ref())),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+fn write_json_string(value: &str, json: &mut String) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            '\u{08}' => json.push_str("\\b"),
+            '\u{0C}' => json.push_str("\\f"),
+            c if (c as u32) < 0x20 => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+fn json_error(message: &str, position: usize) -> DataError {
+    DataError::new(message, DataErrorType::InvalidJson(position))
+}
+
+struct JsonParser<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    len: usize,
+    depth: usize,
+}
+
+impl JsonParser<'_> {
+    fn position(&mut self) -> usize {
+        self.chars.peek().map(|(i, _)| *i).unwrap_or(self.len)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| matches!(c, ' ' | '\t' | '\n' | '\r')).is_some() {}
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), DataError> {
+        let position = self.position();
+        match self.chars.next() {
+            Some((_, c)) if c == expected => Ok(()),
+            _ => Err(json_error(&format!("Expected '{}'", expected), position)),
+        }
+    }
+
+    fn enter(&mut self, position: usize) -> Result<(), DataError> {
+        self.depth += 1;
+        match self.depth > MAX_JSON_DEPTH {
+            true => Err(json_error("Maximum nesting depth exceeded", position)),
+            false => Ok(()),
+        }
+    }
+
+    fn expect_word(&mut self, word: &str) -> Result<(), DataError> {
+        for c in word.chars() {
+            self.expect(c)?;
+        }
+        Ok(())
+    }
+
+    fn parse_value<T, Companion>(&mut self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, DataError>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        self.skip_whitespace();
+        let position = self.position();
+        match self.chars.peek().map(|(_, c)| *c) {
+            Some('n') => {
+                self.expect_word("null")?;
+                data.push_to_data_block(BasicData::Unit)
+            }
+            Some('t') => {
+                self.expect_word("true")?;
+                data.push_to_data_block(BasicData::True)
+            }
+            Some('f') => {
+                self.expect_word("false")?;
+                data.push_to_data_block(BasicData::False)
+            }
+            Some('"') => {
+                let value = self.parse_string()?;
+                data.add_string(&value)
+            }
+            Some('[') => {
+                self.enter(position)?;
+                self.chars.next();
+                let mut items = vec![];
+                self.skip_whitespace();
+                if self.chars.next_if(|(_, c)| *c == ']').is_none() {
+                    loop {
+                        items.push(self.parse_value(data)?);
+                        self.skip_whitespace();
+                        if self.chars.next_if(|(_, c)| *c == ',').is_none() {
+                            self.expect(']')?;
+                            break;
+                        }
+                    }
+                }
+                self.depth -= 1;
+                add_json_list(data, items)
+            }
+            Some('{') => {
+                self.enter(position)?;
+                self.chars.next();
+                let mut items = vec![];
+                self.skip_whitespace();
+                if self.chars.next_if(|(_, c)| *c == '}').is_none() {
+                    loop {
+                        self.skip_whitespace();
+                        if self.chars.peek().map(|(_, c)| *c) != Some('"') {
+                            return Err(json_error("Expected string key", self.position()));
+                        }
+                        let key = self.parse_string()?;
+                        self.skip_whitespace();
+                        self.expect(':')?;
+                        let value = self.parse_value(data)?;
+                        let symbol = data.parse_add_symbol(&key)?;
+                        items.push(data.add_pair((symbol, value))?);
+                        self.skip_whitespace();
+                        if self.chars.next_if(|(_, c)| *c == ',').is_none() {
+                            self.expect('}')?;
+                            break;
+                        }
+                    }
+                }
+                self.depth -= 1;
+                add_json_list(data, items)
+            }
+            Some(c) if c == '-' || c.is_ascii_digit() => {
+                let number = self.parse_number()?;
+                data.push_to_data_block(BasicData::Number(number))
+            }
+            Some(_) => Err(json_error("Unexpected character", position)),
+            None => Err(json_error("Unexpected end of input", position)),
+        }
+    }
+
+    fn parse_number(&mut self) -> Result<SimpleNumber, DataError> {
+        let position = self.position();
+        let mut text = String::new();
+        let mut integer = true;
+
+        if let Some((_, c)) = self.chars.next_if(|(_, c)| *c == '-') {
+            text.push(c);
+        }
+        if !self.take_digits(&mut text) {
+            return Err(json_error("Expected digit", self.position()));
+        }
+        if let Some((_, c)) = self.chars.next_if(|(_, c)| *c == '.') {
+            integer = false;
+            text.push(c);
+            if !self.take_digits(&mut text) {
+                return Err(json_error("Expected digit", self.position()));
+            }
+        }
+        if let Some((_, c)) = self.chars.next_if(|(_, c)| matches!(c, 'e' | 'E')) {
+            integer = false;
+            text.push(c);
+            if let Some((_, c)) = self.chars.next_if(|(_, c)| matches!(c, '+' | '-')) {
+                text.push(c);
+            }
+            if !self.take_digits(&mut text) {
+                return Err(json_error("Expected digit", self.position()));
+            }
+        }
+
+        if integer && let Ok(v) = text.parse::<i64>() {
+            return Ok(SimpleNumber::Integer(v));
+        }
+
+        text.parse::<f64>()
+            .map(SimpleNumber::Float)
+            .map_err(|_| json_error("Invalid number", position))
+    }
+
+    fn take_digits(&mut self, text: &mut String) -> bool {
+        let start = text.len();
+        while let Some((_, c)) = self.chars.next_if(|(_, c)| c.is_ascii_digit()) {
+            text.push(c);
+        }
+        text.len() > start
+    }
+
+    fn parse_string(&mut self) -> Result<String, DataError> {
+        self.expect('"')?;
+        let mut value = String::new();
+        loop {
+            let position = self.position();
+            match self.chars.next() {
+                Some((_, '"')) => return Ok(value),
+                Some((_, '\\')) => {
+                    let escaped = match self.chars.next() {
+                        Some((_, '"')) => '"',
+                        Some((_, '\\')) => '\\',
+                        Some((_, '/')) => '/',
+                        Some((_, 'b')) => '\u{08}',
+                        Some((_, 'f')) => '\u{0C}',
+                        Some((_, 'n')) => '\n',
+                        Some((_, 'r')) => '\r',
+                        Some((_, 't')) => '\t',
+                        Some((_, 'u')) => self.parse_unicode_escape(position)?,
+                        _ => return Err(json_error("Invalid escape sequence", position)),
+                    };
+                    value.push(escaped);
+                }
+                Some((_, c)) if (c as u32) < 0x20 => return Err(json_error("Control character in string", position)),
+                Some((_, c)) => value.push(c),
+                None => return Err(json_error("Unterminated string", position)),
+            }
+        }
+    }
+
+    fn parse_hex(&mut self, position: usize) -> Result<u32, DataError> {
+        let mut value = 0;
+        for _ in 0..4 {
+            match self.chars.next().and_then(|(_, c)| c.to_digit(16)) {
+                Some(digit) => value = value * 16 + digit,
+                None => return Err(json_error("Invalid unicode escape", position)),
+            }
+        }
+        Ok(value)
+    }
+
+    fn parse_unicode_escape(&mut self, position: usize) -> Result<char, DataError> {
+        let first = self.parse_hex(position)?;
+        let code = match first {
+            0xD800..=0xDBFF => {
+                self.expect('\\')?;
+                self.expect('u')?;
+                match self.parse_hex(position)? {
+                    second @ 0xDC00..=0xDFFF => 0x10000 + ((first - 0xD800) << 10) + (second - 0xDC00),
+                    _ => return Err(json_error("Invalid unicode surrogate pair", position)),
+                }
+            }
+            code => code,
+        };
+        char::from_u32(code).ok_or_else(|| json_error("Invalid unicode escape", position))
+    }
+}
+
+fn add_json_list<T, Companion>(data: &mut BasicGarnishData<T, Companion>, items: Vec<usize>) -> Result<usize, DataError>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    let mut list = data.start_list(items.len())?;
+    for item in items {
+        list = data.add_to_list(list, item)?;
+    }
+    data.end_list(list)
+}
+
+#[cfg(test)]
+mod tests {
+    use garnish_lang_traits::{GarnishData, GarnishDataType};
+
+    use crate::basic::utilities::test_data;
+    use crate::error::DataErrorType;
+    use crate::{BasicData, BasicObject, DataError, Decimal, MAX_JSON_DEPTH, SimpleNumber, basic_object, symbol_value};
+
+    #[test]
+    fn parse_literals() {
+        let mut data = test_data();
+        let null = data.add_json("null").unwrap();
+        let t = data.add_json(" true ").unwrap();
+        let f = data.add_json("false").unwrap();
+
+        assert_eq!(data.get_object_from_data_block(null).unwrap(), BasicObject::Unit);
+        assert_eq!(data.get_object_from_data_block(t).unwrap(), BasicObject::True);
+        assert_eq!(data.get_object_from_data_block(f).unwrap(), BasicObject::False);
+    }
+
+    #[test]
+    fn parse_numbers() {
+        let mut data = test_data();
+        let cases = [
+            ("10", basic_object!(Number 10)),
+            ("-5", basic_object!(Number(-5))),
+            ("2.5", basic_object!(Number 2.5)),
+            ("1e2", basic_object!(Number 100.0)),
+        ];
+
+        for (json, expected) in cases {
+            let index = data.add_json(json).unwrap();
+            assert_eq!(data.get_object_from_data_block(index).unwrap(), expected);
+        }
+
+        let large = data.add_json("3000000000").unwrap();
+        assert_eq!(data.get_from_data_block_ensure_index(large).unwrap(), &BasicData::Number(SimpleNumber::Integer(3000000000)));
+
+        let beyond_integer = data.add_json("10000000000000000000").unwrap();
+        assert_eq!(data.get_from_data_block_ensure_index(beyond_integer).unwrap(), &BasicData::Number(SimpleNumber::Float(1e19)));
+    }
+
+    #[test]
+    fn parse_string_escapes() {
+        let mut data = test_data();
+        let index = data.add_json(r#""a\"b\\c\n\u00e9\ud83d\ude00""#).unwrap();
+
+        assert_eq!(
+            data.get_object_from_data_block(index).unwrap(),
+            BasicObject::CharList("a\"b\\c\né😀".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_array_and_object() {
+        let mut data = test_data();
+        let index = data
+            .add_json(r#"{"name": "service", "ports": [80, 443], "tls": {"enabled": true}}"#)
+            .unwrap();
+
+        let name = data.get_list_item_with_symbol(index, symbol_value("name")).unwrap().unwrap();
+        let ports = data.get_list_item_with_symbol(index, symbol_value("ports")).unwrap().unwrap();
+        let tls = data.get_list_item_with_symbol(index, symbol_value("tls")).unwrap().unwrap();
+        let enabled = data.get_list_item_with_symbol(tls, symbol_value("enabled")).unwrap().unwrap();
+
+        assert_eq!(
+            data.get_object_from_data_block(name).unwrap(),
+            BasicObject::CharList("service".to_string())
+        );
+        assert_eq!(data.get_object_from_data_block(ports).unwrap(), basic_object!((Number 80), (Number 443)));
+        assert_eq!(data.get_data_type(enabled).unwrap(), GarnishDataType::True);
+        assert_eq!(data.get_symbol_string(symbol_value("name")).unwrap(), Some("name".to_string()));
+    }
+
+    #[test]
+    fn parse_empty_array_and_object() {
+        let mut data = test_data();
+        let array = data.add_json("[]").unwrap();
+        let object = data.add_json("{ }").unwrap();
+
+        assert_eq!(data.get_object_from_data_block(array).unwrap(), BasicObject::List(vec![]));
+        assert_eq!(data.get_object_from_data_block(object).unwrap(), BasicObject::List(vec![]));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cases = [
+            ("", "Unexpected end of input", 0),
+            ("[1, 2", "Expected ']'", 5),
+            ("{\"a\" 1}", "Expected ':'", 5),
+            ("{1: 2}", "Expected string key", 1),
+            ("\"abc", "Unterminated string", 4),
+            ("tru", "Expected 'e'", 3),
+            ("1 2", "Unexpected trailing characters", 2),
+            ("-", "Expected digit", 1),
+        ];
+
+        for (json, message, position) in cases {
+            let mut data = test_data();
+            assert_eq!(
+                data.add_json(json),
+                Err(DataError::new(message, DataErrorType::InvalidJson(position))),
+                "{}",
+                json
+            );
+        }
+    }
+
+    #[test]
+    fn nesting_depth_limit() {
+        let mut data = test_data();
+        let nested = format!("{}{}", "[".repeat(MAX_JSON_DEPTH), "]".repeat(MAX_JSON_DEPTH));
+        assert!(data.add_json(&nested).is_ok());
+
+        let too_deep = "[".repeat(100_000);
+        assert_eq!(
+            data.add_json(&too_deep),
+            Err(DataError::new(
+                "Maximum nesting depth exceeded",
+                DataErrorType::InvalidJson(MAX_JSON_DEPTH)
+            ))
+        );
+    }
+
+    #[test]
+    fn render_values() {
+        let mut data = test_data();
+        let cases = [
+            (basic_object!(Unit), "null"),
+            (basic_object!(True), "true"),
+            (basic_object!(Number 10), "10"),
+            (basic_object!(Number 2.5), "2.5"),
+            (BasicObject::Number(SimpleNumber::Decimal(Decimal::new(1250, 2).unwrap())), "12.50"),
+            (basic_object!(Char 'a'), "\"a\""),
+            (basic_object!(CharList "a\"\n"), "\"a\\\"\\n\""),
+            (basic_object!(ByteList 1, 2), "[1,2]"),
+            (basic_object!((Number 1), (CharList "a")), "[1,\"a\"]"),
+            (basic_object!((Number 1)..(Number 3)), "[1,3]"),
+            (basic_object!(((Number 1)..(Number 9))..(Number 2)), "[1,9,2]"),
+            (basic_object!(((Number 1), (Number 2)) <> (Number 3)), "[1,2,3]"),
+        ];
+
+        for (object, expected) in cases {
+            let index = data.push_object_to_data_block(object).unwrap();
+            assert_eq!(data.to_json(index).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn render_associative_list() {
+        let mut data = test_data();
+        let name = data.parse_add_symbol("name").unwrap();
+        let value = data.add_string("service").unwrap();
+        let pair = data.add_pair((name, value)).unwrap();
+        let list = data.start_list(1).unwrap();
+        let list = data.add_to_list(list, pair).unwrap();
+        let list = data.end_list(list).unwrap();
+
+        assert_eq!(data.to_json(list).unwrap(), r#"{"name":"service"}"#);
+    }
+
+    #[test]
+    fn round_trip() {
+        let json = r#"{"name":"service","ports":[80,443],"ratio":0.5,"tags":[],"tls":{"enabled":true,"cert":null}}"#;
+        let mut data = test_data();
+        let index = data.add_json(json).unwrap();
+
+        assert_eq!(data.to_json(index).unwrap(), json);
+    }
+
+    #[test]
+    fn unrepresentable_values() {
+        let mut data = test_data();
+        for (object, data_type) in [
+            (basic_object!(Expression 1), GarnishDataType::Expression),
+            (basic_object!(External 1), GarnishDataType::External),
+            (basic_object!((Expression 1) ~ (Number 1)), GarnishDataType::Partial),
+            (basic_object!((Number 1), (External 1)), GarnishDataType::External),
+        ] {
+            let index = data.push_object_to_data_block(object).unwrap();
+            assert_eq!(
+                data.to_json(index),
+                Err(DataError::new(
+                    "Value cannot be represented as JSON",
+                    DataErrorType::NotJsonRepresentable(data_type)
+                ))
+            );
+        }
+    }
+
+    #[test]
+    fn unnamed_symbol() {
+        let mut data = test_data();
+        let index = data.push_object_to_data_block(basic_object!(Symbol "unnamed")).unwrap();
+
+        assert_eq!(
+            data.to_json(index),
+            Err(DataError::new(
+                "Symbol has no registered name",
+                DataErrorType::NotJsonRepresentable(GarnishDataType::Symbol)
+            ))
+        );
+    }
+}
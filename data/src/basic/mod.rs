@@ -3,20 +3,38 @@ mod clone;
 mod data;
 mod dump;
 mod garnish;
+mod gc;
+mod handle;
+mod host;
 mod internal;
+mod json;
 mod merge_to_symbol_list;
 mod object;
 mod optimize;
 mod ordering;
+mod packed;
 mod search;
+#[cfg(feature = "serde")]
+mod serde;
+mod snapshot;
 mod storage;
 mod companion;
 
-pub use object::{BasicObject};
+pub use object::BasicObject;
 pub use data::{BasicData, BasicDataUnitCustom};
 pub use garnish::BasicDataFactory;
 pub use garnish::ConversionDelegate;
 pub use companion::BasicDataCompanion;
 pub use basic::NoOpCompanion;
+pub use gc::CollectionSettings;
+pub use handle::DataHandle;
+pub use json::MAX_JSON_DEPTH;
+pub use host::{HostArgument, HostFunction, HostFunctionCompanion, HostResult};
+pub use packed::{PACKED_CHUNK_SIZE, PACKED_LIST_THRESHOLD};
+pub use search::HASHED_LIST_THRESHOLD;
+pub use snapshot::{SnapshotReader, SnapshotWriter};
+pub use storage::{ReallocationStrategy, ShrinkPolicy, StorageSettings};
+#[cfg(feature = "serde")]
+pub use self::serde::{DataDeserializer, DataSerializer, ListSerializer};
 
 pub use basic::*;
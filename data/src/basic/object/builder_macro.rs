@@ -159,7 +159,7 @@ mod tests {
     fn build_symbol() {
         let value: BasicObject = basic_object!(Symbol "my_symbol");
 
-        assert_eq!(value, BasicObject::Symbol(8904929874702161741));
+        assert_eq!(value, BasicObject::Symbol(7248772672978976256));
     }
 
     #[test]
@@ -280,7 +280,7 @@ mod tests {
         let value: BasicObject = basic_object!(SymList(Symbol "my_symbol", Number 100));
 
         assert_eq!(value, BasicObject::SymbolList(vec![
-            SymbolListPart::Symbol(8904929874702161741),
+            SymbolListPart::Symbol(7248772672978976256),
             SymbolListPart::Number(100.into())
         ]));
     }
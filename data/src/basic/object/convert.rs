@@ -0,0 +1,140 @@
+use garnish_lang_traits::{IntoGarnish, RuntimeError};
+
+use crate::basic::companion::BasicDataCompanion;
+use crate::{BasicDataCustom, BasicGarnishData, BasicObject, DataError};
+
+impl<T, Companion> IntoGarnish<BasicGarnishData<T, Companion>> for BasicObject<T>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    fn into_garnish(self, data: &mut BasicGarnishData<T, Companion>) -> Result<usize, RuntimeError<DataError>> {
+        Ok(data.push_object_to_data_block(self)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use garnish_lang_traits::convert::{read_field, read_variant};
+    use garnish_lang_traits::{ByteList, FromGarnish, GarnishData, IntoGarnish};
+
+    use crate::basic::utilities::test_data;
+    use crate::{BasicGarnishDataUnit, BasicObject, basic_object, symbol_value};
+
+    fn round_trip<V>(value: V) -> V
+    where
+        V: IntoGarnish<BasicGarnishDataUnit> + FromGarnish<BasicGarnishDataUnit>,
+    {
+        let mut data = test_data();
+        let index = value.into_garnish(&mut data).unwrap();
+        V::from_garnish(&data, index).unwrap()
+    }
+
+    #[test]
+    fn primitives_round_trip() {
+        assert_eq!(round_trip(()), ());
+        assert!(round_trip(true));
+        assert_eq!(round_trip(10), 10);
+        assert_eq!(round_trip(10i64), 10i64);
+        assert_eq!(round_trip(2.5), 2.5);
+        assert_eq!(round_trip('a'), 'a');
+        assert_eq!(round_trip(5u8), 5u8);
+        assert_eq!(round_trip("value".to_string()), "value");
+    }
+
+    #[test]
+    fn non_ascii_string_round_trip() {
+        assert_eq!(round_trip("héllo ✓".to_string()), "héllo ✓");
+    }
+
+    #[test]
+    fn list_and_byte_list_round_trip() {
+        assert_eq!(round_trip(vec![1, 2, 3]), vec![1, 2, 3]);
+        assert_eq!(round_trip(ByteList(vec![1, 2, 3])), ByteList(vec![1, 2, 3]));
+        assert_eq!(round_trip(vec![Some(1), None]), vec![Some(1), None]);
+    }
+
+    #[test]
+    fn byte_list_is_not_list() {
+        let mut data = test_data();
+        let index = ByteList(vec![1, 2, 3]).into_garnish(&mut data).unwrap();
+
+        let result = Vec::<u8>::from_garnish(&data, index);
+
+        assert_eq!(result.unwrap_err().get_message(), "Expected List, got ByteList");
+    }
+
+    #[test]
+    fn wrong_type() {
+        let mut data = test_data();
+        let index = 10.into_garnish(&mut data).unwrap();
+
+        let result = String::from_garnish(&data, index);
+
+        assert_eq!(result.unwrap_err().get_message(), "Expected CharList, got Number");
+    }
+
+    #[test]
+    fn integer_out_of_range() {
+        let mut data = test_data();
+        let index = 300.into_garnish(&mut data).unwrap();
+
+        let result = i8::from_garnish(&data, index);
+
+        assert_eq!(result.unwrap_err().get_message(), "Number 300 is out of range for i8");
+    }
+
+    #[test]
+    fn negative_integer_to_unsigned() {
+        let mut data = test_data();
+        let index = (-1).into_garnish(&mut data).unwrap();
+
+        let result = u64::from_garnish(&data, index);
+
+        assert_eq!(result.unwrap_err().get_message(), "Number -1 is out of range for u64");
+    }
+
+    #[test]
+    fn float_to_integer() {
+        let mut data = test_data();
+        let whole = 2.0.into_garnish(&mut data).unwrap();
+        let fraction = 2.5.into_garnish(&mut data).unwrap();
+
+        assert_eq!(i32::from_garnish(&data, whole).unwrap(), 2);
+        assert_eq!(i32::from_garnish(&data, fraction).unwrap_err().get_message(), "Expected integer Number, got 2.5");
+    }
+
+    #[test]
+    fn basic_object_into_garnish() {
+        let mut data = test_data();
+        let index = basic_object!((Number 10), (Number 20)).into_garnish(&mut data).unwrap();
+
+        assert_eq!(Vec::<i32>::from_garnish(&data, index).unwrap(), vec![10, 20]);
+    }
+
+    #[test]
+    fn fields() {
+        let mut data = test_data();
+        let index = data
+            .push_object_to_data_block(BasicObject::List(vec![Box::new(basic_object!((Symbol "value") = (Number 10)))]))
+            .unwrap();
+
+        assert_eq!(read_field::<_, i32>(&data, index, "value").unwrap(), 10);
+        assert_eq!(read_field::<_, Option<i32>>(&data, index, "other").unwrap(), None);
+        assert_eq!(
+            read_field::<_, i32>(&data, index, "other").unwrap_err().get_message(),
+            "Missing field 'other'"
+        );
+    }
+
+    #[test]
+    fn variants() {
+        let mut data = test_data();
+        let unit = data.push_object_to_data_block(basic_object!(Symbol "First")).unwrap();
+        let pair = data.push_object_to_data_block(basic_object!((Symbol "Second") = (Number 10))).unwrap();
+
+        assert_eq!(read_variant(&data, unit).unwrap(), (symbol_value("First"), None));
+        assert_eq!(read_variant(&data, pair).unwrap(), (symbol_value("Second"), Some(pair - 1)));
+        assert_eq!(data.get_number(pair - 1).unwrap(), 10.into());
+    }
+}
@@ -1,7 +1,9 @@
 mod builder_macro;
+mod convert;
 
-use garnish_lang_traits::{GarnishData, GarnishDataType, SymbolListPart};
+use garnish_lang_traits::{Extents, GarnishData, GarnishDataType, SymbolListPart, TypeConstants};
 
+use crate::error::DataErrorType;
 use crate::{BasicData, BasicDataCustom, BasicGarnishData, BasicNumber, DataError};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -58,20 +60,8 @@ where
             }
             BasicObject::Expression(expr) => self.push_to_data_block(BasicData::Expression(expr)),
             BasicObject::External(ext) => self.push_to_data_block(BasicData::External(ext)),
-            BasicObject::CharList(str) => {
-                let list_index = self.push_to_data_block(BasicData::CharList(str.len()))?;
-                for c in str.chars() {
-                    self.push_to_data_block(BasicData::Char(c))?;
-                }
-                Ok(list_index)
-            }
-            BasicObject::ByteList(bytelist) => {
-                let list_index = self.push_to_data_block(BasicData::ByteList(bytelist.len()))?;
-                for b in bytelist {
-                    self.push_to_data_block(BasicData::Byte(b))?;
-                }
-                Ok(list_index)
-            }
+            BasicObject::CharList(str) => self.add_string(&str),
+            BasicObject::ByteList(bytelist) => self.add_byte_slice(&bytelist),
             BasicObject::Pair(left, right) => {
                 let left_index = self.push_object_to_data_block(*left)?;
                 let right_index = self.push_object_to_data_block(*right)?;
@@ -115,14 +105,154 @@ where
             }
         }
     }
+
+    /// Reads the value at `index` into an owned [`BasicObject`] tree.
+    ///
+    /// Concatenations are flattened into a single [`BasicObject::List`]. Slices are resolved into the items they select,
+    /// producing a [`BasicObject::CharList`], [`BasicObject::ByteList`] or [`BasicObject::SymbolList`] when slicing those types and a [`BasicObject::List`] otherwise.
+    pub fn get_object_from_data_block(&self, index: usize) -> Result<BasicObject<T>, DataError> {
+        Ok(match self.get_from_data_block_ensure_index(index)? {
+            BasicData::Unit => BasicObject::Unit,
+            BasicData::True => BasicObject::True,
+            BasicData::False => BasicObject::False,
+            BasicData::Type(ty) => BasicObject::Type(*ty),
+            BasicData::Number(num) => BasicObject::Number(*num),
+            BasicData::Char(c) => BasicObject::Char(*c),
+            BasicData::Byte(b) => BasicObject::Byte(*b),
+            BasicData::Symbol(sym) => BasicObject::Symbol(*sym),
+            BasicData::SymbolList(_) => BasicObject::SymbolList(
+                self.get_symbol_list_iter(index, Extents::new(0.into(), BasicNumber::max_value()))?
+                    .collect(),
+            ),
+            BasicData::Expression(expr) => BasicObject::Expression(*expr),
+            BasicData::External(ext) => BasicObject::External(*ext),
+            BasicData::CharList(_) | BasicData::PackedCharList(_, _) => BasicObject::CharList(
+                self.get_char_list_iter(index, Extents::new(0.into(), BasicNumber::max_value()))?
+                    .collect(),
+            ),
+            BasicData::ByteList(_) | BasicData::PackedByteList(_) => BasicObject::ByteList(
+                self.get_byte_list_iter(index, Extents::new(0.into(), BasicNumber::max_value()))?
+                    .collect(),
+            ),
+            BasicData::Pair(left, right) => BasicObject::Pair(
+                Box::new(self.get_object_from_data_block(*left)?),
+                Box::new(self.get_object_from_data_block(*right)?),
+            ),
+            BasicData::Range(start, end) => BasicObject::Range(
+                Box::new(self.get_object_from_data_block(*start)?),
+                Box::new(self.get_object_from_data_block(*end)?),
+            ),
+            BasicData::Partial(receiver, input) => BasicObject::Partial(
+                Box::new(self.get_object_from_data_block(*receiver)?),
+                Box::new(self.get_object_from_data_block(*input)?),
+            ),
+            BasicData::List(_, _) => BasicObject::List(
+                self.get_list_item_iter(index, Extents::new(0.into(), BasicNumber::max_value()))?
+                    .map(|item| self.get_object_from_data_block(item).map(Box::new))
+                    .collect::<Result<_, _>>()?,
+            ),
+            BasicData::Concatenation(_, _) => BasicObject::List(
+                self.get_concatenation_iter(index, Extents::new(0.into(), BasicNumber::max_value()))?
+                    .map(|item| self.get_object_from_data_block(item).map(Box::new))
+                    .collect::<Result<_, _>>()?,
+            ),
+            BasicData::Slice(value, range) => {
+                let selection = self.slice_selection(*range)?;
+                let extents = selection.extents();
+
+                match self.get_from_data_block_ensure_index(*value)? {
+                    BasicData::CharList(_) | BasicData::PackedCharList(_, _) => {
+                        BasicObject::CharList(selection.select(self.get_char_list_iter(*value, extents)?).into_iter().collect())
+                    }
+                    BasicData::ByteList(_) | BasicData::PackedByteList(_) => {
+                        BasicObject::ByteList(selection.select(self.get_byte_list_iter(*value, extents)?))
+                    }
+                    BasicData::SymbolList(_) => BasicObject::SymbolList(selection.select(self.get_symbol_list_iter(*value, extents)?)),
+                    BasicData::List(_, _) => BasicObject::List(
+                        selection
+                            .select(self.get_list_item_iter(*value, extents)?)
+                            .into_iter()
+                            .map(|item| self.get_object_from_data_block(item).map(Box::new))
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    BasicData::Concatenation(_, _) => BasicObject::List(
+                        selection
+                            .select(self.get_concatenation_iter(*value, extents)?)
+                            .into_iter()
+                            .map(|item| self.get_object_from_data_block(item).map(Box::new))
+                            .collect::<Result<_, _>>()?,
+                    ),
+                    data => Err(DataError::not_type_error(GarnishDataType::List, data.get_data_type()))?,
+                }
+            }
+            BasicData::Custom(custom) => BasicObject::Custom(Box::new(custom.clone())),
+            _ => Err(DataError::new("Not a basic type", DataErrorType::NotBasicType))?,
+        })
+    }
+
+    /// Resolves the range of a slice into the items it selects, matching how the runtime reads ranges.
+    ///
+    /// Range ends are stored inclusively. A range stepping away from its end selects no items.
+    pub(crate) fn slice_selection(&self, range: usize) -> Result<SliceSelection, DataError> {
+        let range = self.get_stepped_range(range)?;
+        let start = i64::from(self.get_from_data_block_ensure_index(range.start)?.as_number()?);
+        let end = i64::from(self.get_from_data_block_ensure_index(range.end)?.as_number()?);
+        let step = match range.step {
+            Some(step) => i64::from(self.get_from_data_block_ensure_index(step)?.as_number()?),
+            None => 1,
+        };
+
+        let magnitude = step.saturating_abs();
+        // items are read upward from the lowest index at or after zero that the range reaches, ranges stepping down are reversed after
+        let (low, last) = match step {
+            0 => Err(DataError::from(format!("Slice range from {} to {} has a step of zero", start, end)))?,
+            1.. => (start.max(0), end),
+            _ => (end.max(0), start),
+        };
+        let first = low.saturating_add(start.saturating_sub(low).rem_euclid(magnitude));
+
+        let extents = match last < first {
+            true => Extents::new(first.into(), first.into()),
+            false => Extents::new(first.into(), last.saturating_add(1).into()),
+        };
+
+        Ok(SliceSelection {
+            extents,
+            step: magnitude as usize,
+            reverse: step < 0,
+        })
+    }
+}
+
+/// Items selected by the range of a slice, read from its value with [`SliceSelection::extents`] then stepped through with [`SliceSelection::select`].
+pub(crate) struct SliceSelection {
+    extents: Extents<BasicNumber>,
+    step: usize,
+    reverse: bool,
+}
+
+impl SliceSelection {
+    pub(crate) fn extents(&self) -> Extents<BasicNumber> {
+        self.extents.clone()
+    }
+
+    /// Keeps every step-th of the items read with the extents, in the order the range walks them.
+    pub(crate) fn select<I>(&self, items: impl Iterator<Item = I>) -> Vec<I> {
+        let mut items: Vec<I> = items.step_by(self.step).collect();
+        if self.reverse {
+            items.reverse();
+        }
+        items
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use garnish_lang_traits::{GarnishDataType, SymbolListPart};
 
+    use crate::error::DataErrorType;
     use crate::{
-        BasicData,
+        BasicData, DataError,
         basic::{object::BasicObject, utilities::test_data},
     };
 
@@ -397,4 +527,236 @@ mod tests {
         assert_eq!(v1, Ok(0));
         assert_eq!(data, expected_data);
     }
+
+    fn round_trip(object: BasicObject) -> BasicObject {
+        let mut data = test_data();
+        let index = data.push_object_to_data_block(object).unwrap();
+        data.get_object_from_data_block(index).unwrap()
+    }
+
+    #[test]
+    fn get_simple_objects() {
+        for object in [
+            BasicObject::Unit,
+            BasicObject::True,
+            BasicObject::False,
+            BasicObject::Type(GarnishDataType::Number),
+            BasicObject::Number(100.into()),
+            BasicObject::Char('a'),
+            BasicObject::Byte(10),
+            BasicObject::Symbol(100),
+            BasicObject::Expression(5),
+            BasicObject::External(6),
+            BasicObject::CharList("héllo".to_string()),
+            BasicObject::ByteList(vec![1, 2, 3]),
+            BasicObject::SymbolList(vec![SymbolListPart::Symbol(100), SymbolListPart::Number(2.into())]),
+            BasicObject::Custom(Box::new(())),
+        ] {
+            assert_eq!(round_trip(object.clone()), object);
+        }
+    }
+
+    #[test]
+    fn get_nested_objects() {
+        let object = BasicObject::List(vec![
+            Box::new(BasicObject::Number(100.into())),
+            Box::new(BasicObject::Pair(
+                Box::new(BasicObject::Symbol(2)),
+                Box::new(BasicObject::Number(200.into())),
+            )),
+            Box::new(BasicObject::Range(
+                Box::new(BasicObject::Number(1.into())),
+                Box::new(BasicObject::Number(5.into())),
+            )),
+            Box::new(BasicObject::Partial(Box::new(BasicObject::Expression(1)), Box::new(BasicObject::Unit))),
+            Box::new(BasicObject::List(vec![])),
+        ]);
+
+        assert_eq!(round_trip(object.clone()), object);
+    }
+
+    #[test]
+    fn get_concatenation_object_is_flattened() {
+        let object = BasicObject::Concatenation(
+            Box::new(BasicObject::List(vec![
+                Box::new(BasicObject::Number(1.into())),
+                Box::new(BasicObject::Number(2.into())),
+            ])),
+            Box::new(BasicObject::Concatenation(
+                Box::new(BasicObject::Number(3.into())),
+                Box::new(BasicObject::Char('a')),
+            )),
+        );
+
+        assert_eq!(
+            round_trip(object),
+            BasicObject::List(vec![
+                Box::new(BasicObject::Number(1.into())),
+                Box::new(BasicObject::Number(2.into())),
+                Box::new(BasicObject::Number(3.into())),
+                Box::new(BasicObject::Char('a')),
+            ])
+        );
+    }
+
+    #[test]
+    fn get_slice_objects() {
+        let range = || {
+            Box::new(BasicObject::Range(
+                Box::new(BasicObject::Number(1.into())),
+                Box::new(BasicObject::Number(2.into())),
+            ))
+        };
+
+        assert_eq!(
+            round_trip(BasicObject::Slice(Box::new(BasicObject::CharList("abcd".to_string())), range())),
+            BasicObject::CharList("bc".to_string())
+        );
+        assert_eq!(
+            round_trip(BasicObject::Slice(Box::new(BasicObject::ByteList(vec![1, 2, 3, 4])), range())),
+            BasicObject::ByteList(vec![2, 3])
+        );
+        assert_eq!(
+            round_trip(BasicObject::Slice(
+                Box::new(BasicObject::Concatenation(
+                    Box::new(BasicObject::Number(1.into())),
+                    Box::new(BasicObject::Number(2.into()))
+                )),
+                range(),
+            )),
+            BasicObject::List(vec![Box::new(BasicObject::Number(2.into()))])
+        );
+    }
+
+    #[test]
+    fn get_slice_of_symbol_list() {
+        let range = Box::new(BasicObject::Range(
+            Box::new(BasicObject::Number(1.into())),
+            Box::new(BasicObject::Number(2.into())),
+        ));
+
+        assert_eq!(
+            round_trip(BasicObject::Slice(
+                Box::new(BasicObject::SymbolList(vec![
+                    SymbolListPart::Symbol(1),
+                    SymbolListPart::Symbol(2),
+                    SymbolListPart::Number(3.into()),
+                    SymbolListPart::Symbol(4),
+                ])),
+                range
+            )),
+            BasicObject::SymbolList(vec![SymbolListPart::Symbol(2), SymbolListPart::Number(3.into())])
+        );
+    }
+
+    #[test]
+    fn get_slice_of_empty_range() {
+        let range = Box::new(BasicObject::Range(
+            Box::new(BasicObject::Number(3.into())),
+            Box::new(BasicObject::Number(1.into())),
+        ));
+
+        assert_eq!(
+            round_trip(BasicObject::Slice(Box::new(BasicObject::CharList("abcd".to_string())), range)),
+            BasicObject::CharList(String::new())
+        );
+    }
+
+    #[test]
+    fn get_slice_of_negative_range() {
+        let range = Box::new(BasicObject::Range(
+            Box::new(BasicObject::Number((-3).into())),
+            Box::new(BasicObject::Number((-1).into())),
+        ));
+
+        assert_eq!(
+            round_trip(BasicObject::Slice(Box::new(BasicObject::ByteList(vec![1, 2, 3, 4])), range)),
+            BasicObject::ByteList(vec![])
+        );
+    }
+
+    #[test]
+    fn get_slice_of_stepped_range() {
+        let range = |start: i32, end: i32, step: i32| {
+            Box::new(BasicObject::Range(
+                Box::new(BasicObject::Range(
+                    Box::new(BasicObject::Number(start.into())),
+                    Box::new(BasicObject::Number(end.into())),
+                )),
+                Box::new(BasicObject::Number(step.into())),
+            ))
+        };
+
+        assert_eq!(
+            round_trip(BasicObject::Slice(Box::new(BasicObject::CharList("abcdef".to_string())), range(0, 5, 2))),
+            BasicObject::CharList("ace".to_string())
+        );
+        assert_eq!(
+            round_trip(BasicObject::Slice(
+                Box::new(BasicObject::ByteList(vec![0, 1, 2, 3, 4, 5])),
+                range(9, 0, -2)
+            )),
+            BasicObject::ByteList(vec![5, 3, 1])
+        );
+        assert_eq!(
+            round_trip(BasicObject::Slice(
+                Box::new(BasicObject::List((10..16).map(|n| Box::new(BasicObject::Number(n.into()))).collect())),
+                range(-3, 10, 2),
+            )),
+            BasicObject::List(vec![
+                Box::new(BasicObject::Number(11.into())),
+                Box::new(BasicObject::Number(13.into())),
+                Box::new(BasicObject::Number(15.into())),
+            ])
+        );
+        assert_eq!(
+            round_trip(BasicObject::Slice(Box::new(BasicObject::CharList("abcdef".to_string())), range(0, 5, -1))),
+            BasicObject::CharList(String::new())
+        );
+    }
+
+    #[test]
+    fn get_slice_of_zero_step_range() {
+        let mut data = test_data();
+        let range = BasicObject::Range(
+            Box::new(BasicObject::Range(
+                Box::new(BasicObject::Number(0.into())),
+                Box::new(BasicObject::Number(2.into())),
+            )),
+            Box::new(BasicObject::Number(0.into())),
+        );
+        let index = data
+            .push_object_to_data_block(BasicObject::Slice(Box::new(BasicObject::CharList("abc".to_string())), Box::new(range)))
+            .unwrap();
+
+        assert_eq!(
+            data.get_object_from_data_block(index),
+            Err(DataError::from("Slice range from 0 to 2 has a step of zero".to_string()))
+        );
+    }
+
+    #[test]
+    fn get_slice_of_invalid_value() {
+        let mut data = test_data();
+        let range = BasicObject::Range(Box::new(BasicObject::Number(1.into())), Box::new(BasicObject::Number(2.into())));
+        let index = data
+            .push_object_to_data_block(BasicObject::Slice(Box::new(BasicObject::Number(1.into())), Box::new(range)))
+            .unwrap();
+
+        assert_eq!(
+            data.get_object_from_data_block(index),
+            Err(DataError::not_type_error(GarnishDataType::List, GarnishDataType::Number))
+        );
+    }
+
+    #[test]
+    fn get_non_garnish_data() {
+        let mut data = test_data();
+        let index = data.push_to_data_block(BasicData::ListItem(0)).unwrap();
+
+        assert_eq!(
+            data.get_object_from_data_block(index),
+            Err(DataError::new("Not a basic type", DataErrorType::NotBasicType))
+        );
+    }
 }
@@ -1,3 +1,4 @@
+use crate::basic::BasicHandler;
 use crate::{BasicData, BasicDataCustom, BasicGarnishData, DataError, basic::companion::BasicDataCompanion};
 
 impl<T, Companion> BasicGarnishData<T, Companion>
@@ -12,6 +13,7 @@ where
         let original_register = self.current_register();
         let original_value = self.current_value();
         let original_frame = self.current_frame();
+        let original_local = self.current_local();
 
         let index_list_start = self.data_block().cursor;
 
@@ -33,6 +35,16 @@ where
             self.create_index_stack(index)?;
         }
 
+        if let Some(index) = original_local {
+            self.create_index_stack(index)?;
+        }
+
+        for handler in self.handlers().clone() {
+            for index in [handler.value, handler.register, handler.frame, handler.local].into_iter().flatten() {
+                self.create_index_stack(index)?;
+            }
+        }
+
         for additional_data_retention in additional_data_retentions {
             self.create_index_stack(*additional_data_retention)?;
         }
@@ -68,6 +80,24 @@ where
             self.set_current_frame(Some(mapped_index));
         }
 
+        if let Some(original_local) = original_local {
+            let mapped_index = self.lookup_in_data_slice(index_list_start, index_list_end, original_local)?;
+            self.set_current_local(Some(mapped_index));
+        }
+
+        for i in 0..self.handlers().len() {
+            let handler = self.handlers()[i];
+            let map = |index: Option<usize>| index.map(|index| self.lookup_in_data_slice(index_list_start, index_list_end, index)).transpose();
+            let mapped = BasicHandler {
+                jump_index: handler.jump_index,
+                value: map(handler.value)?,
+                register: map(handler.register)?,
+                frame: map(handler.frame)?,
+                local: map(handler.local)?,
+            };
+            self.handlers_mut()[i] = mapped;
+        }
+
         let mut mapped_indexes = vec![0; additional_data_retentions.len()];
         for (i, additional_data_retention) in additional_data_retentions.iter().enumerate() {
             mapped_indexes[i] = self.lookup_in_data_slice(index_list_start, index_list_end, *additional_data_retention)?;
@@ -136,7 +166,7 @@ mod optimize {
                 BasicData::Byte(1),
                 BasicData::Byte(2),
                 BasicData::Byte(3),
-                BasicData::Symbol(8904929874702161741),
+                BasicData::Symbol(7248772672978976256),
                 BasicData::Pair(8, 12),
                 BasicData::List(4, 0),
                 BasicData::ListItem(0),
@@ -62,6 +62,8 @@ where
                 BasicData::SymbolList(_) => {}
                 BasicData::CharList(_) => {}
                 BasicData::ByteList(_) => {}
+                BasicData::PackedCharList(_, _) => {}
+                BasicData::PackedByteList(_) => {}
                 BasicData::Pair(left, right) => {
                     let (left, right) = (left.clone(), right.clone());
                     self.push_to_data_block(BasicData::CloneItem(right))?;
@@ -82,6 +84,16 @@ where
                     self.push_to_data_block(BasicData::CloneItem(right))?;
                     self.push_to_data_block(BasicData::CloneItem(left))?;
                 }
+                BasicData::Closure(expression, captures) => {
+                    let (expression, captures) = (expression.clone(), captures.clone());
+                    self.push_to_data_block(BasicData::CloneItem(captures))?;
+                    self.push_to_data_block(BasicData::CloneItem(expression))?;
+                }
+                BasicData::Error(message, code) => {
+                    let (message, code) = (message.clone(), code.clone());
+                    self.push_to_data_block(BasicData::CloneItem(code))?;
+                    self.push_to_data_block(BasicData::CloneItem(message))?;
+                }
                 BasicData::Concatenation(left, right) => {
                     let (left, right) = (left.clone(), right.clone());
                     self.push_to_data_block(BasicData::CloneItem(right))?;
@@ -122,6 +134,8 @@ where
                 }
                 BasicData::ListItem(_) => {}
                 BasicData::AssociativeItem(_, _) => {}
+                BasicData::AssociativeIndex(_) => {}
+                BasicData::PackedBytes(_) => {}
                 BasicData::Value(previous, value) => {
                     let (previous, value) = (previous.clone(), value.clone());
                     self.push_to_data_block(BasicData::CloneItem(previous))?;
@@ -159,10 +173,15 @@ where
                     self.push_to_data_block(BasicData::CloneItem(register))?;
                 }
                 BasicData::FrameRoot => {}
+                BasicData::FrameLocals(_) => {}
                 BasicData::CloneItem(_) => {}
                 BasicData::CloneIndexMap(_, _) => {}
             }
 
+            if let Some(locals) = self.frame_locals(index) {
+                self.push_to_data_block(BasicData::CloneItem(locals))?;
+            }
+
             current += 1;
 
             iterations += 1;
@@ -0,0 +1,327 @@
+use std::cmp::Ordering;
+
+use garnish_lang_traits::GarnishDataType;
+
+use crate::{BasicData, BasicDataCustom, BasicGarnishData, DataError, basic::companion::BasicDataCompanion, error::DataErrorType};
+
+/// Char lists and byte lists with at least this many items are stored packed.
+///
+/// A packed list is a [`BasicData::PackedCharList`] or [`BasicData::PackedByteList`] followed by [`BasicData::PackedBytes`] slots
+/// holding the UTF-8 text or raw bytes, instead of one [`BasicData::Char`] or [`BasicData::Byte`] slot per item.
+pub const PACKED_LIST_THRESHOLD: usize = 32;
+
+/// Number of bytes held by a single [`BasicData::PackedBytes`] slot.
+pub const PACKED_CHUNK_SIZE: usize = 32;
+
+/// Number of chunk offsets held by one [`BasicData::PackedBytes`] slot of a char offset table.
+const OFFSETS_PER_SLOT: usize = PACKED_CHUNK_SIZE / size_of::<u64>();
+
+pub(crate) fn packed_slot_count(byte_len: usize) -> usize {
+    byte_len.div_ceil(PACKED_CHUNK_SIZE)
+}
+
+/// Number of slots following a packed char list's header.
+///
+/// Text with characters longer than one byte is followed by a char offset table,
+/// holding for each chunk of bytes the number of characters that start before it, so items can be found without decoding the whole text.
+pub(crate) fn packed_char_slot_count(len: usize, byte_len: usize) -> usize {
+    let chunks = packed_slot_count(byte_len);
+    match len == byte_len {
+        true => chunks,
+        false => chunks + chunks.div_ceil(OFFSETS_PER_SLOT),
+    }
+}
+
+fn invalid_packed_char_list(list_index: usize) -> DataError {
+    DataError::new("Packed char list is not valid UTF-8", DataErrorType::InvalidPackedCharList(list_index))
+}
+
+fn is_char_start(byte: u8) -> bool {
+    // UTF-8 continuation bytes are 0b10xxxxxx
+    byte & 0b1100_0000 != 0b1000_0000
+}
+
+impl<T, Companion> BasicGarnishData<T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    pub(crate) fn push_packed_bytes(&mut self, bytes: &[u8]) -> Result<(), DataError> {
+        for chunk in bytes.chunks(PACKED_CHUNK_SIZE) {
+            let mut packed = [0; PACKED_CHUNK_SIZE];
+            packed[..chunk.len()].copy_from_slice(chunk);
+            self.push_to_data_block(BasicData::PackedBytes(packed))?;
+        }
+        Ok(())
+    }
+
+    /// Pushes the UTF-8 bytes of `text` followed by its char offset table, if it needs one.
+    pub(crate) fn push_packed_text(&mut self, text: &str) -> Result<(), DataError> {
+        let bytes = text.as_bytes();
+        self.push_packed_bytes(bytes)?;
+        if text.is_ascii() {
+            return Ok(());
+        }
+
+        let mut offsets = Vec::with_capacity(packed_slot_count(bytes.len()) * size_of::<u64>());
+        let mut count = 0u64;
+        for chunk in bytes.chunks(PACKED_CHUNK_SIZE) {
+            offsets.extend_from_slice(&count.to_le_bytes());
+            count += chunk.iter().filter(|byte| is_char_start(**byte)).count() as u64;
+        }
+        self.push_packed_bytes(&offsets)
+    }
+
+    /// Number of characters starting before `chunk` of the packed char list at `list_index`.
+    fn packed_chunk_char_offset(&self, list_index: usize, byte_len: usize, chunk: usize) -> Result<usize, DataError> {
+        let table_index = list_index + 1 + packed_slot_count(byte_len) + chunk / OFFSETS_PER_SLOT;
+        let slot = self.get_from_data_block_ensure_index(table_index)?.as_packed_bytes()?;
+        let start = (chunk % OFFSETS_PER_SLOT) * size_of::<u64>();
+        let mut offset = [0; size_of::<u64>()];
+        offset.copy_from_slice(&slot[start..start + size_of::<u64>()]);
+        Ok(u64::from_le_bytes(offset) as usize)
+    }
+
+    /// Byte position of character `item_index` in a packed char list with a char offset table, `len` when past the last character.
+    fn packed_char_position(&self, list_index: usize, len: usize, byte_len: usize, item_index: usize) -> Result<usize, DataError> {
+        if item_index >= len {
+            return Ok(byte_len);
+        }
+
+        // binary search for the last chunk whose first character is at or before item_index
+        let (mut low, mut high) = (0, packed_slot_count(byte_len));
+        while high - low > 1 {
+            let mid = low + (high - low) / 2;
+            match self.packed_chunk_char_offset(list_index, byte_len, mid)? <= item_index {
+                true => low = mid,
+                false => high = mid,
+            }
+        }
+
+        let mut count = self.packed_chunk_char_offset(list_index, byte_len, low)?;
+        let mut chunk_start = low * PACKED_CHUNK_SIZE;
+        while chunk_start < byte_len {
+            let chunk = self
+                .get_from_data_block_ensure_index(list_index + 1 + chunk_start / PACKED_CHUNK_SIZE)?
+                .as_packed_bytes()?;
+            for (offset, byte) in chunk.iter().enumerate() {
+                if is_char_start(*byte) {
+                    if count == item_index {
+                        return Ok(chunk_start + offset);
+                    }
+                    count += 1;
+                }
+            }
+            chunk_start += PACKED_CHUNK_SIZE;
+        }
+
+        Err(invalid_packed_char_list(list_index))
+    }
+
+    /// Bytes of the packed list at `list_index` in the range `start..end`.
+    fn packed_bytes_at(&self, list_index: usize, start: usize, end: usize) -> Result<Vec<u8>, DataError> {
+        let mut bytes = Vec::with_capacity(end.saturating_sub(start));
+        let mut position = start;
+        while position < end {
+            let chunk = self
+                .get_from_data_block_ensure_index(list_index + 1 + position / PACKED_CHUNK_SIZE)?
+                .as_packed_bytes()?;
+            let offset = position % PACKED_CHUNK_SIZE;
+            let take = (PACKED_CHUNK_SIZE - offset).min(end - position);
+            bytes.extend_from_slice(&chunk[offset..offset + take]);
+            position += take;
+        }
+        Ok(bytes)
+    }
+
+    fn packed_text_at(&self, list_index: usize, byte_len: usize) -> Result<String, DataError> {
+        String::from_utf8(self.packed_bytes_at(list_index, 0, byte_len)?).map_err(|_| invalid_packed_char_list(list_index))
+    }
+
+    pub(crate) fn char_list_len_at(&self, list_index: usize) -> Result<usize, DataError> {
+        match self.get_from_data_block_ensure_index(list_index)? {
+            BasicData::CharList(len) | BasicData::PackedCharList(len, _) => Ok(*len),
+            data => Err(DataError::not_type_error(GarnishDataType::CharList, data.get_data_type())),
+        }
+    }
+
+    /// Characters `start..end` of the char list at `list_index`, the range must already be clamped to the list's length.
+    pub(crate) fn char_list_chars_at(&self, list_index: usize, start: usize, end: usize) -> Result<Vec<char>, DataError> {
+        match self.get_from_data_block_ensure_index(list_index)? {
+            BasicData::CharList(_) => (start..end)
+                .map(|i| self.get_from_data_block_ensure_index(list_index + 1 + i)?.as_char())
+                .collect(),
+            BasicData::PackedCharList(len, byte_len) if len == byte_len => {
+                Ok(self.packed_bytes_at(list_index, start, end)?.into_iter().map(char::from).collect())
+            }
+            BasicData::PackedCharList(len, byte_len) => {
+                let (len, byte_len) = (*len, *byte_len);
+                let start = self.packed_char_position(list_index, len, byte_len, start)?;
+                let end = self.packed_char_position(list_index, len, byte_len, end)?.max(start);
+                String::from_utf8(self.packed_bytes_at(list_index, start, end)?)
+                    .map(|text| text.chars().collect())
+                    .map_err(|_| invalid_packed_char_list(list_index))
+            }
+            data => Err(DataError::not_type_error(GarnishDataType::CharList, data.get_data_type())),
+        }
+    }
+
+    pub(crate) fn char_list_item_at(&self, list_index: usize, item_index: usize) -> Result<Option<char>, DataError> {
+        if item_index >= self.char_list_len_at(list_index)? {
+            return Ok(None);
+        }
+        Ok(self.char_list_chars_at(list_index, item_index, item_index + 1)?.first().copied())
+    }
+
+    /// Orders the char lists at `left` and `right` character by character, reading them in place.
+    pub(crate) fn char_list_cmp_at(&self, left: usize, right: usize) -> Result<Ordering, DataError> {
+        let (left_len, right_len) = (self.char_list_len_at(left)?, self.char_list_len_at(right)?);
+        for i in 0..left_len.min(right_len) {
+            match self.char_list_item_at(left, i)?.cmp(&self.char_list_item_at(right, i)?) {
+                Ordering::Equal => {}
+                ordering => return Ok(ordering),
+            }
+        }
+        Ok(left_len.cmp(&right_len))
+    }
+
+    pub(crate) fn char_list_string_at(&self, list_index: usize) -> Result<String, DataError> {
+        match self.get_from_data_block_ensure_index(list_index)? {
+            BasicData::PackedCharList(_, byte_len) => self.packed_text_at(list_index, *byte_len),
+            _ => Ok(self
+                .char_list_chars_at(list_index, 0, self.char_list_len_at(list_index)?)?
+                .into_iter()
+                .collect()),
+        }
+    }
+
+    pub(crate) fn byte_list_len_at(&self, list_index: usize) -> Result<usize, DataError> {
+        match self.get_from_data_block_ensure_index(list_index)? {
+            BasicData::ByteList(len) | BasicData::PackedByteList(len) => Ok(*len),
+            data => Err(DataError::not_type_error(GarnishDataType::ByteList, data.get_data_type())),
+        }
+    }
+
+    /// Bytes `start..end` of the byte list at `list_index`, the range must already be clamped to the list's length.
+    pub(crate) fn byte_list_bytes_at(&self, list_index: usize, start: usize, end: usize) -> Result<Vec<u8>, DataError> {
+        match self.get_from_data_block_ensure_index(list_index)? {
+            BasicData::ByteList(_) => (start..end)
+                .map(|i| self.get_from_data_block_ensure_index(list_index + 1 + i)?.as_byte())
+                .collect(),
+            BasicData::PackedByteList(_) => self.packed_bytes_at(list_index, start, end),
+            data => Err(DataError::not_type_error(GarnishDataType::ByteList, data.get_data_type())),
+        }
+    }
+
+    pub(crate) fn byte_list_item_at(&self, list_index: usize, item_index: usize) -> Result<Option<u8>, DataError> {
+        if item_index >= self.byte_list_len_at(list_index)? {
+            return Ok(None);
+        }
+        Ok(self.byte_list_bytes_at(list_index, item_index, item_index + 1)?.first().copied())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use garnish_lang_traits::GarnishDataType;
+
+    use crate::{
+        BasicData, DataError,
+        basic::{
+            packed::{PACKED_CHUNK_SIZE, PACKED_LIST_THRESHOLD},
+            utilities::test_data,
+        },
+        error::DataErrorType,
+    };
+
+    #[test]
+    fn short_lists_are_not_packed() {
+        let mut data = test_data();
+        let chars = data.add_string("abc").unwrap();
+        let bytes = data.add_byte_slice(&[1, 2, 3]).unwrap();
+
+        assert_eq!(data.get_from_data_block_ensure_index(chars), Ok(&BasicData::CharList(3)));
+        assert_eq!(data.get_from_data_block_ensure_index(bytes), Ok(&BasicData::ByteList(3)));
+    }
+
+    #[test]
+    fn ascii_char_list_is_packed() {
+        let mut data = test_data();
+        let text = "a".repeat(PACKED_LIST_THRESHOLD) + "bcdefghij";
+        let index = data.add_string(&text).unwrap();
+
+        assert_eq!(
+            data.get_from_data_block_ensure_index(index),
+            Ok(&BasicData::PackedCharList(text.len(), text.len()))
+        );
+        assert_eq!(data.data_block().cursor, 3);
+        assert_eq!(data.char_list_len_at(index), Ok(text.len()));
+        assert_eq!(data.char_list_string_at(index), Ok(text.clone()));
+        assert_eq!(data.char_list_item_at(index, PACKED_CHUNK_SIZE + 1), Ok(Some('c')));
+        assert_eq!(data.char_list_item_at(index, text.len()), Ok(None));
+        assert_eq!(data.char_list_chars_at(index, 30, 35), Ok(vec!['a', 'a', 'b', 'c', 'd']));
+    }
+
+    #[test]
+    fn multi_byte_char_list_is_packed() {
+        let mut data = test_data();
+        let text = "é😀".repeat(PACKED_LIST_THRESHOLD);
+        let index = data.add_string(&text).unwrap();
+
+        assert_eq!(
+            data.get_from_data_block_ensure_index(index),
+            Ok(&BasicData::PackedCharList(PACKED_LIST_THRESHOLD * 2, text.len()))
+        );
+        // 192 bytes of text in 6 chunks, followed by 2 slots of chunk offsets
+        assert_eq!(data.data_block().cursor, 1 + 6 + 2);
+        assert_eq!(data.char_list_string_at(index), Ok(text));
+        assert_eq!(data.char_list_item_at(index, 3), Ok(Some('😀')));
+        assert_eq!(data.char_list_chars_at(index, 4, 7), Ok(vec!['é', '😀', 'é']));
+    }
+
+    #[test]
+    fn multi_byte_char_list_items_across_chunks() {
+        let mut data = test_data();
+        // mixed widths so characters are split across chunk boundaries at different points
+        let text = "aé😀€".repeat(PACKED_LIST_THRESHOLD) + "end";
+        let index = data.add_string(&text).unwrap();
+        let chars = text.chars().collect::<Vec<_>>();
+
+        for (i, c) in chars.iter().enumerate() {
+            assert_eq!(data.char_list_item_at(index, i), Ok(Some(*c)), "item {}", i);
+        }
+        assert_eq!(data.char_list_item_at(index, chars.len()), Ok(None));
+        assert_eq!(data.char_list_chars_at(index, 30, 37), Ok(chars[30..37].to_vec()));
+        assert_eq!(data.char_list_chars_at(index, chars.len() - 3, chars.len()), Ok(vec!['e', 'n', 'd']));
+        assert_eq!(data.char_list_chars_at(index, 5, 5), Ok(vec![]));
+    }
+
+    #[test]
+    fn byte_list_is_packed() {
+        let mut data = test_data();
+        let bytes = (0..100).collect::<Vec<u8>>();
+        let index = data.add_byte_slice(&bytes).unwrap();
+
+        assert_eq!(data.get_from_data_block_ensure_index(index), Ok(&BasicData::PackedByteList(100)));
+        assert_eq!(data.data_block().cursor, 5);
+        assert_eq!(data.byte_list_len_at(index), Ok(100));
+        assert_eq!(data.byte_list_bytes_at(index, 0, 100), Ok(bytes));
+        assert_eq!(data.byte_list_item_at(index, 64), Ok(Some(64)));
+        assert_eq!(data.byte_list_item_at(index, 100), Ok(None));
+        assert_eq!(data.byte_list_bytes_at(index, 30, 34), Ok(vec![30, 31, 32, 33]));
+    }
+
+    #[test]
+    fn wrong_type() {
+        let mut data = test_data();
+        let index = data.add_byte_slice(&[1, 2, 3]).unwrap();
+
+        assert_eq!(
+            data.char_list_len_at(index),
+            Err(DataError::new(
+                "Not of type",
+                DataErrorType::NotType(GarnishDataType::CharList, GarnishDataType::ByteList)
+            ))
+        );
+    }
+}
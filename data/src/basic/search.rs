@@ -34,6 +34,66 @@ pub(crate) fn search_for_associative_item<T: BasicDataCustom>(items: &[BasicData
     }
 }
 
+/// Lists with at least this many associations get a hash index of them in addition to the sorted associations.
+pub const HASHED_LIST_THRESHOLD: usize = 64;
+
+/// Number of slots used by the hash index of a list with `associations_count` associations, zero when below [`HASHED_LIST_THRESHOLD`].
+pub(crate) fn hash_index_size(associations_count: usize) -> usize {
+    if associations_count >= HASHED_LIST_THRESHOLD { (associations_count * 2).next_power_of_two() } else { 0 }
+}
+
+/// Number of slots following a list's header: items, associations and the hash index, if the list has one.
+pub(crate) fn list_allocation_size(len: usize, associations_count: usize) -> usize {
+    len * 2 + hash_index_size(associations_count)
+}
+
+// symbols are already hashes, lower bits are enough for the starting slot
+fn hash_slot(symbol: u64, index_size: usize) -> usize {
+    (symbol as usize) & (index_size - 1)
+}
+
+/// Fill `index` with an open addressing table of positions into the sorted `associations`.
+///
+/// Repeated symbols resolve to their last position, the same one [`search_for_associative_item_index`] finds.
+pub(crate) fn build_hash_index<T: BasicDataCustom>(associations: &[BasicData<T>], index: &mut [BasicData<T>]) -> Result<(), DataError> {
+    index.fill(BasicData::Empty);
+
+    for (position, item) in associations.iter().enumerate() {
+        let (symbol, _) = item.as_associative_item()?;
+        let next_symbol = associations.get(position + 1).map(|next| next.as_associative_item()).transpose()?;
+        if next_symbol.is_some_and(|(next, _)| next == symbol) {
+            continue;
+        }
+
+        let mut slot = hash_slot(symbol, index.len());
+        while !matches!(index[slot], BasicData::Empty) {
+            slot = (slot + 1) % index.len();
+        }
+        index[slot] = BasicData::AssociativeIndex(position);
+    }
+
+    Ok(())
+}
+
+/// Hash index equivalent of [`search_for_associative_item_index`].
+pub(crate) fn search_hash_index<T: BasicDataCustom>(associations: &[BasicData<T>], index: &[BasicData<T>], search_symbol: u64) -> Result<Option<usize>, DataError> {
+    let mut slot = hash_slot(search_symbol, index.len());
+    for _ in 0..index.len() {
+        match &index[slot] {
+            BasicData::Empty => return Ok(None),
+            BasicData::AssociativeIndex(position) => {
+                if associations[*position].as_associative_item()?.0 == search_symbol {
+                    return Ok(Some(*position));
+                }
+            }
+            _ => return Err(DataError::not_basic_type_error()),
+        }
+        slot = (slot + 1) % index.len();
+    }
+
+    Ok(None)
+}
+
 #[cfg(test)]
 mod tests {
     use crate::error::DataErrorType;
@@ -104,4 +164,66 @@ mod tests {
         let result = search_for_associative_item(&items, 100);
         assert_eq!(result, Err(DataError::new("Not a basic type", DataErrorType::NotBasicType)));
     }
+
+    #[test]
+    fn hash_index_sizes() {
+        assert_eq!(hash_index_size(HASHED_LIST_THRESHOLD - 1), 0);
+        assert_eq!(hash_index_size(HASHED_LIST_THRESHOLD), HASHED_LIST_THRESHOLD * 2);
+        assert_eq!(hash_index_size(100), 256);
+        assert_eq!(list_allocation_size(3, 3), 6);
+        assert_eq!(list_allocation_size(100, 100), 456);
+        assert_eq!(list_allocation_size(100, 10), 200);
+    }
+
+    #[test]
+    fn hash_index_matches_binary_search_for_repeated_symbols() {
+        let associations = vec![
+            BasicData::<()>::AssociativeItem(1, 10),
+            BasicData::<()>::AssociativeItem(2, 11),
+            BasicData::<()>::AssociativeItem(2, 12),
+            BasicData::<()>::AssociativeItem(2, 13),
+            BasicData::<()>::AssociativeItem(3, 14),
+        ];
+        let mut index = vec![BasicData::<()>::Empty; 16];
+        build_hash_index(&associations, &mut index).unwrap();
+
+        for symbol in 1..=3 {
+            assert_eq!(
+                search_hash_index(&associations, &index, symbol),
+                search_for_associative_item_index(&associations, symbol)
+            );
+        }
+        assert_eq!(search_hash_index(&associations, &index, 2), Ok(Some(3)));
+    }
+
+    #[test]
+    fn hash_index_finds_all_items() {
+        // 16 and 32 share a starting slot in a table of 16, forcing a probe
+        let associations = vec![
+            BasicData::<()>::AssociativeItem(3, 10),
+            BasicData::<()>::AssociativeItem(16, 11),
+            BasicData::<()>::AssociativeItem(32, 12),
+            BasicData::<()>::AssociativeItem(u64::MAX, 13),
+        ];
+        let mut index = vec![BasicData::<()>::Unit; 16];
+        build_hash_index(&associations, &mut index).unwrap();
+
+        for (position, item) in associations.iter().enumerate() {
+            let (symbol, _) = item.as_associative_item().unwrap();
+            assert_eq!(search_hash_index(&associations, &index, symbol), Ok(Some(position)));
+        }
+
+        assert_eq!(search_hash_index(&associations, &index, 48), Ok(None));
+        assert_eq!(search_hash_index(&associations, &index, 5), Ok(None));
+    }
+
+    #[test]
+    fn hash_index_full_table() {
+        let associations = vec![BasicData::<()>::AssociativeItem(1, 10), BasicData::<()>::AssociativeItem(2, 11)];
+        let mut index = vec![BasicData::<()>::Empty; 2];
+        build_hash_index(&associations, &mut index).unwrap();
+
+        assert_eq!(search_hash_index(&associations, &index, 2), Ok(Some(1)));
+        assert_eq!(search_hash_index(&associations, &index, 3), Ok(None));
+    }
 }
@@ -0,0 +1,491 @@
+use garnish_lang_traits::{Extents, GarnishData, TypeConstants};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::forward_to_deserialize_any;
+
+use crate::basic::companion::BasicDataCompanion;
+use crate::basic::object::SliceSelection;
+use crate::{BasicData, BasicDataCustom, BasicGarnishData, BasicNumber, DataError, SimpleNumber};
+
+/// Deserializer reading the value at a data index of a [`BasicGarnishData`].
+///
+/// See [`BasicGarnishData::deserialize_from_data_block`] for which values are accepted.
+pub struct DataDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    data: &'a BasicGarnishData<T, Companion>,
+    index: usize,
+}
+
+impl<'a, T, Companion> DataDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    pub fn new(data: &'a BasicGarnishData<T, Companion>, index: usize) -> Self {
+        Self { data, index }
+    }
+
+    fn at(&self, index: usize) -> Self {
+        Self::new(self.data, index)
+    }
+
+    fn get(&self) -> Result<&'a BasicData<T>, DataError> {
+        self.data.get_from_data_block_ensure_index(self.index)
+    }
+
+    fn symbol_name(&self, symbol: u64) -> Result<String, DataError> {
+        self.data
+            .get_symbol_string(symbol)?
+            .ok_or_else(|| de::Error::custom(format!("No name registered for symbol {}", symbol)))
+    }
+
+    /// Items of list like values, or `None` if the value doesn't have items.
+    fn items(&self) -> Result<Option<Items>, DataError> {
+        let extents = || Extents::new(0.into(), BasicNumber::max_value());
+        Ok(match self.get()? {
+            BasicData::List(_, _) => Some(Items::Data(self.data.get_list_item_iter(self.index, extents())?.collect())),
+            BasicData::Concatenation(_, _) => Some(Items::Data(self.data.get_concatenation_iter(self.index, extents())?.collect())),
+            BasicData::CharList(len) | BasicData::ByteList(len) | BasicData::SymbolList(len) => {
+                Some(Items::Data((self.index + 1..self.index + 1 + len).collect()))
+            }
+            BasicData::PackedCharList(_, _) => Some(Items::Chars(self.data.get_char_list_iter(self.index, extents())?.collect())),
+            BasicData::PackedByteList(_) => Some(Items::Bytes(self.data.get_byte_list_iter(self.index, extents())?.collect())),
+            // stepped ranges flatten into start, end and step
+            BasicData::Range(_, _) => {
+                let range = self.data.get_stepped_range(self.index)?;
+                Some(Items::Data([range.start, range.end].into_iter().chain(range.step).collect()))
+            }
+            BasicData::Pair(left, right) => Some(Items::Data(vec![*left, *right])),
+            BasicData::Slice(value, range) => {
+                let selection = self.data.slice_selection(*range)?;
+                self.at(*value).items()?.map(|items| items.select(&selection))
+            }
+            _ => None,
+        })
+    }
+
+    /// Text of symbols, char lists and slices or concatenations made only of characters.
+    fn text(&self) -> Result<Option<String>, DataError> {
+        match self.get()? {
+            BasicData::Symbol(symbol) => self.symbol_name(*symbol).map(Some),
+            BasicData::CharList(_) | BasicData::PackedCharList(_, _) => self.data.char_list_string_at(self.index).map(Some),
+            BasicData::Slice(_, _) | BasicData::Concatenation(_, _) => match self.items()? {
+                Some(Items::Data(items)) => Ok(items
+                    .into_iter()
+                    .map(|item| match self.data.get_from_data_block_ensure_index(item) {
+                        Ok(BasicData::Char(c)) => Some(*c),
+                        _ => None,
+                    })
+                    .collect()),
+                Some(Items::Chars(chars)) => Ok(Some(chars.into_iter().collect())),
+                Some(Items::Bytes(_)) | None => Ok(None),
+            },
+            _ => Ok(None),
+        }
+    }
+
+    /// True for lists where every item is a pair with a symbol on the left.
+    fn is_associative(&self, items: &[usize]) -> Result<bool, DataError> {
+        if items.is_empty() {
+            return Ok(false);
+        }
+        for item in items {
+            match self.data.get_from_data_block_ensure_index(*item)? {
+                BasicData::Pair(left, _) if matches!(self.data.get_from_data_block_ensure_index(*left)?, BasicData::Symbol(_)) => {}
+                _ => return Ok(false),
+            }
+        }
+        Ok(true)
+    }
+
+    fn sequence<'de, V>(self, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.items()? {
+            Some(items) => visitor.visit_seq(SeqDeserializer::new(self.data, items)),
+            None => self.deserialize_any_value(visitor),
+        }
+    }
+
+    fn deserialize_any_value<'de, V>(self, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.get()? {
+            BasicData::Unit => visitor.visit_unit(),
+            BasicData::True => visitor.visit_bool(true),
+            BasicData::False => visitor.visit_bool(false),
+            BasicData::Number(SimpleNumber::Integer(v)) => visitor.visit_i64(*v),
+            BasicData::Number(SimpleNumber::Float(v)) => visitor.visit_f64(*v),
+            BasicData::Number(SimpleNumber::Decimal(v)) => visitor.visit_f64(v.to_f64()),
+            BasicData::Char(c) => visitor.visit_char(*c),
+            BasicData::Byte(b) => visitor.visit_u8(*b),
+            BasicData::Symbol(symbol) => visitor.visit_string(self.symbol_name(*symbol)?),
+            BasicData::CharList(_) | BasicData::PackedCharList(_, _) => visitor.visit_string(self.text()?.unwrap_or_default()),
+            BasicData::ByteList(len) | BasicData::PackedByteList(len) => visitor.visit_byte_buf(self.data.byte_list_bytes_at(self.index, 0, *len)?),
+            BasicData::List(_, _) => {
+                let items = self
+                    .data
+                    .get_list_item_iter(self.index, Extents::new(0.into(), BasicNumber::max_value()))?
+                    .collect::<Vec<_>>();
+                match self.is_associative(&items)? {
+                    true => visitor.visit_map(MapDeserializer {
+                        data: self.data,
+                        items: items.into_iter(),
+                        value: None,
+                    }),
+                    false => visitor.visit_seq(SeqDeserializer::new(self.data, Items::Data(items))),
+                }
+            }
+            BasicData::Concatenation(_, _) | BasicData::Slice(_, _) | BasicData::Pair(_, _) | BasicData::Range(_, _) => self.sequence(visitor),
+            d => Err(de::Error::custom(format!("Cannot deserialize value of type {:?}", d.get_data_type()))),
+        }
+    }
+}
+
+impl<'de, 'a, T, Companion> de::Deserializer<'de> for DataDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Error = DataError;
+
+    fn deserialize_any<V>(self, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_any_value(visitor)
+    }
+
+    fn deserialize_str<V>(self, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.text()? {
+            Some(text) => visitor.visit_string(text),
+            None => self.deserialize_any_value(visitor),
+        }
+    }
+
+    fn deserialize_string<V>(self, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_identifier<V>(self, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_str(visitor)
+    }
+
+    fn deserialize_option<V>(self, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.get()? {
+            BasicData::Unit => visitor.visit_none(),
+            _ => visitor.visit_some(self),
+        }
+    }
+
+    fn deserialize_newtype_struct<V>(self, _name: &'static str, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V>(self, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        self.sequence(visitor)
+    }
+
+    fn deserialize_tuple<V>(self, _len: usize, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        self.sequence(visitor)
+    }
+
+    fn deserialize_tuple_struct<V>(self, _name: &'static str, _len: usize, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        self.sequence(visitor)
+    }
+
+    fn deserialize_map<V>(self, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        match self.get()? {
+            BasicData::List(_, _) | BasicData::Concatenation(_, _) | BasicData::Slice(_, _) => match self.items()? {
+                Some(Items::Data(items)) => visitor.visit_map(MapDeserializer {
+                    data: self.data,
+                    items: items.into_iter(),
+                    value: None,
+                }),
+                Some(items) => visitor.visit_seq(SeqDeserializer::new(self.data, items)),
+                None => visitor.visit_map(MapDeserializer {
+                    data: self.data,
+                    items: vec![].into_iter(),
+                    value: None,
+                }),
+            },
+            _ => self.deserialize_any_value(visitor),
+        }
+    }
+
+    fn deserialize_struct<V>(self, _name: &'static str, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        let (variant, payload) = match self.get()? {
+            BasicData::Pair(left, right) => match self.at(*left).text()? {
+                Some(variant) => (variant, Some(*right)),
+                None => return Err(de::Error::custom("Expected symbol on left of enum pair")),
+            },
+            _ => match self.text()? {
+                Some(variant) => (variant, None),
+                None => return self.deserialize_any_value(visitor),
+            },
+        };
+
+        visitor.visit_enum(EnumDeserializer {
+            data: self.data,
+            variant,
+            payload,
+        })
+    }
+
+    fn deserialize_ignored_any<V>(self, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char bytes byte_buf unit unit_struct
+    }
+}
+
+/// Items of a list like value.
+///
+/// Packed lists don't store their items as separate data, so their characters and bytes are read as values instead of data indices.
+enum Items {
+    Data(Vec<usize>),
+    Chars(Vec<char>),
+    Bytes(Vec<u8>),
+}
+
+impl Items {
+    fn len(&self) -> usize {
+        match self {
+            Items::Data(items) => items.len(),
+            Items::Chars(chars) => chars.len(),
+            Items::Bytes(bytes) => bytes.len(),
+        }
+    }
+
+    /// Keeps the items a slice's range selects. The extents are clamped to the number of items.
+    fn select(self, selection: &SliceSelection) -> Self {
+        let extents = selection.extents();
+        let end = usize::from(extents.end()).min(self.len());
+        let start = usize::from(extents.start()).min(end);
+        match self {
+            Items::Data(items) => Items::Data(selection.select(items.into_iter().skip(start).take(end - start))),
+            Items::Chars(chars) => Items::Chars(selection.select(chars.into_iter().skip(start).take(end - start))),
+            Items::Bytes(bytes) => Items::Bytes(selection.select(bytes.into_iter().skip(start).take(end - start))),
+        }
+    }
+}
+
+struct SeqDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    data: &'a BasicGarnishData<T, Companion>,
+    items: Items,
+    next: usize,
+}
+
+impl<'a, T, Companion> SeqDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    fn new(data: &'a BasicGarnishData<T, Companion>, items: Items) -> Self {
+        Self { data, items, next: 0 }
+    }
+}
+
+impl<'de, 'a, T, Companion> de::SeqAccess<'de> for SeqDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Error = DataError;
+
+    fn next_element_seed<S>(&mut self, seed: S) -> Result<Option<S::Value>, DataError>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let index = self.next;
+        self.next += 1;
+        match &self.items {
+            Items::Data(items) => items.get(index).map(|item| seed.deserialize(DataDeserializer::new(self.data, *item))),
+            Items::Chars(chars) => chars.get(index).map(|c| seed.deserialize(c.into_deserializer())),
+            Items::Bytes(bytes) => bytes.get(index).map(|b| seed.deserialize(b.into_deserializer())),
+        }
+        .transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len().saturating_sub(self.next))
+    }
+}
+
+struct MapDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    data: &'a BasicGarnishData<T, Companion>,
+    items: std::vec::IntoIter<usize>,
+    value: Option<usize>,
+}
+
+impl<'de, 'a, T, Companion> de::MapAccess<'de> for MapDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Error = DataError;
+
+    fn next_key_seed<K>(&mut self, seed: K) -> Result<Option<K::Value>, DataError>
+    where
+        K: DeserializeSeed<'de>,
+    {
+        let Some(item) = self.items.next() else {
+            return Ok(None);
+        };
+
+        match self.data.get_from_data_block_ensure_index(item)? {
+            BasicData::Pair(key, value) => {
+                self.value = Some(*value);
+                seed.deserialize(DataDeserializer::new(self.data, *key)).map(Some)
+            }
+            d => Err(de::Error::custom(format!(
+                "Expected Pair in associative list, got {:?}",
+                d.get_data_type()
+            ))),
+        }
+    }
+
+    fn next_value_seed<S>(&mut self, seed: S) -> Result<S::Value, DataError>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        match self.value.take() {
+            Some(value) => seed.deserialize(DataDeserializer::new(self.data, value)),
+            None => Err(de::Error::custom("Map value requested before its key")),
+        }
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.items.len())
+    }
+}
+
+struct EnumDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    data: &'a BasicGarnishData<T, Companion>,
+    variant: String,
+    payload: Option<usize>,
+}
+
+impl<'de, 'a, T, Companion> de::EnumAccess<'de> for EnumDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Error = DataError;
+    type Variant = Self;
+
+    fn variant_seed<S>(self, seed: S) -> Result<(S::Value, Self), DataError>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        let variant = seed.deserialize(IntoDeserializer::<DataError>::into_deserializer(self.variant.as_str()))?;
+        Ok((variant, self))
+    }
+}
+
+impl<'de, 'a, T, Companion> de::VariantAccess<'de> for EnumDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Error = DataError;
+
+    fn unit_variant(self) -> Result<(), DataError> {
+        match self.payload {
+            None => Ok(()),
+            Some(_) => Err(de::Error::custom(format!("Unexpected data for unit variant {}", self.variant))),
+        }
+    }
+
+    fn newtype_variant_seed<S>(self, seed: S) -> Result<S::Value, DataError>
+    where
+        S: DeserializeSeed<'de>,
+    {
+        seed.deserialize(self.payload_deserializer()?)
+    }
+
+    fn tuple_variant<V>(self, _len: usize, visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_seq(self.payload_deserializer()?, visitor)
+    }
+
+    fn struct_variant<V>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, DataError>
+    where
+        V: Visitor<'de>,
+    {
+        de::Deserializer::deserialize_map(self.payload_deserializer()?, visitor)
+    }
+}
+
+impl<'a, T, Companion> EnumDeserializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    fn payload_deserializer(&self) -> Result<DataDeserializer<'a, T, Companion>, DataError> {
+        match self.payload {
+            Some(payload) => Ok(DataDeserializer::new(self.data, payload)),
+            None => Err(de::Error::custom(format!("Missing data for variant {}", self.variant))),
+        }
+    }
+}
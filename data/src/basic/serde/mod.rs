@@ -0,0 +1,276 @@
+mod de;
+mod ser;
+
+pub use de::DataDeserializer;
+pub use ser::{DataSerializer, ListSerializer};
+
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+
+use crate::basic::companion::BasicDataCompanion;
+use crate::error::DataErrorType;
+use crate::{BasicDataCustom, BasicGarnishData, DataError};
+
+impl serde::ser::Error for DataError {
+    fn custom<M: std::fmt::Display>(msg: M) -> Self {
+        DataError::new(&msg.to_string(), DataErrorType::Serde)
+    }
+}
+
+impl serde::de::Error for DataError {
+    fn custom<M: std::fmt::Display>(msg: M) -> Self {
+        DataError::new(&msg.to_string(), DataErrorType::Serde)
+    }
+}
+
+impl<T, Companion> BasicGarnishData<T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    /// Serializes a value into the data block, returning the index of the created value.
+    ///
+    /// Values are mapped the same way as the `IntoGarnish` derive.
+    /// Structs and maps become associative lists of pairs, with field names and string map keys added as symbols.
+    /// Sequences and tuples become lists, `None` and unit values become Unit and strings become char lists.
+    /// Unit enum variants become symbols and variants with data become a pair of the variant symbol and its data.
+    pub fn serialize_to_data_block<V>(&mut self, value: &V) -> Result<usize, DataError>
+    where
+        V: Serialize + ?Sized,
+    {
+        value.serialize(&mut DataSerializer::new(self))
+    }
+
+    /// Deserializes the value at `index` into a Rust value, the inverse of [`BasicGarnishData::serialize_to_data_block`].
+    ///
+    /// Symbols are accepted wherever strings are expected, so script values like `:fast` or `name = "value"` deserialize
+    /// into enums and struct fields. Concatenations and slices are read as the flat list of items they contain.
+    pub fn deserialize_from_data_block<V>(&self, index: usize) -> Result<V, DataError>
+    where
+        V: DeserializeOwned,
+    {
+        V::deserialize(DataDeserializer::new(self, index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use garnish_lang_traits::{GarnishData, GarnishDataType};
+    use serde::{Deserialize, Serialize};
+
+    use crate::basic::packed::PACKED_LIST_THRESHOLD;
+    use crate::basic::utilities::test_data;
+    use crate::error::DataErrorType;
+    use crate::{BasicObject, DataError, basic_object, symbol_value};
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Config {
+        name: String,
+        retries: u8,
+        ratio: f64,
+        enabled: bool,
+        tags: Vec<String>,
+        timeout: Option<i32>,
+        mode: Mode,
+        limits: BTreeMap<String, i32>,
+        point: (i32, char),
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    enum Mode {
+        Fast,
+        Limited(i32),
+        Between(i32, i32),
+        Custom { label: String },
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Meters(f64);
+
+    fn config() -> Config {
+        Config {
+            name: "service".to_string(),
+            retries: 3,
+            ratio: 0.5,
+            enabled: true,
+            tags: vec!["a".to_string(), "b".to_string()],
+            timeout: None,
+            mode: Mode::Between(1, 5),
+            limits: BTreeMap::from([("low".to_string(), 1), ("high".to_string(), 10)]),
+            point: (1, 'x'),
+        }
+    }
+
+    #[test]
+    fn struct_round_trip() {
+        let mut data = test_data();
+        let index = data.serialize_to_data_block(&config()).unwrap();
+
+        assert_eq!(data.deserialize_from_data_block::<Config>(index).unwrap(), config());
+    }
+
+    #[test]
+    fn enum_round_trip() {
+        let mut data = test_data();
+        for mode in [Mode::Fast, Mode::Limited(10), Mode::Between(1, 2), Mode::Custom { label: "x".to_string() }] {
+            let index = data.serialize_to_data_block(&mode).unwrap();
+            assert_eq!(data.deserialize_from_data_block::<Mode>(index).unwrap(), mode);
+        }
+    }
+
+    #[test]
+    fn struct_is_associative_list() {
+        let mut data = test_data();
+        let index = data.serialize_to_data_block(&config()).unwrap();
+
+        let name = data.get_list_item_with_symbol(index, symbol_value("name")).unwrap().unwrap();
+        assert_eq!(data.get_object_from_data_block(name).unwrap(), BasicObject::CharList("service".to_string()));
+
+        let low = data.get_list_item_with_symbol(index, symbol_value("limits")).unwrap().unwrap();
+        let low = data.get_list_item_with_symbol(low, symbol_value("low")).unwrap().unwrap();
+        assert_eq!(data.get_object_from_data_block(low).unwrap(), BasicObject::Number(1.into()));
+    }
+
+    #[test]
+    fn serialize_values() {
+        let mut data = test_data();
+        let unit = data.serialize_to_data_block(&Mode::Fast).unwrap();
+        let pair = data.serialize_to_data_block(&Mode::Limited(10)).unwrap();
+        let newtype = data.serialize_to_data_block(&Meters(2.5)).unwrap();
+        let none = data.serialize_to_data_block(&None::<i32>).unwrap();
+
+        assert_eq!(data.get_object_from_data_block(unit).unwrap(), BasicObject::Symbol(symbol_value("Fast")));
+        assert_eq!(data.get_object_from_data_block(pair).unwrap(), basic_object!((Symbol "Limited") = (Number 10)));
+        assert_eq!(data.get_object_from_data_block(newtype).unwrap(), BasicObject::Number(2.5.into()));
+        assert_eq!(data.get_data_type(none).unwrap(), GarnishDataType::Unit);
+    }
+
+    #[test]
+    fn deserialize_script_like_values() {
+        let mut data = test_data();
+        data.parse_add_symbol("name").unwrap();
+        data.parse_add_symbol("Fast").unwrap();
+        data.parse_add_symbol("level").unwrap();
+        let index = data
+            .push_object_to_data_block(BasicObject::List(vec![
+                Box::new(basic_object!((Symbol "name") = (CharList "service"))),
+                Box::new(basic_object!((Symbol "level") = (Symbol "Fast"))),
+            ]))
+            .unwrap();
+
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Settings {
+            name: String,
+            level: Mode,
+            missing: Option<i32>,
+        }
+
+        assert_eq!(
+            data.deserialize_from_data_block::<Settings>(index).unwrap(),
+            Settings { name: "service".to_string(), level: Mode::Fast, missing: None }
+        );
+    }
+
+    #[test]
+    fn deserialize_packed_char_list() {
+        let mut data = test_data();
+        let text = "a long description that is stored packed".to_string();
+        let index = data.add_string(&text).unwrap();
+
+        assert_eq!(data.deserialize_from_data_block::<String>(index).unwrap(), text);
+    }
+
+    #[test]
+    fn packed_lists_round_trip_as_sequences() {
+        struct Bytes(Vec<u8>);
+
+        impl Serialize for Bytes {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_bytes(&self.0)
+            }
+        }
+
+        let mut data = test_data();
+        let text = "a".repeat(PACKED_LIST_THRESHOLD) + "bcd";
+        let bytes = (0..PACKED_LIST_THRESHOLD as u8 + 3).collect::<Vec<_>>();
+        let text_index = data.serialize_to_data_block(&text).unwrap();
+        let bytes_index = data.serialize_to_data_block(&Bytes(bytes.clone())).unwrap();
+
+        assert_eq!(data.get_data_type(text_index).unwrap(), GarnishDataType::CharList);
+        assert_eq!(
+            data.deserialize_from_data_block::<Vec<char>>(text_index).unwrap(),
+            text.chars().collect::<Vec<_>>()
+        );
+        assert_eq!(data.deserialize_from_data_block::<Vec<u8>>(bytes_index).unwrap(), bytes);
+    }
+
+    #[test]
+    fn deserialize_slice_of_packed_list() {
+        let mut data = test_data();
+        let bytes = (0..PACKED_LIST_THRESHOLD as u8 + 3).collect::<Vec<_>>();
+        let list = data.add_byte_slice(&bytes).unwrap();
+        let start = data.add_number(2.into()).unwrap();
+        let end = data.add_number(4.into()).unwrap();
+        let range = data.add_range(start, end).unwrap();
+        let index = data.add_slice(list, range).unwrap();
+
+        assert_eq!(data.deserialize_from_data_block::<Vec<u8>>(index).unwrap(), vec![2, 3, 4]);
+    }
+
+    #[test]
+    fn deserialize_slice_with_stepped_range() {
+        let mut data = test_data();
+        let bytes = (0..PACKED_LIST_THRESHOLD as u8 + 3).collect::<Vec<_>>();
+        let list = data.add_byte_slice(&bytes).unwrap();
+        let range = data
+            .push_object_to_data_block(basic_object!(((Number 7)..(Number 1))..(Number(-3))))
+            .unwrap();
+        let index = data.add_slice(list, range).unwrap();
+
+        assert_eq!(data.deserialize_from_data_block::<Vec<u8>>(index).unwrap(), vec![7, 4, 1]);
+    }
+
+    #[test]
+    fn deserialize_stepped_range_as_sequence() {
+        let mut data = test_data();
+        let index = data
+            .push_object_to_data_block(basic_object!(((Number 1)..(Number 9))..(Number 2)))
+            .unwrap();
+
+        assert_eq!(data.deserialize_from_data_block::<Vec<i32>>(index).unwrap(), vec![1, 9, 2]);
+    }
+
+    #[test]
+    fn deserialize_concatenation_as_sequence() {
+        let mut data = test_data();
+        let index = data
+            .push_object_to_data_block(BasicObject::Concatenation(
+                Box::new(basic_object!((Number 1), (Number 2))),
+                Box::new(BasicObject::Number(3.into())),
+            ))
+            .unwrap();
+
+        assert_eq!(data.deserialize_from_data_block::<Vec<i32>>(index).unwrap(), vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn out_of_range_number() {
+        let mut data = test_data();
+
+        let result = data.serialize_to_data_block(&u64::MAX);
+
+        assert_eq!(result, Err(DataError::new(&format!("Number {} is out of range", u64::MAX), DataErrorType::Serde)));
+    }
+
+    #[test]
+    fn wrong_type() {
+        let mut data = test_data();
+        let index = data.serialize_to_data_block(&"text").unwrap();
+
+        let result = data.deserialize_from_data_block::<i32>(index);
+
+        assert_eq!(result, Err(DataError::new("invalid type: string \"text\", expected i32", DataErrorType::Serde)));
+    }
+}
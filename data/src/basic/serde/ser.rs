@@ -0,0 +1,425 @@
+use garnish_lang_traits::GarnishData;
+use serde::Serialize;
+use serde::ser;
+
+use crate::basic::companion::BasicDataCompanion;
+use crate::{BasicData, BasicDataCustom, BasicGarnishData, DataError, SimpleNumber};
+
+/// Serializer writing values into the data block of a [`BasicGarnishData`].
+///
+/// Each serialized value produces the data index of its root. See [`BasicGarnishData::serialize_to_data_block`] for how values are mapped.
+pub struct DataSerializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    data: &'a mut BasicGarnishData<T, Companion>,
+    symbol_strings: bool,
+}
+
+impl<'a, T, Companion> DataSerializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    pub fn new(data: &'a mut BasicGarnishData<T, Companion>) -> Self {
+        Self { data, symbol_strings: false }
+    }
+
+    fn add_integer<V>(&mut self, value: V) -> Result<usize, DataError>
+    where
+        V: TryInto<i64> + Copy + std::fmt::Display,
+    {
+        match value.try_into() {
+            Ok(v) => self.data.push_to_data_block(BasicData::Number(SimpleNumber::Integer(v))),
+            Err(_) => Err(ser::Error::custom(format!("Number {} is out of range", value))),
+        }
+    }
+
+    fn add_list(&mut self, items: Vec<usize>) -> Result<usize, DataError> {
+        let mut list = self.data.start_list(items.len())?;
+        for item in items {
+            list = self.data.add_to_list(list, item)?;
+        }
+        self.data.end_list(list)
+    }
+
+    fn add_field(&mut self, name: &str, value: usize) -> Result<usize, DataError> {
+        let symbol = self.data.parse_add_symbol(name)?;
+        self.data.add_pair((symbol, value))
+    }
+}
+
+impl<'a, 'b, T, Companion> ser::Serializer for &'b mut DataSerializer<'a, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Ok = usize;
+    type Error = DataError;
+    type SerializeSeq = ListSerializer<'a, 'b, T, Companion>;
+    type SerializeTuple = ListSerializer<'a, 'b, T, Companion>;
+    type SerializeTupleStruct = ListSerializer<'a, 'b, T, Companion>;
+    type SerializeTupleVariant = ListSerializer<'a, 'b, T, Companion>;
+    type SerializeMap = ListSerializer<'a, 'b, T, Companion>;
+    type SerializeStruct = ListSerializer<'a, 'b, T, Companion>;
+    type SerializeStructVariant = ListSerializer<'a, 'b, T, Companion>;
+
+    fn serialize_bool(self, v: bool) -> Result<usize, DataError> {
+        self.data.push_to_data_block(if v { BasicData::True } else { BasicData::False })
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<usize, DataError> {
+        self.add_integer(v)
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<usize, DataError> {
+        self.add_integer(v)
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<usize, DataError> {
+        self.add_integer(v)
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<usize, DataError> {
+        self.add_integer(v)
+    }
+
+    fn serialize_i128(self, v: i128) -> Result<usize, DataError> {
+        self.add_integer(v)
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<usize, DataError> {
+        self.add_integer(v)
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<usize, DataError> {
+        self.add_integer(v)
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<usize, DataError> {
+        self.add_integer(v)
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<usize, DataError> {
+        self.add_integer(v)
+    }
+
+    fn serialize_u128(self, v: u128) -> Result<usize, DataError> {
+        self.add_integer(v)
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<usize, DataError> {
+        self.serialize_f64(v as f64)
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<usize, DataError> {
+        self.data.push_to_data_block(BasicData::Number(SimpleNumber::Float(v)))
+    }
+
+    fn serialize_char(self, v: char) -> Result<usize, DataError> {
+        self.data.push_to_data_block(BasicData::Char(v))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<usize, DataError> {
+        match std::mem::take(&mut self.symbol_strings) {
+            true => self.data.parse_add_symbol(v),
+            false => self.data.add_string(v),
+        }
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<usize, DataError> {
+        self.data.add_byte_slice(v)
+    }
+
+    fn serialize_none(self) -> Result<usize, DataError> {
+        self.data.push_to_data_block(BasicData::Unit)
+    }
+
+    fn serialize_some<V>(self, value: &V) -> Result<usize, DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<usize, DataError> {
+        self.data.push_to_data_block(BasicData::Unit)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<usize, DataError> {
+        self.serialize_unit()
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _variant_index: u32, variant: &'static str) -> Result<usize, DataError> {
+        self.data.parse_add_symbol(variant)
+    }
+
+    fn serialize_newtype_struct<V>(self, _name: &'static str, value: &V) -> Result<usize, DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<V>(self, _name: &'static str, _variant_index: u32, variant: &'static str, value: &V) -> Result<usize, DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        let value = value.serialize(&mut *self)?;
+        self.add_field(variant, value)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, DataError> {
+        Ok(ListSerializer::new(self, len, None))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, DataError> {
+        Ok(ListSerializer::new(self, Some(len), None))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeTupleStruct, DataError> {
+        Ok(ListSerializer::new(self, Some(len), None))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, DataError> {
+        Ok(ListSerializer::new(self, Some(len), Some(variant)))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, DataError> {
+        Ok(ListSerializer::new(self, len, None))
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<Self::SerializeStruct, DataError> {
+        Ok(ListSerializer::new(self, Some(len), None))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, DataError> {
+        Ok(ListSerializer::new(self, Some(len), Some(variant)))
+    }
+}
+
+/// Collects item indices for all compound values, creating the list once every item has been added.
+pub struct ListSerializer<'a, 'b, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    serializer: &'b mut DataSerializer<'a, T, Companion>,
+    items: Vec<usize>,
+    variant: Option<&'static str>,
+    key: Option<usize>,
+}
+
+impl<'a, 'b, T, Companion> ListSerializer<'a, 'b, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    fn new(serializer: &'b mut DataSerializer<'a, T, Companion>, len: Option<usize>, variant: Option<&'static str>) -> Self {
+        Self {
+            serializer,
+            items: Vec::with_capacity(len.unwrap_or(0)),
+            variant,
+            key: None,
+        }
+    }
+
+    fn add_item<V>(&mut self, value: &V) -> Result<(), DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        let item = value.serialize(&mut *self.serializer)?;
+        self.items.push(item);
+        Ok(())
+    }
+
+    fn add_field<V>(&mut self, key: &str, value: &V) -> Result<(), DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        let value = value.serialize(&mut *self.serializer)?;
+        let item = self.serializer.add_field(key, value)?;
+        self.items.push(item);
+        Ok(())
+    }
+
+    fn finish(self) -> Result<usize, DataError> {
+        let list = self.serializer.add_list(self.items)?;
+        match self.variant {
+            Some(variant) => self.serializer.add_field(variant, list),
+            None => Ok(list),
+        }
+    }
+}
+
+impl<'a, 'b, T, Companion> ser::SerializeSeq for ListSerializer<'a, 'b, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Ok = usize;
+    type Error = DataError;
+
+    fn serialize_element<V>(&mut self, value: &V) -> Result<(), DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        self.add_item(value)
+    }
+
+    fn end(self) -> Result<usize, DataError> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, T, Companion> ser::SerializeTuple for ListSerializer<'a, 'b, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Ok = usize;
+    type Error = DataError;
+
+    fn serialize_element<V>(&mut self, value: &V) -> Result<(), DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        self.add_item(value)
+    }
+
+    fn end(self) -> Result<usize, DataError> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, T, Companion> ser::SerializeTupleStruct for ListSerializer<'a, 'b, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Ok = usize;
+    type Error = DataError;
+
+    fn serialize_field<V>(&mut self, value: &V) -> Result<(), DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        self.add_item(value)
+    }
+
+    fn end(self) -> Result<usize, DataError> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, T, Companion> ser::SerializeTupleVariant for ListSerializer<'a, 'b, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Ok = usize;
+    type Error = DataError;
+
+    fn serialize_field<V>(&mut self, value: &V) -> Result<(), DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        self.add_item(value)
+    }
+
+    fn end(self) -> Result<usize, DataError> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, T, Companion> ser::SerializeMap for ListSerializer<'a, 'b, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Ok = usize;
+    type Error = DataError;
+
+    fn serialize_key<V>(&mut self, key: &V) -> Result<(), DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        // string keys become symbols so the resulting list is associative
+        self.serializer.symbol_strings = true;
+        let key = key.serialize(&mut *self.serializer);
+        self.serializer.symbol_strings = false;
+        self.key = Some(key?);
+        Ok(())
+    }
+
+    fn serialize_value<V>(&mut self, value: &V) -> Result<(), DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| <DataError as ser::Error>::custom("Map value serialized before its key"))?;
+        let value = value.serialize(&mut *self.serializer)?;
+        let item = self.serializer.data.add_pair((key, value))?;
+        self.items.push(item);
+        Ok(())
+    }
+
+    fn end(self) -> Result<usize, DataError> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, T, Companion> ser::SerializeStruct for ListSerializer<'a, 'b, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Ok = usize;
+    type Error = DataError;
+
+    fn serialize_field<V>(&mut self, key: &'static str, value: &V) -> Result<(), DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        self.add_field(key, value)
+    }
+
+    fn end(self) -> Result<usize, DataError> {
+        self.finish()
+    }
+}
+
+impl<'a, 'b, T, Companion> ser::SerializeStructVariant for ListSerializer<'a, 'b, T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    type Ok = usize;
+    type Error = DataError;
+
+    fn serialize_field<V>(&mut self, key: &'static str, value: &V) -> Result<(), DataError>
+    where
+        V: ?Sized + Serialize,
+    {
+        self.add_field(key, value)
+    }
+
+    fn end(self) -> Result<usize, DataError> {
+        self.finish()
+    }
+}
@@ -0,0 +1,862 @@
+use garnish_lang_traits::{GarnishDataType, Instruction};
+
+use crate::basic::BasicHandler;
+use crate::basic::storage::{ReallocationStrategy, ShrinkPolicy, StorageBlock, StorageSettings};
+use crate::error::DataErrorType;
+use crate::{BasicData, BasicDataCustom, BasicGarnishData, DataError, Decimal, OverflowPolicy, SimpleNumber, basic::companion::BasicDataCompanion};
+
+const SNAPSHOT_MAGIC: &[u8; 4] = b"GSNP";
+const SNAPSHOT_VERSION: u8 = 13;
+
+const INSTRUCTIONS: [Instruction; 70] = [
+    Instruction::Invalid,
+    Instruction::Put,
+    Instruction::PutValue,
+    Instruction::PushValue,
+    Instruction::UpdateValue,
+    Instruction::JumpTo,
+    Instruction::EndExpression,
+    Instruction::Add,
+    Instruction::Subtract,
+    Instruction::Multiply,
+    Instruction::Divide,
+    Instruction::IntegerDivide,
+    Instruction::Power,
+    Instruction::Opposite,
+    Instruction::AbsoluteValue,
+    Instruction::Remainder,
+    Instruction::BitwiseNot,
+    Instruction::BitwiseAnd,
+    Instruction::BitwiseOr,
+    Instruction::BitwiseXor,
+    Instruction::BitwiseShiftLeft,
+    Instruction::BitwiseShiftRight,
+    Instruction::And,
+    Instruction::Or,
+    Instruction::Xor,
+    Instruction::Not,
+    Instruction::Tis,
+    Instruction::JumpIfTrue,
+    Instruction::JumpIfFalse,
+    Instruction::TypeOf,
+    Instruction::ApplyType,
+    Instruction::TypeEqual,
+    Instruction::Equal,
+    Instruction::NotEqual,
+    Instruction::LessThan,
+    Instruction::LessThanOrEqual,
+    Instruction::GreaterThan,
+    Instruction::GreaterThanOrEqual,
+    Instruction::MakePair,
+    Instruction::MakeList,
+    Instruction::Apply,
+    Instruction::PartialApply,
+    Instruction::EmptyApply,
+    Instruction::Reapply,
+    Instruction::Access,
+    Instruction::AccessLeftInternal,
+    Instruction::AccessRightInternal,
+    Instruction::AccessLengthInternal,
+    Instruction::Resolve,
+    Instruction::StartSideEffect,
+    Instruction::EndSideEffect,
+    Instruction::MakeRange,
+    Instruction::MakeStartExclusiveRange,
+    Instruction::MakeEndExclusiveRange,
+    Instruction::MakeExclusiveRange,
+    Instruction::Concat,
+    Instruction::PutLocal,
+    Instruction::UpdateLocal,
+    Instruction::MakeClosure,
+    Instruction::StartTry,
+    Instruction::EndTry,
+    Instruction::EndCatch,
+    Instruction::StartIteration,
+    Instruction::Map,
+    Instruction::Filter,
+    Instruction::Fold,
+    Instruction::Any,
+    Instruction::All,
+    Instruction::Find,
+    Instruction::Sort,
+];
+
+const DATA_TYPES: [GarnishDataType; 23] = [
+    GarnishDataType::Invalid,
+    GarnishDataType::Unit,
+    GarnishDataType::Number,
+    GarnishDataType::Type,
+    GarnishDataType::Char,
+    GarnishDataType::CharList,
+    GarnishDataType::Byte,
+    GarnishDataType::ByteList,
+    GarnishDataType::Symbol,
+    GarnishDataType::SymbolList,
+    GarnishDataType::Pair,
+    GarnishDataType::Range,
+    GarnishDataType::Concatenation,
+    GarnishDataType::Slice,
+    GarnishDataType::Partial,
+    GarnishDataType::List,
+    GarnishDataType::Expression,
+    GarnishDataType::External,
+    GarnishDataType::True,
+    GarnishDataType::False,
+    GarnishDataType::Custom,
+    GarnishDataType::Closure,
+    GarnishDataType::Error,
+];
+
+/// Byte buffer that a snapshot is written to.
+///
+/// Passed to [`BasicDataCustom::write_snapshot`] so custom data can be encoded along with the rest of a snapshot.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct SnapshotWriter {
+    bytes: Vec<u8>,
+}
+
+impl SnapshotWriter {
+    pub fn new() -> Self {
+        Self { bytes: vec![] }
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.bytes.push(value);
+    }
+
+    pub fn write_u32(&mut self, value: u32) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    pub fn write_usize(&mut self, value: usize) {
+        self.write_u64(value as u64);
+    }
+
+    pub fn write_bytes(&mut self, value: &[u8]) {
+        self.write_usize(value.len());
+        self.bytes.extend_from_slice(value);
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    fn write_bool(&mut self, value: bool) {
+        self.write_u8(value as u8);
+    }
+
+    fn write_optional_usize(&mut self, value: Option<usize>) {
+        match value {
+            Some(v) => {
+                self.write_u8(1);
+                self.write_usize(v);
+            }
+            None => self.write_u8(0),
+        }
+    }
+}
+
+/// Cursor over the bytes of a snapshot.
+///
+/// Passed to [`BasicDataCustom::read_snapshot`] so custom data can be decoded along with the rest of a snapshot.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotReader<'a> {
+    bytes: &'a [u8],
+    cursor: usize,
+}
+
+impl<'a> SnapshotReader<'a> {
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, cursor: 0 }
+    }
+
+    pub fn cursor(&self) -> usize {
+        self.cursor
+    }
+
+    pub fn read_u8(&mut self) -> Result<u8, DataError> {
+        Ok(self.take(1)?[0])
+    }
+
+    pub fn read_u32(&mut self) -> Result<u32, DataError> {
+        let mut buf = [0u8; 4];
+        buf.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(buf))
+    }
+
+    pub fn read_u64(&mut self) -> Result<u64, DataError> {
+        let mut buf = [0u8; 8];
+        buf.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(buf))
+    }
+
+    pub fn read_usize(&mut self) -> Result<usize, DataError> {
+        let value = self.read_u64()?;
+        usize::try_from(value).map_err(|_| invalid_snapshot(self.cursor, "size value too large for platform"))
+    }
+
+    pub fn read_bytes(&mut self) -> Result<&'a [u8], DataError> {
+        let len = self.read_usize()?;
+        self.take(len)
+    }
+
+    fn read_bool(&mut self) -> Result<bool, DataError> {
+        match self.read_u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(invalid_snapshot(self.cursor, "invalid boolean")),
+        }
+    }
+
+    fn read_overflow_policy(&mut self) -> Result<OverflowPolicy, DataError> {
+        match self.read_u8()? {
+            0 => Ok(OverflowPolicy::Error),
+            1 => Ok(OverflowPolicy::PromoteToFloat),
+            _ => Err(invalid_snapshot(self.cursor, "invalid overflow policy")),
+        }
+    }
+
+    fn read_optional_usize(&mut self) -> Result<Option<usize>, DataError> {
+        match self.read_u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(self.read_usize()?)),
+            _ => Err(invalid_snapshot(self.cursor, "invalid optional tag")),
+        }
+    }
+
+    fn take(&mut self, count: usize) -> Result<&'a [u8], DataError> {
+        let end = self.cursor.checked_add(count).filter(|end| *end <= self.bytes.len());
+        match end {
+            Some(end) => {
+                let slice = &self.bytes[self.cursor..end];
+                self.cursor = end;
+                Ok(slice)
+            }
+            None => Err(invalid_snapshot(self.cursor, "unexpected end of snapshot")),
+        }
+    }
+}
+
+fn invalid_snapshot(position: usize, reason: &str) -> DataError {
+    DataError::new(reason, DataErrorType::InvalidSnapshot(position))
+}
+
+impl<T, Companion> BasicGarnishData<T, Companion>
+where
+    T: BasicDataCustom,
+    Companion: BasicDataCompanion<T>,
+{
+    /// Captures the complete execution state, value stack, registers, frames, locals, handlers, instruction cursor, error values and strict mode settings and all blocks, as bytes.
+    ///
+    /// The companion is not part of the snapshot and is provided again when restoring.
+    pub fn snapshot(&self) -> Result<Vec<u8>, DataError> {
+        let mut writer = SnapshotWriter::new();
+        for b in SNAPSHOT_MAGIC {
+            writer.write_u8(*b);
+        }
+        writer.write_u8(SNAPSHOT_VERSION);
+
+        writer.write_usize(self.instruction_pointer());
+        writer.write_optional_usize(self.current_value());
+        writer.write_optional_usize(self.current_register());
+        writer.write_optional_usize(self.current_frame());
+        writer.write_optional_usize(self.current_local());
+        writer.write_usize(self.handlers().len());
+        for handler in self.handlers() {
+            writer.write_usize(handler.jump_index);
+            writer.write_optional_usize(handler.value);
+            writer.write_optional_usize(handler.register);
+            writer.write_optional_usize(handler.frame);
+            writer.write_optional_usize(handler.local);
+        }
+        writer.write_usize(self.data_retention_count());
+        writer.write_bool(self.error_values);
+        writer.write_bool(self.strict_mode);
+        writer.write_u8(match self.overflow_policy {
+            OverflowPolicy::Error => 0,
+            OverflowPolicy::PromoteToFloat => 1,
+        });
+
+        for block in [
+            self.instruction_block(),
+            self.jump_table_block(),
+            self.symbol_table_block(),
+            self.expression_symbol_block(),
+            self.data_block(),
+            self.custom_data_block(),
+        ] {
+            self.write_block(&mut writer, block)?;
+        }
+
+        Ok(writer.into_bytes())
+    }
+
+    /// Creates new data from bytes produced by [`BasicGarnishData::snapshot`].
+    pub fn from_snapshot(bytes: &[u8], companion: Companion) -> Result<Self, DataError> {
+        let mut reader = SnapshotReader::new(bytes);
+        if !bytes.starts_with(SNAPSHOT_MAGIC) {
+            return Err(invalid_snapshot(0, "missing snapshot header"));
+        }
+        reader.take(SNAPSHOT_MAGIC.len())?;
+        let version = reader.read_u8()?;
+        if version != SNAPSHOT_VERSION {
+            return Err(DataError::new("Unsupported snapshot version", DataErrorType::UnsupportedSnapshotVersion(version)));
+        }
+
+        let instruction_pointer = reader.read_usize()?;
+        let current_value = reader.read_optional_usize()?;
+        let current_register = reader.read_optional_usize()?;
+        let current_frame = reader.read_optional_usize()?;
+        let current_local = reader.read_optional_usize()?;
+        let handler_count = reader.read_usize()?;
+        let mut handlers = vec![];
+        for _ in 0..handler_count {
+            handlers.push(BasicHandler {
+                jump_index: reader.read_usize()?,
+                value: reader.read_optional_usize()?,
+                register: reader.read_optional_usize()?,
+                frame: reader.read_optional_usize()?,
+                local: reader.read_optional_usize()?,
+            });
+        }
+        let data_retention_count = reader.read_usize()?;
+        let error_values = reader.read_bool()?;
+        let strict_mode = reader.read_bool()?;
+        let overflow_policy = reader.read_overflow_policy()?;
+
+        let mut blocks = vec![];
+        for _ in 0..6 {
+            blocks.push(Self::read_block(&mut reader)?);
+        }
+
+        if reader.cursor() != bytes.len() {
+            return Err(invalid_snapshot(reader.cursor(), "trailing bytes after snapshot"));
+        }
+
+        let mut this = Self::new_with_settings(
+            blocks[0].0.settings.clone(),
+            blocks[1].0.settings.clone(),
+            blocks[2].0.settings.clone(),
+            blocks[3].0.settings.clone(),
+            blocks[4].0.settings.clone(),
+            blocks[5].0.settings.clone(),
+            companion,
+        )?;
+
+        this.reallocate_heap(blocks[0].0.size, blocks[1].0.size, blocks[2].0.size, blocks[3].0.size, blocks[4].0.size, blocks[5].0.size)?;
+
+        let mut blocks = blocks.into_iter().map(|(_, items)| items);
+        this.fill_block(Self::instruction_block_mut, blocks.next().unwrap_or_default());
+        this.fill_block(Self::jump_table_block_mut, blocks.next().unwrap_or_default());
+        this.fill_block(Self::symbol_table_block_mut, blocks.next().unwrap_or_default());
+        this.fill_block(Self::expression_symbol_block_mut, blocks.next().unwrap_or_default());
+        this.fill_block(Self::data_block_mut, blocks.next().unwrap_or_default());
+        this.fill_block(Self::custom_data_block_mut, blocks.next().unwrap_or_default());
+
+        this.set_instruction_pointer(instruction_pointer);
+        this.set_current_value(current_value);
+        this.set_current_register(current_register);
+        this.set_current_frame(current_frame);
+        this.set_current_local(current_local);
+        *this.handlers_mut() = handlers;
+        this.set_data_retention_count(data_retention_count);
+        this.set_error_values(error_values);
+        this.set_strict_mode(strict_mode);
+        this.set_overflow_policy(overflow_policy);
+
+        Ok(this)
+    }
+
+    /// Replaces all state with the contents of a snapshot, keeping the current companion and collection settings.
+    ///
+    /// Existing [`DataHandle`](crate::DataHandle)s are released and no longer updated.
+    pub fn restore_snapshot(&mut self, bytes: &[u8]) -> Result<(), DataError> {
+        let collection_settings = self.collection_settings().clone();
+        *self = Self::from_snapshot(bytes, self.companion.clone())?;
+        self.set_collection_settings(collection_settings);
+        Ok(())
+    }
+
+    fn write_block(&self, writer: &mut SnapshotWriter, block: &StorageBlock) -> Result<(), DataError> {
+        writer.write_usize(block.settings.initial_size());
+        writer.write_usize(block.settings.max_items());
+        match block.settings.reallocation_strategy() {
+            ReallocationStrategy::FixedSize(size) => {
+                writer.write_u8(0);
+                writer.write_usize(size);
+            }
+            ReallocationStrategy::Multiplicative(multiplier) => {
+                writer.write_u8(1);
+                writer.write_usize(multiplier);
+            }
+            ReallocationStrategy::CappedMultiplicative(multiplier, max_growth) => {
+                writer.write_u8(2);
+                writer.write_usize(multiplier);
+                writer.write_usize(max_growth);
+            }
+        }
+        match block.settings.shrink_policy() {
+            ShrinkPolicy::Never => writer.write_u8(0),
+            ShrinkPolicy::ToFit => writer.write_u8(1),
+            ShrinkPolicy::Threshold { usage_percent, headroom_percent } => {
+                writer.write_u8(2);
+                writer.write_usize(usage_percent);
+                writer.write_usize(headroom_percent);
+            }
+        }
+        writer.write_usize(block.size);
+        writer.write_usize(block.cursor);
+
+        for item in &self.data()[block.start..block.start + block.cursor] {
+            write_basic_data(writer, item)?;
+        }
+
+        Ok(())
+    }
+
+    fn read_block(reader: &mut SnapshotReader) -> Result<(StorageBlock, Vec<BasicData<T>>), DataError> {
+        let initial_size = reader.read_usize()?;
+        let max_items = reader.read_usize()?;
+        let strategy = match reader.read_u8()? {
+            0 => ReallocationStrategy::FixedSize(reader.read_usize()?),
+            1 => ReallocationStrategy::Multiplicative(reader.read_usize()?),
+            2 => ReallocationStrategy::CappedMultiplicative(reader.read_usize()?, reader.read_usize()?),
+            _ => return Err(invalid_snapshot(reader.cursor(), "invalid reallocation strategy")),
+        };
+        let shrink_policy = match reader.read_u8()? {
+            0 => ShrinkPolicy::Never,
+            1 => ShrinkPolicy::ToFit,
+            2 => ShrinkPolicy::Threshold { usage_percent: reader.read_usize()?, headroom_percent: reader.read_usize()? },
+            _ => return Err(invalid_snapshot(reader.cursor(), "invalid shrink policy")),
+        };
+        let size = reader.read_usize()?;
+        let cursor = reader.read_usize()?;
+        if cursor > size {
+            return Err(invalid_snapshot(reader.cursor(), "block cursor past block size"));
+        }
+
+        let mut items = Vec::with_capacity(cursor);
+        for _ in 0..cursor {
+            items.push(read_basic_data(reader)?);
+        }
+
+        Ok((StorageBlock::new(size, StorageSettings::new_with_shrink_policy(initial_size, max_items, strategy, shrink_policy)), items))
+    }
+
+    fn fill_block(&mut self, block: fn(&mut Self) -> &mut StorageBlock, items: Vec<BasicData<T>>) {
+        let start = block(self).start;
+        block(self).cursor = items.len();
+        for (i, item) in items.into_iter().enumerate() {
+            self.data_mut()[start + i] = item;
+        }
+    }
+}
+
+fn write_basic_data<T: BasicDataCustom>(writer: &mut SnapshotWriter, data: &BasicData<T>) -> Result<(), DataError> {
+    match data {
+        BasicData::Unit => writer.write_u8(0),
+        BasicData::True => writer.write_u8(1),
+        BasicData::False => writer.write_u8(2),
+        BasicData::Type(t) => {
+            writer.write_u8(3);
+            writer.write_u8(*t as u8);
+        }
+        BasicData::Number(n) => {
+            writer.write_u8(4);
+            match n {
+                SimpleNumber::Integer(v) => {
+                    writer.write_u8(0);
+                    writer.write_u64(*v as u64);
+                }
+                SimpleNumber::Float(v) => {
+                    writer.write_u8(1);
+                    writer.write_u64(v.to_bits());
+                }
+                SimpleNumber::Decimal(v) => {
+                    writer.write_u8(2);
+                    writer.write_u64(v.units() as u64);
+                    writer.write_u64((v.units() >> 64) as u64);
+                    writer.write_u32(v.scale());
+                }
+            }
+        }
+        BasicData::Char(c) => {
+            writer.write_u8(5);
+            writer.write_u32(*c as u32);
+        }
+        BasicData::Byte(b) => {
+            writer.write_u8(6);
+            writer.write_u8(*b);
+        }
+        BasicData::Symbol(s) => {
+            writer.write_u8(7);
+            writer.write_u64(*s);
+        }
+        BasicData::SymbolList(v) => write_tag_and_sizes(writer, 8, &[*v]),
+        BasicData::Expression(v) => write_tag_and_sizes(writer, 9, &[*v]),
+        BasicData::External(v) => write_tag_and_sizes(writer, 10, &[*v]),
+        BasicData::CharList(v) => write_tag_and_sizes(writer, 11, &[*v]),
+        BasicData::ByteList(v) => write_tag_and_sizes(writer, 12, &[*v]),
+        BasicData::Pair(a, b) => write_tag_and_sizes(writer, 13, &[*a, *b]),
+        BasicData::Range(a, b) => write_tag_and_sizes(writer, 14, &[*a, *b]),
+        BasicData::Slice(a, b) => write_tag_and_sizes(writer, 15, &[*a, *b]),
+        BasicData::Partial(a, b) => write_tag_and_sizes(writer, 16, &[*a, *b]),
+        BasicData::List(a, b) => write_tag_and_sizes(writer, 17, &[*a, *b]),
+        BasicData::Concatenation(a, b) => write_tag_and_sizes(writer, 18, &[*a, *b]),
+        BasicData::Custom(c) => {
+            writer.write_u8(19);
+            c.write_snapshot(writer)?;
+        }
+        BasicData::Empty => writer.write_u8(20),
+        BasicData::UninitializedList(a, b) => write_tag_and_sizes(writer, 21, &[*a, *b]),
+        BasicData::ListItem(v) => write_tag_and_sizes(writer, 22, &[*v]),
+        BasicData::AssociativeItem(s, v) => {
+            writer.write_u8(23);
+            writer.write_u64(*s);
+            writer.write_usize(*v);
+        }
+        BasicData::Value(a, b) => write_tag_and_sizes(writer, 24, &[*a, *b]),
+        BasicData::ValueRoot(v) => write_tag_and_sizes(writer, 25, &[*v]),
+        BasicData::Register(a, b) => write_tag_and_sizes(writer, 26, &[*a, *b]),
+        BasicData::RegisterRoot(v) => write_tag_and_sizes(writer, 27, &[*v]),
+        BasicData::InstructionWithData(instruction, v) => {
+            writer.write_u8(28);
+            writer.write_u8(*instruction as u8);
+            writer.write_usize(*v);
+        }
+        BasicData::Instruction(instruction) => {
+            writer.write_u8(29);
+            writer.write_u8(*instruction as u8);
+        }
+        BasicData::JumpPoint(v) => write_tag_and_sizes(writer, 30, &[*v]),
+        BasicData::Frame(a, b) => write_tag_and_sizes(writer, 31, &[*a, *b]),
+        BasicData::FrameIndex(v) => write_tag_and_sizes(writer, 32, &[*v]),
+        BasicData::FrameRegister(v) => write_tag_and_sizes(writer, 33, &[*v]),
+        BasicData::FrameRoot => writer.write_u8(34),
+        BasicData::CloneItem(v) => write_tag_and_sizes(writer, 35, &[*v]),
+        BasicData::CloneIndexMap(a, b) => write_tag_and_sizes(writer, 36, &[*a, *b]),
+        BasicData::AssociativeIndex(v) => write_tag_and_sizes(writer, 37, &[*v]),
+        BasicData::PackedCharList(a, b) => write_tag_and_sizes(writer, 38, &[*a, *b]),
+        BasicData::PackedByteList(v) => write_tag_and_sizes(writer, 39, &[*v]),
+        BasicData::FrameLocals(v) => write_tag_and_sizes(writer, 41, &[*v]),
+        BasicData::Closure(a, b) => write_tag_and_sizes(writer, 42, &[*a, *b]),
+        BasicData::Error(a, b) => write_tag_and_sizes(writer, 43, &[*a, *b]),
+        BasicData::PackedBytes(bytes) => {
+            writer.write_u8(40);
+            writer.write_bytes(bytes);
+        }
+    }
+
+    Ok(())
+}
+
+fn write_tag_and_sizes(writer: &mut SnapshotWriter, tag: u8, sizes: &[usize]) {
+    writer.write_u8(tag);
+    for size in sizes {
+        writer.write_usize(*size);
+    }
+}
+
+fn read_basic_data<T: BasicDataCustom>(reader: &mut SnapshotReader) -> Result<BasicData<T>, DataError> {
+    let tag = reader.read_u8()?;
+    Ok(match tag {
+        0 => BasicData::Unit,
+        1 => BasicData::True,
+        2 => BasicData::False,
+        3 => BasicData::Type(read_data_type(reader)?),
+        4 => match reader.read_u8()? {
+            0 => BasicData::Number(SimpleNumber::Integer(reader.read_u64()? as i64)),
+            1 => BasicData::Number(SimpleNumber::Float(f64::from_bits(reader.read_u64()?))),
+            2 => {
+                let low = reader.read_u64()? as i128;
+                let high = reader.read_u64()? as i128;
+                let scale = reader.read_u32()?;
+                let decimal = Decimal::new(high << 64 | low, scale).ok_or_else(|| invalid_snapshot(reader.cursor(), "invalid decimal scale"))?;
+                BasicData::Number(SimpleNumber::Decimal(decimal))
+            }
+            _ => return Err(invalid_snapshot(reader.cursor(), "invalid number tag")),
+        },
+        5 => {
+            let value = reader.read_u32()?;
+            BasicData::Char(char::from_u32(value).ok_or_else(|| invalid_snapshot(reader.cursor(), "invalid char value"))?)
+        }
+        6 => BasicData::Byte(reader.read_u8()?),
+        7 => BasicData::Symbol(reader.read_u64()?),
+        8 => BasicData::SymbolList(reader.read_usize()?),
+        9 => BasicData::Expression(reader.read_usize()?),
+        10 => BasicData::External(reader.read_usize()?),
+        11 => BasicData::CharList(reader.read_usize()?),
+        12 => BasicData::ByteList(reader.read_usize()?),
+        13 => BasicData::Pair(reader.read_usize()?, reader.read_usize()?),
+        14 => BasicData::Range(reader.read_usize()?, reader.read_usize()?),
+        15 => BasicData::Slice(reader.read_usize()?, reader.read_usize()?),
+        16 => BasicData::Partial(reader.read_usize()?, reader.read_usize()?),
+        17 => BasicData::List(reader.read_usize()?, reader.read_usize()?),
+        18 => BasicData::Concatenation(reader.read_usize()?, reader.read_usize()?),
+        19 => BasicData::Custom(T::read_snapshot(reader)?),
+        20 => BasicData::Empty,
+        21 => BasicData::UninitializedList(reader.read_usize()?, reader.read_usize()?),
+        22 => BasicData::ListItem(reader.read_usize()?),
+        23 => BasicData::AssociativeItem(reader.read_u64()?, reader.read_usize()?),
+        24 => BasicData::Value(reader.read_usize()?, reader.read_usize()?),
+        25 => BasicData::ValueRoot(reader.read_usize()?),
+        26 => BasicData::Register(reader.read_usize()?, reader.read_usize()?),
+        27 => BasicData::RegisterRoot(reader.read_usize()?),
+        28 => BasicData::InstructionWithData(read_instruction(reader)?, reader.read_usize()?),
+        29 => BasicData::Instruction(read_instruction(reader)?),
+        30 => BasicData::JumpPoint(reader.read_usize()?),
+        31 => BasicData::Frame(reader.read_usize()?, reader.read_usize()?),
+        32 => BasicData::FrameIndex(reader.read_usize()?),
+        33 => BasicData::FrameRegister(reader.read_usize()?),
+        34 => BasicData::FrameRoot,
+        35 => BasicData::CloneItem(reader.read_usize()?),
+        36 => BasicData::CloneIndexMap(reader.read_usize()?, reader.read_usize()?),
+        37 => BasicData::AssociativeIndex(reader.read_usize()?),
+        38 => BasicData::PackedCharList(reader.read_usize()?, reader.read_usize()?),
+        39 => BasicData::PackedByteList(reader.read_usize()?),
+        40 => BasicData::PackedBytes(
+            reader
+                .read_bytes()?
+                .try_into()
+                .map_err(|_| invalid_snapshot(reader.cursor(), "invalid packed bytes length"))?,
+        ),
+        41 => BasicData::FrameLocals(reader.read_usize()?),
+        42 => BasicData::Closure(reader.read_usize()?, reader.read_usize()?),
+        43 => BasicData::Error(reader.read_usize()?, reader.read_usize()?),
+        _ => return Err(invalid_snapshot(reader.cursor(), "unknown data tag")),
+    })
+}
+
+fn read_instruction(reader: &mut SnapshotReader) -> Result<Instruction, DataError> {
+    let value = reader.read_u8()?;
+    INSTRUCTIONS.get(value as usize).copied().ok_or_else(|| invalid_snapshot(reader.cursor(), "unknown instruction"))
+}
+
+fn read_data_type(reader: &mut SnapshotReader) -> Result<GarnishDataType, DataError> {
+    let value = reader.read_u8()?;
+    DATA_TYPES.get(value as usize).copied().ok_or_else(|| invalid_snapshot(reader.cursor(), "unknown data type"))
+}
+
+#[cfg(test)]
+mod tests {
+    use garnish_lang_traits::{GarnishData, GarnishDataType, Instruction};
+
+    use crate::basic::snapshot::{DATA_TYPES, INSTRUCTIONS};
+    use crate::basic::storage::{ReallocationStrategy, ShrinkPolicy, StorageSettings};
+    use crate::error::DataErrorType;
+    use crate::{
+        BasicData, BasicDataCustom, BasicGarnishData, BasicGarnishDataUnit, DataError, Decimal, NoOpCompanion, OverflowPolicy, SimpleNumber, SnapshotReader, SnapshotWriter, basic_object,
+    };
+
+    #[test]
+    fn instruction_table_matches_discriminants() {
+        for (i, instruction) in INSTRUCTIONS.iter().enumerate() {
+            assert_eq!(*instruction as usize, i);
+        }
+    }
+
+    #[test]
+    fn data_type_table_matches_discriminants() {
+        for (i, data_type) in DATA_TYPES.iter().enumerate() {
+            assert_eq!(*data_type as usize, i);
+        }
+    }
+
+    #[test]
+    fn empty_data_round_trip() {
+        let data = BasicGarnishDataUnit::new(NoOpCompanion::new()).unwrap();
+        let bytes = data.snapshot().unwrap();
+        let restored = BasicGarnishDataUnit::from_snapshot(&bytes, NoOpCompanion::new()).unwrap();
+
+        assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn all_blocks_and_state_round_trip() {
+        let mut data = BasicGarnishDataUnit::new_with_settings(
+            StorageSettings::new(5, usize::MAX, ReallocationStrategy::Multiplicative(2)),
+            StorageSettings::new(5, usize::MAX, ReallocationStrategy::FixedSize(3)),
+            StorageSettings::new(5, usize::MAX, ReallocationStrategy::FixedSize(10)),
+            StorageSettings::new_with_shrink_policy(5, usize::MAX, ReallocationStrategy::CappedMultiplicative(2, 8), ShrinkPolicy::ToFit),
+            StorageSettings::new_with_shrink_policy(
+                20,
+                usize::MAX,
+                ReallocationStrategy::FixedSize(10),
+                ShrinkPolicy::Threshold { usage_percent: 25, headroom_percent: 50 },
+            ),
+            StorageSettings::new(5, usize::MAX, ReallocationStrategy::FixedSize(10)),
+            NoOpCompanion::new(),
+        )
+        .unwrap();
+
+        data.push_instruction(Instruction::Put, Some(1)).unwrap();
+        data.push_instruction(Instruction::Add, None).unwrap();
+        data.push_to_jump_table(0).unwrap();
+        data.parse_add_symbol("value").unwrap();
+        data.push_to_expression_symbol_block(10, 0).unwrap();
+        data.push_to_custom_data_block(()).unwrap();
+        data.add_number(SimpleNumber::Decimal(Decimal::new(-123_456_789_012_345_678_901_234_567, 4).unwrap())).unwrap();
+        data.add_string(&"packed text é ".repeat(4)).unwrap();
+        data.add_byte_slice(&[7; 40]).unwrap();
+
+        let object = data
+            .push_object_to_data_block(basic_object!(Unit, True, (Number 100), (Number 1.5), (Char 'z'), (CharList "hello"), ((ByteList 1, 2, 3) = (Symbol "my_symbol")), (Type Number)))
+            .unwrap();
+        data.push_value_stack(object).unwrap();
+        data.push_register(object).unwrap();
+        data.set_local(0, object).unwrap();
+        data.push_frame(1).unwrap();
+        data.set_local(0, object).unwrap();
+        data.push_handler(0).unwrap();
+        data.set_instruction_cursor(1).unwrap();
+        data.set_error_values(true);
+        data.set_strict_mode(true);
+        data.retain_all_current_data();
+
+        let bytes = data.snapshot().unwrap();
+        let restored = BasicGarnishDataUnit::from_snapshot(&bytes, NoOpCompanion::new()).unwrap();
+
+        assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn restore_replaces_existing_state() {
+        let mut data = BasicGarnishDataUnit::new(NoOpCompanion::new()).unwrap();
+        let value = data.add_number(100.into()).unwrap();
+        data.push_value_stack(value).unwrap();
+        let bytes = data.snapshot().unwrap();
+
+        let mut other = BasicGarnishDataUnit::new(NoOpCompanion::new()).unwrap();
+        other.add_string("some other data").unwrap();
+        other.restore_snapshot(&bytes).unwrap();
+
+        assert_eq!(other, data);
+        assert_eq!(other.get_current_value(), Some(value));
+    }
+
+    #[test]
+    fn strict_mode_round_trip() {
+        let mut data = BasicGarnishDataUnit::new(NoOpCompanion::new()).unwrap();
+        data.set_strict_mode(true);
+
+        let restored = BasicGarnishDataUnit::from_snapshot(&data.snapshot().unwrap(), NoOpCompanion::new()).unwrap();
+
+        assert!(restored.strict_mode());
+    }
+
+    #[test]
+    fn overflow_policy_round_trip() {
+        let mut data = BasicGarnishDataUnit::new(NoOpCompanion::new()).unwrap();
+        data.set_overflow_policy(OverflowPolicy::PromoteToFloat);
+
+        let restored = BasicGarnishDataUnit::from_snapshot(&data.snapshot().unwrap(), NoOpCompanion::new()).unwrap();
+
+        assert_eq!(restored.overflow_policy(), OverflowPolicy::PromoteToFloat);
+    }
+
+    #[test]
+    fn error_values_round_trip() {
+        let mut data = BasicGarnishDataUnit::new(NoOpCompanion::new()).unwrap();
+        data.set_error_values(true);
+
+        let restored = BasicGarnishDataUnit::from_snapshot(&data.snapshot().unwrap(), NoOpCompanion::new()).unwrap();
+
+        assert!(restored.error_values());
+    }
+
+    #[test]
+    fn invalid_header() {
+        let result = BasicGarnishDataUnit::from_snapshot(b"ABCD", NoOpCompanion::new());
+
+        assert_eq!(
+            result,
+            Err(DataError::new("missing snapshot header", DataErrorType::InvalidSnapshot(0)))
+        );
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let result = BasicGarnishDataUnit::from_snapshot(b"GSNP\x0e", NoOpCompanion::new());
+
+        assert_eq!(result, Err(DataError::new("Unsupported snapshot version", DataErrorType::UnsupportedSnapshotVersion(14))));
+    }
+
+    #[test]
+    fn truncated_snapshot() {
+        let data = BasicGarnishDataUnit::new(NoOpCompanion::new()).unwrap();
+        let bytes = data.snapshot().unwrap();
+        let result = BasicGarnishDataUnit::from_snapshot(&bytes[..bytes.len() - 1], NoOpCompanion::new());
+
+        assert!(result.is_err());
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd)]
+    struct TestCustom {
+        value: String,
+    }
+
+    impl BasicDataCustom for TestCustom {
+        fn write_snapshot(&self, writer: &mut SnapshotWriter) -> Result<(), DataError> {
+            writer.write_bytes(self.value.as_bytes());
+            Ok(())
+        }
+
+        fn read_snapshot(reader: &mut SnapshotReader) -> Result<Self, DataError> {
+            let bytes = reader.read_bytes()?;
+            Ok(TestCustom { value: String::from_utf8_lossy(bytes).to_string() })
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq, Eq, PartialOrd)]
+    struct OpaqueCustom;
+
+    impl BasicDataCustom for OpaqueCustom {}
+
+    #[derive(Default, Debug, Clone, PartialEq, Eq, PartialOrd)]
+    struct TestCompanion;
+
+    impl<T: BasicDataCustom> crate::BasicDataCompanion<T> for TestCompanion {
+        fn resolve(_data: &mut BasicGarnishData<T, Self>, _symbol: u64) -> Result<bool, DataError> {
+            Ok(false)
+        }
+
+        fn apply(_data: &mut BasicGarnishData<T, Self>, _external_value: usize, _input_addr: usize) -> Result<bool, DataError> {
+            Ok(false)
+        }
+
+        fn defer_op(
+            _data: &mut BasicGarnishData<T, Self>,
+            _operation: Instruction,
+            _left: (GarnishDataType, usize),
+            _right: (GarnishDataType, usize),
+        ) -> Result<bool, DataError> {
+            Ok(false)
+        }
+    }
+
+    #[test]
+    fn custom_data_round_trip() {
+        let mut data = BasicGarnishData::<TestCustom, TestCompanion>::new(TestCompanion).unwrap();
+        data.push_to_custom_data_block(TestCustom { value: "custom".to_string() }).unwrap();
+        data.push_to_data_block(BasicData::Custom(TestCustom { value: "in data".to_string() })).unwrap();
+
+        let bytes = data.snapshot().unwrap();
+        let restored = BasicGarnishData::<TestCustom, TestCompanion>::from_snapshot(&bytes, TestCompanion).unwrap();
+
+        assert_eq!(restored, data);
+    }
+
+    #[test]
+    fn custom_data_without_snapshot_support() {
+        let mut data = BasicGarnishData::<OpaqueCustom, TestCompanion>::new(TestCompanion).unwrap();
+        data.push_to_custom_data_block(OpaqueCustom).unwrap();
+
+        let result = data.snapshot();
+
+        assert_eq!(result, Err(DataError::new("Custom data does not support snapshots", DataErrorType::CustomDataNotSnapshotable)));
+    }
+}
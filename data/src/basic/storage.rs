@@ -21,8 +21,29 @@ impl StorageBlock {
         match self.settings.reallocation_strategy() {
             ReallocationStrategy::FixedSize(size) => self.size + size,
             ReallocationStrategy::Multiplicative(multiplier) => self.size * multiplier,
+            ReallocationStrategy::CappedMultiplicative(multiplier, max_growth) => (self.size * multiplier).min(self.size + max_growth),
         }
     }
+
+    /// Size this block should have after data was discarded, according to its [`ShrinkPolicy`].
+    pub fn shrunk_size(&self) -> usize {
+        let floor = self.cursor.max(self.settings.initial_size());
+        match self.settings.shrink_policy() {
+            ShrinkPolicy::Never => self.size,
+            ShrinkPolicy::ToFit => floor.min(self.size),
+            ShrinkPolicy::Threshold { usage_percent, headroom_percent } => {
+                if self.cursor * 100 >= self.size * usage_percent {
+                    return self.size;
+                }
+                (self.cursor + self.cursor * headroom_percent / 100).max(floor).min(self.size)
+            }
+        }
+    }
+
+    /// Size this block has after [`compact`](crate::BasicGarnishData::compact), its used size but never below the initial size.
+    pub fn fitted_size(&self) -> usize {
+        self.cursor.max(self.settings.initial_size()).min(self.size)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -30,23 +51,43 @@ pub struct StorageSettings {
     pub(crate) initial_size: usize,
     pub(crate) max_items: usize,
     pub(crate) reallocation_strategy: ReallocationStrategy,
+    pub(crate) shrink_policy: ShrinkPolicy,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ReallocationStrategy {
     FixedSize(usize),
     Multiplicative(usize),
+    /// Multiplies the size, adding at most the second value in items per reallocation.
+    CappedMultiplicative(usize, usize),
+}
+
+/// When a block gives memory back after `optimize` or garbage collection discards data.
+///
+/// Blocks never shrink below their used size or their initial size.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum ShrinkPolicy {
+    #[default]
+    Never,
+    /// Shrink to the used size.
+    ToFit,
+    /// Shrink once less than `usage_percent` of the block is used, keeping `headroom_percent` of the used size free.
+    Threshold { usage_percent: usize, headroom_percent: usize },
 }
 
 impl Default for StorageSettings {
     fn default() -> Self {
-        Self { initial_size: 10, max_items: usize::MAX, reallocation_strategy: ReallocationStrategy::FixedSize(10) }
+        Self { initial_size: 10, max_items: usize::MAX, reallocation_strategy: ReallocationStrategy::FixedSize(10), shrink_policy: ShrinkPolicy::Never }
     }
 }
 
 impl StorageSettings {
     pub fn new(initial_size: usize, max_items: usize, reallocation_strategy: ReallocationStrategy) -> Self {
-        Self { initial_size, max_items, reallocation_strategy }
+        Self { initial_size, max_items, reallocation_strategy, shrink_policy: ShrinkPolicy::Never }
+    }
+
+    pub fn new_with_shrink_policy(initial_size: usize, max_items: usize, reallocation_strategy: ReallocationStrategy, shrink_policy: ShrinkPolicy) -> Self {
+        Self { initial_size, max_items, reallocation_strategy, shrink_policy }
     }
 
     pub fn initial_size(&self) -> usize {
@@ -60,11 +101,15 @@ impl StorageSettings {
     pub fn reallocation_strategy(&self) -> ReallocationStrategy {
         self.reallocation_strategy.clone()
     }
+
+    pub fn shrink_policy(&self) -> ShrinkPolicy {
+        self.shrink_policy.clone()
+    }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::basic::storage::{ReallocationStrategy, StorageBlock, StorageSettings};
+    use crate::basic::storage::{ReallocationStrategy, ShrinkPolicy, StorageBlock, StorageSettings};
 
     #[test]
     pub fn next_size_fixed_size() {
@@ -79,4 +124,45 @@ mod tests {
         let next_size = storage.next_size();
         assert_eq!(next_size, 20);
     }
-}
\ No newline at end of file
+
+    #[test]
+    pub fn next_size_capped_multiplicative() {
+        let storage = StorageBlock::new(10, StorageSettings::new(10, 10, ReallocationStrategy::CappedMultiplicative(4, 15)));
+        assert_eq!(storage.next_size(), 25);
+
+        let storage = StorageBlock::new(4, StorageSettings::new(4, 10, ReallocationStrategy::CappedMultiplicative(2, 15)));
+        assert_eq!(storage.next_size(), 8);
+    }
+
+    fn block_with_policy(size: usize, cursor: usize, shrink_policy: ShrinkPolicy) -> StorageBlock {
+        let mut storage = StorageBlock::new(size, StorageSettings::new_with_shrink_policy(10, usize::MAX, ReallocationStrategy::FixedSize(10), shrink_policy));
+        storage.cursor = cursor;
+        storage
+    }
+
+    #[test]
+    pub fn shrunk_size_never() {
+        assert_eq!(block_with_policy(100, 5, ShrinkPolicy::Never).shrunk_size(), 100);
+    }
+
+    #[test]
+    pub fn shrunk_size_to_fit() {
+        assert_eq!(block_with_policy(100, 25, ShrinkPolicy::ToFit).shrunk_size(), 25);
+        assert_eq!(block_with_policy(100, 5, ShrinkPolicy::ToFit).shrunk_size(), 10);
+    }
+
+    #[test]
+    pub fn shrunk_size_threshold() {
+        let policy = ShrinkPolicy::Threshold { usage_percent: 25, headroom_percent: 50 };
+        assert_eq!(block_with_policy(100, 30, policy.clone()).shrunk_size(), 100);
+        assert_eq!(block_with_policy(100, 20, policy.clone()).shrunk_size(), 30);
+        assert_eq!(block_with_policy(100, 2, policy).shrunk_size(), 10);
+    }
+
+    #[test]
+    pub fn fitted_size() {
+        assert_eq!(block_with_policy(100, 25, ShrinkPolicy::Never).fitted_size(), 25);
+        assert_eq!(block_with_policy(100, 5, ShrinkPolicy::Never).fitted_size(), 10);
+        assert_eq!(block_with_policy(5, 5, ShrinkPolicy::Never).fitted_size(), 5);
+    }
+}
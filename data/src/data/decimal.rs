@@ -0,0 +1,350 @@
+use crate::DataError;
+use crate::error::DataErrorType;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter};
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+
+/// Exact base 10 number used by [`crate::data::SimpleNumber::Decimal`].
+///
+/// Stored as a count of units and a scale, so `12.50` is 1250 units with a scale of 2.
+/// Addition, subtraction, multiplication and remainder are exact. Division keeps at least [`Decimal::DIVISION_SCALE`] digits, rounding half away from zero.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Copy, Clone, Debug, Default)]
+pub struct Decimal {
+    units: i128,
+    scale: u32,
+}
+
+const fn ten_pow(exp: u32) -> Option<i128> {
+    10i128.checked_pow(exp)
+}
+
+// divide rounding half away from zero
+fn divide_rounded(numerator: i128, denominator: i128) -> Option<i128> {
+    let quotient = numerator.checked_div(denominator)?;
+    let remainder = numerator.checked_rem(denominator)?;
+
+    if remainder.unsigned_abs() * 2 >= denominator.unsigned_abs() {
+        quotient.checked_add(numerator.signum() * denominator.signum())
+    } else {
+        Some(quotient)
+    }
+}
+
+impl Decimal {
+    /// Largest scale a decimal can have. Multiplication results past this are rounded.
+    pub const MAX_SCALE: u32 = 28;
+    /// Minimum number of fractional digits kept by division.
+    pub const DIVISION_SCALE: u32 = 16;
+
+    /// Create a decimal of `units` divided by 10 to the power of `scale`. Fails if scale is greater than [`Decimal::MAX_SCALE`].
+    pub fn new(units: i128, scale: u32) -> Option<Self> {
+        if scale > Self::MAX_SCALE { None } else { Some(Decimal { units, scale }) }
+    }
+
+    pub fn from_integer(value: i64) -> Self {
+        Decimal {
+            units: value as i128,
+            scale: 0,
+        }
+    }
+
+    pub fn units(&self) -> i128 {
+        self.units
+    }
+
+    pub fn scale(&self) -> u32 {
+        self.scale
+    }
+
+    pub fn to_f64(&self) -> f64 {
+        // parsing the displayed value gives a correctly rounded float
+        f64::from_str(&self.to_string()).unwrap_or(f64::NAN)
+    }
+
+    /// Integer part of this decimal, truncated toward zero. Fails if it doesn't fit in an [`i64`].
+    pub fn trunc_to_i64(&self) -> Option<i64> {
+        i64::try_from(self.units / ten_pow(self.scale)?).ok()
+    }
+
+    /// Same value with trailing fractional zeros removed.
+    pub fn normalized(&self) -> Self {
+        let mut result = *self;
+        while result.scale > 0 && result.units % 10 == 0 {
+            result.units /= 10;
+            result.scale -= 1;
+        }
+        result
+    }
+
+    /// Same value with the given scale, rounding half away from zero when the scale is reduced.
+    pub fn rescale(&self, scale: u32) -> Option<Self> {
+        match scale.cmp(&self.scale) {
+            Ordering::Equal => Some(*self),
+            Ordering::Greater => Decimal::new(self.units.checked_mul(ten_pow(scale - self.scale)?)?, scale),
+            Ordering::Less => Decimal::new(divide_rounded(self.units, ten_pow(self.scale - scale)?)?, scale),
+        }
+    }
+
+    fn aligned(&self, other: &Self) -> Option<(i128, i128, u32)> {
+        let scale = self.scale.max(other.scale);
+        Some((self.rescale(scale)?.units, other.rescale(scale)?.units, scale))
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        let (left, right, scale) = self.aligned(other)?;
+        Decimal::new(left.checked_add(right)?, scale)
+    }
+
+    pub fn checked_sub(&self, other: &Self) -> Option<Self> {
+        let (left, right, scale) = self.aligned(other)?;
+        Decimal::new(left.checked_sub(right)?, scale)
+    }
+
+    pub fn checked_mul(&self, other: &Self) -> Option<Self> {
+        let units = self.units.checked_mul(other.units)?;
+        let scale = self.scale + other.scale;
+        if scale > Self::MAX_SCALE {
+            Decimal { units, scale }.rescale(Self::MAX_SCALE)
+        } else {
+            Decimal::new(units, scale)
+        }
+    }
+
+    /// Divide keeping at least [`Decimal::DIVISION_SCALE`] digits, then drop trailing zeros back down to the larger operand scale.
+    pub fn checked_div(&self, other: &Self) -> Option<Self> {
+        if other.units == 0 {
+            return None;
+        }
+
+        let operand_scale = self.scale.max(other.scale);
+        let scale = operand_scale.max(Self::DIVISION_SCALE);
+        let numerator = self.units.checked_mul(ten_pow(scale + other.scale - self.scale)?)?;
+        let result = Decimal::new(divide_rounded(numerator, other.units)?, scale)?.normalized();
+
+        if result.scale < operand_scale {
+            result.rescale(operand_scale)
+        } else {
+            Some(result)
+        }
+    }
+
+    /// Quotient truncated toward zero.
+    pub fn checked_integer_div(&self, other: &Self) -> Option<i64> {
+        let (left, right, _) = self.aligned(other)?;
+        i64::try_from(left.checked_div(right)?).ok()
+    }
+
+    pub fn checked_rem(&self, other: &Self) -> Option<Self> {
+        let (left, right, scale) = self.aligned(other)?;
+        Decimal::new(left.checked_rem(right)?, scale)
+    }
+
+    pub fn checked_pow(&self, exp: u32) -> Option<Self> {
+        // square and multiply so large exponents fail fast instead of looping
+        let mut result = Decimal::from_integer(1);
+        let mut base = *self;
+        let mut exp = exp;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                result = result.checked_mul(&base)?;
+            }
+            exp >>= 1;
+            if exp > 0 {
+                base = base.checked_mul(&base)?;
+            }
+        }
+        Some(result)
+    }
+
+    pub fn checked_neg(&self) -> Option<Self> {
+        Decimal::new(self.units.checked_neg()?, self.scale)
+    }
+
+    pub fn checked_abs(&self) -> Option<Self> {
+        Decimal::new(self.units.checked_abs()?, self.scale)
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let digits = self.units.unsigned_abs().to_string();
+        let scale = self.scale as usize;
+        let digits = if digits.len() <= scale {
+            format!("{}{}", "0".repeat(scale - digits.len() + 1), digits)
+        } else {
+            digits
+        };
+        let (whole, fraction) = digits.split_at(digits.len() - scale);
+
+        if self.units < 0 {
+            f.write_str("-")?;
+        }
+        f.write_str(whole)?;
+        if scale > 0 {
+            f.write_str(".")?;
+            f.write_str(fraction)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for Decimal {
+    type Err = DataError;
+
+    /// Parse an optionally signed decimal value like `-12.50`. Scale is the number of digits after the period.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || {
+            DataError::new(
+                format!("Could not parse decimal from {:?}", s).as_str(),
+                DataErrorType::FailedToParseDecimal(s.to_string()),
+            )
+        };
+
+        let (negative, unsigned) = match s.strip_prefix('-') {
+            Some(rest) => (true, rest),
+            None => (false, s.strip_prefix('+').unwrap_or(s)),
+        };
+
+        let (whole, fraction) = match unsigned.split_once('.') {
+            Some((whole, fraction)) => (whole, fraction),
+            None => (unsigned, ""),
+        };
+
+        if whole.is_empty() && fraction.is_empty() || !whole.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+            return Err(error());
+        }
+
+        let scale = u32::try_from(fraction.len()).map_err(|_| error())?;
+        let units = i128::from_str(&format!("{}{}", whole, fraction)).map_err(|_| error())?;
+        let units = if negative { -units } else { units };
+
+        Decimal::new(units, scale).ok_or_else(error)
+    }
+}
+
+impl PartialEq for Decimal {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Decimal {}
+
+impl PartialOrd for Decimal {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Decimal {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // compare whole parts first so aligning the fractions can't overflow
+        // both parts share the sign of the value, so tuple ordering holds for negative values too
+        let split = |d: &Decimal| {
+            let factor = 10i128.pow(d.scale);
+            (d.units / factor, d.units % factor)
+        };
+
+        let (left_whole, left_fraction) = split(self);
+        let (right_whole, right_fraction) = split(other);
+        let scale = self.scale.max(other.scale);
+
+        left_whole.cmp(&right_whole).then_with(|| {
+            let left_fraction = left_fraction * 10i128.pow(scale - self.scale);
+            let right_fraction = right_fraction * 10i128.pow(scale - other.scale);
+            left_fraction.cmp(&right_fraction)
+        })
+    }
+}
+
+impl Hash for Decimal {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let normalized = self.normalized();
+        normalized.units.hash(state);
+        normalized.scale.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::data::Decimal;
+    use std::str::FromStr;
+
+    fn dec(s: &str) -> Decimal {
+        Decimal::from_str(s).unwrap()
+    }
+
+    #[test]
+    fn parse_and_display() {
+        assert_eq!(dec("12.50").to_string(), "12.50");
+        assert_eq!(dec("-0.05").to_string(), "-0.05");
+        assert_eq!(dec("7").to_string(), "7");
+        assert_eq!(dec(".5").to_string(), "0.5");
+        assert_eq!(dec("+3.").to_string(), "3");
+        assert_eq!(dec("12.50").units(), 1250);
+        assert_eq!(dec("12.50").scale(), 2);
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert!(Decimal::from_str("").is_err());
+        assert!(Decimal::from_str(".").is_err());
+        assert!(Decimal::from_str("1.2.3").is_err());
+        assert!(Decimal::from_str("1e5").is_err());
+        assert!(Decimal::from_str("0.00000000000000000000000000001").is_err());
+    }
+
+    #[test]
+    fn equality_ignores_scale() {
+        assert_eq!(dec("1.50"), dec("1.5"));
+        assert!(dec("-1.5") < dec("-1.2"));
+        assert!(dec("-2") < dec("-1.5"));
+        assert!(dec("0.3") > dec("-0.5"));
+    }
+
+    #[test]
+    fn exact_arithmetic() {
+        assert_eq!(dec("0.1").checked_add(&dec("0.2")).unwrap().to_string(), "0.3");
+        assert_eq!(dec("10.00").checked_sub(&dec("0.01")).unwrap().to_string(), "9.99");
+        assert_eq!(dec("19.99").checked_mul(&dec("3")).unwrap().to_string(), "59.97");
+        assert_eq!(dec("1.10").checked_mul(&dec("1.10")).unwrap().to_string(), "1.2100");
+        assert_eq!(dec("10.00").checked_rem(&dec("3")).unwrap().to_string(), "1.00");
+        assert_eq!(dec("1.5").checked_pow(3).unwrap().to_string(), "3.375");
+    }
+
+    #[test]
+    fn division() {
+        assert_eq!(dec("10.00").checked_div(&dec("4")).unwrap().to_string(), "2.50");
+        assert_eq!(dec("10").checked_div(&dec("3")).unwrap().to_string(), "3.3333333333333333");
+        assert_eq!(dec("20").checked_div(&dec("3")).unwrap().to_string(), "6.6666666666666667");
+        assert_eq!(dec("1").checked_div(&dec("0")), None);
+        assert_eq!(dec("10.5").checked_integer_div(&dec("2")), Some(5));
+    }
+
+    #[test]
+    fn rescale_rounds_half_away_from_zero() {
+        assert_eq!(dec("2.345").rescale(2).unwrap().to_string(), "2.35");
+        assert_eq!(dec("-2.345").rescale(2).unwrap().to_string(), "-2.35");
+        assert_eq!(dec("2.344").rescale(2).unwrap().to_string(), "2.34");
+        assert_eq!(dec("2.3").rescale(3).unwrap().to_string(), "2.300");
+    }
+
+    #[test]
+    fn overflow() {
+        let max = Decimal::new(i128::MAX, 0).unwrap();
+        assert_eq!(max.checked_add(&dec("1")), None);
+        assert_eq!(max.checked_mul(&dec("2")), None);
+        assert_eq!(max.trunc_to_i64(), None);
+    }
+
+    #[test]
+    fn to_f64() {
+        assert_eq!(dec("12.50").to_f64(), 12.5);
+        assert_eq!(dec("-0.1").to_f64(), -0.1);
+    }
+}
@@ -33,6 +33,8 @@ where
             SimpleData::Range(s, e) => format!("Range({}, {})", s, e),
             SimpleData::Slice(l, r) => format!("Slice({}, {})", l, r),
             SimpleData::Partial(l, r) => format!("Partial({}, {})", l, r),
+            SimpleData::Closure(e, c) => format!("Closure({}, {})", e, c),
+            SimpleData::Error(m, c) => format!("Error({}, {})", m, c),
             SimpleData::List(i, h) => format!(
                 "List([{}], [{}])",
                 i.iter().map(|i| i.to_string()).collect::<Vec<String>>().join(", "),
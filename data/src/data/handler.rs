@@ -0,0 +1,45 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Sizes of the runtime stacks when a handler was pushed, restored when unwinding to it.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Debug, Hash)]
+pub struct SimpleHandler {
+    jump_index: usize,
+    register_len: usize,
+    values_len: usize,
+    locals_len: usize,
+    local_starts_len: usize,
+}
+
+impl SimpleHandler {
+    pub fn new(jump_index: usize, register_len: usize, values_len: usize, locals_len: usize, local_starts_len: usize) -> Self {
+        SimpleHandler {
+            jump_index,
+            register_len,
+            values_len,
+            locals_len,
+            local_starts_len,
+        }
+    }
+
+    pub fn jump_index(&self) -> usize {
+        self.jump_index
+    }
+
+    pub fn register_len(&self) -> usize {
+        self.register_len
+    }
+
+    pub fn values_len(&self) -> usize {
+        self.values_len
+    }
+
+    pub fn locals_len(&self) -> usize {
+        self.locals_len
+    }
+
+    pub fn local_starts_len(&self) -> usize {
+        self.local_starts_len
+    }
+}
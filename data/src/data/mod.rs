@@ -5,6 +5,8 @@ use std::fmt::Debug;
 use std::hash::Hash;
 
 use garnish_lang_traits::GarnishDataType;
+pub use decimal::*;
+pub use handler::*;
 pub use iterators::*;
 pub use number::*;
 pub use parsing::*;
@@ -12,7 +14,9 @@ pub use stack_frame::*;
 
 use crate::{DataError, NoCustom, SimpleDataType, symbol_value};
 
+mod decimal;
 mod display;
+mod handler;
 mod iterators;
 mod number;
 mod parsing;
@@ -142,6 +146,8 @@ where
     Range(usize, usize),
     Slice(usize, usize),
     Partial(usize, usize),
+    Closure(usize, usize),
+    Error(usize, usize),
     List(Vec<usize>, Vec<usize>),
     Concatenation(usize, usize),
     StackFrame(SimpleStackFrame),
@@ -172,6 +178,8 @@ where
             SimpleData::Range(_, _) => GarnishDataType::Range,
             SimpleData::Slice(_, _) => GarnishDataType::Slice,
             SimpleData::Partial(_, _) => GarnishDataType::Partial,
+            SimpleData::Closure(_, _) => GarnishDataType::Closure,
+            SimpleData::Error(_, _) => GarnishDataType::Error,
             SimpleData::List(_, _) => GarnishDataType::List,
             SimpleData::StackFrame(_) | SimpleData::Custom(_) => GarnishDataType::Custom,
         }
@@ -296,6 +304,20 @@ where
         }
     }
 
+    pub fn as_closure(&self) -> DataCastResult<(usize, usize)> {
+        match self {
+            SimpleData::Closure(e, c) => Ok((*e, *c)),
+            _ => Err(DataError::from(format!("{:?} is not a Closure", self))),
+        }
+    }
+
+    pub fn as_error(&self) -> DataCastResult<(usize, usize)> {
+        match self {
+            SimpleData::Error(m, c) => Ok((*m, *c)),
+            _ => Err(DataError::from(format!("{:?} is not an Error", self))),
+        }
+    }
+
     pub fn as_concatenation(&self) -> DataCastResult<(usize, usize)> {
         match self {
             SimpleData::Concatenation(l, r) => Ok((*l, *r)),
@@ -622,6 +644,26 @@ mod tests {
         assert!(SimpleDataNC::Unit.as_partial().is_err());
     }
 
+    #[test]
+    fn as_closure() {
+        assert_eq!(SimpleDataNC::Closure(10, 20).as_closure().unwrap(), (10, 20));
+    }
+
+    #[test]
+    fn as_closure_not_closure() {
+        assert!(SimpleDataNC::Unit.as_closure().is_err());
+    }
+
+    #[test]
+    fn as_error() {
+        assert_eq!(SimpleDataNC::Error(10, 20).as_error().unwrap(), (10, 20));
+    }
+
+    #[test]
+    fn as_error_not_error() {
+        assert!(SimpleDataNC::Unit.as_error().is_err());
+    }
+
     #[test]
     fn as_concatenation() {
         assert_eq!(SimpleDataNC::Concatenation(10, 20).as_concatenation().unwrap(), (10, 20));
@@ -1,7 +1,7 @@
-use crate::data::DataCastResult;
+use crate::data::{DataCastResult, Decimal};
 use crate::data::SimpleNumber::*;
 use crate::DataError;
-use garnish_lang_traits::{GarnishNumber, TypeConstants};
+use garnish_lang_traits::{GarnishNumber, Instruction, TypeConstants};
 use std::cmp::Ordering;
 use std::fmt::{Display, Formatter};
 use std::hash::{Hash, Hasher};
@@ -9,18 +9,172 @@ use std::ops::{Add, Div, Mul, Rem, Sub};
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
 
-/// Number data type for [`garnish_lang_traits::GarnishData`]. Implements all math operations and switches between [`i32`] and [`f64`] depending on operands.
+/// Number data type for [`garnish_lang_traits::GarnishData`]. Implements all math operations and switches between [`i64`], [`Decimal`] and [`f64`] depending on operands.
+///
+/// Integers combined with decimals produce decimals, anything combined with a float produces a float.
+/// Integer and decimal results that don't fit fail, use an [`OverflowPolicy`] to handle them another way.
 #[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
 #[derive(Copy, Clone, Debug)]
 pub enum SimpleNumber {
-    Integer(i32),
+    Integer(i64),
     Float(f64),
+    Decimal(Decimal),
+}
+
+/// Determines what happens when an integer operation on [`SimpleNumber`] doesn't fit in an [`i64`].
+///
+/// The policy is a setting of the data, see [`crate::BasicGarnishData::set_overflow_policy`], and is passed to the operations below
+/// and to parsing with [`crate::data::parse_simple_number_with`]. [`GarnishNumber`] operations on their own use [`OverflowPolicy::Error`].
+///
+/// There is no big integer promotion, a [`SimpleNumber`] is a fixed size [`Copy`] value.
+/// Exact values past [`i64`] can be written as decimals with a `d` suffix, which hold 128 bits.
+#[cfg_attr(feature = "serde", derive(Deserialize, Serialize))]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum OverflowPolicy {
+    /// Operation fails. Arithmetic results in [`None`] and parsing results in an error.
+    #[default]
+    Error,
+    /// Operation is performed again with [`f64`] values.
+    PromoteToFloat,
+}
+
+impl OverflowPolicy {
+    /// Performs the number operation of an arithmetic or bitwise instruction, None for other instructions.
+    pub fn apply(self, operation: Instruction, left: SimpleNumber, right: SimpleNumber) -> Option<SimpleNumber> {
+        match operation {
+            Instruction::Add => self.plus(left, right),
+            Instruction::Subtract => self.subtract(left, right),
+            Instruction::Multiply => self.multiply(left, right),
+            Instruction::Divide => self.divide(left, right),
+            Instruction::IntegerDivide => self.integer_divide(left, right),
+            Instruction::Power => self.power(left, right),
+            Instruction::Remainder => self.remainder(left, right),
+            Instruction::BitwiseAnd => left.bitwise_and(right),
+            Instruction::BitwiseOr => left.bitwise_or(right),
+            Instruction::BitwiseXor => left.bitwise_xor(right),
+            Instruction::BitwiseShiftLeft => left.bitwise_shift_left(right),
+            Instruction::BitwiseShiftRight => left.bitwise_shift_right(right),
+            _ => None,
+        }
+    }
+
+    /// Performs the number operation of a unary arithmetic or bitwise instruction, None for other instructions.
+    pub fn apply_unary(self, operation: Instruction, value: SimpleNumber) -> Option<SimpleNumber> {
+        match operation {
+            Instruction::AbsoluteValue => self.absolute_value(value),
+            Instruction::Opposite => self.opposite(value),
+            Instruction::BitwiseNot => value.bitwise_not(),
+            _ => None,
+        }
+    }
+
+    pub fn plus(self, left: SimpleNumber, right: SimpleNumber) -> Option<SimpleNumber> {
+        do_op(self, &left, &right, i64::overflowing_add, Decimal::checked_add, f64::add)
+    }
+
+    pub fn subtract(self, left: SimpleNumber, right: SimpleNumber) -> Option<SimpleNumber> {
+        do_op(self, &left, &right, i64::overflowing_sub, Decimal::checked_sub, f64::sub)
+    }
+
+    pub fn multiply(self, left: SimpleNumber, right: SimpleNumber) -> Option<SimpleNumber> {
+        do_op(self, &left, &right, i64::overflowing_mul, Decimal::checked_mul, f64::mul)
+    }
+
+    pub fn divide(self, left: SimpleNumber, right: SimpleNumber) -> Option<SimpleNumber> {
+        if right == Integer(0) || right == Float(0.0) {
+            return None;
+        }
+
+        do_op(self, &left, &right, i64::overflowing_div, Decimal::checked_div, f64::div)
+    }
+
+    pub fn power(self, left: SimpleNumber, right: SimpleNumber) -> Option<SimpleNumber> {
+        if right < Integer(0) {
+            return None;
+        }
+
+        match (left, right) {
+            (Integer(v1), Integer(v2)) => {
+                // exponents beyond u32 can only stay in range for bases of -1, 0 and 1
+                let result = match u32::try_from(v2) {
+                    Ok(exp) => v1.overflowing_pow(exp),
+                    Err(_) => match v1 {
+                        0 | 1 => (v1, false),
+                        -1 => (if v2 % 2 == 0 { 1 } else { -1 }, false),
+                        _ => (0, true),
+                    },
+                };
+
+                integer_result(result, self, || (v1 as f64).powf(v2 as f64))
+            }
+            (SimpleNumber::Decimal(v1), Integer(v2)) => decimal_result(u32::try_from(v2).ok().and_then(|exp| v1.checked_pow(exp)), self, || {
+                v1.to_f64().powf(v2 as f64)
+            }),
+            (left, right) => float_result(f64::from(left).powf(f64::from(right))),
+        }
+    }
+
+    pub fn integer_divide(self, left: SimpleNumber, right: SimpleNumber) -> Option<SimpleNumber> {
+        if right == Integer(0) || right == Float(0.0) {
+            return None;
+        }
+
+        match (left, right) {
+            (Integer(v1), Integer(v2)) => integer_result(v1.overflowing_div(v2), self, || (v1 as f64).div(v2 as f64).trunc()),
+            (Float(_), _) | (_, Float(_)) => float_to_integer(f64::from(left).div(f64::from(right)), self),
+            (left, right) => left
+                .to_exact()?
+                .checked_integer_div(&right.to_exact()?)
+                .map(Integer)
+                .or_else(|| float_to_integer(f64::from(left).div(f64::from(right)), self)),
+        }
+    }
+
+    pub fn remainder(self, left: SimpleNumber, right: SimpleNumber) -> Option<SimpleNumber> {
+        if right == Integer(0) || right == Float(0.0) {
+            return None;
+        }
+
+        do_op(self, &left, &right, i64::overflowing_rem, Decimal::checked_rem, f64::rem)
+    }
+
+    pub fn absolute_value(self, value: SimpleNumber) -> Option<SimpleNumber> {
+        match value {
+            Integer(v) => integer_result(v.overflowing_abs(), self, || (v as f64).abs()),
+            Float(v) => Some(Float(v.abs())),
+            SimpleNumber::Decimal(v) => decimal_result(v.checked_abs(), self, || v.to_f64().abs()),
+        }
+    }
+
+    pub fn opposite(self, value: SimpleNumber) -> Option<SimpleNumber> {
+        match value {
+            Integer(v) => integer_result(v.overflowing_neg(), self, || -(v as f64)),
+            Float(v) => Some(Float(-v)),
+            SimpleNumber::Decimal(v) => decimal_result(v.checked_neg(), self, || -v.to_f64()),
+        }
+    }
+
+    pub fn increment(self, value: SimpleNumber) -> Option<SimpleNumber> {
+        match value {
+            Integer(v) => integer_result(v.overflowing_add(1), self, || v as f64 + 1.0),
+            Float(v) => Some(Float(v + 1.0)),
+            SimpleNumber::Decimal(v) => decimal_result(v.checked_add(&Decimal::from_integer(1)), self, || v.to_f64() + 1.0),
+        }
+    }
+
+    pub fn decrement(self, value: SimpleNumber) -> Option<SimpleNumber> {
+        match value {
+            Integer(v) => integer_result(v.overflowing_sub(1), self, || v as f64 - 1.0),
+            Float(v) => Some(Float(v - 1.0)),
+            SimpleNumber::Decimal(v) => decimal_result(v.checked_sub(&Decimal::from_integer(1)), self, || v.to_f64() - 1.0),
+        }
+    }
 }
 
 impl Eq for SimpleNumber {}
 
 impl SimpleNumber {
-    pub fn as_integer(&self) -> DataCastResult<i32> {
+    pub fn as_integer(&self) -> DataCastResult<i64> {
         match self {
             SimpleNumber::Integer(v) => Ok(*v),
             _ => Err(DataError::from(format!("{:?} is not an Integer.", self))),
@@ -34,17 +188,35 @@ impl SimpleNumber {
         }
     }
 
+    pub fn as_decimal(&self) -> DataCastResult<Decimal> {
+        match self {
+            SimpleNumber::Decimal(v) => Ok(*v),
+            _ => Err(DataError::from(format!("{:?} is not a Decimal.", self))),
+        }
+    }
+
     pub fn to_integer(&self) -> Self {
         match self {
             SimpleNumber::Integer(v) => Integer(*v),
-            SimpleNumber::Float(v) => Float(*v as f64),
+            SimpleNumber::Float(v) => Float(*v),
+            SimpleNumber::Decimal(v) => SimpleNumber::Decimal(*v),
         }
     }
 
     pub fn to_float(&self) -> Self {
         match self {
-            SimpleNumber::Integer(v) => Integer(*v as i32),
+            SimpleNumber::Integer(v) => Integer(*v),
             SimpleNumber::Float(v) => Float(*v),
+            SimpleNumber::Decimal(v) => SimpleNumber::Decimal(*v),
+        }
+    }
+
+    // exact value for integers and decimals, floats have no exact representation
+    fn to_exact(self) -> Option<Decimal> {
+        match self {
+            SimpleNumber::Integer(v) => Some(Decimal::from_integer(v)),
+            SimpleNumber::Decimal(v) => Some(v),
+            SimpleNumber::Float(_) => None,
         }
     }
 }
@@ -60,6 +232,7 @@ impl Display for SimpleNumber {
         match self {
             Integer(v) => f.write_str(v.to_string().as_str()),
             Float(v) => f.write_str(v.to_string().as_str()),
+            SimpleNumber::Decimal(v) => v.fmt(f),
         }
     }
 }
@@ -69,6 +242,14 @@ impl Hash for SimpleNumber {
         match self {
             Integer(v) => v.hash(state),
             Float(v) => format!("{}", v).hash(state),
+            SimpleNumber::Decimal(v) => {
+                // whole decimals equal integers, so they need to hash the same
+                let normalized = v.normalized();
+                match i64::try_from(normalized.units()) {
+                    Ok(units) if normalized.scale() == 0 => units.hash(state),
+                    _ => v.hash(state),
+                }
+            }
         }
     }
 }
@@ -81,6 +262,7 @@ macro_rules! simple_number_from {
                     match x {
                         Integer(v) => v as $x,
                         Float(v) => v as $x,
+                        SimpleNumber::Decimal(v) => v.to_f64() as $x,
                     }
                 }
             }
@@ -90,6 +272,7 @@ macro_rules! simple_number_from {
                     match *x {
                         Integer(v) => v as $x,
                         Float(v) => v as $x,
+                        SimpleNumber::Decimal(v) => v.to_f64() as $x,
                     }
                 }
             }
@@ -102,7 +285,23 @@ macro_rules! integer_to_simple {
         $(
             impl From<$x> for SimpleNumber {
                 fn from(x: $x) -> Self {
-                    SimpleNumber::Integer(x as i32)
+                    SimpleNumber::Integer(x as i64)
+                }
+            }
+        )*
+    }
+}
+
+// unsigned values above i64::MAX can't be stored as an integer, fall back to a float instead of wrapping
+macro_rules! wide_integer_to_simple {
+    ( $( $x:ty ),* ) => {
+        $(
+            impl From<$x> for SimpleNumber {
+                fn from(x: $x) -> Self {
+                    match i64::try_from(x) {
+                        Ok(v) => SimpleNumber::Integer(v),
+                        Err(_) => SimpleNumber::Float(x as f64),
+                    }
                 }
             }
         )*
@@ -122,7 +321,8 @@ macro_rules! float_to_simple {
 }
 
 simple_number_from!(i8, i16, i32, i64, u8, u16, u32, u64, isize, f32, f64);
-integer_to_simple!(i8, i16, i32, i64, u8, u16, u32, u64, isize, usize);
+integer_to_simple!(i8, i16, i32, i64, u8, u16, u32, isize);
+wide_integer_to_simple!(u64, usize);
 float_to_simple!(f32, f64);
 
 impl From<SimpleNumber> for usize {
@@ -130,6 +330,7 @@ impl From<SimpleNumber> for usize {
         match x {
             SimpleNumber::Integer(v) => v.max(0) as usize,
             SimpleNumber::Float(v) => v.max(0.0) as usize,
+            SimpleNumber::Decimal(v) => v.to_f64().max(0.0) as usize,
         }
     }
 }
@@ -144,9 +345,8 @@ impl PartialEq for SimpleNumber {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
             (SimpleNumber::Integer(v1), SimpleNumber::Integer(v2)) => v1 == v2,
-            (SimpleNumber::Float(v1), SimpleNumber::Float(v2)) => v1 == v2,
-            (SimpleNumber::Integer(v1), SimpleNumber::Float(v2)) => f64::from(*v1) == *v2,
-            (SimpleNumber::Float(v1), SimpleNumber::Integer(v2)) => *v1 == f64::from(*v2),
+            (SimpleNumber::Float(_), _) | (_, SimpleNumber::Float(_)) => f64::from(self) == f64::from(other),
+            _ => self.to_exact() == other.to_exact(),
         }
     }
 }
@@ -155,52 +355,71 @@ impl PartialOrd for SimpleNumber {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
         match (self, other) {
             (SimpleNumber::Integer(v1), SimpleNumber::Integer(v2)) => v1.partial_cmp(v2),
-            (SimpleNumber::Float(v1), SimpleNumber::Float(v2)) => v1.partial_cmp(v2),
-            (SimpleNumber::Integer(v1), SimpleNumber::Float(v2)) => f64::from(*v1).partial_cmp(v2),
-            (SimpleNumber::Float(v1), SimpleNumber::Integer(v2)) => (v1).partial_cmp(&f64::from(*v2)),
+            (SimpleNumber::Float(_), _) | (_, SimpleNumber::Float(_)) => f64::from(self).partial_cmp(&f64::from(other)),
+            _ => self.to_exact()?.partial_cmp(&other.to_exact()?),
         }
     }
 }
 
-fn do_op<IntOp, FloatOp>(left: &SimpleNumber, right: &SimpleNumber, int_op: IntOp, float_op: FloatOp) -> Option<SimpleNumber>
+fn float_result(f: f64) -> Option<SimpleNumber> {
+    if f.is_infinite() { None } else { Some(Float(f)) }
+}
+
+fn integer_result<Promote>(result: (i64, bool), policy: OverflowPolicy, promote: Promote) -> Option<SimpleNumber>
 where
-    IntOp: Fn(i32, i32) -> (i32, bool),
-    FloatOp: Fn(f64, f64) -> f64,
+    Promote: FnOnce() -> f64,
 {
-    Some(match (left, right) {
-        (Integer(v1), Integer(v2)) => {
-            let (v, o) = int_op(*v1, *v2);
-            if o {
-                return None;
-            }
+    match (result, policy) {
+        ((v, false), _) => Some(Integer(v)),
+        (_, OverflowPolicy::Error) => None,
+        (_, OverflowPolicy::PromoteToFloat) => float_result(promote()),
+    }
+}
 
-            Integer(v)
-        }
-        (Float(v1), Float(v2)) => {
-            let f = float_op(*v1, *v2);
-            if f.is_infinite() {
-                return None;
-            } else {
-                Float(f)
-            }
-        }
-        (Integer(v1), Float(v2)) => {
-            let f = float_op(f64::from(*v1), *v2);
-            if f.is_infinite() {
-                return None;
-            } else {
-                Float(f)
-            }
-        }
-        (Float(v1), Integer(v2)) => {
-            let f = float_op(*v1, f64::from(*v2));
-            if f.is_infinite() {
-                return None;
-            } else {
-                Float(f)
-            }
+fn decimal_result<Promote>(result: Option<Decimal>, policy: OverflowPolicy, promote: Promote) -> Option<SimpleNumber>
+where
+    Promote: FnOnce() -> f64,
+{
+    match (result, policy) {
+        (Some(v), _) => Some(SimpleNumber::Decimal(v)),
+        (None, OverflowPolicy::Error) => None,
+        (None, OverflowPolicy::PromoteToFloat) => float_result(promote()),
+    }
+}
+
+fn float_to_integer(f: f64, policy: OverflowPolicy) -> Option<SimpleNumber> {
+    let f = f.trunc();
+    if f.is_nan() {
+        None
+    } else if f >= i64::MIN as f64 && f < i64::MAX as f64 {
+        Some(Integer(f as i64))
+    } else {
+        match policy {
+            OverflowPolicy::Error => None,
+            OverflowPolicy::PromoteToFloat => float_result(f),
         }
-    })
+    }
+}
+
+fn do_op<IntOp, DecimalOp, FloatOp>(
+    policy: OverflowPolicy,
+    left: &SimpleNumber,
+    right: &SimpleNumber,
+    int_op: IntOp,
+    decimal_op: DecimalOp,
+    float_op: FloatOp,
+) -> Option<SimpleNumber>
+where
+    IntOp: Fn(i64, i64) -> (i64, bool),
+    DecimalOp: Fn(&Decimal, &Decimal) -> Option<Decimal>,
+    FloatOp: Fn(f64, f64) -> f64,
+{
+    let promote = || float_op(f64::from(left), f64::from(right));
+    match (left, right) {
+        (Integer(v1), Integer(v2)) => integer_result(int_op(*v1, *v2), policy, promote),
+        (Float(_), _) | (_, Float(_)) => float_result(promote()),
+        _ => decimal_result(decimal_op(&left.to_exact()?, &right.to_exact()?), policy, promote),
+    }
 }
 
 impl TypeConstants for SimpleNumber {
@@ -251,178 +470,53 @@ impl Add<f64> for SimpleNumber {
 
 impl GarnishNumber for SimpleNumber {
     fn plus(self, rhs: Self) -> Option<Self> {
-        do_op(&self, &rhs, i32::overflowing_add, f64::add)
+        OverflowPolicy::Error.plus(self, rhs)
     }
 
     fn subtract(self, rhs: Self) -> Option<Self> {
-        do_op(&self, &rhs, i32::overflowing_sub, f64::sub)
+        OverflowPolicy::Error.subtract(self, rhs)
     }
 
     fn multiply(self, rhs: Self) -> Option<Self> {
-        do_op(&self, &rhs, i32::overflowing_mul, f64::mul)
+        OverflowPolicy::Error.multiply(self, rhs)
     }
 
     fn divide(self, rhs: Self) -> Option<Self> {
-        if rhs == Integer(0) || rhs == Float(0.0) {
-            return None;
-        }
-
-        do_op(&self, &rhs, i32::overflowing_div, f64::div)
+        OverflowPolicy::Error.divide(self, rhs)
     }
 
     fn power(self, rhs: Self) -> Option<Self> {
-        Some(match (self, rhs) {
-            (Integer(v1), Integer(v2)) => {
-                if v2 < 0 {
-                    return None;
-                }
-
-                let (v, o) = v1.overflowing_pow(v2 as u32);
-                if o {
-                    return None;
-                }
-
-                Integer(v)
-            }
-            (Float(v1), Float(v2)) => {
-                if v2 < 0.0 {
-                    return None;
-                }
-
-                let f = v1.powf(v2);
-                if f.is_infinite() {
-                    return None;
-                } else {
-                    Float(f)
-                }
-            }
-            (Integer(v1), Float(v2)) => {
-                if v2 < 0.0 {
-                    return None;
-                }
-
-                let f = f64::from(v1).powf(v2);
-                if f.is_infinite() {
-                    return None;
-                } else {
-                    Float(f)
-                }
-            }
-            (Float(v1), Integer(v2)) => {
-                if v2 < 0 {
-                    return None;
-                }
-
-                let f = v1.powf(f64::from(v2));
-                if f.is_infinite() {
-                    return None;
-                } else {
-                    Float(f)
-                }
-            }
-        })
+        OverflowPolicy::Error.power(self, rhs)
     }
 
     fn integer_divide(self, rhs: Self) -> Option<Self> {
-        if rhs == Integer(0) || rhs == Float(0.0) {
-            return None;
-        }
-
-        Some(match (self, rhs) {
-            (Integer(v1), Integer(v2)) => {
-                let (v, o) = v1.overflowing_div(v2);
-                if o {
-                    return None;
-                }
-
-                Integer(v)
-            }
-            (Float(v1), Float(v2)) => {
-                let v = v1.div(v2) as i32;
-
-                Integer(v)
-            }
-            (Integer(v1), Float(v2)) => {
-                let v = (v1 as f64).div(v2) as i32;
-
-                Integer(v)
-            }
-            (Float(v1), Integer(v2)) => {
-                let v = v1.div(v2 as f64) as i32;
-
-                Integer(v)
-            }
-        })
+        OverflowPolicy::Error.integer_divide(self, rhs)
     }
 
     fn remainder(self, rhs: Self) -> Option<Self> {
-        if rhs == Integer(0) || rhs == Float(0.0) {
-            return None;
-        }
-
-        do_op(&self, &rhs, i32::overflowing_rem, f64::rem)
+        OverflowPolicy::Error.remainder(self, rhs)
     }
 
     fn absolute_value(self) -> Option<Self> {
-        Some(match self {
-            Integer(v) => {
-                let (v, o) = v.overflowing_abs();
-                if o {
-                    return None;
-                }
-
-                Integer(v)
-            }
-            Float(v) => Float(v.abs()),
-        })
+        OverflowPolicy::Error.absolute_value(self)
     }
 
     fn opposite(self) -> Option<Self> {
-        Some(match self {
-            Integer(v) => {
-                let (v, o) = v.overflowing_neg();
-                if o {
-                    return None;
-                }
-
-                Integer(v)
-            }
-            Float(v) => Float(-v),
-        })
+        OverflowPolicy::Error.opposite(self)
     }
 
     fn increment(self) -> Option<Self> {
-        Some(match self {
-            Integer(v) => {
-                let (v, o) = v.overflowing_add(1);
-                if o {
-                    return None;
-                }
-
-                Integer(v)
-            }
-            Float(v) => Float(v + 1.0),
-        })
+        OverflowPolicy::Error.increment(self)
     }
 
     fn decrement(self) -> Option<Self> {
-        Some(match self {
-            Integer(v) => {
-                let (v, o) = v.overflowing_sub(1);
-                if o {
-                    return None;
-                }
-
-                Integer(v)
-            }
-            Float(v) => Float(v - 1.0),
-        })
+        OverflowPolicy::Error.decrement(self)
     }
 
     fn bitwise_not(self) -> Option<Self> {
         Some(match self {
             Integer(v) => Integer(!v),
-            Float(_) => return None,
+            Float(_) | SimpleNumber::Decimal(_) => return None,
         })
     }
 
@@ -448,25 +542,25 @@ impl GarnishNumber for SimpleNumber {
     }
 
     fn bitwise_shift_left(self, rhs: Self) -> Option<Self> {
-        Some(match (self, rhs) {
-            (Integer(v1), Integer(v2)) => Integer(v1 << v2),
-            _ => return None,
-        })
+        match (self, rhs) {
+            (Integer(v1), Integer(v2)) => v1.checked_shl(u32::try_from(v2).ok()?).map(Integer),
+            _ => None,
+        }
     }
 
     fn bitwise_shift_right(self, rhs: Self) -> Option<Self> {
-        Some(match (self, rhs) {
-            (Integer(v1), Integer(v2)) => Integer(v1 >> v2),
-            _ => return None,
-        })
+        match (self, rhs) {
+            (Integer(v1), Integer(v2)) => v1.checked_shr(u32::try_from(v2).ok()?).map(Integer),
+            _ => None,
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::data::SimpleNumber;
+    use crate::data::{Decimal, OverflowPolicy, SimpleNumber};
     use crate::data::SimpleNumber::*;
-    use garnish_lang_traits::GarnishNumber;
+    use garnish_lang_traits::{GarnishNumber, Instruction};
 
     #[test]
     fn from_all() {
@@ -579,11 +673,11 @@ mod tests {
 
     #[test]
     fn add_overflow() {
-        assert_eq!(Integer(i32::MAX).plus(Integer(1)), None);
+        assert_eq!(Integer(i64::MAX).plus(Integer(1)), None);
         assert_eq!(Float(f64::MAX).plus(Float(f64::MAX)), None);
         // following can't be infinite
-        // assert_eq!(Float(f64::MAX).plus(Integer(i32::MAX)), None);
-        // assert_eq!(Integer(i32::MAX).plus(Float(f64::MAX)), None);
+        // assert_eq!(Float(f64::MAX).plus(Integer(i64::MAX)), None);
+        // assert_eq!(Integer(i64::MAX).plus(Float(f64::MAX)), None);
     }
 
     #[test]
@@ -596,11 +690,11 @@ mod tests {
 
     #[test]
     fn subtract_overflow() {
-        assert_eq!(Integer(i32::MIN).subtract(Integer(1)), None);
+        assert_eq!(Integer(i64::MIN).subtract(Integer(1)), None);
         assert_eq!(Float(f64::MIN).subtract(Float(f64::MAX)), None);
         // following can't be infinite
-        // assert_eq!(Float(f64::MIN).subtract(Integer(i32::MAX)), None);
-        // assert_eq!(Integer(i32::MIN).subtract(Float(f64::MAX)), None);
+        // assert_eq!(Float(f64::MIN).subtract(Integer(i64::MAX)), None);
+        // assert_eq!(Integer(i64::MIN).subtract(Float(f64::MAX)), None);
     }
 
     #[test]
@@ -613,10 +707,10 @@ mod tests {
 
     #[test]
     fn multiply_overflow() {
-        assert_eq!(Integer(i32::MAX).multiply(Integer(2)), None);
+        assert_eq!(Integer(i64::MAX).multiply(Integer(2)), None);
         assert_eq!(Float(f64::MAX).multiply(Float(f64::MAX)), None);
-        assert_eq!(Integer(i32::MAX).multiply(Float(f64::MAX)), None);
-        assert_eq!(Float(f64::MAX).multiply(Integer(i32::MAX)), None);
+        assert_eq!(Integer(i64::MAX).multiply(Float(f64::MAX)), None);
+        assert_eq!(Float(f64::MAX).multiply(Integer(i64::MAX)), None);
     }
 
     #[test]
@@ -629,16 +723,16 @@ mod tests {
 
     #[test]
     fn division_overflow() {
-        assert_eq!(Integer(i32::MIN).divide(Integer(-1)), None);
+        assert_eq!(Integer(i64::MIN).divide(Integer(-1)), None);
         assert_eq!(Float(f64::MIN).divide(Float(-f64::MIN_POSITIVE)), None);
-        assert_eq!(Integer(i32::MIN).divide(Float(-f64::MIN_POSITIVE)), None);
+        assert_eq!(Integer(i64::MIN).divide(Float(-f64::MIN_POSITIVE)), None);
         // following can't be infinite
-        // assert_eq!(Float(-f64::MIN_POSITIVE).divide(Integer(i32::MIN)).unwrap(), Float(0.5));
+        // assert_eq!(Float(-f64::MIN_POSITIVE).divide(Integer(i64::MIN)).unwrap(), Float(0.5));
     }
 
     #[test]
     fn division_by_zero() {
-        assert_eq!(Integer(i32::MAX).divide(Integer(0)), None);
+        assert_eq!(Integer(i64::MAX).divide(Integer(0)), None);
         assert_eq!(Float(10.0).divide(Float(0.0)), None);
         assert_eq!(Integer(10).divide(Float(0.0)), None);
         assert_eq!(Float(10.0).divide(Integer(0)), None);
@@ -654,10 +748,10 @@ mod tests {
 
     #[test]
     fn power_overflow() {
-        assert_eq!(Integer(i32::MAX).power(Integer(2)), None);
+        assert_eq!(Integer(i64::MAX).power(Integer(2)), None);
         assert_eq!(Float(f64::MAX).power(Float(f64::MAX)), None);
-        assert_eq!(Integer(i32::MAX).power(Float(f64::MAX)), None);
-        assert_eq!(Float(f64::MAX).power(Integer(i32::MAX)), None);
+        assert_eq!(Integer(i64::MAX).power(Float(f64::MAX)), None);
+        assert_eq!(Float(f64::MAX).power(Integer(i64::MAX)), None);
     }
 
     #[test]
@@ -678,15 +772,16 @@ mod tests {
 
     #[test]
     fn integer_division_overflow() {
-        assert_eq!(Integer(i32::MIN).integer_divide(Integer(-1)), None);
-        assert_eq!(Float(f64::MIN).integer_divide(Float(-1.0)), Some(Integer(2147483647)));
-        assert_eq!(Integer(i32::MIN).integer_divide(Float(-1.0)), Some(Integer(2147483647)));
-        assert_eq!(Float(f64::MIN).integer_divide(Integer(-1)), Some(Integer(2147483647)));
+        assert_eq!(Integer(i64::MIN).integer_divide(Integer(-1)), None);
+        assert_eq!(Float(f64::MIN).integer_divide(Float(-1.0)), None);
+        assert_eq!(Integer(i64::MIN).integer_divide(Float(-1.0)), None);
+        assert_eq!(Float(f64::MIN).integer_divide(Integer(-1)), None);
+        assert_eq!(Float(f64::NAN).integer_divide(Float(1.0)), None);
     }
 
     #[test]
     fn integer_division_by_zero() {
-        assert_eq!(Integer(i32::MIN).integer_divide(Integer(0)), None);
+        assert_eq!(Integer(i64::MIN).integer_divide(Integer(0)), None);
         assert_eq!(Float(10.0).integer_divide(Float(0.0)), None);
         assert_eq!(Integer(10).integer_divide(Float(0.0)), None);
         assert_eq!(Float(10.0).integer_divide(Integer(0)), None);
@@ -702,12 +797,12 @@ mod tests {
 
     #[test]
     fn remainder_overflow() {
-        assert_eq!(Integer(i32::MIN).remainder(Integer(-1)), None);
+        assert_eq!(Integer(i64::MIN).remainder(Integer(-1)), None);
     }
 
     #[test]
     fn remainder_by_zero() {
-        assert_eq!(Integer(i32::MIN).remainder(Integer(0)), None);
+        assert_eq!(Integer(i64::MIN).remainder(Integer(0)), None);
     }
 
     #[test]
@@ -718,7 +813,7 @@ mod tests {
 
     #[test]
     fn absolute_value_overflow() {
-        assert_eq!(Integer(i32::MIN).absolute_value(), None);
+        assert_eq!(Integer(i64::MIN).absolute_value(), None);
     }
 
     #[test]
@@ -729,7 +824,7 @@ mod tests {
 
     #[test]
     fn opposite_overflow() {
-        assert_eq!(Integer(i32::MIN).opposite(), None);
+        assert_eq!(Integer(i64::MIN).opposite(), None);
     }
 
     #[test]
@@ -740,7 +835,7 @@ mod tests {
 
     #[test]
     fn increment_overflow() {
-        assert_eq!(Integer(i32::MAX).increment(), None);
+        assert_eq!(Integer(i64::MAX).increment(), None);
     }
 
     #[test]
@@ -751,7 +846,7 @@ mod tests {
 
     #[test]
     fn decrement_overflow() {
-        assert_eq!(Integer(i32::MIN).decrement(), None);
+        assert_eq!(Integer(i64::MIN).decrement(), None);
     }
 
     #[test]
@@ -790,6 +885,136 @@ mod tests {
         assert!(Float(10.0).bitwise_shift_right(Float(1.0)).is_none());
     }
 
+    #[test]
+    fn integers_beyond_i32() {
+        assert_eq!(Integer(3_000_000_000).plus(Integer(3_000_000_000)), Some(Integer(6_000_000_000)));
+        assert_eq!(SimpleNumber::from(5_000_000_000u64), Integer(5_000_000_000));
+        assert_eq!(SimpleNumber::from(u64::MAX), Float(u64::MAX as f64));
+    }
+
+    #[test]
+    fn overflow_policy_defaults_to_error() {
+        assert_eq!(OverflowPolicy::default(), OverflowPolicy::Error);
+        assert_eq!(Integer(i64::MAX).plus(Integer(1)), None);
+        assert_eq!(OverflowPolicy::Error.plus(Integer(i64::MAX), Integer(1)), None);
+    }
+
+    #[test]
+    fn overflow_promotes_to_float() {
+        let policy = OverflowPolicy::PromoteToFloat;
+
+        assert_eq!(policy.plus(Integer(i64::MAX), Integer(1)), Some(Float(i64::MAX as f64 + 1.0)));
+        assert_eq!(policy.subtract(Integer(i64::MIN), Integer(1)), Some(Float(i64::MIN as f64 - 1.0)));
+        assert_eq!(policy.multiply(Integer(i64::MAX), Integer(2)), Some(Float(i64::MAX as f64 * 2.0)));
+        assert_eq!(policy.divide(Integer(i64::MIN), Integer(-1)), Some(Float(-(i64::MIN as f64))));
+        assert_eq!(policy.power(Integer(10), Integer(30)), Some(Float(1e30)));
+        assert_eq!(policy.integer_divide(Integer(i64::MIN), Integer(-1)), Some(Float(-(i64::MIN as f64))));
+        assert_eq!(policy.integer_divide(Float(1e30), Float(1.0)), Some(Float(1e30)));
+        assert_eq!(policy.absolute_value(Integer(i64::MIN)), Some(Float(-(i64::MIN as f64))));
+        assert_eq!(policy.opposite(Integer(i64::MIN)), Some(Float(-(i64::MIN as f64))));
+        assert_eq!(policy.increment(Integer(i64::MAX)), Some(Float(i64::MAX as f64 + 1.0)));
+        assert_eq!(policy.decrement(Integer(i64::MIN)), Some(Float(i64::MIN as f64 - 1.0)));
+
+        // non-overflowing results stay integers
+        assert_eq!(policy.plus(Integer(10), Integer(20)), Some(Integer(30)));
+        // float overflow still fails
+        assert_eq!(policy.plus(Float(f64::MAX), Float(f64::MAX)), None);
+    }
+
+    #[test]
+    fn overflow_policy_applies_instructions() {
+        let policy = OverflowPolicy::PromoteToFloat;
+
+        assert_eq!(policy.apply(Instruction::Add, Integer(i64::MAX), Integer(1)), Some(Float(i64::MAX as f64 + 1.0)));
+        assert_eq!(policy.apply(Instruction::BitwiseAnd, Integer(6), Integer(3)), Some(Integer(2)));
+        assert_eq!(policy.apply(Instruction::Apply, Integer(6), Integer(3)), None);
+        assert_eq!(policy.apply_unary(Instruction::Opposite, Integer(i64::MIN)), Some(Float(-(i64::MIN as f64))));
+        assert_eq!(policy.apply_unary(Instruction::BitwiseNot, Integer(0)), Some(Integer(-1)));
+    }
+
+    #[test]
+    fn large_exponents() {
+        assert_eq!(Integer(1).power(Integer(u32::MAX as i64 + 1)), Some(Integer(1)));
+        assert_eq!(Integer(-1).power(Integer(u32::MAX as i64 + 2)), Some(Integer(-1)));
+        assert_eq!(Integer(2).power(Integer(u32::MAX as i64 + 1)), None);
+    }
+
+    #[test]
+    fn shift_out_of_range() {
+        assert_eq!(Integer(1).bitwise_shift_left(Integer(64)), None);
+        assert_eq!(Integer(1).bitwise_shift_right(Integer(-1)), None);
+    }
+
+    fn dec(units: i128, scale: u32) -> SimpleNumber {
+        SimpleNumber::Decimal(Decimal::new(units, scale).unwrap())
+    }
+
+    #[test]
+    fn decimal_arithmetic_is_exact() {
+        assert_eq!(dec(10, 2).plus(dec(20, 2)).unwrap().to_string(), "0.30");
+        assert_eq!(dec(1999, 2).multiply(Integer(3)).unwrap().to_string(), "59.97");
+        assert_eq!(Integer(10).subtract(dec(1, 2)).unwrap().to_string(), "9.99");
+        assert_eq!(dec(1000, 2).divide(Integer(4)).unwrap().to_string(), "2.50");
+        assert_eq!(dec(1000, 2).integer_divide(Integer(3)), Some(Integer(3)));
+        assert_eq!(dec(1000, 2).remainder(Integer(3)).unwrap().to_string(), "1.00");
+        assert_eq!(dec(15, 1).power(Integer(2)).unwrap().to_string(), "2.25");
+        assert_eq!(dec(-15, 1).absolute_value().unwrap().to_string(), "1.5");
+        assert_eq!(dec(15, 1).opposite().unwrap().to_string(), "-1.5");
+        assert_eq!(dec(15, 1).increment().unwrap().to_string(), "2.5");
+        assert_eq!(dec(15, 1).decrement().unwrap().to_string(), "0.5");
+        assert_eq!(dec(10, 0).divide(dec(0, 2)), None);
+        assert_eq!(dec(1, 0).bitwise_and(Integer(1)), None);
+    }
+
+    #[test]
+    fn decimal_with_float_is_float() {
+        assert_eq!(dec(15, 1).plus(Float(1.0)), Some(Float(2.5)));
+        assert_eq!(Float(1.0).multiply(dec(15, 1)), Some(Float(1.5)));
+        assert_eq!(dec(15, 1).power(dec(2, 0)), Some(Float(2.25)));
+    }
+
+    #[test]
+    fn decimal_comparison() {
+        assert_eq!(dec(200, 2), Integer(2));
+        assert_eq!(dec(15, 1), dec(150, 2));
+        assert_eq!(dec(15, 1), Float(1.5));
+        assert!(dec(15, 1) > Integer(1));
+        assert!(dec(-15, 1) < dec(-12, 1));
+        assert!(dec(15, 1) < Float(1.6));
+    }
+
+    #[test]
+    fn decimal_hash_matches_equal_integer() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::hash::{Hash, Hasher};
+
+        let hash = |n: SimpleNumber| {
+            let mut hasher = DefaultHasher::new();
+            n.hash(&mut hasher);
+            hasher.finish()
+        };
+
+        assert_eq!(hash(dec(200, 2)), hash(Integer(2)));
+        assert_eq!(hash(dec(150, 2)), hash(dec(15, 1)));
+    }
+
+    #[test]
+    fn decimal_overflow_policy() {
+        let max = dec(i128::MAX, 0);
+        assert_eq!(max.plus(Integer(1)), None);
+
+        assert_eq!(OverflowPolicy::PromoteToFloat.plus(max, Integer(1)), Some(Float(i128::MAX as f64)));
+    }
+
+    #[test]
+    fn decimal_casts() {
+        assert_eq!(i32::from(dec(1299, 2)), 12);
+        assert_eq!(f64::from(dec(1299, 2)), 12.99);
+        assert_eq!(usize::from(dec(-1299, 2)), 0);
+        assert_eq!(dec(1299, 2).as_decimal().unwrap(), Decimal::new(1299, 2).unwrap());
+        assert!(Integer(1).as_decimal().is_err());
+    }
+
     #[test]
     fn usize_from_number() {
         assert_eq!(usize::from(Integer(10)), 10);
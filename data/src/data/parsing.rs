@@ -1,7 +1,8 @@
-use crate::data::SimpleNumber;
+use crate::data::{Decimal, OverflowPolicy, SimpleNumber};
 use crate::DataError;
 use crate::error::DataErrorType;
 use std::iter;
+use std::num::IntErrorKind;
 use std::str::FromStr;
 
 /// Called when building instructions to parse strings into character lists.
@@ -34,8 +35,8 @@ pub fn parse_char_list(input: &str) -> Result<String, DataError> {
     for c in input.chars().skip(start_quote_count).take(real_len) {
         if in_unicode {
             if c == '}' {
-                match parse_number_internal(unicode_characters.as_str(), 16)? {
-                    SimpleNumber::Float(_) => Err(DataError::from(format!(
+                match parse_number_internal(unicode_characters.as_str(), 16, OverflowPolicy::Error)? {
+                    SimpleNumber::Float(_) | SimpleNumber::Decimal(_) => Err(DataError::from(format!(
                         "Float numbers are not allowed in Unicode escape. {:?}",
                         unicode_characters
                     )))?,
@@ -143,12 +144,12 @@ fn parse_byte_list_numbers(input: &str) -> Result<Vec<u8>, DataError> {
             current_number.push(c);
         } else if c == ' ' && current_number.len() > 0 {
             match parse_simple_number(current_number.as_str())? {
-                SimpleNumber::Float(_) => Err(DataError::from(format!(
+                SimpleNumber::Float(_) | SimpleNumber::Decimal(_) => Err(DataError::from(format!(
                     "Float numbers are not allowed in ByteLists. {:?}",
                     current_number
                 )))?,
                 SimpleNumber::Integer(v) => {
-                    if v < 0 || v > u8::MAX as i32 {
+                    if v < 0 || v > u8::MAX as i64 {
                         Err(DataError::new("Number to large for byte value", DataErrorType::NumberToLargeForByteValue(current_number.to_string())))?;
                     }
 
@@ -164,12 +165,17 @@ fn parse_byte_list_numbers(input: &str) -> Result<Vec<u8>, DataError> {
     Ok(numbers)
 }
 
-/// Called when building instructions to parse strings into [`SimpleNumber`].
+/// Called when building instructions to parse strings into [`SimpleNumber`]. Integers that don't fit in an [`i64`] are an error.
 pub fn parse_simple_number(input: &str) -> Result<SimpleNumber, DataError> {
-    parse_number_internal(input, 10)
+    parse_simple_number_with(input, OverflowPolicy::Error)
 }
 
-fn parse_number_internal(input: &str, default_radix: u32) -> Result<SimpleNumber, DataError> {
+/// Parses a string into a [`SimpleNumber`], handling integers that don't fit in an [`i64`] according to policy.
+pub fn parse_simple_number_with(input: &str, policy: OverflowPolicy) -> Result<SimpleNumber, DataError> {
+    parse_number_internal(input, 10, policy)
+}
+
+fn parse_number_internal(input: &str, default_radix: u32, policy: OverflowPolicy) -> Result<SimpleNumber, DataError> {
     let (radix, input) = match input.find('_') {
         None => (default_radix, input),
         Some(i) => {
@@ -196,8 +202,24 @@ fn parse_number_internal(input: &str, default_radix: u32) -> Result<SimpleNumber
 
     // consider remaining underscores visual separators and replace with empty
     let stripped = input.replace("_", "");
-    match i32::from_str_radix(&stripped, radix) {
+
+    // a trailing 'd' marks an exact decimal, only in base 10 since it is a digit in larger radixes
+    if radix == 10
+        && let Some(value) = stripped.strip_suffix('d')
+        && value.chars().all(|c| c.is_ascii_digit() || matches!(c, '.' | '-' | '+'))
+    {
+        return Decimal::from_str(value).map(SimpleNumber::Decimal);
+    }
+
+    match i64::from_str_radix(&stripped, radix) {
         Ok(v) => Ok(v.into()),
+        Err(e) if matches!(e.kind(), IntErrorKind::PosOverflow | IntErrorKind::NegOverflow) => match policy {
+            OverflowPolicy::Error => Err(DataError::new(
+                format!("Integer {:?} does not fit in 64 bits", input).as_str(),
+                DataErrorType::IntegerOverflow(input.to_string()),
+            )),
+            OverflowPolicy::PromoteToFloat => Ok(radix_digits_to_float(&stripped, radix).into()),
+        },
         Err(_) => {
             if radix == 10 {
                 match f64::from_str(&stripped) {
@@ -211,9 +233,28 @@ fn parse_number_internal(input: &str, default_radix: u32) -> Result<SimpleNumber
     }
 }
 
+// only called with input that i64::from_str_radix reported as overflowing, so every digit is valid for the radix
+fn radix_digits_to_float(input: &str, radix: u32) -> f64 {
+    if radix == 10 {
+        // correctly rounded, folding digits would accumulate error
+        return f64::from_str(input).unwrap_or(f64::NAN);
+    }
+
+    let (negative, digits) = match input.strip_prefix('-') {
+        Some(digits) => (true, digits),
+        None => (false, input.strip_prefix('+').unwrap_or(input)),
+    };
+
+    let value = digits.chars().filter_map(|c| c.to_digit(radix)).fold(0.0, |acc, d| acc * radix as f64 + d as f64);
+
+    if negative { -value } else { value }
+}
+
 #[cfg(test)]
 mod numbers {
-    use crate::data::parse_simple_number;
+    use crate::data::{parse_simple_number, parse_simple_number_with, OverflowPolicy};
+    use crate::error::DataErrorType;
+    use crate::DataError;
     use crate::data::SimpleNumber::*;
 
     #[test]
@@ -236,14 +277,14 @@ mod numbers {
 
     #[test]
     fn min_integer() {
-        let input = i32::MIN.to_string();
-        assert_eq!(parse_simple_number(input.as_str()).unwrap(), Integer(i32::MIN));
+        let input = i64::MIN.to_string();
+        assert_eq!(parse_simple_number(input.as_str()).unwrap(), Integer(i64::MIN));
     }
 
     #[test]
     fn max_integer() {
-        let input = i32::MAX.to_string();
-        assert_eq!(parse_simple_number(input.as_str()).unwrap(), Integer(i32::MAX));
+        let input = i64::MAX.to_string();
+        assert_eq!(parse_simple_number(input.as_str()).unwrap(), Integer(i64::MAX));
     }
 
     #[test]
@@ -311,6 +352,49 @@ mod numbers {
         let input = "016_A6.789";
         assert!(parse_simple_number(input).is_err());
     }
+
+    #[test]
+    fn decimal_literals() {
+        assert_eq!(parse_simple_number("12.50d").unwrap(), Decimal(crate::data::Decimal::new(1250, 2).unwrap()));
+        assert_eq!(parse_simple_number("1_000.25d").unwrap().to_string(), "1000.25");
+        assert_eq!(parse_simple_number("5d").unwrap().to_string(), "5");
+        assert_eq!(parse_simple_number("016_5d").unwrap(), Integer(0x5d));
+        assert!(parse_simple_number("1.2.3d").is_err());
+    }
+
+    #[test]
+    fn integer_beyond_i32() {
+        assert_eq!(parse_simple_number("4_294_967_296").unwrap(), Integer(4294967296));
+    }
+
+    #[test]
+    fn integer_overflow_is_err() {
+        let result = parse_simple_number("9223372036854775808");
+        assert_eq!(
+            result,
+            Err(DataError::new(
+                "Integer \"9223372036854775808\" does not fit in 64 bits",
+                DataErrorType::IntegerOverflow("9223372036854775808".to_string())
+            ))
+        );
+    }
+
+    #[test]
+    fn integer_overflow_promotes_to_float() {
+        let policy = OverflowPolicy::PromoteToFloat;
+        assert_eq!(
+            parse_simple_number_with("9223372036854775808", policy).unwrap(),
+            Float(9223372036854775808.0)
+        );
+        assert_eq!(
+            parse_simple_number_with("-9223372036854775809", policy).unwrap(),
+            Float(-9223372036854775809.0)
+        );
+        assert_eq!(
+            parse_simple_number_with(format!("016_{}", "f".repeat(17)).as_str(), policy).unwrap(),
+            Float(16f64.powi(17) - 1.0)
+        );
+    }
 }
 
 #[cfg(test)]
@@ -26,11 +26,25 @@ pub enum DataErrorType {
     CouldNotParse(String, GarnishDataType),
     NumberToLargeForByteValue(String),
     FailedToParseFloat(String),
+    IntegerOverflow(String),
+    FailedToParseDecimal(String),
     NotACloneNode,
     NoMappedIndexFoundDuringClone(usize),
     UninitializedListContainsNonListItem(GarnishDataType),
     CannotClone,
     CloneLimitReached,
+    InvalidSnapshot(usize),
+    UnsupportedSnapshotVersion(u8),
+    CustomDataNotSnapshotable,
+    HostFunctionArgumentCount(usize, usize, usize),
+    HostFunctionArgumentType(usize, GarnishDataType),
+    HostFunctionFailed,
+    Serde,
+    InvalidJson(usize),
+    NotJsonRepresentable(GarnishDataType),
+    SymbolHashCollision(u64),
+    InvalidPackedCharList(usize),
+    CollectedDataReferenced(usize),
 }
 
 /// Error implemenation for [`crate::SimpleGarnishData`].
@@ -130,6 +144,12 @@ fn format_error_type(error_type: &DataErrorType) -> String {
         DataErrorType::FailedToParseFloat(value) => {
             format!("Failed to parse float: \"{}\"", value)
         }
+        DataErrorType::IntegerOverflow(value) => {
+            format!("Integer overflow: \"{}\"", value)
+        }
+        DataErrorType::FailedToParseDecimal(value) => {
+            format!("Failed to parse decimal: \"{}\"", value)
+        }
         DataErrorType::NotACloneNode => "Not a clone node".to_string(),
         DataErrorType::NoMappedIndexFoundDuringClone(index) => {
             format!("No mapped index found during clone: {}", index)
@@ -139,6 +159,18 @@ fn format_error_type(error_type: &DataErrorType) -> String {
         }
         DataErrorType::CannotClone => "Cannot clone".to_string(),
         DataErrorType::CloneLimitReached => "Clone limit reached".to_string(),
+        DataErrorType::InvalidSnapshot(position) => format!("Invalid snapshot at byte: {}", position),
+        DataErrorType::UnsupportedSnapshotVersion(version) => format!("Unsupported snapshot version: {}", version),
+        DataErrorType::CustomDataNotSnapshotable => "Custom data does not support snapshots".to_string(),
+        DataErrorType::HostFunctionArgumentCount(min, max, got) => format!("Host function expected between {} and {} arguments, got {}", min, max, got),
+        DataErrorType::HostFunctionArgumentType(position, got) => format!("Host function argument {} has unexpected type: {:?}", position, got),
+        DataErrorType::HostFunctionFailed => "Host function failed".to_string(),
+        DataErrorType::Serde => "Serde error".to_string(),
+        DataErrorType::InvalidJson(position) => format!("Invalid JSON at byte: {}", position),
+        DataErrorType::NotJsonRepresentable(got_type) => format!("No JSON representation for type: {:?}", got_type),
+        DataErrorType::SymbolHashCollision(symbol) => format!("Symbol hash collision: {}", symbol),
+        DataErrorType::InvalidPackedCharList(index) => format!("Packed char list is not valid UTF-8 at data index: {}", index),
+        DataErrorType::CollectedDataReferenced(index) => format!("Collected data is still referenced at data index: {}", index),
     }
 }
 
@@ -154,6 +186,10 @@ impl DataError {
     pub fn not_basic_type_error() -> Self {
         DataError::new("Not a basic type", DataErrorType::NotBasicType)
     }
+
+    pub fn error_type(&self) -> &DataErrorType {
+        &self.error_type
+    }
 }
 
 impl Display for DataError {
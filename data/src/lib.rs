@@ -1,7 +1,4 @@
-pub use error::DataError;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::Hash;
-use std::hash::Hasher;
+pub use error::{DataError, DataErrorType};
 
 mod clone;
 mod data;
@@ -17,11 +14,25 @@ pub use runtime::SimpleDataFactory;
 pub use simple::*;
 pub use basic::*;
 
-/// Utility to convert strings to [`u64`], the Symbol type for [`SimpleGarnishData`].
+/// Utility to convert strings to [`u64`], the Symbol type for [`SimpleGarnishData`] and [`BasicGarnishData`].
+///
+/// Uses the 64 bit FNV-1a hash of the UTF-8 bytes of `value`, offset basis `0xcbf29ce484222325` and prime `0x100000001b3`.
+/// The result is stable across Rust releases and processes, so symbols can be persisted and compared between runs.
 pub fn symbol_value(value: &str) -> u64 {
-    let mut h = DefaultHasher::new();
-    value.hash(&mut h);
-    let hv = h.finish();
+    const FNV_OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+    const FNV_PRIME: u64 = 0x100000001b3;
 
-    hv
+    value.bytes().fold(FNV_OFFSET_BASIS, |hash, byte| (hash ^ byte as u64).wrapping_mul(FNV_PRIME))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::symbol_value;
+
+    #[test]
+    fn symbol_value_is_fnv_1a() {
+        assert_eq!(symbol_value(""), 0xcbf29ce484222325);
+        assert_eq!(symbol_value("a"), 0xaf63dc4c8601ec8c);
+        assert_eq!(symbol_value("foobar"), 0x85944171f73967e8);
+    }
 }
@@ -1,9 +1,10 @@
+use std::cmp::Ordering;
 use std::convert::TryInto;
 
 use garnish_lang_traits::{Extents, GarnishData, GarnishDataFactory, GarnishDataType, Instruction, SymbolListPart, TypeConstants};
 
-use crate::data::{NumberIterator, SimpleNumber, SizeIterator, parse_byte_list, parse_char_list, parse_simple_number};
-use crate::{ByteListIterator, CharListIterator, DataError, DataIndexIterator, SimpleData, SimpleDataType, SimpleGarnishData, SimpleInstruction, SimpleStackFrame, SymbolListPartIterator, symbol_value};
+use crate::data::{Decimal, NumberIterator, SimpleNumber, SizeIterator, UNIT_INDEX, parse_byte_list, parse_char_list, parse_simple_number, parse_simple_number_with};
+use crate::{ByteListIterator, CharListIterator, DataError, DataIndexIterator, SimpleData, SimpleDataType, SimpleGarnishData, SimpleHandler, SimpleInstruction, SimpleStackFrame, SymbolListPartIterator, symbol_value};
 
 /// Factory implementation for SimpleGarnishData
 pub struct SimpleDataFactory;
@@ -23,7 +24,7 @@ impl GarnishDataFactory<usize, SimpleNumber, char, u8, u64, DataError, SizeItera
                 Ok(c) => Some(c),
                 Err(_) => None,
             },
-            SimpleNumber::Float(_) => None,
+            SimpleNumber::Float(_) | SimpleNumber::Decimal(_) => None,
         }
     }
 
@@ -33,7 +34,7 @@ impl GarnishDataFactory<usize, SimpleNumber, char, u8, u64, DataError, SizeItera
                 Ok(b) => Some(b),
                 Err(_) => None,
             },
-            SimpleNumber::Float(_) => None,
+            SimpleNumber::Float(_) | SimpleNumber::Decimal(_) => None,
         }
     }
 
@@ -174,6 +175,15 @@ where
         self.get(index)?.as_symbol()
     }
 
+    fn cmp_symbol_names(&self, left: u64, right: u64) -> Result<Ordering, Self::Error> {
+        Ok(match (self.data.get_symbol(left), self.data.get_symbol(right)) {
+            (Some(left), Some(right)) => left.cmp(right),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => left.cmp(&right),
+        })
+    }
+
     fn get_expression(&self, index: usize) -> Result<usize, Self::Error> {
         self.get(index)?.as_expression()
     }
@@ -202,6 +212,14 @@ where
         self.get(addr)?.as_partial()
     }
 
+    fn get_closure(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error> {
+        self.get(addr)?.as_closure()
+    }
+
+    fn get_error(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error> {
+        self.get(addr)?.as_error()
+    }
+
     fn get_list_len(&self, index: usize) -> Result<usize, Self::Error> {
         Ok(self.get(index)?.as_list()?.0.len())
     }
@@ -212,7 +230,7 @@ where
                 None => Ok(None),
                 Some(v) => Ok(Some(*v)),
             },
-            SimpleNumber::Float(_) => Err(DataError::from("Cannot index list with decimal value.".to_string())), // should return None
+            SimpleNumber::Float(_) | SimpleNumber::Decimal(_) => Err(DataError::from("Cannot index list with decimal value.".to_string())), // should return None
         }
     }
 
@@ -275,7 +293,7 @@ where
                 None => Err(format!("No value at index {:?} for char list at {:?}", item_index, addr))?,
                 Some(c) => Ok(Some(c)),
             },
-            SimpleNumber::Float(_) => Err(DataError::from("Cannot index char list with decimal value.".to_string())), // should return None
+            SimpleNumber::Float(_) | SimpleNumber::Decimal(_) => Err(DataError::from("Cannot index char list with decimal value.".to_string())), // should return None
         }
     }
 
@@ -295,7 +313,7 @@ where
                 None => Err(format!("No value at index {:?} for byte list at {:?}", item_index, addr))?,
                 Some(c) => Ok(Some(*c)),
             },
-            SimpleNumber::Float(_) => Err(DataError::from("Cannot index byte list with decimal value.".to_string())), // should return None
+            SimpleNumber::Float(_) | SimpleNumber::Decimal(_) => Err(DataError::from("Cannot index byte list with decimal value.".to_string())), // should return None
         }
     }
 
@@ -315,7 +333,7 @@ where
                 None => Err(format!("No value at index {:?} for symbol list at {:?}", item_index, addr))?,
                 Some(c) => Ok(Some(SymbolListPart::Symbol(*c))),
             },
-            SimpleNumber::Float(_) => Err(DataError::from("Cannot index symbol list with decimal value.".to_string())), // should return None
+            SimpleNumber::Float(_) | SimpleNumber::Decimal(_) => Err(DataError::from("Cannot index symbol list with decimal value.".to_string())), // should return None
         }
     }
 
@@ -404,6 +422,24 @@ where
         Ok(self.data.len() - 1)
     }
 
+    fn add_closure(&mut self, expression: Self::Size, captures: Self::Size) -> Result<Self::Size, Self::Error> {
+        self.data.push(SimpleData::Closure(expression, captures));
+        Ok(self.data.len() - 1)
+    }
+
+    fn add_error(&mut self, message: Self::Size, code: Self::Size) -> Result<Self::Size, Self::Error> {
+        self.data.push(SimpleData::Error(message, code));
+        Ok(self.data.len() - 1)
+    }
+
+    fn add_char_list(&mut self, chars: Vec<Self::Char>) -> Result<Self::Size, Self::Error> {
+        self.add_string(chars.into_iter().collect::<String>())
+    }
+
+    fn add_byte_list(&mut self, bytes: Vec<Self::Byte>) -> Result<Self::Size, Self::Error> {
+        self.add_u8_vec(bytes)
+    }
+
     fn merge_to_symbol_list(&mut self, first: Self::Size, second: Self::Size) -> Result<Self::Size, Self::Error> {
         match (self.get_data().get(first), self.get_data().get(second)) {
             (Some(SimpleData::Symbol(sym1)), Some(SimpleData::Symbol(sym2))) => {
@@ -553,10 +589,15 @@ where
 
     fn push_frame(&mut self, index: usize) -> Result<(), Self::Error> {
         let r = self.add_stack_frame(SimpleStackFrame::new(index))?;
+        self.local_starts.push(self.locals.len());
         self.push_register(r)
     }
 
     fn pop_frame(&mut self) -> Result<Option<usize>, Self::Error> {
+        if let Some(start) = self.local_starts.pop() {
+            self.locals.truncate(start);
+        }
+
         while let Some(item) = self.register.pop() {
             match self.data.get(item) {
                 None => return Err(format!("Register address ({}) has no data", item))?,
@@ -570,6 +611,40 @@ where
         Ok(None)
     }
 
+    fn get_local(&self, index: usize) -> Option<usize> {
+        let start = self.local_starts.last().cloned().unwrap_or(0);
+        self.locals.get(start + index).cloned()
+    }
+
+    fn set_local(&mut self, index: usize, addr: usize) -> Result<(), Self::Error> {
+        let slot = self.local_starts.last().cloned().unwrap_or(0) + index;
+        if slot >= self.locals.len() {
+            self.locals.resize(slot + 1, UNIT_INDEX);
+        }
+        self.locals[slot] = addr;
+        Ok(())
+    }
+
+    fn push_handler(&mut self, index: usize) -> Result<(), Self::Error> {
+        self.handlers.push(SimpleHandler::new(index, self.register.len(), self.values.len(), self.locals.len(), self.local_starts.len()));
+        Ok(())
+    }
+
+    fn pop_handler(&mut self) -> Result<Option<usize>, Self::Error> {
+        Ok(self.handlers.pop().map(|handler| handler.jump_index()))
+    }
+
+    fn unwind_handler(&mut self) -> Result<Option<usize>, Self::Error> {
+        Ok(self.handlers.pop().map(|handler| {
+            // stack frames live in the register so truncating it also drops frames pushed after the handler
+            self.register.truncate(handler.register_len());
+            self.values.truncate(handler.values_len());
+            self.locals.truncate(handler.locals_len());
+            self.local_starts.truncate(handler.local_starts_len());
+            handler.jump_index()
+        }))
+    }
+
     //
     // Add Conversions
     //
@@ -613,9 +688,12 @@ where
                     s.push(c);
                 }
 
-                match s.parse::<i32>() {
+                match s.parse::<i64>() {
                     Ok(v) => self.add_number(v.into()),
-                    Err(_) => self.add_unit(),
+                    Err(_) => match s.parse::<Decimal>() {
+                        Ok(v) => self.add_number(SimpleNumber::Decimal(v)),
+                        Err(_) => self.add_unit(),
+                    },
                 }
             }
             _ => self.add_unit(),
@@ -640,6 +718,11 @@ where
         SimpleGarnishData::end_byte_list(self)
     }
 
+    fn parse_add_number(&mut self, from: &str) -> Result<Self::Size, Self::Error> {
+        let number = parse_simple_number_with(from, self.overflow_policy)?;
+        self.add_number(number)
+    }
+
     fn parse_add_symbol(&mut self, from: &str) -> Result<Self::Size, Self::Error> {
         let sym = Self::DataFactory::parse_symbol(from)?;
         self.data.insert_symbol(sym, from.to_string());
@@ -653,6 +736,22 @@ where
     fn defer_op(&mut self, operation: Instruction, left: (GarnishDataType, Self::Size), right: (GarnishDataType, Self::Size)) -> Result<bool, Self::Error> {
         (self.op_handler)(self, operation, left, right)
     }
+
+    fn error_values(&self) -> bool {
+        self.error_values
+    }
+
+    fn strict_mode(&self) -> bool {
+        self.strict_mode
+    }
+
+    fn number_op(&self, operation: Instruction, left: Self::Number, right: Self::Number) -> Option<Self::Number> {
+        self.overflow_policy.apply(operation, left, right)
+    }
+
+    fn unary_number_op(&self, operation: Instruction, value: Self::Number) -> Option<Self::Number> {
+        self.overflow_policy.apply_unary(operation, value)
+    }
 }
 
 // Non-trait implementations for removed trait functions
@@ -687,7 +786,7 @@ where
                 None => Err(format!("No list item at index {:?} for list at addr {:?}", item_index, list_index))?,
                 Some(v) => Ok(Some(*v)),
             },
-            SimpleNumber::Float(_) => Err(DataError::from("Cannot index list with decimal value.".to_string())), // should return None
+            SimpleNumber::Float(_) | SimpleNumber::Decimal(_) => Err(DataError::from("Cannot index list with decimal value.".to_string())), // should return None
         }
     }
 
@@ -845,6 +944,20 @@ mod tests {
         assert_eq!(runtime.get_current_instruction().unwrap().0, Instruction::Add);
     }
 
+    #[test]
+    fn conversion_round_trip() {
+        use garnish_lang_traits::{ByteList, FromGarnish, IntoGarnish};
+
+        let mut data = SimpleGarnishData::new();
+        let numbers = vec![1, 2, 3].into_garnish(&mut data).unwrap();
+        let text = "value".into_garnish(&mut data).unwrap();
+        let bytes = ByteList(vec![1, 2]).into_garnish(&mut data).unwrap();
+
+        assert_eq!(Vec::<i32>::from_garnish(&data, numbers).unwrap(), vec![1, 2, 3]);
+        assert_eq!(String::from_garnish(&data, text).unwrap(), "value");
+        assert_eq!(ByteList::from_garnish(&data, bytes).unwrap(), ByteList(vec![1, 2]));
+    }
+
     #[test]
     fn pop_jump_path_clears_registers_to_current_frame() {
         let mut data = SimpleGarnishData::new();
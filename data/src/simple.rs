@@ -1,5 +1,5 @@
-use crate::SimpleNumber;
-use crate::data::{DisplayForCustomItem, SimpleDataList, SimpleStackFrame, UNIT_INDEX};
+use crate::{OverflowPolicy, SimpleNumber};
+use crate::data::{DisplayForCustomItem, SimpleDataList, SimpleHandler, SimpleStackFrame, UNIT_INDEX};
 use crate::error::DataError;
 use crate::instruction::SimpleInstruction;
 use garnish_lang_traits::helpers::iterate_concatenation_mut;
@@ -67,6 +67,18 @@ where
     pub(crate) data: SimpleDataList<T>,
     pub(crate) end_of_constant_data: usize,
     pub(crate) values: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) locals: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) local_starts: Vec<usize>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) handlers: Vec<SimpleHandler>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) error_values: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) strict_mode: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub(crate) overflow_policy: OverflowPolicy,
     pub(crate) instructions: Vec<SimpleInstruction>,
     pub(crate) instruction_cursor: usize,
     pub(crate) expression_table: Vec<usize>,
@@ -75,11 +87,23 @@ where
     pub(crate) current_byte_list: Option<Vec<u8>>,
     pub(crate) cache: HashMap<u64, usize>,
     pub(crate) max_char_list_depth: usize,
+    #[cfg_attr(feature = "serde", serde(skip, default = "serde_default_resolver"))]
     pub(crate) resolver: SimpleResolver<T, A>,
+    #[cfg_attr(feature = "serde", serde(skip, default = "serde_default_op_handler"))]
     pub(crate) op_handler: SimpleOpHandler<T, A>,
     pub(crate) auxiliary_data: A,
 }
 
+#[cfg(feature = "serde")]
+fn serde_default_resolver<T: SimpleDataType, A>() -> SimpleResolver<T, A> {
+    default_resolver
+}
+
+#[cfg(feature = "serde")]
+fn serde_default_op_handler<T: SimpleDataType, A>() -> SimpleOpHandler<T, A> {
+    default_op_handler
+}
+
 /// Alias for [`SimpleGarnishData`] with [`NoCustom`] type parameter.
 pub type SimpleDataRuntimeNC = SimpleGarnishData<NoCustom>;
 
@@ -90,6 +114,12 @@ impl SimpleGarnishData<NoCustom> {
             data: SimpleDataList::default(),
             end_of_constant_data: 0,
             values: vec![],
+            locals: vec![],
+            local_starts: vec![],
+            handlers: vec![],
+            error_values: false,
+            strict_mode: false,
+            overflow_policy: OverflowPolicy::Error,
             instruction_cursor: 0,
             instructions: vec![],
             expression_table: vec![],
@@ -117,6 +147,12 @@ where
             end_of_constant_data: data.len() - 1,
             data,
             values: vec![],
+            locals: vec![],
+            local_starts: vec![],
+            handlers: vec![],
+            error_values: false,
+            strict_mode: false,
+            overflow_policy: OverflowPolicy::Error,
             instruction_cursor: 0,
             instructions: vec![],
             expression_table: vec![],
@@ -139,6 +175,21 @@ where
         &mut self.auxiliary_data
     }
 
+    /// When set, failed casts and accesses produce [`GarnishDataType::Error`] values instead of Unit.
+    pub fn set_error_values(&mut self, enabled: bool) {
+        self.error_values = enabled;
+    }
+
+    /// When set, operations that would produce Unit from unsupported operands or missing values raise errors instead.
+    pub fn set_strict_mode(&mut self, enabled: bool) {
+        self.strict_mode = enabled;
+    }
+
+    /// How number operations and parsing handle integers that don't fit in 64 bits, [`OverflowPolicy::Error`] by default.
+    pub fn set_overflow_policy(&mut self, policy: OverflowPolicy) {
+        self.overflow_policy = policy;
+    }
+
     pub fn set_resolver(&mut self, resolver: SimpleResolver<T, A>) {
         self.resolver = resolver;
     }
@@ -488,25 +539,40 @@ where
                     self.add_to_char_list(c)?;
                 }
             }
-            GarnishDataType::External => {
-                let e = self.get_external(from)?;
-                let s = format!("External({})", e);
+            GarnishDataType::Closure => {
+                let (e, _) = self.get_closure(from)?;
+                let s = format!("Closure({})", self.get_expression(e)?);
 
                 for c in s.chars() {
                     self.add_to_char_list(c)?;
                 }
             }
-            GarnishDataType::Range => {
-                let (start, end) = self
-                    .get_range(from)
-                    .and_then(|(start, end)| Ok((self.get_number(start)?, self.get_number(end)?)))?;
-
-                let s = format!("{}..{}", start, end);
+            GarnishDataType::Error => {
+                let (message, code) = self.get_error(from)?;
+                for c in "[Error ".chars() {
+                    self.add_to_char_list(c)?;
+                }
+                self.add_to_current_char_list(code, depth + 1)?;
+                self.add_to_char_list(' ')?;
+                self.add_to_current_char_list(message, depth + 1)?;
+                self.add_to_char_list(']')?;
+            }
+            GarnishDataType::External => {
+                let e = self.get_external(from)?;
+                let s = format!("External({})", e);
 
                 for c in s.chars() {
                     self.add_to_char_list(c)?;
                 }
             }
+            GarnishDataType::Range => {
+                // stepped ranges hold their contiguous range as start, displayed as start..end..step
+                let (start, end) = self.get_range(from)?;
+                self.add_to_current_char_list(start, depth + 1)?;
+                self.add_to_char_list('.')?;
+                self.add_to_char_list('.')?;
+                self.add_to_current_char_list(end, depth + 1)?;
+            }
             GarnishDataType::Pair => {
                 let (left, right) = self.get_pair(from)?;
                 if depth > 0 {
@@ -0,0 +1,371 @@
+//! Derive macros for the [`IntoGarnish`] and [`FromGarnish`] conversion traits of `garnish_lang_traits`.
+//!
+//! Conversions follow these rules.
+//!
+//! - Structs with named fields become associative lists of `:field = value` pairs.
+//! - Tuple structs with a single field convert as that field. Tuple structs with more fields become lists.
+//! - Unit structs become Unit.
+//! - Unit enum variants become symbols. Variants with data become a pair of the variant symbol and its data, converted like the struct forms above.
+//! - Fields of type `Vec<u8>` become byte lists. Other `Vec` fields become lists.
+//!
+//! The following attributes are supported.
+//!
+//! - `#[garnish(rename = "name")]` on fields and variants changes the symbol used.
+//! - `#[garnish(skip)]` on fields leaves the field out when converting into garnish and uses [`Default`] when converting back.
+//! - `#[garnish(crate = "path")]` on the type changes the path used to reach the traits crate, for example `garnish_lang` when using the
+//!   `garnish_lang` crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{ToTokens, format_ident, quote};
+use syn::{Data, DeriveInput, Fields, GenericArgument, Ident, LitStr, Path, PathArguments, Type, parse_macro_input, parse_quote};
+
+#[proc_macro_derive(IntoGarnish, attributes(garnish))]
+pub fn derive_into_garnish(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_into_garnish(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+#[proc_macro_derive(FromGarnish, attributes(garnish))]
+pub fn derive_from_garnish(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_garnish(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+#[derive(Default)]
+struct Options {
+    rename: Option<String>,
+    skip: bool,
+    krate: Option<Path>,
+}
+
+fn parse_options(attrs: &[syn::Attribute]) -> syn::Result<Options> {
+    let mut options = Options::default();
+    for attr in attrs.iter().filter(|a| a.path().is_ident("garnish")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("rename") {
+                options.rename = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if meta.path.is_ident("crate") {
+                options.krate = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+            } else {
+                return Err(meta.error("unsupported garnish attribute"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+fn crate_path(input: &DeriveInput) -> syn::Result<Path> {
+    Ok(parse_options(&input.attrs)?.krate.unwrap_or_else(|| parse_quote!(::garnish_lang_traits)))
+}
+
+fn is_byte_vec(ty: &Type) -> bool {
+    let Type::Path(path) = ty else {
+        return false;
+    };
+    let Some(segment) = path.path.segments.last() else {
+        return false;
+    };
+    if segment.ident != "Vec" {
+        return false;
+    }
+    match &segment.arguments {
+        PathArguments::AngleBracketed(args) if args.args.len() == 1 => {
+            matches!(args.args.first(), Some(GenericArgument::Type(Type::Path(inner))) if inner.path.is_ident("u8"))
+        }
+        _ => false,
+    }
+}
+
+struct Field {
+    member: TokenStream2,
+    binding: Ident,
+    name: String,
+    ty: Type,
+    skip: bool,
+}
+
+impl Field {
+    /// Type actually converted for this field.
+    fn conversion_type(&self, krate: &Path) -> Type {
+        match is_byte_vec(&self.ty) {
+            true => parse_quote!(#krate::ByteList),
+            false => self.ty.clone(),
+        }
+    }
+}
+
+fn collect_fields(fields: &Fields) -> syn::Result<Vec<Field>> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(i, field)| {
+            let options = parse_options(&field.attrs)?;
+            let (member, binding, name) = match &field.ident {
+                Some(ident) => (quote!(#ident), ident.clone(), ident.to_string()),
+                None => {
+                    let index = syn::Index::from(i);
+                    (quote!(#index), format_ident!("__field{}", i), i.to_string())
+                }
+            };
+            Ok(Field {
+                member,
+                binding,
+                name: options.rename.unwrap_or(name),
+                ty: field.ty.clone(),
+                skip: options.skip,
+            })
+        })
+        .collect()
+}
+
+fn all_fields(input: &DeriveInput) -> syn::Result<Vec<Field>> {
+    match &input.data {
+        Data::Struct(data) => collect_fields(&data.fields),
+        Data::Enum(data) => {
+            let mut fields = vec![];
+            for variant in data.variants.iter() {
+                fields.extend(collect_fields(&variant.fields)?);
+            }
+            Ok(fields)
+        }
+        Data::Union(_) => Ok(vec![]),
+    }
+}
+
+/// Adds the data type parameter and requires every converted field type to implement `bound`.
+///
+/// Field types that refer to the type being derived are left out, so recursive types don't produce cyclic bounds.
+fn impl_generics(input: &DeriveInput, krate: &Path, bound: TokenStream2) -> syn::Result<syn::Generics> {
+    let mut generics = input.generics.clone();
+    generics.params.push(parse_quote!(__D));
+    let where_clause = generics.make_where_clause();
+    where_clause.predicates.push(parse_quote!(__D: #krate::GarnishData));
+
+    let own_name = input.ident.to_string();
+    let mut bounded: Vec<String> = vec![];
+    for field in all_fields(input)?.iter().filter(|f| !f.skip) {
+        let ty = field.conversion_type(krate);
+        let ty_string = ty.to_token_stream().to_string();
+        let refers_to_self = ty.to_token_stream().into_iter().any(|token| token.to_string() == own_name);
+        if refers_to_self || bounded.contains(&ty_string) {
+            continue;
+        }
+        bounded.push(ty_string);
+        where_clause.predicates.push(parse_quote!(#ty: #bound));
+    }
+
+    Ok(generics)
+}
+
+fn into_value(krate: &Path, field: &Field, value: TokenStream2) -> TokenStream2 {
+    match is_byte_vec(&field.ty) {
+        true => quote!(#krate::IntoGarnish::<__D>::into_garnish(#krate::ByteList(#value), data)?),
+        false => quote!(#krate::IntoGarnish::<__D>::into_garnish(#value, data)?),
+    }
+}
+
+/// Builds the expression adding a set of fields, where `access` produces the expression holding each field's value.
+fn into_fields(krate: &Path, fields: &Fields, access: impl Fn(&Field) -> TokenStream2) -> syn::Result<TokenStream2> {
+    let collected = collect_fields(fields)?;
+    let included = collected.iter().filter(|f| !f.skip).collect::<Vec<_>>();
+    Ok(match fields {
+        Fields::Named(_) => {
+            let pairs = included.iter().map(|f| {
+                let name = &f.name;
+                let value = into_value(krate, f, access(f));
+                quote!({
+                    let __value = #value;
+                    #krate::convert::add_field(data, #name, __value)?
+                })
+            });
+            quote!({
+                let __items = vec![#(#pairs),*];
+                #krate::convert::add_list(data, __items)?
+            })
+        }
+        Fields::Unnamed(_) if included.len() == 1 => into_value(krate, included[0], access(included[0])),
+        Fields::Unnamed(_) => {
+            let items = included.iter().map(|f| into_value(krate, f, access(f)));
+            quote!({
+                let __items = vec![#(#items),*];
+                #krate::convert::add_list(data, __items)?
+            })
+        }
+        Fields::Unit => quote!(#krate::IntoGarnish::<__D>::into_garnish((), data)?),
+    })
+}
+
+fn from_value(krate: &Path, field: &Field, read: impl Fn(Type) -> TokenStream2) -> TokenStream2 {
+    let value = read(field.conversion_type(krate));
+    match is_byte_vec(&field.ty) {
+        true => quote!(#value.0),
+        false => value,
+    }
+}
+
+/// Builds the constructor for a set of fields read from the value at `addr`.
+fn from_fields(krate: &Path, constructor: TokenStream2, fields: &Fields, addr: TokenStream2) -> syn::Result<TokenStream2> {
+    let collected = collect_fields(fields)?;
+    let included = collected.iter().filter(|f| !f.skip).count();
+    let mut position = 0usize;
+    let values = collected
+        .iter()
+        .map(|f| {
+            let member = &f.member;
+            if f.skip {
+                return quote!(#member: ::core::default::Default::default());
+            }
+            let value = match fields {
+                Fields::Named(_) => {
+                    let name = &f.name;
+                    from_value(
+                        krate,
+                        f,
+                        |ty| quote!(#krate::convert::read_field::<__D, #ty>(data, #addr.clone(), #name)?),
+                    )
+                }
+                _ if included == 1 => from_value(
+                    krate,
+                    f,
+                    |ty| quote!(<#ty as #krate::FromGarnish<__D>>::from_garnish(data, #addr.clone())?),
+                ),
+                _ => {
+                    let item = position;
+                    position += 1;
+                    from_value(krate, f, |ty| quote!(#krate::convert::read_item::<__D, #ty>(data, #addr.clone(), #item)?))
+                }
+            };
+            quote!(#member: #value)
+        })
+        .collect::<Vec<_>>();
+
+    Ok(match fields {
+        Fields::Unit => quote!({
+            <() as #krate::FromGarnish<__D>>::from_garnish(data, #addr.clone())?;
+            #constructor
+        }),
+        _ => quote!(#constructor { #(#values),* }),
+    })
+}
+
+fn expand_into_garnish(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let krate = crate_path(&input)?;
+    let name = &input.ident;
+    let generics = impl_generics(&input, &krate, quote!(#krate::IntoGarnish<__D>))?;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => into_fields(&krate, &data.fields, |f| {
+            let member = &f.member;
+            quote!(self.#member)
+        })?,
+        Data::Enum(data) => {
+            let arms = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    let symbol = parse_options(&variant.attrs)?.rename.unwrap_or_else(|| ident.to_string());
+                    let fields = collect_fields(&variant.fields)?;
+                    let patterns = fields.iter().map(|f| {
+                        let (member, binding) = (&f.member, &f.binding);
+                        match &variant.fields {
+                            Fields::Named(_) => quote!(#binding),
+                            _ => quote!(#member: #binding),
+                        }
+                    });
+                    Ok(match &variant.fields {
+                        Fields::Unit => quote!(Self::#ident => #krate::convert::add_variant(data, #symbol, None)?,),
+                        _ => {
+                            let payload = into_fields(&krate, &variant.fields, |f| {
+                                let binding = &f.binding;
+                                quote!(#binding)
+                            })?;
+                            quote!(Self::#ident { #(#patterns),* } => {
+                                let __payload = #payload;
+                                #krate::convert::add_variant(data, #symbol, Some(__payload))?
+                            })
+                        }
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! {
+                #[allow(unused_variables)]
+                match self {
+                    #(#arms)*
+                }
+            }
+        }
+        Data::Union(_) => return Err(syn::Error::new(Span::call_site(), "IntoGarnish cannot be derived for unions")),
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #krate::IntoGarnish<__D> for #name #ty_generics #where_clause {
+            #[allow(clippy::needless_question_mark)]
+            fn into_garnish(self, data: &mut __D) -> Result<__D::Size, #krate::RuntimeError<__D::Error>> {
+                Ok(#body)
+            }
+        }
+    })
+}
+
+fn expand_from_garnish(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let krate = crate_path(&input)?;
+    let name = &input.ident;
+    let generics = impl_generics(&input, &krate, quote!(#krate::FromGarnish<__D>))?;
+    let (impl_generics, _, where_clause) = generics.split_for_impl();
+    let (_, ty_generics, _) = input.generics.split_for_impl();
+
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let value = from_fields(&krate, quote!(Self), &data.fields, quote!(addr))?;
+            quote!(Ok(#value))
+        }
+        Data::Enum(data) => {
+            let checks = data
+                .variants
+                .iter()
+                .map(|variant| {
+                    let ident = &variant.ident;
+                    let symbol = parse_options(&variant.attrs)?.rename.unwrap_or_else(|| ident.to_string());
+                    let value = match &variant.fields {
+                        Fields::Unit => quote!(Self::#ident),
+                        fields => {
+                            let value = from_fields(&krate, quote!(Self::#ident), fields, quote!(__payload))?;
+                            quote!({
+                                let __payload = #krate::convert::variant_payload::<__D>(__payload.clone())?;
+                                #value
+                            })
+                        }
+                    };
+                    Ok(quote! {
+                        if #krate::convert::is_variant::<__D>(&__symbol, #symbol)? {
+                            return Ok(#value);
+                        }
+                    })
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            quote! {
+                let (__symbol, __payload) = #krate::convert::read_variant(data, addr)?;
+                #(#checks)*
+                Err(#krate::convert::unknown_variant::<__D>(&__symbol))
+            }
+        }
+        Data::Union(_) => return Err(syn::Error::new(Span::call_site(), "FromGarnish cannot be derived for unions")),
+    };
+
+    Ok(quote! {
+        #[automatically_derived]
+        impl #impl_generics #krate::FromGarnish<__D> for #name #ty_generics #where_clause {
+            fn from_garnish(data: &__D, addr: __D::Size) -> Result<Self, #krate::RuntimeError<__D::Error>> {
+                #body
+            }
+        }
+    })
+}
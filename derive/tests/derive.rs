@@ -0,0 +1,232 @@
+use garnish_lang_simple_data::{BasicGarnishData, BasicGarnishDataUnit, BasicObject, NoOpCompanion, symbol_value};
+use garnish_lang_traits::convert::{read_field, read_variant};
+use garnish_lang_traits::{ByteList, FromGarnish, GarnishData, GarnishDataType, IntoGarnish};
+
+#[derive(Debug, Clone, PartialEq, IntoGarnish, FromGarnish)]
+struct Config {
+    name: String,
+    retries: i32,
+    ratio: f64,
+    enabled: bool,
+    tags: Vec<String>,
+    payload: Vec<u8>,
+    timeout: Option<i32>,
+    mode: Mode,
+}
+
+#[derive(Debug, Clone, PartialEq, IntoGarnish, FromGarnish)]
+enum Mode {
+    Fast,
+    #[garnish(rename = "slow")]
+    Slow,
+    Limited(i32),
+    Between(i32, i32),
+    Custom {
+        label: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, IntoGarnish, FromGarnish)]
+struct Meters(f64);
+
+#[derive(Debug, Clone, PartialEq, IntoGarnish, FromGarnish)]
+struct Point(i32, i32);
+
+#[derive(Debug, Clone, PartialEq, IntoGarnish, FromGarnish)]
+struct Marker;
+
+#[derive(Debug, Clone, PartialEq, IntoGarnish, FromGarnish)]
+struct Renamed {
+    #[garnish(rename = "user_name")]
+    name: String,
+    #[garnish(skip)]
+    cached: i32,
+}
+
+#[derive(Debug, Clone, PartialEq, IntoGarnish, FromGarnish)]
+struct Wrapper<V> {
+    value: V,
+}
+
+#[derive(Debug, Clone, PartialEq, IntoGarnish, FromGarnish)]
+struct Tree {
+    value: i32,
+    children: Vec<Tree>,
+}
+
+fn data() -> BasicGarnishDataUnit {
+    BasicGarnishData::new(NoOpCompanion::new()).unwrap()
+}
+
+fn round_trip<V>(value: V) -> V
+where
+    V: IntoGarnish<BasicGarnishDataUnit> + FromGarnish<BasicGarnishDataUnit>,
+{
+    let mut data = data();
+    let index = value.into_garnish(&mut data).unwrap();
+    V::from_garnish(&data, index).unwrap()
+}
+
+fn config() -> Config {
+    Config {
+        name: "service".to_string(),
+        retries: 3,
+        ratio: 0.5,
+        enabled: true,
+        tags: vec!["a".to_string(), "b".to_string()],
+        payload: vec![1, 2, 3],
+        timeout: None,
+        mode: Mode::Between(1, 5),
+    }
+}
+
+#[test]
+fn struct_round_trip() {
+    assert_eq!(round_trip(config()), config());
+}
+
+#[test]
+fn struct_is_associative_list() {
+    let mut data = data();
+    let index = config().into_garnish(&mut data).unwrap();
+
+    assert_eq!(read_field::<_, String>(&data, index, "name").unwrap(), "service");
+    assert_eq!(read_field::<_, i32>(&data, index, "retries").unwrap(), 3);
+    assert_eq!(
+        read_field::<_, Vec<String>>(&data, index, "tags").unwrap(),
+        vec!["a".to_string(), "b".to_string()]
+    );
+}
+
+#[test]
+fn byte_vec_is_byte_list() {
+    let mut data = data();
+    let index = config().into_garnish(&mut data).unwrap();
+
+    assert_eq!(read_field::<_, ByteList>(&data, index, "payload").unwrap(), ByteList(vec![1, 2, 3]));
+}
+
+#[test]
+fn missing_optional_field() {
+    let mut data = data();
+    let object = BasicObject::List(vec![Box::new(BasicObject::Pair(
+        Box::new(BasicObject::Symbol(symbol_value("retries"))),
+        Box::new(BasicObject::Number(3.into())),
+    ))]);
+    let index = data.push_object_to_data_block(object).unwrap();
+
+    assert_eq!(read_field::<_, Option<i32>>(&data, index, "timeout").unwrap(), None);
+    assert_eq!(read_field::<_, i32>(&data, index, "retries").unwrap(), 3);
+}
+
+#[test]
+fn missing_required_field() {
+    let mut data = data();
+    let index = data.push_object_to_data_block(BasicObject::List(vec![])).unwrap();
+
+    let result = Config::from_garnish(&data, index);
+
+    assert_eq!(result.unwrap_err().get_message(), "Missing field 'name'");
+}
+
+#[test]
+fn enum_variants_round_trip() {
+    for mode in [
+        Mode::Fast,
+        Mode::Slow,
+        Mode::Limited(10),
+        Mode::Between(1, 2),
+        Mode::Custom { label: "x".to_string() },
+    ] {
+        assert_eq!(round_trip(mode.clone()), mode);
+    }
+}
+
+#[test]
+fn unit_variant_is_symbol() {
+    let mut data = data();
+    let fast = Mode::Fast.into_garnish(&mut data).unwrap();
+    let slow = Mode::Slow.into_garnish(&mut data).unwrap();
+
+    assert_eq!(read_variant(&data, fast).unwrap(), (symbol_value("Fast"), None));
+    assert_eq!(read_variant(&data, slow).unwrap(), (symbol_value("slow"), None));
+}
+
+#[test]
+fn data_variant_is_pair() {
+    let mut data = data();
+    let index = Mode::Limited(10).into_garnish(&mut data).unwrap();
+
+    let (symbol, payload) = read_variant(&data, index).unwrap();
+
+    assert_eq!(symbol, symbol_value("Limited"));
+    assert_eq!(i32::from_garnish(&data, payload.unwrap()).unwrap(), 10);
+}
+
+#[test]
+fn unknown_variant() {
+    let mut data = data();
+    let index = data.push_object_to_data_block(BasicObject::Symbol(symbol_value("Other"))).unwrap();
+
+    let result = Mode::from_garnish(&data, index);
+
+    assert_eq!(*result.unwrap_err().get_message(), format!("Unknown variant {}", symbol_value("Other")));
+}
+
+#[test]
+fn tuple_structs() {
+    let mut data = data();
+    let index = Meters(2.5).into_garnish(&mut data).unwrap();
+
+    assert_eq!(data.get_data_type(index).unwrap(), GarnishDataType::Number);
+    assert_eq!(round_trip(Meters(2.5)), Meters(2.5));
+    assert_eq!(round_trip(Point(1, 2)), Point(1, 2));
+}
+
+#[test]
+fn unit_struct() {
+    let mut data = data();
+    let index = Marker.into_garnish(&mut data).unwrap();
+
+    assert_eq!(data.get_data_type(index).unwrap(), GarnishDataType::Unit);
+    assert_eq!(round_trip(Marker), Marker);
+}
+
+#[test]
+fn rename_and_skip() {
+    let mut data = data();
+    let index = Renamed {
+        name: "user".to_string(),
+        cached: 10,
+    }
+    .into_garnish(&mut data)
+    .unwrap();
+
+    assert_eq!(read_field::<_, String>(&data, index, "user_name").unwrap(), "user");
+    assert_eq!(
+        Renamed::from_garnish(&data, index).unwrap(),
+        Renamed {
+            name: "user".to_string(),
+            cached: 0
+        }
+    );
+}
+
+#[test]
+fn generic_struct() {
+    let value = Wrapper {
+        value: vec![Wrapper { value: 1 }, Wrapper { value: 2 }],
+    };
+
+    assert_eq!(round_trip(value.clone()), value);
+}
+
+#[test]
+fn recursive_struct() {
+    let value = Tree {
+        value: 1,
+        children: vec![Tree { value: 2, children: vec![] }],
+    };
+
+    assert_eq!(round_trip(value.clone()), value);
+}
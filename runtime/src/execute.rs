@@ -1,9 +1,9 @@
 use crate::error::instruction_error;
 use crate::ops::{
-    absolute_value, access, access_left_internal, access_length_internal, access_right_internal, add, and, apply, bitwise_and, bitwise_left_shift, bitwise_not, bitwise_or, bitwise_right_shift,
-    bitwise_xor, concat, divide, empty_apply, end_expression, end_side_effect, equal, greater_than, greater_than_or_equal, integer_divide, jump, jump_if_false, jump_if_true, less_than,
-    less_than_or_equal, make_end_exclusive_range, make_exclusive_range, make_list, make_pair, make_range, make_start_exclusive_range, multiply, not, not_equal, opposite, or, partial_apply, power,
-    push_value, put, put_value, reapply, remainder, resolve, start_side_effect, subtract, tis, type_cast, type_equal, type_of, update_value, xor,
+    absolute_value, access, all, any, access_left_internal, access_length_internal, access_right_internal, add, and, apply, bitwise_and, bitwise_left_shift, bitwise_not, bitwise_or, bitwise_right_shift,
+    bitwise_xor, catch_error, concat, divide, empty_apply, end_catch, end_expression, end_side_effect, end_try, equal, filter, find, fold, greater_than, greater_than_or_equal, integer_divide, jump, jump_if_false, jump_if_true, less_than,
+    less_than_or_equal, make_closure, make_end_exclusive_range, make_exclusive_range, make_list, make_pair, make_range, make_start_exclusive_range, map, multiply, not, not_equal, opposite, or, partial_apply, power,
+    push_value, put, put_local, put_value, reapply, remainder, resolve, sort, start_iteration, start_side_effect, start_try, subtract, tis, type_cast, type_equal, type_of, update_local, update_value, xor,
 };
 use garnish_lang_traits::{GarnishData, Instruction, RuntimeError, TypeConstants};
 use log::trace;
@@ -32,7 +32,14 @@ impl SimpleRuntimeInfo {
     }
 }
 
-pub fn execute_current_instruction<Data: GarnishData>(data: &mut Data) -> Result<SimpleRuntimeInfo, RuntimeError<Data::Error>> {
+/// Executes the instruction at the cursor, handing errors to the latest try handler.
+///
+/// Caught error messages are added as char lists, so the data's characters need to be created from Rust's [`char`].
+pub fn execute_current_instruction<Data>(data: &mut Data) -> Result<SimpleRuntimeInfo, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
     let (instruction, instruction_data) = match data.get_instruction(data.get_instruction_cursor()) {
         None => return Ok(SimpleRuntimeInfo::new(SimpleRuntimeState::End)),
         Some(v) => v,
@@ -40,7 +47,37 @@ pub fn execute_current_instruction<Data: GarnishData>(data: &mut Data) -> Result
 
     trace!("Executing instruction {:?} at {:?} with data {:?}", instruction, data.get_instruction_cursor(), instruction_data);
 
-    let next_instruction = match instruction {
+    let cursor = data.get_instruction_cursor();
+    let next_instruction = match execute_instruction(data, instruction, instruction_data) {
+        Ok(next_instruction) => next_instruction,
+        Err(error) => catch_error(data, error, cursor)?,
+    };
+
+    let next_instruction = match next_instruction {
+        Some(i) => i,
+        None => data.get_instruction_cursor() + Data::Size::one(),
+    };
+
+    match next_instruction >= data.get_instruction_len() {
+        true => Ok(SimpleRuntimeInfo::new(SimpleRuntimeState::End)),
+        false => {
+            data.set_instruction_cursor(next_instruction)?;
+            data.safe_point()?;
+            Ok(SimpleRuntimeInfo::new(SimpleRuntimeState::Running))
+        }
+    }
+}
+
+fn execute_instruction<Data>(
+    data: &mut Data,
+    instruction: Instruction,
+    instruction_data: Option<Data::Size>,
+) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
+    Ok(match instruction {
         Instruction::Invalid => None,
         Instruction::Add => add(data)?,
         Instruction::Subtract => subtract(data)?,
@@ -87,6 +124,7 @@ pub fn execute_current_instruction<Data: GarnishData>(data: &mut Data) -> Result
         Instruction::EndExpression => end_expression(data)?,
         Instruction::Apply => apply(data)?,
         Instruction::PartialApply => partial_apply(data)?,
+        Instruction::MakeClosure => make_closure(data)?,
         Instruction::EmptyApply => empty_apply(data)?,
         Instruction::And => match instruction_data {
             None => instruction_error(instruction, data.get_instruction_cursor())?,
@@ -104,6 +142,14 @@ pub fn execute_current_instruction<Data: GarnishData>(data: &mut Data) -> Result
             None => instruction_error(instruction, data.get_instruction_cursor())?,
             Some(i) => make_list(data, i)?,
         },
+        Instruction::PutLocal => match instruction_data {
+            None => instruction_error(instruction, data.get_instruction_cursor())?,
+            Some(i) => put_local(data, i)?,
+        },
+        Instruction::UpdateLocal => match instruction_data {
+            None => instruction_error(instruction, data.get_instruction_cursor())?,
+            Some(i) => update_local(data, i)?,
+        },
         Instruction::Resolve => match instruction_data {
             None => instruction_error(instruction, data.get_instruction_cursor())?,
             Some(i) => resolve(data, i)?,
@@ -124,18 +170,19 @@ pub fn execute_current_instruction<Data: GarnishData>(data: &mut Data) -> Result
             None => instruction_error(instruction, data.get_instruction_cursor())?,
             Some(i) => jump(data, i)?,
         },
-    };
-
-    let next_instruction = match next_instruction {
-        Some(i) => i,
-        None => data.get_instruction_cursor() + Data::Size::one(),
-    };
-
-    match next_instruction >= data.get_instruction_len() {
-        true => Ok(SimpleRuntimeInfo::new(SimpleRuntimeState::End)),
-        false => {
-            data.set_instruction_cursor(next_instruction)?;
-            Ok(SimpleRuntimeInfo::new(SimpleRuntimeState::Running))
-        }
-    }
+        Instruction::StartTry => match instruction_data {
+            None => instruction_error(instruction, data.get_instruction_cursor())?,
+            Some(i) => start_try(data, i)?,
+        },
+        Instruction::EndTry => end_try(data)?,
+        Instruction::EndCatch => end_catch(data)?,
+        Instruction::StartIteration => start_iteration(data)?,
+        Instruction::Map => map(data)?,
+        Instruction::Filter => filter(data)?,
+        Instruction::Fold => fold(data)?,
+        Instruction::Any => any(data)?,
+        Instruction::All => all(data)?,
+        Instruction::Find => find(data)?,
+        Instruction::Sort => sort(data)?,
+    })
 }
@@ -1,12 +1,17 @@
 use crate::runtime::list::get_access_addr;
-use crate::runtime::utilities::{next_ref, push_unit};
+use crate::runtime::utilities::{next_ref, push_failure, push_missing};
 use garnish_lang_traits::{GarnishData, GarnishDataType, Instruction, RuntimeError};
 
-pub fn access<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+pub fn access<Data>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
     let right_addr = next_ref(this)?;
     let left_addr = next_ref(this)?;
 
-    match (this.get_data_type(left_addr.clone())?, this.get_data_type(right_addr.clone())?) {
+    let (left_type, right_type) = (this.get_data_type(left_addr.clone())?, this.get_data_type(right_addr.clone())?);
+    match (left_type, right_type) {
         (GarnishDataType::Symbol, GarnishDataType::Symbol)
         | (GarnishDataType::Symbol, GarnishDataType::SymbolList)
         | (GarnishDataType::SymbolList, GarnishDataType::Symbol)
@@ -28,13 +33,16 @@ pub fn access<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>,
         | (GarnishDataType::Concatenation, GarnishDataType::Number)
         | (GarnishDataType::Concatenation, GarnishDataType::Symbol)
         | (GarnishDataType::Slice, GarnishDataType::Number)
-        | (GarnishDataType::Slice, GarnishDataType::Symbol) => match get_access_addr(this, right_addr, left_addr)? {
-            None => push_unit(this)?,
+        | (GarnishDataType::Slice, GarnishDataType::Symbol)
+        | (GarnishDataType::Error, GarnishDataType::Symbol) => match get_access_addr(this, right_addr, left_addr)? {
+            None => push_missing(this, Instruction::Access, left_type, right_type)?,
             Some(i) => this.push_register(i)?,
         },
+        (GarnishDataType::Error, _) => this.push_register(left_addr)?,
+        (_, GarnishDataType::Error) => this.push_register(right_addr)?,
         (l, r) => {
             if !this.defer_op(Instruction::Access, (l, left_addr), (r, right_addr))? {
-                push_unit(this)?
+                push_failure(this, "access", format!("Could not access {:?} with {:?}", l, r))?
             }
         }
     }
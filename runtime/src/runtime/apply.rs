@@ -1,7 +1,11 @@
-use crate::runtime::error::state_error;
+use crate::runtime::error::{OrNumberError, state_error};
+use crate::runtime::internals::concatenation_len;
 use crate::runtime::list::{access_with_integer, access_with_symbol};
+use crate::runtime::range::{add_stepped_range, is_stepped_range, range_item, range_len, range_numbers, range_parts};
 use crate::runtime::utilities::*;
-use garnish_lang_traits::{Extents, GarnishData, GarnishDataType, GarnishNumber, Instruction, RuntimeError, SymbolListPart, TypeConstants};
+use garnish_lang_traits::{
+    Extents, GarnishData, GarnishDataFactory, GarnishDataType, GarnishNumber, Instruction, RuntimeError, SymbolListPart, TypeConstants,
+};
 use log::trace;
 
 pub fn apply<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
@@ -36,13 +40,40 @@ pub fn empty_apply<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Si
     apply_internal(this, Instruction::EmptyApply, false)
 }
 
+// sets a closure's captured values to the local slots of the newly pushed frame
+fn restore_captures<Data: GarnishData>(this: &mut Data, captures: Data::Size) -> Result<(), RuntimeError<Data::Error>> {
+    let mut slot = Data::Size::zero();
+
+    for item in this.get_list_item_iter(captures, Extents::new(Data::Number::zero(), Data::Number::max_value()))? {
+        this.set_local(slot.clone(), item)?;
+        slot += Data::Size::one();
+    }
+
+    Ok(())
+}
+
 fn apply_internal<Data: GarnishData>(this: &mut Data, instruction: Instruction, use_right: bool) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
     let right_addr = next_ref(this)?;
     let left_addr = next_ref(this)?;
 
     // currently, apply is responsible for advancing the instruction cursor itself
-    // assume default, apply expression will update to its value
-    let mut next_instruction = this.get_instruction_cursor() + Data::Size::one();
+    // expressions return to the instruction after this one
+    let next_instruction = this.get_instruction_cursor() + Data::Size::one();
+
+    Ok(Some(apply_value(this, left_addr, right_addr, instruction, use_right, next_instruction.clone())?.unwrap_or(next_instruction)))
+}
+
+/// Applies right to left, returning the instruction to jump to when left is an expression, in which case the pushed frame returns to the given point.
+/// When there is nothing to jump to, the result has already been pushed to the register.
+pub(crate) fn apply_value<Data: GarnishData>(
+    this: &mut Data,
+    left_addr: Data::Size,
+    right_addr: Data::Size,
+    instruction: Instruction,
+    use_right: bool,
+    return_point: Data::Size,
+) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    let mut next_instruction = None;
 
     match (this.get_data_type(left_addr.clone())?, this.get_data_type(right_addr.clone())?) {
         (GarnishDataType::Expression, _) => {
@@ -56,13 +87,26 @@ fn apply_internal<Data: GarnishData>(this: &mut Data, instruction: Instruction,
 
             trace!("Next instruction will be {:?}", n);
 
-            next_instruction = n;
+            next_instruction = Some(n);
 
             trace!("Pushing to value stack {:?}", right_addr);
             this.push_value_stack(right_addr)?;
 
-            trace!("Pushing point to jump path {:?} + 1", this.get_instruction_cursor());
-            this.push_frame(this.get_instruction_cursor() + Data::Size::one())?;
+            trace!("Pushing point to jump path {:?}", return_point);
+            this.push_frame(return_point)?;
+        }
+        (GarnishDataType::Closure, _) => {
+            let (expression, captures) = this.get_closure(left_addr)?;
+            let expression_index = this.get_expression(expression)?;
+
+            next_instruction = match this.get_from_jump_table(expression_index.clone()) {
+                None => state_error(format!("No jump point at index {:?}", expression_index))?,
+                Some(i) => Some(i),
+            };
+
+            this.push_value_stack(right_addr)?;
+            this.push_frame(return_point)?;
+            restore_captures(this, captures)?;
         }
         (GarnishDataType::External, _) => {
             let external_value = this.get_external(left_addr)?;
@@ -75,9 +119,18 @@ fn apply_internal<Data: GarnishData>(this: &mut Data, instruction: Instruction,
             }
         }
         (GarnishDataType::Partial, _) => {
-            let (expression, input) = this.get_partial(left_addr)?;
-            match this.get_data_type(expression.clone())? {
-                GarnishDataType::Expression => {
+            let (receiver, input) = this.get_partial(left_addr)?;
+            let (expression, captures) = match this.get_data_type(receiver.clone())? {
+                GarnishDataType::Expression => (Some(receiver), None),
+                GarnishDataType::Closure => {
+                    let (expression, captures) = this.get_closure(receiver)?;
+                    (Some(expression), Some(captures))
+                }
+                _ => (None, None),
+            };
+
+            match expression {
+                Some(expression) => {
                     let value = if use_right { this.add_concatenation(input, right_addr)? } else { input };
                     this.push_value_stack(value)?;
 
@@ -87,10 +140,14 @@ fn apply_internal<Data: GarnishData>(this: &mut Data, instruction: Instruction,
                         Some(i) => i,
                     };
 
-                    next_instruction = n;
-                    this.push_frame(this.get_instruction_cursor() + Data::Size::one())?;
+                    next_instruction = Some(n);
+                    this.push_frame(return_point)?;
+
+                    if let Some(captures) = captures {
+                        restore_captures(this, captures)?;
+                    }
                 }
-                _ => this.add_unit().and_then(|i| this.push_register(i))?,
+                None => this.add_unit().and_then(|i| this.push_register(i))?,
             }
         }
         (GarnishDataType::Symbol, GarnishDataType::SymbolList) | (GarnishDataType::SymbolList, GarnishDataType::Symbol) | (GarnishDataType::SymbolList, GarnishDataType::SymbolList) => {
@@ -100,38 +157,43 @@ fn apply_internal<Data: GarnishData>(this: &mut Data, instruction: Instruction,
             let addr = narrow_range(this, left_addr, right_addr)?;
             this.push_register(addr)?;
         }
-        (GarnishDataType::Slice, GarnishDataType::Range) => {
+        (GarnishDataType::Slice, GarnishDataType::Range) if !is_stepped_range(this, right_addr.clone())? => {
             // create new slice by narrowing this give range
             let (value, slice_range) = this.get_slice(left_addr)?;
             let range_addr = narrow_range(this, slice_range, right_addr)?;
             let addr = this.add_slice(value, range_addr)?;
             this.push_register(addr)?;
         }
-        (GarnishDataType::SymbolList, GarnishDataType::Number) | (GarnishDataType::List, GarnishDataType::Number) => {
+        (GarnishDataType::SymbolList, GarnishDataType::Number)
+        | (GarnishDataType::List, GarnishDataType::Number)
+        | (GarnishDataType::Range, GarnishDataType::Number) => {
             let num = this.get_number(right_addr)?;
-            match access_with_integer(this, num, left_addr)? {
-                None => push_unit(this)?,
+            match access_with_integer(this, num, left_addr.clone())? {
+                None => {
+                    let left_type = this.get_data_type(left_addr)?;
+                    push_missing(this, instruction, left_type, GarnishDataType::Number)?
+                }
                 Some(i) => this.push_register(i)?,
             }
         }
         (GarnishDataType::Pair, GarnishDataType::Number) => {
             let num = this.get_number(right_addr)?;
             match access_with_integer(this, num, left_addr)? {
-                None => push_unit(this)?,
+                None => push_missing(this, instruction, GarnishDataType::Pair, GarnishDataType::Number)?,
                 Some(i) => this.push_register(i)?,
             }
         }
         (GarnishDataType::Pair, GarnishDataType::Symbol) => {
             let sym = this.get_symbol(right_addr)?;
             match access_with_symbol(this, sym, left_addr)? {
-                None => push_unit(this)?,
+                None => push_missing(this, instruction, GarnishDataType::Pair, GarnishDataType::Symbol)?,
                 Some(i) => this.push_register(i)?,
             }
         }
         (GarnishDataType::List, GarnishDataType::Symbol) => {
             let sym = this.get_symbol(right_addr)?;
             match access_with_symbol(this, sym, left_addr)? {
-                None => push_unit(this)?,
+                None => push_missing(this, instruction, GarnishDataType::List, GarnishDataType::Symbol)?,
                 Some(i) => this.push_register(i)?,
             }
         }
@@ -167,51 +229,143 @@ fn apply_internal<Data: GarnishData>(this: &mut Data, instruction: Instruction,
         | (GarnishDataType::Concatenation, GarnishDataType::Range)
         | (GarnishDataType::CharList, GarnishDataType::Range)
         | (GarnishDataType::ByteList, GarnishDataType::Range)
-        | (GarnishDataType::SymbolList, GarnishDataType::Range) => {
-            // create slice
+        | (GarnishDataType::SymbolList, GarnishDataType::Range)
+            if !is_stepped_range(this, right_addr.clone())? =>
+        {
+            // create slice, slices only cover contiguous items
             let addr = this.add_slice(left_addr, right_addr)?;
             this.push_register(addr)?;
         }
+        (GarnishDataType::List, GarnishDataType::Range)
+        | (GarnishDataType::Concatenation, GarnishDataType::Range)
+        | (GarnishDataType::CharList, GarnishDataType::Range)
+        | (GarnishDataType::ByteList, GarnishDataType::Range)
+        | (GarnishDataType::SymbolList, GarnishDataType::Range)
+        | (GarnishDataType::Slice, GarnishDataType::Range) => {
+            let addr = stepped_items(this, left_addr, right_addr)?;
+            this.push_register(addr)?;
+        }
         (l, r) => {
             if !this.defer_op(instruction, (l, left_addr), (r, right_addr))? {
-                push_unit(this)?
+                push_unsupported(this, instruction, &[l, r])?
             }
         }
     }
 
-    Ok(Some(next_instruction))
+    Ok(next_instruction)
 }
 
-pub(crate) fn narrow_range<Data: GarnishData>(this: &mut Data, to_narrow: Data::Size, by: Data::Size) -> Result<Data::Size, RuntimeError<Data::Error>> {
-    let (start, end) = this.get_range(by)?;
-    let (old_start, _) = this.get_range(to_narrow)?;
-
-    match (this.get_data_type(start.clone())?, this.get_data_type(end.clone())?, this.get_data_type(old_start.clone())?) {
-        (GarnishDataType::Number, GarnishDataType::Number, GarnishDataType::Number) => {
-            let (start_int, end_int, old_start_int) = (this.get_number(start)?, this.get_number(end)?, this.get_number(old_start)?);
-
-            match (old_start_int.plus(start_int.clone()), end_int.subtract(start_int)) {
-                (Some(new_start), Some(adjusted_end)) => {
-                    // end is always len away from start
-                    // offset end by same amount as start
-                    match new_start.clone().plus(adjusted_end.clone()) {
-                        Some(new_end) => {
-                            let start_addr = this.add_number(new_start.clone())?;
-                            let end_addr = this.add_number(new_end)?;
-                            let range_addr = this.add_range(start_addr, end_addr)?;
-
-                            Ok(range_addr)
-                        }
-                        _ => state_error(format!("Could not narrow range."))?,
+/// Collects the items of value at the indexes of a stepped range, since slices only cover contiguous items.
+///
+/// Char lists and byte lists, or slices of them, produce the same type of list. Other values produce a list.
+fn stepped_items<Data: GarnishData>(this: &mut Data, value: Data::Size, range: Data::Size) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    let (start, end, step) = match range_parts(this, range)? {
+        Some(parts) => parts,
+        None => return Ok(this.add_unit()?),
+    };
+
+    let zero = Data::Number::zero();
+    let count = item_count(this, value.clone())?;
+    let len = range_len::<Data>(start.clone(), end, step.clone())?;
+
+    let mut items = vec![];
+    for index in range_numbers::<Data>(start, step.clone(), len) {
+        let index = index?;
+        if index < zero || index >= count {
+            // once out of bounds, further indexes only move away from the items
+            match (step > zero) == (index >= zero) {
+                true => break,
+                false => continue,
+            }
+        }
+
+        if let Some(item) = access_with_integer(this, index, value.clone())? {
+            items.push(item);
+        }
+    }
+
+    let source_type = match this.get_data_type(value.clone())? {
+        GarnishDataType::Slice => {
+            let (source, _) = this.get_slice(value)?;
+            this.get_data_type(source)?
+        }
+        t => t,
+    };
+
+    match source_type {
+        GarnishDataType::CharList => {
+            let chars = items.into_iter().map(|item| this.get_char(item)).collect::<Result<Vec<_>, _>>()?;
+            Ok(this.add_char_list(chars)?)
+        }
+        GarnishDataType::ByteList => {
+            let bytes = items.into_iter().map(|item| this.get_byte(item)).collect::<Result<Vec<_>, _>>()?;
+            Ok(this.add_byte_list(bytes)?)
+        }
+        _ => {
+            let mut len = Data::Size::zero();
+            for _ in &items {
+                len += Data::Size::one();
+            }
+
+            let mut list = this.start_list(len)?;
+            for item in items {
+                list = this.add_to_list(list, item)?;
+            }
+            Ok(this.end_list(list)?)
+        }
+    }
+}
+
+// number of items that can be accessed by index, slices are limited to the items of their value
+fn item_count<Data: GarnishData>(this: &mut Data, addr: Data::Size) -> Result<Data::Number, RuntimeError<Data::Error>> {
+    let len = match this.get_data_type(addr.clone())? {
+        GarnishDataType::List => this.get_list_len(addr)?,
+        GarnishDataType::CharList => this.get_char_list_len(addr)?,
+        GarnishDataType::ByteList => this.get_byte_list_len(addr)?,
+        GarnishDataType::SymbolList => this.get_symbol_list_len(addr)?,
+        GarnishDataType::Concatenation => concatenation_len(this, addr)?,
+        GarnishDataType::Slice => {
+            let (value, range) = this.get_slice(addr)?;
+            let available = item_count(this, value)?;
+
+            return match range_parts(this, range)? {
+                Some((start, end, step)) => {
+                    let available = available.subtract(start.clone()).or_num_err()?;
+                    let len = range_len::<Data>(start, end, step)?;
+                    match len < available {
+                        true => Ok(len),
+                        false => Ok(available),
                     }
                 }
-                _ => state_error(format!("Could not narrow range."))?,
-            }
+                None => Ok(Data::Number::zero()),
+            };
+        }
+        _ => Data::Size::zero(),
+    };
+
+    Ok(Data::DataFactory::size_to_number(len))
+}
+
+pub(crate) fn narrow_range<Data: GarnishData>(this: &mut Data, to_narrow: Data::Size, by: Data::Size) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    match (range_parts(this, to_narrow.clone())?, range_parts(this, by.clone())?) {
+        (Some((old_start, _, old_step)), Some((start, end, step))) => {
+            // narrowing range holds indexes into the range being narrowed
+            let new_start = range_item::<Data>(old_start.clone(), old_step.clone(), start)?;
+            let new_end = range_item::<Data>(old_start, old_step.clone(), end)?;
+            let new_step = old_step.multiply(step).or_num_err()?;
+
+            add_stepped_range(this, new_start, new_end, new_step)
+        }
+        _ => {
+            let range = this.get_stepped_range(by)?;
+            let old_start = this.get_stepped_range(to_narrow)?.start;
+            state_error(format!(
+                "Attempting to create slice from slice with an invalid range. Slice range starting with {:?}. Range {:?} {:?}",
+                this.get_data_type(old_start)?,
+                this.get_data_type(range.start)?,
+                this.get_data_type(range.end)?
+            ))?
         }
-        (s1, e1, s2) => state_error(format!(
-            "Attempting to create slice from slice with an invalid range. Slice range starting with {:?}. Range {:?} {:?}",
-            s2, s1, e1
-        ))?,
     }
 }
 
@@ -259,7 +413,7 @@ mod data_apply {
 #[cfg(test)]
 mod tests {
     use crate::runtime::apply::{apply, empty_apply};
-    use crate::runtime::tests::{MockGarnishData, MockSymbolListPartIterator};
+    use crate::runtime::tests::{MockGarnishData, MockIterator, MockSymbolListPartIterator};
     use garnish_lang_traits::{GarnishDataType, SymbolListPart};
 
     #[test]
@@ -575,6 +729,100 @@ mod tests {
         assert_eq!(result, Some(3000));
     }
 
+    #[test]
+    fn apply_closure() {
+        let mut mock_data = MockGarnishData::new_basic_data(vec![GarnishDataType::Expression, GarnishDataType::List, GarnishDataType::Closure, GarnishDataType::Number]);
+
+        mock_data.stub_get_closure = |_, i| {
+            assert_eq!(i, 2);
+            Ok((0, 1))
+        };
+        mock_data.stub_get_expression = |_, i| {
+            assert_eq!(i, 0);
+            Ok(200)
+        };
+        mock_data.stub_get_jump_point = |_, index| {
+            assert_eq!(index, 200);
+            Some(3000)
+        };
+        mock_data.stub_push_value_stack = |_, i| {
+            assert_eq!(i, 3);
+            Ok(())
+        };
+        mock_data.stub_get_instruction_cursor = |_| 123;
+        mock_data.stub_push_jump_path = |_, i| {
+            assert_eq!(i, 124);
+            Ok(())
+        };
+        mock_data.stub_get_list_item_iter = |_, addr| {
+            assert_eq!(addr, 1);
+            MockIterator::new_range(10, 12)
+        };
+        mock_data.stub_set_local = |_, index, addr| {
+            assert_eq!(index + 10, addr);
+            Ok(())
+        };
+
+        let result = apply(&mut mock_data).unwrap();
+
+        assert_eq!(result, Some(3000));
+    }
+
+    #[test]
+    fn apply_partial_closure() {
+        let mut mock_data = MockGarnishData::new_basic_data(vec![
+            GarnishDataType::Expression,
+            GarnishDataType::List,
+            GarnishDataType::Closure,
+            GarnishDataType::Number,
+            GarnishDataType::Partial,
+            GarnishDataType::Number,
+        ]);
+
+        mock_data.stub_get_partial = |_, i| {
+            assert_eq!(i, 4);
+            Ok((2, 3))
+        };
+        mock_data.stub_get_closure = |_, i| {
+            assert_eq!(i, 2);
+            Ok((0, 1))
+        };
+        mock_data.stub_get_expression = |_, i| {
+            assert_eq!(i, 0);
+            Ok(200)
+        };
+        mock_data.stub_add_concatenation = |_, left, right| {
+            assert_eq!(3, left);
+            assert_eq!(5, right);
+            Ok(100)
+        };
+        mock_data.stub_get_jump_point = |_, index| {
+            assert_eq!(index, 200);
+            Some(3000)
+        };
+        mock_data.stub_push_value_stack = |_, i| {
+            assert_eq!(i, 100);
+            Ok(())
+        };
+        mock_data.stub_get_instruction_cursor = |_| 123;
+        mock_data.stub_push_jump_path = |_, i| {
+            assert_eq!(i, 124);
+            Ok(())
+        };
+        mock_data.stub_get_list_item_iter = |_, addr| {
+            assert_eq!(addr, 1);
+            MockIterator::new_range(10, 12)
+        };
+        mock_data.stub_set_local = |_, index, addr| {
+            assert_eq!(index + 10, addr);
+            Ok(())
+        };
+
+        let result = apply(&mut mock_data).unwrap();
+
+        assert_eq!(result, Some(3000));
+    }
+
     #[test]
     fn empty_apply_partial_expression() {
         let mut mock_data = MockGarnishData::new_basic_data(vec![GarnishDataType::Expression, GarnishDataType::Number, GarnishDataType::Partial, GarnishDataType::Unit]);
@@ -630,6 +878,7 @@ mod slice {
             assert_eq!(num, 1);
             Ok(1)
         };
+        mock_data.stub_get_range = |_, _| Ok((5, 6));
         mock_data.stub_get_symbol_list_len = |_, _| Ok(2);
         mock_data.stub_get_symbol_list_item = |_, _, index| Ok(Some(SymbolListPart::Symbol((index + 1) as u32 * 100u32)));
         mock_data.stub_add_slice = |_, list, range| {
@@ -1,49 +1,46 @@
 use garnish_lang_traits::Instruction;
 use log::trace;
 
-use crate::runtime::utilities::{next_ref, next_two_raw_ref, push_number, push_unit};
-use garnish_lang_traits::{GarnishData, GarnishDataType, GarnishNumber, RuntimeError, TypeConstants};
+use crate::runtime::utilities::{next_ref, next_two_raw_ref, push_number, push_undefined, push_unsupported};
+use garnish_lang_traits::{GarnishData, GarnishDataType, RuntimeError, TypeConstants};
 
 pub fn add<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::Add, Data::Number::plus)
+    perform_op(this, Instruction::Add)
 }
 
 pub fn subtract<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::Subtract, Data::Number::subtract)
+    perform_op(this, Instruction::Subtract)
 }
 
 pub fn multiply<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::Multiply, Data::Number::multiply)
+    perform_op(this, Instruction::Multiply)
 }
 
 pub fn power<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::Power, Data::Number::power)
+    perform_op(this, Instruction::Power)
 }
 
 pub fn divide<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::Divide, Data::Number::divide)
+    perform_op(this, Instruction::Divide)
 }
 
 pub fn integer_divide<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::IntegerDivide, Data::Number::integer_divide)
+    perform_op(this, Instruction::IntegerDivide)
 }
 
 pub fn remainder<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::Remainder, Data::Number::remainder)
+    perform_op(this, Instruction::Remainder)
 }
 
 pub fn absolute_value<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_unary_op(this, Instruction::AbsoluteValue, Data::Number::absolute_value)
+    perform_unary_op(this, Instruction::AbsoluteValue)
 }
 
 pub fn opposite<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_unary_op(this, Instruction::Opposite, Data::Number::opposite)
+    perform_unary_op(this, Instruction::Opposite)
 }
 
-pub(crate) fn perform_unary_op<Data: GarnishData, Op>(this: &mut Data, op_name: Instruction, op: Op) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>
-where
-    Op: FnOnce(Data::Number) -> Option<Data::Number>,
-{
+pub(crate) fn perform_unary_op<Data: GarnishData>(this: &mut Data, op_name: Instruction) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
     let addr = next_ref(this)?;
 
     let t = this.get_data_type(addr.clone())?;
@@ -53,14 +50,15 @@ where
         GarnishDataType::Number => {
             let value = this.get_number(addr)?;
 
-            match op(value) {
+            match this.unary_number_op(op_name, value) {
                 Some(result) => push_number(this, result)?,
-                None => push_unit(this)?,
+                None => push_undefined(this, op_name, &[t])?,
             }
         }
+        GarnishDataType::Error => this.push_register(addr)?,
         l => {
             if !this.defer_op(op_name, (l, addr), (GarnishDataType::Unit, Data::Size::zero()))? {
-                push_unit(this)?
+                push_unsupported(this, op_name, &[l])?
             }
         }
     }
@@ -68,10 +66,7 @@ where
     Ok(None)
 }
 
-pub(crate) fn perform_op<Data: GarnishData, Op>(this: &mut Data, op_name: Instruction, op: Op) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>
-where
-    Op: FnOnce(Data::Number, Data::Number) -> Option<Data::Number>,
-{
+pub(crate) fn perform_op<Data: GarnishData>(this: &mut Data, op_name: Instruction) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
     let (right_addr, left_addr) = next_two_raw_ref(this)?;
 
     let types = (this.get_data_type(left_addr.clone())?, this.get_data_type(right_addr.clone())?);
@@ -82,14 +77,16 @@ where
             let left = this.get_number(left_addr)?;
             let right = this.get_number(right_addr)?;
 
-            match op(left, right) {
+            match this.number_op(op_name, left, right) {
                 Some(result) => push_number(this, result)?,
-                None => push_unit(this)?,
+                None => push_undefined(this, op_name, &[types.0, types.1])?,
             }
         }
+        (GarnishDataType::Error, _) => this.push_register(left_addr)?,
+        (_, GarnishDataType::Error) => this.push_register(right_addr)?,
         (l, r) => {
             if !this.defer_op(op_name, (l, left_addr), (r, right_addr))? {
-                push_unit(this)?
+                push_unsupported(this, op_name, &[l, r])?
             }
         }
     }
@@ -1,28 +1,28 @@
 use crate::runtime::arithmetic::{perform_op, perform_unary_op};
-use garnish_lang_traits::{GarnishData, GarnishNumber, Instruction, RuntimeError};
+use garnish_lang_traits::{GarnishData, Instruction, RuntimeError};
 
 pub fn bitwise_not<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_unary_op(this, Instruction::BitwiseNot, Data::Number::bitwise_not)
+    perform_unary_op(this, Instruction::BitwiseNot)
 }
 
 pub fn bitwise_and<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::BitwiseAnd, Data::Number::bitwise_and)
+    perform_op(this, Instruction::BitwiseAnd)
 }
 
 pub fn bitwise_or<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::BitwiseOr, Data::Number::bitwise_or)
+    perform_op(this, Instruction::BitwiseOr)
 }
 
 pub fn bitwise_xor<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::BitwiseXor, Data::Number::bitwise_xor)
+    perform_op(this, Instruction::BitwiseXor)
 }
 
 pub fn bitwise_left_shift<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::BitwiseShiftLeft, Data::Number::bitwise_shift_left)
+    perform_op(this, Instruction::BitwiseShiftLeft)
 }
 
 pub fn bitwise_right_shift<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_op(this, Instruction::BitwiseShiftRight, Data::Number::bitwise_shift_right)
+    perform_op(this, Instruction::BitwiseShiftRight)
 }
 
 #[cfg(test)]
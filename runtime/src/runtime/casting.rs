@@ -1,9 +1,10 @@
 use garnish_lang_traits::{Extents, Instruction, SymbolListPart};
 use garnish_lang_traits::helpers::iterate_concatenation_mut;
 
-use crate::runtime::error::OrNumberError;
+use crate::runtime::error::{OrNumberError, state_error};
+use crate::runtime::range::{range_len, range_numbers, range_parts};
 use crate::runtime::internals::concatenation_len;
-use crate::runtime::utilities::{get_range, next_ref, next_two_raw_ref, push_unit};
+use crate::runtime::utilities::{get_range, next_ref, next_two_raw_ref, push_failure, push_unit};
 use garnish_lang_traits::{GarnishData, GarnishDataFactory, GarnishDataType, GarnishNumber, RuntimeError, TypeConstants};
 
 pub fn type_of<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
@@ -14,7 +15,11 @@ pub fn type_of<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>,
     Ok(None)
 }
 
-pub fn type_cast<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+pub fn type_cast<Data>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
     let (right, left) = next_two_raw_ref(this)?;
 
     let (left_type, mut right_type) = (this.get_data_type(left.clone())?, this.get_data_type(right.clone().clone())?);
@@ -30,7 +35,25 @@ pub fn type_cast<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size
 
         // Casts that defer to data object and only expect an addr to push
         (GarnishDataType::CharList, GarnishDataType::Number) => {
-            this.add_number_from(left).and_then(|r| this.push_register(r))?;
+            let addr = this.add_number_from(left)?;
+            match this.get_data_type(addr.clone())? {
+                GarnishDataType::Unit => push_cast_failure(this, left_type, right_type)?,
+                _ => this.push_register(addr)?,
+            }
+        }
+        // Concatenations join their items' char list forms, used to build interpolated char lists
+        (GarnishDataType::Concatenation, GarnishDataType::CharList) => {
+            let mut chars = vec![];
+            iterate_concatenation_mut(this, left, |this, _, addr| {
+                let addr = match this.get_data_type(addr.clone())? {
+                    GarnishDataType::CharList => addr,
+                    _ => this.add_char_list_from(addr)?,
+                };
+                chars.extend(this.get_char_list_iter(addr, Extents::new(Data::Number::zero(), Data::Number::max_value()))?);
+                Ok(None)
+            })?;
+
+            this.add_char_list(chars).and_then(|r| this.push_register(r))?;
         }
         (_, GarnishDataType::CharList) => {
             this.add_char_list_from(left).and_then(|r| this.push_register(r))?;
@@ -43,22 +66,22 @@ pub fn type_cast<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size
         }
         // Primitives
         (GarnishDataType::Number, GarnishDataType::Char) => {
-            primitive_cast(this, left, Data::get_number, <Data as GarnishData>::DataFactory::number_to_char, Data::add_char)?;
+            primitive_cast(this, left, GarnishDataType::Char, Data::get_number, <Data as GarnishData>::DataFactory::number_to_char, Data::add_char)?;
         }
         (GarnishDataType::Number, GarnishDataType::Byte) => {
-            primitive_cast(this, left, Data::get_number, <Data as GarnishData>::DataFactory::number_to_byte, Data::add_byte)?;
+            primitive_cast(this, left, GarnishDataType::Byte, Data::get_number, <Data as GarnishData>::DataFactory::number_to_byte, Data::add_byte)?;
         }
         (GarnishDataType::Char, GarnishDataType::Number) => {
-            primitive_cast(this, left, Data::get_char, <Data as GarnishData>::DataFactory::char_to_number, Data::add_number)?;
+            primitive_cast(this, left, GarnishDataType::Number, Data::get_char, <Data as GarnishData>::DataFactory::char_to_number, Data::add_number)?;
         }
         (GarnishDataType::Char, GarnishDataType::Byte) => {
-            primitive_cast(this, left, Data::get_char, <Data as GarnishData>::DataFactory::char_to_byte, Data::add_byte)?;
+            primitive_cast(this, left, GarnishDataType::Byte, Data::get_char, <Data as GarnishData>::DataFactory::char_to_byte, Data::add_byte)?;
         }
         (GarnishDataType::Byte, GarnishDataType::Number) => {
-            primitive_cast(this, left, Data::get_byte, <Data as GarnishData>::DataFactory::byte_to_number, Data::add_number)?;
+            primitive_cast(this, left, GarnishDataType::Number, Data::get_byte, <Data as GarnishData>::DataFactory::byte_to_number, Data::add_number)?;
         }
         (GarnishDataType::Byte, GarnishDataType::Char) => {
-            primitive_cast(this, left, Data::get_byte, <Data as GarnishData>::DataFactory::byte_to_char, Data::add_char)?;
+            primitive_cast(this, left, GarnishDataType::Char, Data::get_byte, <Data as GarnishData>::DataFactory::byte_to_char, Data::add_char)?;
         }
         (GarnishDataType::CharList, GarnishDataType::Char) => {
             let len = this.get_char_list_len(left.clone())?;
@@ -66,10 +89,10 @@ pub fn type_cast<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size
                 let mut iter = this.get_char_list_iter(left.clone(), Extents::new(Data::Number::zero(), Data::Number::max_value()))?;
                 match iter.next() {
                     Some(c) => this.add_char(c).and_then(|r| this.push_register(r))?,
-                    None => push_unit(this)?,
+                    None => push_cast_failure(this, left_type, right_type)?,
                 }
             } else {
-                push_unit(this)?;
+                push_cast_failure(this, left_type, right_type)?;
             }
         }
         (GarnishDataType::SymbolList, GarnishDataType::List) => {
@@ -87,16 +110,16 @@ pub fn type_cast<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size
             this.end_list(list_index).and_then(|r| this.push_register(r))?
         }
         (GarnishDataType::Range, GarnishDataType::List) => {
-            let (start, end) = this.get_range(left.clone())?;
-            let len = end - start + Data::Size::one();
-            let (start, end, _) = get_range(this, left)?;
-            let mut count = start;
-
-            let mut list_index = this.start_list(len)?;
-            while count <= end {
-                let addr = this.add_number(count.clone())?;
+            let (start, end, step) = match range_parts(this, left)? {
+                Some(parts) => parts,
+                None => state_error("Invalid range values for cast to List".to_string())?,
+            };
+            let len = range_len::<Data>(start.clone(), end, step.clone())?;
+
+            let mut list_index = this.start_list(Data::DataFactory::number_to_size(len.clone()).or_num_err()?)?;
+            for number in range_numbers::<Data>(start, step, len) {
+                let addr = this.add_number(number?)?;
                 list_index = this.add_to_list(list_index.clone(), addr)?;
-                count = count.increment().or_num_err()?;
             }
 
             this.end_list(list_index).and_then(|r| this.push_register(r))?
@@ -183,11 +206,12 @@ pub fn type_cast<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size
 
         // Final Catches
         (GarnishDataType::Unit, _) => push_unit(this)?,
+        (GarnishDataType::Error, _) => this.push_register(left)?,
         (_, GarnishDataType::False) => this.add_false().and_then(|r| this.push_register(r))?,
         (_, GarnishDataType::True) => this.add_true().and_then(|r| this.push_register(r))?,
         (l, r) => {
             if !this.defer_op(Instruction::ApplyType, (l, left), (r, right))? {
-                push_unit(this)?
+                push_cast_failure(this, l, r)?
             }
         }
     }
@@ -237,9 +261,18 @@ pub(crate) fn list_from_byte_list<Data: GarnishData>(this: &mut Data, byte_list_
     Ok(None)
 }
 
+fn push_cast_failure<Data>(this: &mut Data, from: GarnishDataType, to: GarnishDataType) -> Result<(), RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
+    push_failure(this, "cast", format!("Could not cast {:?} to {:?}", from, to))
+}
+
 pub(crate) fn primitive_cast<Data: GarnishData, From, To, GetFunc, CastFunc, AddFunc>(
     this: &mut Data,
     addr: Data::Size,
+    to: GarnishDataType,
     get: GetFunc,
     cast: CastFunc,
     add: AddFunc,
@@ -248,14 +281,18 @@ where
     GetFunc: Fn(&Data, Data::Size) -> Result<From, Data::Error>,
     CastFunc: Fn(From) -> Option<To>,
     AddFunc: FnOnce(&mut Data, To) -> Result<Data::Size, Data::Error>,
+    Data::Char: std::convert::From<char>,
 {
-    let i = get(this, addr)?;
+    let i = get(this, addr.clone())?;
     match cast(i) {
         Some(i) => {
             let r = add(this, i)?;
             this.push_register(r)?;
         }
-        None => push_unit(this)?,
+        None => {
+            let from = this.get_data_type(addr)?;
+            push_cast_failure(this, from, to)?
+        }
     }
 
     Ok(None)
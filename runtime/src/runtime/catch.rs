@@ -0,0 +1,147 @@
+use log::trace;
+
+use crate::runtime::error::state_error;
+use garnish_lang_traits::{GarnishData, GarnishDataFactory, RuntimeError, TypeConstants};
+
+pub fn start_try<Data: GarnishData>(this: &mut Data, index: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    this.push_handler(index)?;
+
+    Ok(None)
+}
+
+pub fn end_try<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    match this.pop_handler()? {
+        Some(_) => Ok(None),
+        None => state_error("No handler to remove at end of try.".to_string()),
+    }
+}
+
+pub fn end_catch<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    match this.pop_value_stack() {
+        Some(_) => Ok(None),
+        None => state_error("Could not pop error value at end of catch.".to_string()),
+    }
+}
+
+/// Unwinds to the latest handler, making a value of the error the input of its catch block.
+///
+/// Returns the original error when no handler is registered.
+pub fn catch_error<Data>(
+    this: &mut Data,
+    error: RuntimeError<Data::Error>,
+    instruction: Data::Size,
+) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
+    let index = match this.unwind_handler()? {
+        None => return Err(error),
+        Some(index) => index,
+    };
+
+    let point = match this.get_from_jump_table(index.clone()) {
+        None => state_error(format!("No jump point at index {:?}", index))?,
+        Some(point) => point,
+    };
+
+    trace!(
+        "Caught error {:?} from instruction {:?}, jumping to point {:?}",
+        error, instruction, point
+    );
+
+    let value = add_error_value(this, &error, instruction)?;
+    this.push_value_stack(value)?;
+
+    Ok(Some(point))
+}
+
+/// Adds a list of `:message`, `:type` and `:instruction` pairs describing the error.
+fn add_error_value<Data>(this: &mut Data, error: &RuntimeError<Data::Error>, instruction: Data::Size) -> Result<Data::Size, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
+    let message = match (error.get_message().is_empty(), std::error::Error::source(error)) {
+        (true, Some(source)) => source.to_string(),
+        _ => error.get_message().clone(),
+    };
+
+    let message = this.add_char_list(message.chars().map(Data::Char::from).collect())?;
+    let error_type = this.parse_add_symbol(format!("{:?}", error.get_type()).as_str())?;
+    let instruction = this.add_number(Data::DataFactory::size_to_number(instruction))?;
+
+    let mut list = this.start_list(Data::Size::one() + Data::Size::one() + Data::Size::one())?;
+    for (key, value) in [("message", message), ("type", error_type), ("instruction", instruction)] {
+        let key = this.parse_add_symbol(key)?;
+        let pair = this.add_pair((key, value))?;
+        list = this.add_to_list(list, pair)?;
+    }
+
+    Ok(this.end_list(list)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::catch::{catch_error, end_catch, end_try, start_try};
+    use crate::runtime::tests::{MockError, MockGarnishData};
+    use garnish_lang_traits::RuntimeError;
+
+    #[test]
+    fn test_start_try() {
+        let mut data = MockGarnishData::new_basic_data(vec![]);
+
+        data.stub_push_handler = |_, index| {
+            assert_eq!(index, 3);
+            Ok(())
+        };
+
+        let result = start_try(&mut data, 3).unwrap();
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_end_try() {
+        let mut data = MockGarnishData::new_basic_data(vec![]);
+
+        data.stub_pop_handler = |_| Ok(Some(3));
+
+        let result = end_try(&mut data).unwrap();
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn end_try_without_handler_is_err() {
+        let mut data = MockGarnishData::new_basic_data(vec![]);
+
+        data.stub_pop_handler = |_| Ok(None);
+
+        let result = end_try(&mut data);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_end_catch() {
+        let mut data = MockGarnishData::new_basic_data(vec![]);
+
+        data.stub_pop_value_stack = |_| Some(5);
+
+        let result = end_catch(&mut data).unwrap();
+
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn catch_without_handler_returns_error() {
+        let mut data = MockGarnishData::new_basic_data(vec![]);
+
+        data.stub_unwind_handler = |_| Ok(None);
+
+        let result = catch_error(&mut data, RuntimeError::<MockError>::new("Failed"), 10);
+
+        assert_eq!(result.unwrap_err().get_message(), "Failed");
+    }
+}
@@ -0,0 +1,36 @@
+use crate::runtime::utilities::next_two_raw_ref;
+use garnish_lang_traits::{GarnishData, RuntimeError};
+
+pub fn make_closure<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    let (captures, expression) = next_two_raw_ref(this)?;
+
+    this.add_closure(expression, captures).and_then(|i| this.push_register(i))?;
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::runtime::closure::make_closure;
+    use crate::runtime::tests::MockGarnishData;
+    use garnish_lang_traits::GarnishDataType;
+
+    #[test]
+    fn test_make_closure() {
+        let mut data = MockGarnishData::new_basic_data(vec![GarnishDataType::Expression, GarnishDataType::List]);
+
+        data.stub_add_closure = |_, expression, captures| {
+            assert_eq!(0, expression);
+            assert_eq!(1, captures);
+            Ok(100)
+        };
+        data.stub_push_register = |_, addr| {
+            assert_eq!(100, addr);
+            Ok(())
+        };
+
+        let result = make_closure(&mut data).unwrap();
+
+        assert_eq!(result, None);
+    }
+}
@@ -1,47 +1,52 @@
 use crate::runtime::error::OrNumberError;
-use crate::runtime::utilities::{get_range, next_two_raw_ref, push_boolean, push_unit};
-use garnish_lang_traits::{GarnishDataType, GarnishData, GarnishDataFactory, GarnishNumber, RuntimeError, TypeConstants};
+use crate::runtime::iteration::collect_items;
+use crate::runtime::utilities::{check_unsupported, get_range, next_two_raw_ref, push_boolean, push_undefined};
+use garnish_lang_traits::{Extents, GarnishDataType, GarnishData, GarnishDataFactory, GarnishNumber, Instruction, RuntimeError, SymbolListPart, TypeConstants};
 use std::cmp::Ordering;
 
 pub fn less_than<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_comparison(this, Ordering::Greater).and_then(|result| match result {
-        Some(result) => push_boolean(this, result.is_lt()),
-        None => push_unit(this),
-    })?;
-
-    Ok(None)
+    push_comparison(this, Instruction::LessThan, Ordering::Greater, Ordering::is_lt)
 }
 
 pub fn less_than_or_equal<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_comparison(this, Ordering::Greater).and_then(|result| match result {
-        Some(result) => push_boolean(this, result.is_le()),
-        None => push_unit(this),
-    })?;
-
-    Ok(None)
+    push_comparison(this, Instruction::LessThanOrEqual, Ordering::Greater, Ordering::is_le)
 }
 
 pub fn greater_than<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_comparison(this, Ordering::Less).and_then(|result| match result {
-        Some(result) => push_boolean(this, result.is_gt()),
-        None => push_unit(this),
-    })?;
-
-    Ok(None)
+    push_comparison(this, Instruction::GreaterThan, Ordering::Less, Ordering::is_gt)
 }
 
 pub fn greater_than_or_equal<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
-    perform_comparison(this, Ordering::Less).and_then(|result| match result {
-        Some(result) => push_boolean(this, result.is_ge()),
-        None => push_unit(this),
-    })?;
-
-    Ok(None)
+    push_comparison(this, Instruction::GreaterThanOrEqual, Ordering::Less, Ordering::is_ge)
 }
 
-fn perform_comparison<Data: GarnishData>(this: &mut Data, false_ord: Ordering) -> Result<Option<Ordering>, RuntimeError<Data::Error>> {
+fn push_comparison<Data: GarnishData>(
+    this: &mut Data,
+    instruction: Instruction,
+    false_ord: Ordering,
+    check: fn(Ordering) -> bool,
+) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
     let (right, left) = next_two_raw_ref(this)?;
 
+    match (this.get_data_type(left.clone())?, this.get_data_type(right.clone())?) {
+        (GarnishDataType::Error, _) => this.push_register(left)?,
+        (_, GarnishDataType::Error) => this.push_register(right)?,
+        (left_type, right_type) => match perform_comparison(this, instruction, left, right, false_ord)? {
+            Some(result) => push_boolean(this, check(result))?,
+            None => push_undefined(this, instruction, &[left_type, right_type])?,
+        },
+    }
+
+    Ok(None)
+}
+
+fn perform_comparison<Data: GarnishData>(
+    this: &mut Data,
+    instruction: Instruction,
+    left: Data::Size,
+    right: Data::Size,
+    false_ord: Ordering,
+) -> Result<Option<Ordering>, RuntimeError<Data::Error>> {
     let result = match (this.get_data_type(left.clone())?, this.get_data_type(right.clone())?) {
         (GarnishDataType::Number, GarnishDataType::Number) => this.get_number(left)?.partial_cmp(&this.get_number(right)?),
         (GarnishDataType::Char, GarnishDataType::Char) => this.get_char(left)?.partial_cmp(&this.get_char(right)?),
@@ -97,10 +102,17 @@ fn perform_comparison<Data: GarnishData>(this: &mut Data, false_ord: Ordering) -
                         Data::get_char_list_len,
                     )?
                 }
-                _ => return Ok(Some(false_ord)),
+                (l, r) => {
+                    // unlike types are never ordered, so comparing them is false outside of strict mode
+                    check_unsupported(this, instruction, &[l, r])?;
+                    return Ok(Some(false_ord));
+                }
             }
         }
-        _ => return Ok(Some(false_ord)),
+        (l, r) => {
+            check_unsupported(this, instruction, &[l, r])?;
+            return Ok(Some(false_ord));
+        }
     };
 
     Ok(result)
@@ -137,3 +149,124 @@ where
 
     Ok(len1.partial_cmp(&len2))
 }
+
+/// Orders any two values, for operations that need every value to have a place, such as sorting.
+///
+/// Values of different types are ordered by their type, in the order [`GarnishDataType`] declares them.
+/// Char lists, byte lists, lists, concatenations and slices compare item by item, symbols by their names with unnamed symbols last,
+/// symbol lists part by part with symbols before numbers,
+/// and pairs, ranges, partials, closures and errors by their parts.
+pub(crate) fn total_order<Data: GarnishData>(this: &mut Data, left: Data::Size, right: Data::Size) -> Result<Ordering, RuntimeError<Data::Error>> {
+    let (left_type, right_type) = (this.get_data_type(left.clone())?, this.get_data_type(right.clone())?);
+    if left_type != right_type {
+        return Ok(left_type.cmp(&right_type));
+    }
+
+    let result = match left_type {
+        GarnishDataType::Number => this.get_number(left)?.partial_cmp(&this.get_number(right)?),
+        GarnishDataType::Char => this.get_char(left)?.partial_cmp(&this.get_char(right)?),
+        GarnishDataType::Byte => this.get_byte(left)?.partial_cmp(&this.get_byte(right)?),
+        GarnishDataType::Type => Some(this.get_type(left)?.cmp(&this.get_type(right)?)),
+        GarnishDataType::Expression => this.get_expression(left)?.partial_cmp(&this.get_expression(right)?),
+        GarnishDataType::External => this.get_external(left)?.partial_cmp(&this.get_external(right)?),
+        GarnishDataType::CharList => cmp_list(
+            this,
+            left,
+            right,
+            Data::Number::zero(),
+            Data::Number::zero(),
+            Data::get_char_list_item,
+            Data::get_char_list_len,
+        )?,
+        GarnishDataType::ByteList => cmp_list(
+            this,
+            left,
+            right,
+            Data::Number::zero(),
+            Data::Number::zero(),
+            Data::get_byte_list_item,
+            Data::get_byte_list_len,
+        )?,
+        GarnishDataType::Symbol => Some(this.cmp_symbol_names(this.get_symbol(left)?, this.get_symbol(right)?)?),
+        GarnishDataType::SymbolList => Some(cmp_symbol_lists(this, left, right)?),
+        GarnishDataType::Pair => {
+            let (left, right) = (this.get_pair(left)?, this.get_pair(right)?);
+            Some(cmp_parts(this, left, right)?)
+        }
+        GarnishDataType::Range => {
+            let (left, right) = (this.get_range(left)?, this.get_range(right)?);
+            Some(cmp_parts(this, left, right)?)
+        }
+        GarnishDataType::Partial => {
+            let (left, right) = (this.get_partial(left)?, this.get_partial(right)?);
+            Some(cmp_parts(this, left, right)?)
+        }
+        GarnishDataType::Closure => {
+            let (left, right) = (this.get_closure(left)?, this.get_closure(right)?);
+            Some(cmp_parts(this, left, right)?)
+        }
+        GarnishDataType::Error => {
+            let (left, right) = (this.get_error(left)?, this.get_error(right)?);
+            Some(cmp_parts(this, left, right)?)
+        }
+        GarnishDataType::List | GarnishDataType::Concatenation | GarnishDataType::Slice => {
+            match (collect_items(this, left)?, collect_items(this, right)?) {
+                (Some(left), Some(right)) => Some(cmp_items(this, left, right)?),
+                _ => None,
+            }
+        }
+        // no value to distinguish
+        GarnishDataType::Invalid | GarnishDataType::Unit | GarnishDataType::True | GarnishDataType::False | GarnishDataType::Custom => None,
+    };
+
+    Ok(result.unwrap_or(Ordering::Equal))
+}
+
+// symbols are ordered by name and come before numbers
+fn cmp_symbol_lists<Data: GarnishData>(this: &Data, left: Data::Size, right: Data::Size) -> Result<Ordering, RuntimeError<Data::Error>> {
+    let extents = || Extents::new(Data::Number::zero(), Data::Number::max_value());
+    let mut left_parts = this.get_symbol_list_iter(left, extents())?;
+    let mut right_parts = this.get_symbol_list_iter(right, extents())?;
+
+    loop {
+        let ordering = match (left_parts.next(), right_parts.next()) {
+            (None, None) => return Ok(Ordering::Equal),
+            (None, Some(_)) => return Ok(Ordering::Less),
+            (Some(_), None) => return Ok(Ordering::Greater),
+            (Some(SymbolListPart::Symbol(left)), Some(SymbolListPart::Symbol(right))) => this.cmp_symbol_names(left, right)?,
+            (Some(SymbolListPart::Number(left)), Some(SymbolListPart::Number(right))) => left.partial_cmp(&right).unwrap_or(Ordering::Equal),
+            (Some(SymbolListPart::Symbol(_)), Some(SymbolListPart::Number(_))) => Ordering::Less,
+            (Some(SymbolListPart::Number(_)), Some(SymbolListPart::Symbol(_))) => Ordering::Greater,
+        };
+
+        if ordering != Ordering::Equal {
+            return Ok(ordering);
+        }
+    }
+}
+
+fn cmp_parts<Data: GarnishData>(
+    this: &mut Data,
+    left: (Data::Size, Data::Size),
+    right: (Data::Size, Data::Size),
+) -> Result<Ordering, RuntimeError<Data::Error>> {
+    match total_order(this, left.0, right.0)? {
+        Ordering::Equal => total_order(this, left.1, right.1),
+        ordering => Ok(ordering),
+    }
+}
+
+fn cmp_items<Data: GarnishData>(this: &mut Data, left: Data::Size, right: Data::Size) -> Result<Ordering, RuntimeError<Data::Error>> {
+    let extents = || Extents::new(Data::Number::zero(), Data::Number::max_value());
+    let left = this.get_list_item_iter(left, extents())?.collect::<Vec<_>>();
+    let right = this.get_list_item_iter(right, extents())?.collect::<Vec<_>>();
+
+    for (left_item, right_item) in left.iter().zip(right.iter()) {
+        match total_order(this, left_item.clone(), right_item.clone())? {
+            Ordering::Equal => (),
+            ordering => return Ok(ordering),
+        }
+    }
+
+    Ok(left.len().cmp(&right.len()))
+}
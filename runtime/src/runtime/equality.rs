@@ -3,7 +3,8 @@ use std::fmt::Debug;
 use log::trace;
 
 use crate::runtime::error::state_error;
-use crate::runtime::utilities::{get_range, next_two_raw_ref, push_boolean};
+use crate::runtime::range::{is_stepped_range, range_len, range_parts};
+use crate::runtime::utilities::{get_range_extents, next_two_raw_ref, push_boolean};
 use garnish_lang_traits::{Extents, GarnishData, GarnishDataType, RuntimeError, TypeConstants};
 
 pub fn equal<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
@@ -62,6 +63,23 @@ fn perform_equality_check<Data: GarnishData>(this: &mut Data) -> Result<bool, Ru
     Ok(true)
 }
 
+// stepped ranges are equal when they produce the same items
+fn stepped_ranges_equal<Data: GarnishData>(this: &Data, left: Data::Size, right: Data::Size) -> Result<bool, RuntimeError<Data::Error>> {
+    let ((start1, end1, step1), (start2, end2, step2)) = match (range_parts(this, left)?, range_parts(this, right)?) {
+        (Some(left), Some(right)) => (left, right),
+        _ => return Ok(false),
+    };
+
+    let len1 = range_len::<Data>(start1.clone(), end1, step1.clone())?;
+    let len2 = range_len::<Data>(start2.clone(), end2, step2.clone())?;
+
+    if len1 != len2 {
+        return Ok(false);
+    }
+
+    Ok(len1 == Data::Number::zero() || (start1 == start2 && (len1 == Data::Number::one() || step1 == step2)))
+}
+
 fn data_equal<Data: GarnishData>(this: &mut Data, left_addr: Data::Size, right_addr: Data::Size) -> Result<bool, RuntimeError<Data::Error>> {
     let (left_type, right_type) = (this.get_data_type(left_addr.clone())?, this.get_data_type(right_addr.clone())?);
 
@@ -123,6 +141,11 @@ fn data_equal<Data: GarnishData>(this: &mut Data, left_addr: Data::Size, right_a
             this.get_symbol_list_iter(left_addr.clone(), Extents::new(Data::Number::zero(), Data::Number::max_value()))?,
             this.get_symbol_list_iter(right_addr.clone(), Extents::new(Data::Number::zero(), Data::Number::max_value()))?,
         )?,
+        (GarnishDataType::Range, GarnishDataType::Range)
+            if is_stepped_range(this, left_addr.clone())? || is_stepped_range(this, right_addr.clone())? =>
+        {
+            stepped_ranges_equal(this, left_addr, right_addr)?
+        }
         (GarnishDataType::Range, GarnishDataType::Range) => {
             let (start1, end1) = this.get_range(left_addr)?;
             let (start2, end2) = this.get_range(right_addr)?;
@@ -153,6 +176,18 @@ fn data_equal<Data: GarnishData>(this: &mut Data, left_addr: Data::Size, right_a
 
             true
         }
+        (GarnishDataType::Error, GarnishDataType::Error) => {
+            let (message1, code1) = this.get_error(left_addr)?;
+            let (message2, code2) = this.get_error(right_addr)?;
+
+            this.push_register(message1)?;
+            this.push_register(message2)?;
+
+            this.push_register(code1)?;
+            this.push_register(code2)?;
+
+            true
+        }
         (GarnishDataType::Concatenation, GarnishDataType::Concatenation) => {
             compare_item_iterators(this, left_addr, right_addr, Data::get_concatenation_iter)?
         }
@@ -221,11 +256,8 @@ fn data_equal<Data: GarnishData>(this: &mut Data, left_addr: Data::Size, right_a
             let (value1, range1) = this.get_slice(left_addr.clone())?;
             let (value2, range2) = this.get_slice(right_addr.clone())?;
 
-            let (start1, end1, _) = get_range(this, range1)?;
-            let (start2, end2, _) = get_range(this, range2)?;
-
-            let extents1 = Extents::new(start1, end1);
-            let extents2 = Extents::new(start2, end2);
+            let extents1 = get_range_extents(this, range1)?;
+            let extents2 = get_range_extents(this, range2)?;
 
             match (this.get_data_type(value1.clone())?, this.get_data_type(value2.clone())?) {
                 (GarnishDataType::CharList, GarnishDataType::CharList) => compare_index_iterator_values(
@@ -330,10 +362,10 @@ where
     Value: PartialEq + Clone,
 {
     let (slice_value, slice_range) = this.get_slice(slice_addr.clone())?;
-    let (start, end, _) = get_range(this, slice_range)?;
+    let extents = get_range_extents(this, slice_range)?;
     if this.get_data_type(slice_value.clone())? == expected_data_type {
         let mut iter1 = get_value_iter(this, value_addr.clone(), Extents::new(Data::Number::zero(), Data::Number::max_value()))?;
-        let mut iter2 = get_value_iter(this, slice_value.clone(), Extents::new(start, end))?;
+        let mut iter2 = get_value_iter(this, slice_value.clone(), extents)?;
         
         let mut index1 = iter1.next();
         let mut index2 = iter2.next();
@@ -410,8 +442,7 @@ where
     GetValueIterFn: Fn(&Data, Data::Size, Extents<Data::Number>) -> Result<Iter, Data::Error>,
 {
     let (value2, range2) = this.get_slice(slice_addr.clone())?;
-    let (start2, end2, _) = get_range(this, range2)?;
-    let extents2 = Extents::new(start2, end2);
+    let extents2 = get_range_extents(this, range2)?;
 
     match this.get_data_type(value2.clone())? {
         GarnishDataType::List => compare_item_iterators_2(this, list_addr, value2.clone(), get_value_iter, Data::get_list_item_iter, Extents::new(Data::Number::zero(), Data::Number::max_value()), extents2),
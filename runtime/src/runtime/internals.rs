@@ -1,11 +1,15 @@
 use crate::runtime::error::state_error;
-use crate::runtime::range::range_len;
-use crate::runtime::utilities::{next_ref, push_number, push_unit};
+use crate::runtime::range::{range_len, range_parts};
+use crate::runtime::utilities::{next_ref, push_failure, push_number, push_unit};
 use garnish_lang_traits::helpers::iterate_concatenation_mut;
 use garnish_lang_traits::Instruction;
 use garnish_lang_traits::{GarnishData, GarnishDataFactory, GarnishDataType, RuntimeError, TypeConstants};
 
-pub fn access_left_internal<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+pub fn access_left_internal<Data>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
     let r = next_ref(this)?;
     match this.get_data_type(r.clone())? {
         GarnishDataType::Pair => {
@@ -13,7 +17,7 @@ pub fn access_left_internal<Data: GarnishData>(this: &mut Data) -> Result<Option
             this.push_register(left)?;
         }
         GarnishDataType::Range => {
-            let (start, _) = this.get_range(r)?;
+            let start = this.get_stepped_range(r)?.start;
             match this.get_data_type(start.clone())? {
                 GarnishDataType::Number => {
                     this.push_register(start)?;
@@ -29,9 +33,13 @@ pub fn access_left_internal<Data: GarnishData>(this: &mut Data) -> Result<Option
             let (left, _) = this.get_concatenation(r)?;
             this.push_register(left)?;
         }
+        GarnishDataType::Error => {
+            let (message, _) = this.get_error(r)?;
+            this.push_register(message)?;
+        }
         t => {
             if !this.defer_op(Instruction::AccessLeftInternal, (t, r), (GarnishDataType::Unit, Data::Size::zero()))? {
-                push_unit(this)?
+                push_failure(this, "access", format!("Could not access left internal of {:?}", t))?
             }
         }
     }
@@ -39,7 +47,11 @@ pub fn access_left_internal<Data: GarnishData>(this: &mut Data) -> Result<Option
     Ok(None)
 }
 
-pub fn access_right_internal<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+pub fn access_right_internal<Data>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
     let r = next_ref(this)?;
     match this.get_data_type(r.clone())? {
         GarnishDataType::Pair => {
@@ -47,7 +59,7 @@ pub fn access_right_internal<Data: GarnishData>(this: &mut Data) -> Result<Optio
             this.push_register(right)?;
         }
         GarnishDataType::Range => {
-            let (_, end) = this.get_range(r)?;
+            let end = this.get_stepped_range(r)?.end;
             match this.get_data_type(end.clone())? {
                 GarnishDataType::Number => {
                     this.push_register(end)?;
@@ -63,9 +75,13 @@ pub fn access_right_internal<Data: GarnishData>(this: &mut Data) -> Result<Optio
             let (_, right) = this.get_concatenation(r)?;
             this.push_register(right)?;
         }
+        GarnishDataType::Error => {
+            let (_, code) = this.get_error(r)?;
+            this.push_register(code)?;
+        }
         t => {
             if !this.defer_op(Instruction::AccessRightInternal, (t, r), (GarnishDataType::Unit, Data::Size::zero()))? {
-                push_unit(this)?
+                push_failure(this, "access", format!("Could not access right internal of {:?}", t))?
             }
         }
     }
@@ -73,7 +89,11 @@ pub fn access_right_internal<Data: GarnishData>(this: &mut Data) -> Result<Optio
     Ok(None)
 }
 
-pub fn access_length_internal<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+pub fn access_length_internal<Data>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
     let r = next_ref(this)?;
     match this.get_data_type(r.clone())? {
         GarnishDataType::Pair => {
@@ -98,17 +118,14 @@ pub fn access_length_internal<Data: GarnishData>(this: &mut Data) -> Result<Opti
             push_number(this, len)?;
         }
         GarnishDataType::Range => {
-            let (start, end) = this.get_range(r)?;
-            match (this.get_data_type(end.clone())?, this.get_data_type(start.clone())?) {
-                (GarnishDataType::Number, GarnishDataType::Number) => {
-                    let start_int = this.get_number(start)?;
-                    let end_int = this.get_number(end)?;
-                    let result = range_len::<Data>(start_int, end_int)?;
+            match range_parts(this, r)? {
+                Some((start, end, step)) => {
+                    let result = range_len::<Data>(start, end, step)?;
 
                     let addr = this.add_number(result)?;
                     this.push_register(addr)?;
                 }
-                _ => push_unit(this)?,
+                None => push_unit(this)?,
             }
         }
         GarnishDataType::Slice => {
@@ -118,7 +135,7 @@ pub fn access_length_internal<Data: GarnishData>(this: &mut Data) -> Result<Opti
                 (GarnishDataType::Number, GarnishDataType::Number) => {
                     let start = this.get_number(start)?;
                     let end = this.get_number(end)?;
-                    let addr = this.add_number(range_len::<Data>(start, end)?)?;
+                    let addr = this.add_number(range_len::<Data>(start, end, Data::Number::one())?)?;
                     this.push_register(addr)?;
                 }
                 (s, e) => state_error(format!("Non integer values used for range {:?} {:?}", s, e))?,
@@ -131,7 +148,7 @@ pub fn access_length_internal<Data: GarnishData>(this: &mut Data) -> Result<Opti
         }
         t => {
             if !this.defer_op(Instruction::AccessLengthInternal, (t, r), (GarnishDataType::Unit, Data::Size::zero()))? {
-                push_unit(this)?
+                push_failure(this, "access", format!("Could not access length internal of {:?}", t))?
             }
         }
     }
@@ -0,0 +1,497 @@
+use log::trace;
+
+use std::cmp::Ordering;
+
+use crate::runtime::apply::apply_value;
+use crate::runtime::comparison::total_order;
+use crate::runtime::error::{OrNumberError, state_error};
+use crate::runtime::range::{range_item, range_len, range_numbers, range_parts};
+use crate::runtime::utilities::{get_range, next_ref, next_two_raw_ref, push_boolean, push_unit, push_unsupported};
+use garnish_lang_traits::{Extents, GarnishData, GarnishDataFactory, GarnishDataType, GarnishNumber, Instruction, RuntimeError, TypeConstants};
+
+/// Collects the items of the left value and pushes the state the following iteration instruction steps through.
+///
+/// Registers hold, in order, the list of items, the value applied to each item, the index of the current item,
+/// the accumulated value and the result of the latest application. The index is Unit until the first item is visited.
+///
+/// When the left value can't be iterated, the following iteration instruction is skipped.
+pub fn start_iteration<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    let (right, left) = next_two_raw_ref(this)?;
+    let next_instruction = this.get_instruction_cursor() + Data::Size::one();
+
+    // ranges are iterated lazily, their items are produced as they are visited
+    let items = match this.get_data_type(left.clone())? {
+        GarnishDataType::Range if range_parts(this, left.clone())?.is_some() => Some(left.clone()),
+        _ => collect_items(this, left.clone())?,
+    };
+
+    let items = match (items, this.get_data_type(left.clone())?) {
+        (Some(items), _) => items,
+        (None, GarnishDataType::Error) => {
+            this.push_register(left)?;
+            return Ok(Some(next_instruction + Data::Size::one()));
+        }
+        (None, left_type) => {
+            let operation = match this.get_instruction(next_instruction.clone()) {
+                Some((instruction, _)) => instruction,
+                None => Instruction::StartIteration,
+            };
+
+            let right_type = this.get_data_type(right)?;
+            push_unsupported(this, operation, &[left_type, right_type])?;
+            return Ok(Some(next_instruction + Data::Size::one()));
+        }
+    };
+
+    this.push_register(items)?;
+    this.push_register(right)?;
+    push_unit(this)?;
+    push_unit(this)?;
+    push_unit(this)?;
+
+    Ok(None)
+}
+
+/// Creates a list of the results of applying right to each item.
+pub fn map<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    iterate(this, Instruction::Map)
+}
+
+/// Creates a list of the items that right applies truthfully to.
+pub fn filter<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    iterate(this, Instruction::Filter)
+}
+
+/// Combines the items by applying right to a list of the accumulated value and each item.
+///
+/// Right can be a pair of an initial value and the value to apply, otherwise the first item is the initial value.
+pub fn fold<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    iterate(this, Instruction::Fold)
+}
+
+/// True if right applies truthfully to any item, stopping at the first that does.
+pub fn any<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    iterate(this, Instruction::Any)
+}
+
+/// True if right applies truthfully to every item, stopping at the first that doesn't.
+pub fn all<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    iterate(this, Instruction::All)
+}
+
+/// The first item right applies truthfully to, Unit if there is none.
+pub fn find<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    iterate(this, Instruction::Find)
+}
+
+/// Creates a list of the items in order, using right as a comparator when it isn't Unit.
+///
+/// The comparator is applied to a list of two items and is truthy when the first belongs before the second.
+/// Without a comparator, items are ordered by the total order of values.
+pub fn sort<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    loop {
+        let result = next_ref(this)?;
+        let accumulated = next_ref(this)?;
+        let width = next_ref(this)?;
+        let comparator = next_ref(this)?;
+        let items = next_ref(this)?;
+
+        // a bottom up merge sort, each pass merges neighbouring runs of width items into a chain of pairs that becomes the items of the next pass
+        // the index register holds the width and accumulated holds the chain and the positions of the two runs being merged
+        let (mut items, mut width, mut merged, mut left, mut left_end, mut right, mut right_end) = match this.get_data_type(width.clone())? {
+            GarnishDataType::Unit => {
+                if this.get_data_type(comparator.clone())? == GarnishDataType::Unit {
+                    let sorted = sort_by_total_order(this, items)?;
+                    this.push_register(sorted)?;
+                    return Ok(None);
+                }
+
+                let items = match collect_items(this, items)? {
+                    Some(items) => items,
+                    None => state_error("Cannot sort items that aren't a list".to_string())?,
+                };
+                let len = Data::DataFactory::size_to_number(this.get_list_len(items.clone())?);
+                let one = Data::Number::one();
+                let (left_end, right_end) = (
+                    smaller::<Data>(one.clone(), len.clone()),
+                    smaller::<Data>(one.clone().plus(one.clone()).or_num_err()?, len),
+                );
+                let merged = this.add_unit()?;
+
+                (items, one, merged, Data::Number::zero(), left_end.clone(), left_end, right_end)
+            }
+            GarnishDataType::Number => {
+                let width = this.get_number(width)?;
+                let (merged, runs) = this.get_pair(accumulated)?;
+                let (left_run, right_run) = this.get_pair(runs)?;
+                let ((left, left_end), (right, right_end)) = (this.get_pair(left_run)?, this.get_pair(right_run)?);
+                let (mut left, left_end) = (this.get_number(left)?, this.get_number(left_end)?);
+                let (mut right, right_end) = (this.get_number(right)?, this.get_number(right_end)?);
+
+                // comparator was applied to the next item of the right run and the next item of the left run
+                let merged = match is_truthy(this, result)? {
+                    true => {
+                        let merged = merge_item(this, merged, items.clone(), right.clone())?;
+                        right = right.increment().or_num_err()?;
+                        merged
+                    }
+                    false => {
+                        let merged = merge_item(this, merged, items.clone(), left.clone())?;
+                        left = left.increment().or_num_err()?;
+                        merged
+                    }
+                };
+
+                (items, width, merged, left, left_end, right, right_end)
+            }
+            t => state_error(format!(
+                "Invalid iteration index of type {:?} during {:?} operation.",
+                t,
+                Instruction::Sort
+            ))?,
+        };
+
+        // move items that need no comparison until both runs have items left
+        while left >= left_end || right >= right_end {
+            if left < left_end {
+                merged = merge_item(this, merged, items.clone(), left.clone())?;
+                left = left.increment().or_num_err()?;
+            } else if right < right_end {
+                merged = merge_item(this, merged, items.clone(), right.clone())?;
+                right = right.increment().or_num_err()?;
+            } else {
+                let len = Data::DataFactory::size_to_number(this.get_list_len(items.clone())?);
+                let mut start = right_end.clone();
+                if start >= len {
+                    items = list_from_chain(this, merged)?;
+                    width = width.clone().plus(width).or_num_err()?;
+                    if width >= len {
+                        trace!("Ending {:?} operation after {:?} items", Instruction::Sort, len);
+                        this.push_register(items)?;
+                        return Ok(None);
+                    }
+
+                    start = Data::Number::zero();
+                    merged = this.add_unit()?;
+                }
+
+                left = start.clone();
+                left_end = smaller::<Data>(start.plus(width.clone()).or_num_err()?, len.clone());
+                right = left_end.clone();
+                right_end = smaller::<Data>(left_end.clone().plus(width.clone()).or_num_err()?, len);
+            }
+        }
+
+        let (item, other) = match (item_at(this, items.clone(), right.clone())?, item_at(this, items.clone(), left.clone())?) {
+            (Some(item), Some(other)) => (item, other),
+            _ => state_error(format!(
+                "No items to compare at {:?} and {:?} during {:?} operation.",
+                right,
+                left,
+                Instruction::Sort
+            ))?,
+        };
+        let input = add_list(this, vec![item, other])?;
+
+        let (left, left_end) = (this.add_number(left)?, this.add_number(left_end)?);
+        let (right, right_end) = (this.add_number(right)?, this.add_number(right_end)?);
+        let left_run = this.add_pair((left, left_end))?;
+        let right_run = this.add_pair((right, right_end))?;
+        let runs = this.add_pair((left_run, right_run))?;
+        let accumulated = this.add_pair((merged, runs))?;
+
+        this.push_register(items)?;
+        this.push_register(comparator.clone())?;
+        let width = this.add_number(width)?;
+        this.push_register(width)?;
+        this.push_register(accumulated)?;
+
+        let return_point = this.get_instruction_cursor();
+        if let Some(next_instruction) = apply_value(this, comparator, input, Instruction::Sort, true, return_point)? {
+            return Ok(Some(next_instruction));
+        }
+
+        // comparator produced a result without an expression, continue with it in the register
+    }
+}
+
+// Each application of an expression returns to the iteration instruction itself
+// so the step is repeated with the result until all items have been visited.
+fn iterate<Data: GarnishData>(this: &mut Data, instruction: Instruction) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    loop {
+        let result = next_ref(this)?;
+        let mut accumulated = next_ref(this)?;
+        let index = next_ref(this)?;
+        let mut applied = next_ref(this)?;
+        let items = next_ref(this)?;
+
+        let index = match this.get_data_type(index.clone())? {
+            GarnishDataType::Unit => {
+                let mut index = Data::Number::zero();
+                if instruction == Instruction::Fold {
+                    match this.get_data_type(applied.clone())? {
+                        GarnishDataType::Pair => (accumulated, applied) = this.get_pair(applied)?,
+                        _ => {
+                            if let Some(item) = item_at(this, items.clone(), index.clone())? {
+                                accumulated = item;
+                                index = index.increment().or_num_err()?;
+                            }
+                        }
+                    }
+                }
+                index
+            }
+            GarnishDataType::Number => {
+                let index = this.get_number(index)?;
+                let item = match item_at(this, items.clone(), index.clone())? {
+                    Some(item) => item,
+                    None => state_error(format!("No item at index {:?} during {:?} operation.", index, instruction))?,
+                };
+
+                let truthy = is_truthy(this, result.clone())?;
+
+                match (instruction, truthy) {
+                    (Instruction::Map, _) => accumulated = this.add_pair((accumulated, result))?,
+                    (Instruction::Filter, true) => accumulated = this.add_pair((accumulated, item))?,
+                    (Instruction::Fold, _) => accumulated = result,
+                    (Instruction::Any, true) | (Instruction::All, false) => {
+                        push_boolean(this, instruction == Instruction::Any)?;
+                        return Ok(None);
+                    }
+                    (Instruction::Find, true) => {
+                        this.push_register(item)?;
+                        return Ok(None);
+                    }
+                    _ => (),
+                }
+
+                index.increment().or_num_err()?
+            }
+            t => state_error(format!("Invalid iteration index of type {:?} during {:?} operation.", t, instruction))?,
+        };
+
+        let item = match item_at(this, items.clone(), index.clone())? {
+            Some(item) => item,
+            None => {
+                trace!("Ending {:?} operation after {:?} items", instruction, index);
+                finish(this, instruction, accumulated)?;
+                return Ok(None);
+            }
+        };
+
+        let input = match instruction {
+            Instruction::Fold => {
+                let mut list = this.start_list(Data::Size::one() + Data::Size::one())?;
+                list = this.add_to_list(list, accumulated.clone())?;
+                list = this.add_to_list(list, item)?;
+                this.end_list(list)?
+            }
+            _ => item,
+        };
+
+        this.push_register(items)?;
+        this.push_register(applied.clone())?;
+        let index = this.add_number(index)?;
+        this.push_register(index)?;
+        this.push_register(accumulated)?;
+
+        let return_point = this.get_instruction_cursor();
+        if let Some(next_instruction) = apply_value(this, applied, input, instruction, true, return_point)? {
+            return Ok(Some(next_instruction));
+        }
+
+        // applied value produced a result without an expression, continue with it in the register
+    }
+}
+
+/// Adds a list of the items of a list, range, slice, char list, byte list or concatenation, None for other values.
+///
+/// Ranges are materialized, unlike when visited by an iteration instruction.
+pub(crate) fn collect_items<Data: GarnishData>(this: &mut Data, addr: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    let items = match this.get_data_type(addr.clone())? {
+        GarnishDataType::List => addr,
+        GarnishDataType::Range => add_range_items(this, addr)?,
+        GarnishDataType::Slice => {
+            let (value, range) = this.get_slice(addr)?;
+            let (start, end, _) = get_range(this, range)?;
+            add_sequence_items(this, value, start, end.increment().or_num_err()?)?
+        }
+        GarnishDataType::CharList | GarnishDataType::ByteList | GarnishDataType::Concatenation => {
+            add_sequence_items(this, addr, Data::Number::zero(), Data::Number::max_value())?
+        }
+        _ => return Ok(None),
+    };
+
+    Ok(Some(items))
+}
+
+fn is_truthy<Data: GarnishData>(this: &Data, addr: Data::Size) -> Result<bool, RuntimeError<Data::Error>> {
+    Ok(!matches!(this.get_data_type(addr)?, GarnishDataType::False | GarnishDataType::Unit))
+}
+
+fn smaller<Data: GarnishData>(left: Data::Number, right: Data::Number) -> Data::Number {
+    match left < right {
+        true => left,
+        false => right,
+    }
+}
+
+fn sort_by_total_order<Data: GarnishData>(this: &mut Data, items: Data::Size) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    let items = match collect_items(this, items)? {
+        Some(items) => items,
+        None => state_error("Cannot sort items that aren't a list".to_string())?,
+    };
+    let mut items = this
+        .get_list_item_iter(items, Extents::new(Data::Number::zero(), Data::Number::max_value()))?
+        .collect::<Vec<_>>();
+
+    // sort_by is stable, so items in the same place keep their order
+    let mut error = None;
+    items.sort_by(|left, right| match total_order(this, left.clone(), right.clone()) {
+        Ok(ordering) => ordering,
+        Err(e) => {
+            error.get_or_insert(e);
+            Ordering::Equal
+        }
+    });
+
+    match error {
+        Some(e) => Err(e),
+        None => add_list(this, items),
+    }
+}
+
+// adds the item at position of items to the end of a chain of pairs
+fn merge_item<Data: GarnishData>(
+    this: &mut Data,
+    merged: Data::Size,
+    items: Data::Size,
+    position: Data::Number,
+) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    match item_at(this, items, position.clone())? {
+        Some(item) => Ok(this.add_pair((merged, item))?),
+        None => state_error(format!("No item at index {:?} during {:?} operation.", position, Instruction::Sort))?,
+    }
+}
+
+fn item_at<Data: GarnishData>(this: &mut Data, items: Data::Size, index: Data::Number) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    if this.get_data_type(items.clone())? == GarnishDataType::Range {
+        return match range_parts(this, items)? {
+            Some((start, end, step)) if index < range_len::<Data>(start.clone(), end.clone(), step.clone())? => {
+                let item = range_item::<Data>(start, step, index)?;
+                Ok(Some(this.add_number(item)?))
+            }
+            _ => Ok(None),
+        };
+    }
+
+    let len = Data::DataFactory::size_to_number(this.get_list_len(items.clone())?);
+
+    match index < len {
+        true => Ok(this.get_list_item(items, index)?),
+        false => Ok(None),
+    }
+}
+
+fn finish<Data: GarnishData>(this: &mut Data, instruction: Instruction, accumulated: Data::Size) -> Result<(), RuntimeError<Data::Error>> {
+    match instruction {
+        Instruction::Map | Instruction::Filter => {
+            let list = list_from_chain(this, accumulated)?;
+            this.push_register(list)?;
+        }
+        Instruction::Fold => this.push_register(accumulated)?,
+        Instruction::Any => push_boolean(this, false)?,
+        Instruction::All => push_boolean(this, true)?,
+        _ => push_unit(this)?,
+    }
+
+    Ok(())
+}
+
+// accumulated items are a chain of pairs with the latest item on the right
+fn list_from_chain<Data: GarnishData>(this: &mut Data, chain: Data::Size) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    let mut items = vec![];
+    let mut current = chain;
+    while this.get_data_type(current.clone())? == GarnishDataType::Pair {
+        let (previous, item) = this.get_pair(current)?;
+        items.push(item);
+        current = previous;
+    }
+    items.reverse();
+
+    add_list(this, items)
+}
+
+fn add_list<Data: GarnishData>(this: &mut Data, items: Vec<Data::Size>) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    let len = items.iter().fold(Data::Size::zero(), |len, _| len + Data::Size::one());
+
+    let mut list = this.start_list(len)?;
+    for item in items {
+        list = this.add_to_list(list, item)?;
+    }
+
+    Ok(this.end_list(list)?)
+}
+
+fn add_range_items<Data: GarnishData>(this: &mut Data, range: Data::Size) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    let (start, end, step) = match range_parts(this, range)? {
+        Some(parts) => parts,
+        None => state_error("Cannot iterate range with non number values".to_string())?,
+    };
+
+    let len = range_len::<Data>(start.clone(), end, step.clone())?;
+    let items = range_numbers::<Data>(start, step, len)
+        .map(|number| Ok(this.add_number(number?)?))
+        .collect::<Result<Vec<_>, RuntimeError<Data::Error>>>()?;
+
+    add_list(this, items)
+}
+
+// adds a list of the items of a list, concatenation, char list or byte list from start up to, not including, end
+fn add_sequence_items<Data: GarnishData>(
+    this: &mut Data,
+    value: Data::Size,
+    start: Data::Number,
+    end: Data::Number,
+) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    let extents = || Extents::new(Data::Number::zero(), Data::Number::max_value());
+
+    let items = match this.get_data_type(value.clone())? {
+        GarnishDataType::List => within::<Data, _>(this.get_list_item_iter(value, extents())?, &start, &end)?,
+        GarnishDataType::Concatenation => within::<Data, _>(this.get_concatenation_iter(value, extents())?, &start, &end)?,
+        GarnishDataType::CharList => {
+            let chars = within::<Data, _>(this.get_char_list_iter(value, extents())?, &start, &end)?;
+            chars.into_iter().map(|c| this.add_char(c)).collect::<Result<Vec<_>, _>>()?
+        }
+        GarnishDataType::ByteList => {
+            let bytes = within::<Data, _>(this.get_byte_list_iter(value, extents())?, &start, &end)?;
+            bytes.into_iter().map(|b| this.add_byte(b)).collect::<Result<Vec<_>, _>>()?
+        }
+        t => state_error(format!("Cannot iterate items of type {:?}", t))?,
+    };
+
+    add_list(this, items)
+}
+
+fn within<Data: GarnishData, T>(
+    iter: impl Iterator<Item = T>,
+    start: &Data::Number,
+    end: &Data::Number,
+) -> Result<Vec<T>, RuntimeError<Data::Error>> {
+    let mut items = vec![];
+    let mut index = Data::Number::zero();
+
+    for item in iter {
+        if &index >= end {
+            break;
+        }
+
+        if &index >= start {
+            items.push(item);
+        }
+
+        index = index.increment().or_num_err()?;
+    }
+
+    Ok(items)
+}
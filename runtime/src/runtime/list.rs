@@ -1,6 +1,6 @@
 use crate::runtime::error::state_error;
 use crate::runtime::error::OrNumberError;
-use crate::runtime::range::range_len;
+use crate::runtime::range::{range_item, range_len, range_parts};
 use crate::runtime::utilities::get_range;
 use garnish_lang_traits::SymbolListPart;
 use garnish_lang_traits::helpers::{iterate_concatenation_mut, iterate_rev_concatenation_mut};
@@ -78,22 +78,19 @@ pub(crate) fn access_with_integer<Data: GarnishData>(
         GarnishDataType::ByteList => index_byte_list(this, value, index),
         GarnishDataType::SymbolList => index_symbol_list(this, value, index),
         GarnishDataType::Range => {
-            let (start, end) = this.get_range(value)?;
-            match (this.get_data_type(start.clone())?, this.get_data_type(end.clone())?) {
-                (GarnishDataType::Number, GarnishDataType::Number) => {
-                    let start_int = this.get_number(start)?;
-                    let end_int = this.get_number(end)?;
-                    let len = range_len::<Data>(start_int.clone(), end_int)?;
+            match range_parts(this, value)? {
+                Some((start, end, step)) => {
+                    let len = range_len::<Data>(start.clone(), end, step.clone())?;
 
-                    if index >= len {
+                    if index >= len || index < Data::Number::zero() {
                         return Ok(None);
                     } else {
-                        let result = start_int.plus(index).or_num_err()?;
+                        let result = range_item::<Data>(start, step, index)?;
                         let addr = this.add_number(result)?;
                         Ok(Some(addr))
                     }
                 }
-                _ => Ok(None),
+                None => Ok(None),
             }
         }
         GarnishDataType::Slice => {
@@ -144,13 +141,7 @@ fn index_list<Data: GarnishData>(
     if index < Data::Number::zero() {
         Ok(None)
     } else {
-        match this.get_list_item(list, index)? {
-            Some(addr) => Ok(Some(addr)),
-            None => {
-                let index = this.add_unit()?;
-                Ok(Some(index))
-            },
-        }
+        Ok(this.get_list_item(list, index)?)
     }
 }
 
@@ -311,6 +302,16 @@ pub(crate) fn access_with_symbol<Data: GarnishData>(
         GarnishDataType::Concatenation => {
             Ok(iterate_rev_concatenation_mut(this, value, |this, _index, addr| get_value_if_association(this, addr, sym.clone()))?.0)
         }
+        GarnishDataType::Error => {
+            let (message, code) = this.get_error(value)?;
+            if sym == <Data as GarnishData>::DataFactory::parse_symbol("message")? {
+                Ok(Some(message))
+            } else if sym == <Data as GarnishData>::DataFactory::parse_symbol("code")? {
+                Ok(Some(code))
+            } else {
+                Ok(None)
+            }
+        }
         _ => Err(RuntimeError::unsupported_types()),
     }
 }
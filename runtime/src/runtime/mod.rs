@@ -3,11 +3,14 @@ mod apply;
 mod arithmetic;
 mod bitwise;
 mod casting;
+mod catch;
+mod closure;
 mod comparison;
 mod concat;
 mod equality;
 pub mod error;
 mod internals;
+mod iteration;
 mod jumps;
 mod list;
 mod logical;
@@ -25,10 +28,13 @@ pub mod ops {
     pub use super::arithmetic::*;
     pub use super::bitwise::*;
     pub use super::casting::*;
+    pub use super::catch::*;
+    pub use super::closure::*;
     pub use super::comparison::*;
     pub use super::concat::*;
     pub use super::equality::*;
     pub use super::internals::*;
+    pub use super::iteration::*;
     pub use super::jumps::*;
     pub use super::list::*;
     pub use super::logical::*;
@@ -237,6 +243,7 @@ mod tests {
         pub stub_get_char: fn(&T, addr: i32) -> Result<char, MockError>,
         pub stub_get_byte: fn(&T, addr: i32) -> Result<u8, MockError>,
         pub stub_get_symbol: fn(&T, addr: i32) -> Result<u32, MockError>,
+        pub stub_cmp_symbol_names: fn(&T, left: u32, right: u32) -> Result<std::cmp::Ordering, MockError>,
         pub stub_get_expression: fn(&T, addr: i32) -> Result<i32, MockError>,
         pub stub_get_external: fn(&T, addr: i32) -> Result<i32, MockError>,
         pub stub_get_pair: fn(&T, addr: i32) -> Result<(i32, i32), MockError>,
@@ -244,6 +251,8 @@ mod tests {
         pub stub_get_range: fn(&T, addr: i32) -> Result<(i32, i32), MockError>,
         pub stub_get_slice: fn(&T, addr: i32) -> Result<(i32, i32), MockError>,
         pub stub_get_partial: fn(&T, addr: i32) -> Result<(i32, i32), MockError>,
+        pub stub_get_closure: fn(&T, addr: i32) -> Result<(i32, i32), MockError>,
+        pub stub_get_error: fn(&T, addr: i32) -> Result<(i32, i32), MockError>,
         pub stub_get_list_len: fn(&T, addr: i32) -> Result<i32, MockError>,
         pub stub_get_list_item: fn(&T, list_addr: i32, item_addr: i32) -> Result<Option<i32>, MockError>,
         pub stub_get_list_item_with_symbol: fn(&T, list_addr: i32, sym: u32) -> Result<Option<i32>, MockError>,
@@ -276,7 +285,11 @@ mod tests {
         pub stub_add_range: fn(&mut T, start: i32, end: i32) -> Result<i32, MockError>,
         pub stub_add_slice: fn(&mut T, list: i32, range: i32) -> Result<i32, MockError>,
         pub stub_add_partial: fn(&mut T, list: i32, range: i32) -> Result<i32, MockError>,
+        pub stub_add_closure: fn(&mut T, expression: i32, captures: i32) -> Result<i32, MockError>,
+        pub stub_add_error: fn(&mut T, message: i32, code: i32) -> Result<i32, MockError>,
         pub stub_merge_to_symbol_list: fn(&mut T, first: i32, second: i32) -> Result<i32, MockError>,
+        pub stub_add_char_list: fn(&mut T, chars: Vec<char>) -> Result<i32, MockError>,
+        pub stub_add_byte_list: fn(&mut T, bytes: Vec<u8>) -> Result<i32, MockError>,
         pub stub_start_list: fn(&mut T, len: i32) -> Result<i32, MockError>,
         pub stub_add_to_list: fn(&mut T, list_index: i32, item_index: i32) -> Result<i32, MockError>,
         pub stub_end_list: fn(&mut T, list_index: i32) -> Result<i32, MockError>,
@@ -296,6 +309,11 @@ mod tests {
         pub stub_get_jump_point_mut: fn(&T, index: i32) -> Option<&'static mut i32>,
         pub stub_push_jump_path: fn(&mut T, index: i32) -> Result<(), MockError>,
         pub stub_pop_jump_path: fn(&mut T) -> Result<Option<i32>, MockError>,
+        pub stub_get_local: fn(&T, index: i32) -> Option<i32>,
+        pub stub_set_local: fn(&mut T, index: i32, addr: i32) -> Result<(), MockError>,
+        pub stub_push_handler: fn(&mut T, index: i32) -> Result<(), MockError>,
+        pub stub_pop_handler: fn(&mut T) -> Result<Option<i32>, MockError>,
+        pub stub_unwind_handler: fn(&mut T) -> Result<Option<i32>, MockError>,
         // stub_size_to_number: fn(&T, from: i32) -> i32,
         // stub_number_to_size: fn(&T, from: i32) -> Option<i32>,
         // stub_number_to_char: fn(&T, from: i32) -> Option<char>,
@@ -316,6 +334,8 @@ mod tests {
         // stub_parse_byte_list: fn(&T, from: &str) -> Result<Vec<u8>, MockError>,
         pub stub_resolve: fn(&mut T, symbol: u32) -> Result<bool, MockError>,
         pub stub_defer_op: fn(&mut T, operation: Instruction, left: (GarnishDataType, i32), right: (GarnishDataType, i32)) -> Result<bool, MockError>,
+        pub stub_error_values: fn(&T) -> bool,
+        pub stub_strict_mode: fn(&T) -> bool,
         pub stub_apply: fn(&mut T, external_value: i32, input_addr: i32) -> Result<bool, MockError>,
     }
 
@@ -444,6 +464,7 @@ mod tests {
                 stub_get_char: stub_fn_1,
                 stub_get_byte: stub_fn_1,
                 stub_get_symbol: stub_fn_1,
+                stub_cmp_symbol_names: stub_fn_2,
                 stub_get_expression: stub_fn_1,
                 stub_get_external: stub_fn_1,
                 stub_get_pair: stub_fn_1,
@@ -451,6 +472,8 @@ mod tests {
                 stub_get_range: stub_fn_1,
                 stub_get_slice: stub_fn_1,
                 stub_get_partial: stub_fn_1,
+                stub_get_closure: stub_fn_1,
+                stub_get_error: stub_fn_1,
                 stub_get_list_len: stub_fn_1,
                 stub_get_list_item: stub_fn_2,
                 stub_get_list_item_with_symbol: stub_fn_2,
@@ -483,7 +506,11 @@ mod tests {
                 stub_add_range: stub_fn_2_mut,
                 stub_add_slice: stub_fn_2_mut,
                 stub_add_partial: stub_fn_2_mut,
+                stub_add_closure: stub_fn_2_mut,
+                stub_add_error: stub_fn_2_mut,
                 stub_merge_to_symbol_list: stub_fn_2_mut,
+                stub_add_char_list: stub_fn_1_mut,
+                stub_add_byte_list: stub_fn_1_mut,
                 stub_start_list: stub_fn_1_mut,
                 stub_add_to_list: stub_fn_2_mut,
                 stub_end_list: stub_fn_1_mut,
@@ -503,6 +530,11 @@ mod tests {
                 stub_get_jump_point_mut: stub_fn_1,
                 stub_push_jump_path: stub_fn_1_mut,
                 stub_pop_jump_path: stub_fn_0_mut,
+                stub_get_local: stub_fn_1,
+                stub_set_local: stub_fn_2_mut,
+                stub_push_handler: stub_fn_1_mut,
+                stub_pop_handler: stub_fn_0_mut,
+                stub_unwind_handler: stub_fn_0_mut,
                 // stub_size_to_number: stub_fn_1,
                 // stub_number_to_size: stub_fn_1,
                 // stub_number_to_char: stub_fn_1,
@@ -523,6 +555,8 @@ mod tests {
                 // stub_parse_byte_list: stub_parse_fn,
                 stub_resolve: |_, _| unimplemented!(),
                 stub_defer_op: |_, _, _, _| unimplemented!(),
+                stub_error_values: |_| false,
+                stub_strict_mode: |_| false,
                 stub_apply: |_, _, _| unimplemented!(),
             }
         }
@@ -602,6 +636,10 @@ mod tests {
             (self.stub_get_symbol)(self.data(), addr)
         }
 
+        fn cmp_symbol_names(&self, left: Self::Symbol, right: Self::Symbol) -> Result<std::cmp::Ordering, Self::Error> {
+            (self.stub_cmp_symbol_names)(self.data(), left, right)
+        }
+
         fn get_expression(&self, addr: Self::Size) -> Result<Self::Size, Self::Error> {
             (self.stub_get_expression)(self.data(), addr)
         }
@@ -630,6 +668,14 @@ mod tests {
             (self.stub_get_partial)(self.data(), addr)
         }
 
+        fn get_closure(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error> {
+            (self.stub_get_closure)(self.data(), addr)
+        }
+
+        fn get_error(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error> {
+            (self.stub_get_error)(self.data(), addr)
+        }
+
         fn get_list_len(&self, addr: Self::Size) -> Result<Self::Size, Self::Error> {
             (self.stub_get_list_len)(self.data(), addr)
         }
@@ -746,6 +792,22 @@ mod tests {
             (self.stub_add_partial)(self.data_mut(), list, range)
         }
 
+        fn add_closure(&mut self, expression: Self::Size, captures: Self::Size) -> Result<Self::Size, Self::Error> {
+            (self.stub_add_closure)(self.data_mut(), expression, captures)
+        }
+
+        fn add_error(&mut self, message: Self::Size, code: Self::Size) -> Result<Self::Size, Self::Error> {
+            (self.stub_add_error)(self.data_mut(), message, code)
+        }
+
+        fn add_char_list(&mut self, chars: Vec<Self::Char>) -> Result<Self::Size, Self::Error> {
+            (self.stub_add_char_list)(self.data_mut(), chars)
+        }
+
+        fn add_byte_list(&mut self, bytes: Vec<Self::Byte>) -> Result<Self::Size, Self::Error> {
+            (self.stub_add_byte_list)(self.data_mut(), bytes)
+        }
+
         fn merge_to_symbol_list(&mut self, first: Self::Size, second: Self::Size) -> Result<Self::Size, Self::Error> {
             (self.stub_merge_to_symbol_list)(self.data_mut(), first, second)
         }
@@ -826,6 +888,26 @@ mod tests {
             (self.stub_pop_jump_path)(self.data_mut())
         }
 
+        fn get_local(&self, index: Self::Size) -> Option<Self::Size> {
+            (self.stub_get_local)(self.data(), index)
+        }
+
+        fn set_local(&mut self, index: Self::Size, addr: Self::Size) -> Result<(), Self::Error> {
+            (self.stub_set_local)(self.data_mut(), index, addr)
+        }
+
+        fn push_handler(&mut self, index: Self::Size) -> Result<(), Self::Error> {
+            (self.stub_push_handler)(self.data_mut(), index)
+        }
+
+        fn pop_handler(&mut self) -> Result<Option<Self::Size>, Self::Error> {
+            (self.stub_pop_handler)(self.data_mut())
+        }
+
+        fn unwind_handler(&mut self) -> Result<Option<Self::Size>, Self::Error> {
+            (self.stub_unwind_handler)(self.data_mut())
+        }
+
         fn add_char_list_from(&mut self, from: Self::Size) -> Result<Self::Size, Self::Error> {
             (self.stub_add_char_list_from)(self.data_mut(), from)
         }
@@ -861,5 +943,13 @@ mod tests {
         fn defer_op(&mut self, operation: Instruction, left: (GarnishDataType, Self::Size), right: (GarnishDataType, Self::Size)) -> Result<bool, Self::Error> {
             (self.stub_defer_op)(self.data_mut(), operation, left, right)
         }
+
+        fn error_values(&self) -> bool {
+            (self.stub_error_values)(self.data())
+        }
+
+        fn strict_mode(&self) -> bool {
+            (self.stub_strict_mode)(self.data())
+        }
     }
 }
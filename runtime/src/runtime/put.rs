@@ -41,3 +41,19 @@ pub fn update_value<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::S
 
     Ok(None)
 }
+
+pub fn put_local<Data: GarnishData>(this: &mut Data, index: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    match this.get_local(index) {
+        None => push_unit(this)?,
+        Some(i) => this.push_register(i)?,
+    }
+
+    Ok(None)
+}
+
+pub fn update_local<Data: GarnishData>(this: &mut Data, index: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+    let r = next_ref(this)?;
+    this.set_local(index, r)?;
+
+    Ok(None)
+}
@@ -1,7 +1,10 @@
-use garnish_lang_traits::{RuntimeError, GarnishDataType, GarnishData, GarnishNumber};
+use garnish_lang_traits::{RuntimeError, GarnishDataType, GarnishData, GarnishDataFactory, GarnishNumber, TypeConstants};
 use crate::runtime::utilities::{next_two_raw_ref, push_unit};
 use crate::runtime::error::OrNumberError;
 
+const RANGE_STEP_PRECISION: &str = "1000000000";
+
+/// Creates a range from left to right, or a stepped range when left is already a range and right is the step.
 pub fn make_range<Data: GarnishData>(this: &mut Data) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
     make_range_internal(this, false, false)
 }
@@ -18,8 +21,99 @@ pub fn make_exclusive_range<Data: GarnishData>(this: &mut Data) -> Result<Option
     make_range_internal(this, true, true)
 }
 
-pub(crate) fn range_len<Data: GarnishData>(start: Data::Number, end: Data::Number) -> Result<Data::Number, RuntimeError<Data::Error>> {
-    end.subtract(start).or_num_err()?.increment().or_num_err()
+/// Number of items from start to end, inclusive, counting by step. Zero when step moves away from end.
+///
+/// Floating point steps can land the last item a rounding error past end, so an item within a billionth of a step of end is still counted.
+pub(crate) fn range_len<Data: GarnishData>(
+    start: Data::Number,
+    end: Data::Number,
+    step: Data::Number,
+) -> Result<Data::Number, RuntimeError<Data::Error>> {
+    let distance = end.subtract(start).or_num_err()?;
+    let zero = Data::Number::zero();
+
+    if distance != zero && (distance < zero) != (step < zero) {
+        return Ok(zero);
+    }
+
+    let len = distance.clone().integer_divide(step.clone()).or_num_err()?.increment().or_num_err()?;
+
+    // how far past end the item after the last counted one lands
+    let overshoot = len.clone().multiply(step.clone()).or_num_err()?.subtract(distance).or_num_err()?;
+    let tolerance = step
+        .absolute_value()
+        .or_num_err()?
+        .divide(Data::DataFactory::parse_number(RANGE_STEP_PRECISION)?)
+        .or_num_err()?;
+
+    match overshoot.absolute_value().or_num_err()? <= tolerance {
+        true => len.increment().or_num_err(),
+        false => Ok(len),
+    }
+}
+
+/// Start, end and step of a range whose values are numbers.
+pub(crate) type RangeParts<Data> = (
+    <Data as GarnishData>::Number,
+    <Data as GarnishData>::Number,
+    <Data as GarnishData>::Number,
+);
+
+/// Start, end and step of a range whose values are numbers, None otherwise.
+pub(crate) fn range_parts<Data: GarnishData>(this: &Data, addr: Data::Size) -> Result<Option<RangeParts<Data>>, RuntimeError<Data::Error>> {
+    let range = this.get_stepped_range(addr)?;
+
+    let step = match range.step {
+        None => Data::Number::one(),
+        Some(step) => match this.get_data_type(step.clone())? {
+            GarnishDataType::Number => this.get_number(step)?,
+            _ => return Ok(None),
+        },
+    };
+
+    match (this.get_data_type(range.start.clone())?, this.get_data_type(range.end.clone())?) {
+        (GarnishDataType::Number, GarnishDataType::Number) => Ok(Some((this.get_number(range.start)?, this.get_number(range.end)?, step))),
+        _ => Ok(None),
+    }
+}
+
+/// True if the range was created with a step.
+pub(crate) fn is_stepped_range<Data: GarnishData>(this: &Data, addr: Data::Size) -> Result<bool, RuntimeError<Data::Error>> {
+    Ok(this.get_stepped_range(addr)?.step.is_some())
+}
+
+/// Value of the item at index of a range starting at start and counting by step.
+pub(crate) fn range_item<Data: GarnishData>(start: Data::Number, step: Data::Number, index: Data::Number) -> Result<Data::Number, RuntimeError<Data::Error>> {
+    start.plus(index.multiply(step).or_num_err()?).or_num_err()
+}
+
+/// Lazily produces the len items of a range starting at start and counting by step.
+pub(crate) fn range_numbers<Data: GarnishData>(
+    start: Data::Number,
+    step: Data::Number,
+    len: Data::Number,
+) -> impl Iterator<Item = Result<Data::Number, RuntimeError<Data::Error>>> {
+    Data::DataFactory::make_number_iterator_range(Data::Number::zero(), len).map(move |index| range_item::<Data>(start.clone(), step.clone(), index))
+}
+
+/// Adds a range with the given values, only storing the step when it isn't one.
+pub(crate) fn add_stepped_range<Data: GarnishData>(
+    this: &mut Data,
+    start: Data::Number,
+    end: Data::Number,
+    step: Data::Number,
+) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    let start = this.add_number(start)?;
+    let end = this.add_number(end)?;
+    let range = this.add_range(start, end)?;
+
+    match step == Data::Number::one() {
+        true => Ok(range),
+        false => {
+            let step = this.add_number(step)?;
+            Ok(this.add_range(range, step)?)
+        }
+    }
 }
 
 fn make_range_internal<Data: GarnishData>(
@@ -39,14 +133,25 @@ fn make_range_internal<Data: GarnishData>(
             };
 
             let right_addr = if end_exclusive {
-                right_addr
+                this.add_number(this.get_number(right_addr)?.decrement().or_num_err()?)?
             } else {
-                this.add_number(this.get_number(right_addr)?.increment().or_num_err()?)?
+                right_addr
             };
 
             let addr = this.add_range(left_addr, right_addr)?;
             this.push_register(addr)?;
         }
+        (GarnishDataType::Range, GarnishDataType::Number) if !start_exclusive && !end_exclusive => {
+            // a range followed by another end is a step, only valid on contiguous number ranges
+            let step = this.get_number(right_addr.clone())?;
+            match (is_stepped_range(this, left_addr.clone())?, range_parts(this, left_addr.clone())?) {
+                (false, Some(_)) if step != Data::Number::zero() => {
+                    let addr = this.add_range(left_addr, right_addr)?;
+                    this.push_register(addr)?;
+                }
+                _ => push_unit(this)?,
+            }
+        }
         _ => {
             push_unit(this)?;
         }
@@ -1,6 +1,6 @@
-use garnish_lang_traits::{ErrorType, GarnishDataType, GarnishData, RuntimeError};
+use garnish_lang_traits::{ErrorType, GarnishDataType, GarnishData, Instruction, RuntimeError};
 use crate::runtime::list::get_access_addr;
-use crate::runtime::utilities::push_unit;
+use crate::runtime::utilities::{check_strict, push_unit};
 
 pub fn resolve<Data: GarnishData>(
     this: &mut Data,
@@ -29,12 +29,15 @@ pub fn resolve<Data: GarnishData>(
     // check context
     match this.get_data_type(data.clone())? {
         GarnishDataType::Symbol => {
-            match this.resolve(this.get_symbol(data)?)? {
+            let symbol = this.get_symbol(data)?;
+            match this.resolve(symbol.clone())? {
                 true => return Ok(None), // context resolved end look up
                 false => (),           // not resolved fall through
             }
+
+            check_strict(this, format!("No value found for {:?} of Symbol {}", Instruction::Resolve, symbol).as_str())?;
         }
-        _ => (), // not a symbol push unit below
+        t => check_strict(this, format!("No value found for {:?} of {:?}", Instruction::Resolve, t).as_str())?,
     }
 
     // default to unit
@@ -1,7 +1,8 @@
 // use log::trace;
 
 use crate::runtime::range::range_len;
-use garnish_lang_traits::{GarnishDataType, GarnishData, RuntimeError};
+use garnish_lang_traits::{Extents, GarnishDataType, GarnishData, GarnishNumber, Instruction, RuntimeError, TypeConstants};
+use crate::runtime::error::OrNumberError;
 use crate::runtime::error::state_error;
 
 pub(crate) fn next_ref<Data: GarnishData>(this: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
@@ -28,7 +29,13 @@ pub(crate) fn get_range<Data: GarnishData>(
         (s, e) => state_error(format!("Invalid range values {:?} {:?}", s, e))?,
     };
 
-    Ok((start.clone(), end.clone(), range_len::<Data>(start, end)?))
+    Ok((start.clone(), end.clone(), range_len::<Data>(start, end, Data::Number::one())?))
+}
+
+/// Extents of the items covered by a slice's range, ranges include their end while extents don't.
+pub(crate) fn get_range_extents<Data: GarnishData>(this: &Data, addr: Data::Size) -> Result<Extents<Data::Number>, RuntimeError<Data::Error>> {
+    let (start, end, _) = get_range(this, addr)?;
+    Ok(Extents::new(start, end.increment().or_num_err()?))
 }
 
 // push utilities
@@ -38,6 +45,76 @@ pub(crate) fn push_unit<Data: GarnishData>(this: &mut Data) -> Result<(), Runtim
     Ok(())
 }
 
+/// Raises an error with the given message and the current instruction address when the data is in strict mode.
+pub(crate) fn check_strict<Data: GarnishData>(this: &Data, message: &str) -> Result<(), RuntimeError<Data::Error>> {
+    match this.strict_mode() {
+        true => Err(RuntimeError::strict(format!("{} at instruction {:?}", message, this.get_instruction_cursor()))),
+        false => Ok(()),
+    }
+}
+
+fn type_names(types: &[GarnishDataType]) -> String {
+    types.iter().map(|t| format!("{:?}", t)).collect::<Vec<String>>().join(" and ")
+}
+
+/// Errors in strict mode for an operation that isn't defined for the given operand types.
+pub(crate) fn check_unsupported<Data: GarnishData>(
+    this: &Data,
+    operation: Instruction,
+    types: &[GarnishDataType],
+) -> Result<(), RuntimeError<Data::Error>> {
+    check_strict(this, format!("Unsupported types for {:?}, {}", operation, type_names(types)).as_str())
+}
+
+/// Pushes Unit for an operation that isn't defined for the given operand types.
+pub(crate) fn push_unsupported<Data: GarnishData>(
+    this: &mut Data,
+    operation: Instruction,
+    types: &[GarnishDataType],
+) -> Result<(), RuntimeError<Data::Error>> {
+    check_unsupported(this, operation, types)?;
+    push_unit(this)
+}
+
+/// Pushes Unit for an operation that has no result for its operand values, such as dividing by zero or overflowing.
+pub(crate) fn push_undefined<Data: GarnishData>(
+    this: &mut Data,
+    operation: Instruction,
+    types: &[GarnishDataType],
+) -> Result<(), RuntimeError<Data::Error>> {
+    check_strict(this, format!("No result for {:?} of {}", operation, type_names(types)).as_str())?;
+    push_unit(this)
+}
+
+/// Pushes Unit for an access that found no value.
+pub(crate) fn push_missing<Data: GarnishData>(
+    this: &mut Data,
+    operation: Instruction,
+    left: GarnishDataType,
+    right: GarnishDataType,
+) -> Result<(), RuntimeError<Data::Error>> {
+    check_strict(this, format!("No value found for {:?} of {:?} with {:?}", operation, left, right).as_str())?;
+    push_unit(this)
+}
+
+/// Pushes an Error with the given code and message when the data has error values enabled, Unit otherwise.
+pub(crate) fn push_failure<Data>(this: &mut Data, code: &str, message: String) -> Result<(), RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
+    check_strict(this, message.as_str())?;
+
+    if !this.error_values() {
+        return push_unit(this);
+    }
+
+    let message = this.add_char_list(message.chars().map(Data::Char::from).collect())?;
+    let code = this.parse_add_symbol(code)?;
+    this.add_error(message, code).and_then(|v| this.push_register(v))?;
+    Ok(())
+}
+
 pub(crate) fn push_number<Data: GarnishData>(this: &mut Data, value: Data::Number) -> Result<(), RuntimeError<Data::Error>> {
     this.add_number(value).and_then(|v| this.push_register(v))?;
     Ok(())
@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod tests {
+    use crate::basic::testing_utilities::{host_functions, run_script_with};
+    use garnish_lang::simple::BasicDataFactory;
+    use garnish_lang::{GarnishData, GarnishDataFactory};
+
+    #[test]
+    fn guarded_value_without_error() {
+        let data = run_script_with("add <~ (2, 3) !? 0", host_functions(), |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(result).unwrap(), 5.into());
+    }
+
+    #[test]
+    fn catch_value_replaces_failed_expression() {
+        let data = run_script_with("(add <~ (\"a\", 3) !? 0) + 1", host_functions(), |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(result).unwrap(), 1.into());
+    }
+
+    #[test]
+    fn catch_receives_error_value() {
+        let data = run_script_with("add <~ (\"a\", 3) !? $.message", host_functions(), |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(
+            data.get_string_for_data_at(result).unwrap(),
+            "Host function 'add' expected integer Number for argument 1, got CharList (Host function argument 1 has unexpected type: CharList)"
+        );
+    }
+
+    #[test]
+    fn error_value_has_type_and_instruction() {
+        let data = run_script_with("add <~ (\"a\", 3) !? ($.type, $.instruction)", host_functions(), |_| {});
+
+        let result = data.get_current_value().unwrap();
+        let error_type = data.get_list_item(result, 0.into()).unwrap().unwrap();
+        let instruction = data.get_list_item(result, 1.into()).unwrap().unwrap();
+        assert_eq!(data.get_symbol(error_type).unwrap(), BasicDataFactory::parse_symbol("Unknown").unwrap());
+        assert_eq!(data.get_number(instruction).unwrap(), 5.into());
+    }
+
+    #[test]
+    fn catch_inside_applied_expression_restores_frames() {
+        let data = run_script_with("f := { add <~ ($, 3) }; (f <~ \"a\" !? 10) + (f <~ 2 !? 10)", host_functions(), |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(result).unwrap(), 15.into());
+    }
+
+    #[test]
+    fn nested_catch_uses_inner_handler() {
+        let data = run_script_with("((add <~ (\"a\", 3) !? 1) + (add <~ (\"b\", 3))) !? 100", host_functions(), |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(result).unwrap(), 100.into());
+    }
+
+    #[test]
+    fn catch_keeps_locals() {
+        let data = run_script_with("x := 5; y := add <~ (\"a\", 3) !? x + 1; x + y", host_functions(), |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(result).unwrap(), 11.into());
+    }
+}
@@ -0,0 +1,42 @@
+#[cfg(test)]
+mod tests {
+    use crate::basic::testing_utilities::{compile_script, execute_to_end};
+    use garnish_lang::simple::CollectionSettings;
+    use garnish_lang::GarnishData;
+
+    const SCRIPT: &str = "{ $.count < 50 ?> ^~ :count = $.count + 1 :result = $.result + 2 } <~ :count = 0 :result = 1\n\n$.result";
+    const LIST_SCRIPT: &str = "{ $.count < 40 ?> ^~ :count = $.count + 1 :items = (($.count, $.count * 2, 7) ~* { $ + 1 } ~< { $.0 > $.1 }) } <~ :count = 0 :items = (,)\n\n$.items";
+
+    #[test]
+    fn runtime_collects_at_safe_points() {
+        let mut expected = compile_script(SCRIPT);
+        execute_to_end(&mut expected);
+
+        let mut data = compile_script(SCRIPT);
+        data.retain_all_current_data();
+        data.set_collection_settings(CollectionSettings::new(64, 16));
+        execute_to_end(&mut data);
+
+        assert!(data.longest_collection_step() > 0);
+        assert!(data.data_size() < expected.data_size());
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(result).unwrap(), 101.into());
+    }
+
+    #[test]
+    fn small_steps_keep_data_changed_during_a_cycle() {
+        let mut expected = compile_script(LIST_SCRIPT);
+        execute_to_end(&mut expected);
+        let expected_result = expected.get_current_value().unwrap();
+
+        let mut data = compile_script(LIST_SCRIPT);
+        data.retain_all_current_data();
+        data.set_collection_settings(CollectionSettings::new(8, 4));
+        execute_to_end(&mut data);
+
+        assert!(data.longest_collection_step() > 0);
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_string_for_data_at(result), expected.get_string_for_data_at(expected_result));
+    }
+}
@@ -0,0 +1,50 @@
+#[cfg(test)]
+mod tests {
+    use crate::basic::testing_utilities::run_script;
+    use garnish_lang::simple::BasicDataFactory;
+    use garnish_lang::{GarnishData, GarnishDataFactory, GarnishDataType};
+
+    #[test]
+    fn successful_cast_is_unchanged() {
+        let data = run_script("\"12\" ~# 0", |data| data.set_error_values(true));
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(result).unwrap(), 12.into());
+    }
+
+    #[test]
+    fn failed_cast_is_error() {
+        let data = run_script("\"abc\" ~# 0", |data| data.set_error_values(true));
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_data_type(result).unwrap(), GarnishDataType::Error);
+        assert_eq!(
+            data.get_string_for_data_at(result).unwrap(),
+            "[Error :cast Could not cast CharList to Number]"
+        );
+    }
+
+    #[test]
+    fn error_propagates_through_operators() {
+        let data = run_script("((\"abc\" ~# 0) + 5 * 2).message", |data| data.set_error_values(true));
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_string_for_data_at(result).unwrap(), "Could not cast CharList to Number");
+    }
+
+    #[test]
+    fn error_matches_type_pattern() {
+        let data = run_script("{ e #= Error => e.code |> :ok } <~ (\"abc\" ~# 0)", |data| data.set_error_values(true));
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_symbol(result).unwrap(), BasicDataFactory::parse_symbol("cast").unwrap());
+    }
+
+    #[test]
+    fn missing_key_is_unit() {
+        let data = run_script("(:a = 1, :b = 2).c", |data| data.set_error_values(true));
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_data_type(result).unwrap(), GarnishDataType::Unit);
+    }
+}
@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+    use crate::basic::testing_utilities::{host_functions, run_script_with};
+    use garnish_lang::GarnishData;
+
+    #[test]
+    fn call_with_list_arguments() {
+        let data = run_script_with("add <~ (2, 3)", host_functions(), |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(result).unwrap(), 5.into());
+    }
+
+    #[test]
+    fn call_with_omitted_optional_argument() {
+        let data = run_script_with("repeat <~ \"ab\"", host_functions(), |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_string_for_data_at(result).unwrap(), "abab");
+    }
+
+    #[test]
+    fn call_with_list_argument() {
+        let data = run_script_with("total <~ (1, 2, 3.5)", host_functions(), |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(result).unwrap(), 6.5.into());
+    }
+
+    #[test]
+    fn nested_calls() {
+        let data = run_script_with("add <~ ((add <~ (1, 2)), 4)", host_functions(), |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(result).unwrap(), 7.into());
+    }
+}
@@ -0,0 +1,130 @@
+#[cfg(test)]
+mod tests {
+    use crate::basic::testing_utilities::{run_script, run_script_with};
+    use garnish_lang::GarnishData;
+    use garnish_lang::simple::{BasicDataCompanion, BasicDataCustom, BasicGarnishData, HostFunctionCompanion};
+
+    fn result_string<T, Companion>(data: &BasicGarnishData<T, Companion>) -> String
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        let result = data.get_current_value().unwrap();
+        data.get_string_for_data_at(result).unwrap()
+    }
+
+    #[test]
+    fn map_list() {
+        let data = run_script("(1, 2, 3) ~* { $ * 10 }", |_| {});
+
+        assert_eq!(result_string(&data), "10 20 30");
+    }
+
+    #[test]
+    fn filter_range() {
+        let data = run_script("(1..10) ~? { $ % 3 == 0 }", |_| {});
+
+        assert_eq!(result_string(&data), "3 6 9");
+    }
+
+    #[test]
+    fn fold_with_initial_value() {
+        let data = run_script("(\"abc\" ~+ (\"\" = { $.1 <> $.0 })) ~# \"\"", |_| {});
+
+        assert_eq!(result_string(&data), "cba");
+    }
+
+    #[test]
+    fn nested_iteration() {
+        let data = run_script("(1, 2) ~* { n := $; (1, 2) ~* { $ * n } }", |_| {});
+
+        assert_eq!(result_string(&data), "(1 2) (2 4)");
+    }
+
+    #[test]
+    fn map_with_host_function() {
+        let mut companion = HostFunctionCompanion::new();
+        companion.register("double", |a: i32| a * 2);
+
+        let data: BasicGarnishData<(), HostFunctionCompanion> = run_script_with("(1, 2, 3) ~* double", companion, |_| {});
+
+        assert_eq!(result_string(&data), "2 4 6");
+    }
+
+    #[test]
+    fn find_without_match_is_unit() {
+        let data = run_script("(1, 2, 3) ~@ { $ > 5 }", |_| {});
+
+        assert_eq!(result_string(&data), "()");
+    }
+
+    #[test]
+    fn error_in_expression_is_caught_outside_iteration() {
+        let data = run_script("((1, 2) ~* { $ + :a } !? $.type), 5", |data| data.set_strict_mode(true));
+
+        assert_eq!(result_string(&data), ":Strict 5");
+    }
+
+    #[test]
+    fn sort_numbers() {
+        let data = run_script("(3, 1.5, 2, 1) ~< ()", |_| {});
+
+        assert_eq!(result_string(&data), "1 1.5 2 3");
+    }
+
+    #[test]
+    fn sort_char_lists_lexicographically() {
+        let data = run_script("(\"banana\", \"apple\", \"app\") ~< ()", |_| {});
+
+        assert_eq!(result_string(&data), "app apple banana");
+    }
+
+    #[test]
+    fn sort_lists_element_wise() {
+        let data = run_script("((2, 1), (1, 2, 3), (1, 2)) ~< ()", |_| {});
+
+        assert_eq!(result_string(&data), "(1 2) (1 2 3) (2 1)");
+    }
+
+    #[test]
+    fn sort_symbols_by_name() {
+        let data = run_script("(:cherry, :apple, :banana) ~< ()", |_| {});
+
+        assert_eq!(result_string(&data), ":apple :banana :cherry");
+    }
+
+    #[test]
+    fn sort_with_comparator() {
+        let data = run_script("(3, 1, 4, 1, 5, 9, 2, 6) ~< { $.0 > $.1 }", |_| {});
+
+        assert_eq!(result_string(&data), "9 6 5 4 3 2 1 1");
+    }
+
+    #[test]
+    fn sort_with_comparator_is_stable() {
+        let data = run_script("((1, :a), (0, :b), (1, :c), (0, :d)) ~< { $.0.0 < $.1.0 }", |_| {});
+
+        assert_eq!(result_string(&data), "(0 :b) (0 :d) (1 :a) (1 :c)");
+    }
+
+    #[test]
+    fn sort_with_comparator_over_several_passes() {
+        let data = run_script("(7, 3, 9, 1, 8, 2, 6, 4, 5, 0, 11, 10, 12) ~< { $.0 < $.1 }", |_| {});
+
+        assert_eq!(result_string(&data), "0 1 2 3 4 5 6 7 8 9 10 11 12");
+    }
+
+    #[test]
+    fn sort_range_with_comparator() {
+        let data = run_script("(1..6) ~< { $.0 > $.1 }", |_| {});
+
+        assert_eq!(result_string(&data), "6 5 4 3 2 1");
+    }
+
+    #[test]
+    fn sort_empty_list() {
+        let data = run_script("(,) ~< { $.0 < $.1 }", |_| {});
+
+        assert_eq!(result_string(&data), "");
+    }
+}
@@ -0,0 +1,93 @@
+mod catch;
+mod collection;
+mod error;
+mod host_functions;
+mod iteration;
+mod number;
+mod snapshot;
+mod strict;
+
+#[cfg(test)]
+pub mod testing_utilities {
+    use garnish_lang::compiler::build::build;
+    use garnish_lang::compiler::lex::lex;
+    use garnish_lang::compiler::parse::parse;
+    use garnish_lang::simple::{
+        BasicDataCompanion, BasicDataCustom, BasicGarnishData, BasicGarnishDataUnit, HostFunctionCompanion, NoOpCompanion, SimpleRuntimeState,
+        execute_current_instruction,
+    };
+    use garnish_lang::GarnishData;
+
+    pub fn compile_script(script: &str) -> BasicGarnishDataUnit {
+        compile_script_with(script, NoOpCompanion::new())
+    }
+
+    pub fn compile_script_with<T, Companion>(script: &str, companion: Companion) -> BasicGarnishData<T, Companion>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        let mut data = BasicGarnishData::new(companion).unwrap();
+        let tokens = lex(script).unwrap();
+        let parse_result = parse(&tokens).unwrap();
+        build(parse_result.get_root(), parse_result.get_nodes().clone(), &mut data).unwrap();
+
+        let start = data.get_from_jump_table(0).unwrap();
+        data.set_instruction_cursor(start).unwrap();
+        let unit = data.add_unit().unwrap();
+        data.push_value_stack(unit).unwrap();
+
+        data
+    }
+
+    pub fn execute_steps<T, Companion>(data: &mut BasicGarnishData<T, Companion>, steps: usize) -> SimpleRuntimeState
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        for _ in 0..steps {
+            if execute_current_instruction(data).unwrap().get_state() == SimpleRuntimeState::End {
+                return SimpleRuntimeState::End;
+            }
+        }
+
+        SimpleRuntimeState::Running
+    }
+
+    pub fn execute_to_end<T, Companion>(data: &mut BasicGarnishData<T, Companion>)
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        while execute_steps(data, 1) == SimpleRuntimeState::Running {}
+    }
+
+    /// Compiles and runs a script to the end, calling `configure` on the data before executing.
+    pub fn run_script(script: &str, configure: impl FnOnce(&mut BasicGarnishDataUnit)) -> BasicGarnishDataUnit {
+        run_script_with(script, NoOpCompanion::new(), configure)
+    }
+
+    pub fn run_script_with<T, Companion>(
+        script: &str,
+        companion: Companion,
+        configure: impl FnOnce(&mut BasicGarnishData<T, Companion>),
+    ) -> BasicGarnishData<T, Companion>
+    where
+        T: BasicDataCustom,
+        Companion: BasicDataCompanion<T>,
+    {
+        let mut data = compile_script_with(script, companion);
+        configure(&mut data);
+        execute_to_end(&mut data);
+        data
+    }
+
+    /// Companion with the host functions used by scripts in these tests.
+    pub fn host_functions() -> HostFunctionCompanion {
+        let mut companion = HostFunctionCompanion::new();
+        companion.register("add", |a: i32, b: i32| a + b);
+        companion.register("repeat", |text: String, count: Option<i32>| text.repeat(count.unwrap_or(2) as usize));
+        companion.register("total", |items: Vec<f64>| items.iter().sum::<f64>());
+        companion
+    }
+}
@@ -0,0 +1,38 @@
+#[cfg(test)]
+mod tests {
+    use crate::basic::testing_utilities::run_script;
+    use garnish_lang::simple::{BasicGarnishDataUnit, NoOpCompanion, OverflowPolicy, SimpleNumber};
+    use garnish_lang::{GarnishData, GarnishDataType};
+
+    #[test]
+    fn overflow_is_unit_by_default() {
+        let data = run_script("9223372036854775807 + 1", |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_data_type(result).unwrap(), GarnishDataType::Unit);
+    }
+
+    #[test]
+    fn overflow_promotes_to_float_when_set() {
+        let data = run_script("9223372036854775807 + 1, 9223372036854775807 * 2", |data| {
+            data.set_overflow_policy(OverflowPolicy::PromoteToFloat)
+        });
+
+        let result = data.get_current_value().unwrap();
+        let sum = data.get_list_item(result, 0.into()).unwrap().unwrap();
+        let product = data.get_list_item(result, 1.into()).unwrap().unwrap();
+        assert_eq!(data.get_number(sum).unwrap(), SimpleNumber::Float(i64::MAX as f64 + 1.0));
+        assert_eq!(data.get_number(product).unwrap(), SimpleNumber::Float(i64::MAX as f64 * 2.0));
+    }
+
+    #[test]
+    fn parsing_uses_overflow_policy() {
+        let mut data = BasicGarnishDataUnit::new(NoOpCompanion::new()).unwrap();
+        assert!(data.parse_add_number("9223372036854775808").is_err());
+
+        data.set_overflow_policy(OverflowPolicy::PromoteToFloat);
+        let addr = data.parse_add_number("9223372036854775808").unwrap();
+
+        assert_eq!(data.get_number(addr).unwrap(), SimpleNumber::Float(9223372036854775808.0));
+    }
+}
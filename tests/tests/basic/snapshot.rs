@@ -0,0 +1,47 @@
+#[cfg(test)]
+mod tests {
+    use crate::basic::testing_utilities::{compile_script, execute_steps, execute_to_end};
+    use garnish_lang::simple::{BasicGarnishDataUnit, NoOpCompanion, SimpleRuntimeState};
+    use garnish_lang::GarnishData;
+
+    const SCRIPT: &str = "{ $.count < 5 ?> ^~ :count = $.count + 1 :result = $.result * 2 } <~ :count = 0 :result = 1\n\n$.result";
+
+    #[test]
+    fn restored_mid_execution_produces_same_result() {
+        let mut original = compile_script(SCRIPT);
+        assert_eq!(execute_steps(&mut original, 40), SimpleRuntimeState::Running);
+
+        let bytes = original.snapshot().unwrap();
+        let mut restored = BasicGarnishDataUnit::from_snapshot(&bytes, NoOpCompanion::new()).unwrap();
+
+        assert_eq!(restored, original);
+
+        execute_to_end(&mut original);
+        execute_to_end(&mut restored);
+
+        assert_eq!(restored, original);
+
+        let result = restored.get_current_value().unwrap();
+        assert_eq!(restored.get_number(result).unwrap(), 32.into());
+    }
+
+    #[test]
+    fn restored_at_every_step_produces_same_result() {
+        let mut expected = compile_script(SCRIPT);
+        execute_to_end(&mut expected);
+
+        let mut steps = 0;
+        loop {
+            let mut data = compile_script(SCRIPT);
+            if execute_steps(&mut data, steps) == SimpleRuntimeState::End {
+                break;
+            }
+
+            let mut restored = BasicGarnishDataUnit::from_snapshot(&data.snapshot().unwrap(), NoOpCompanion::new()).unwrap();
+            execute_to_end(&mut restored);
+
+            assert_eq!(restored, expected, "restoring after {} steps", steps);
+            steps += 1;
+        }
+    }
+}
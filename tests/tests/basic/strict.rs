@@ -0,0 +1,80 @@
+#[cfg(test)]
+mod tests {
+    use crate::basic::testing_utilities::{compile_script, run_script};
+    use garnish_lang::simple::{BasicDataFactory, SimpleRuntimeState, execute_current_instruction};
+    use garnish_lang::{ErrorType, GarnishData, GarnishDataFactory, GarnishDataType};
+
+    #[test]
+    fn supported_operations_run() {
+        let data = run_script("x := (:a = 1, :b = 2); x.b + 3", |data| data.set_strict_mode(true));
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_number(result).unwrap(), 5.into());
+    }
+
+    #[test]
+    fn unresolved_symbol_raises() {
+        let mut data = compile_script("5 + missing");
+        data.set_strict_mode(true);
+
+        let err = loop {
+            match execute_current_instruction(&mut data) {
+                Err(e) => break e,
+                Ok(info) => assert_eq!(info.get_state(), SimpleRuntimeState::Running),
+            }
+        };
+
+        assert_eq!(err.get_type(), ErrorType::Strict);
+        assert!(err.get_message().starts_with("No value found for Resolve of Symbol"));
+    }
+
+    #[test]
+    fn strict_errors_can_be_caught() {
+        let data = run_script("(5 + :a !? $.message), (5 + :a !? $.type)", |data| data.set_strict_mode(true));
+
+        let result = data.get_current_value().unwrap();
+        let message = data.get_list_item(result, 0.into()).unwrap().unwrap();
+        let error_type = data.get_list_item(result, 1.into()).unwrap().unwrap();
+        assert_eq!(
+            data.get_string_for_data_at(message).unwrap(),
+            "Unsupported types for Add, Number and Symbol at instruction 3"
+        );
+        assert_eq!(data.get_symbol(error_type).unwrap(), BasicDataFactory::parse_symbol("Strict").unwrap());
+    }
+
+    #[test]
+    fn undefined_arithmetic_raises() {
+        let script = "(1 / 0 !? $.message), (9223372036854775807 + 1 !? $.message)";
+        let data = run_script(script, |data| data.set_strict_mode(true));
+
+        let result = data.get_current_value().unwrap();
+        let divide = data.get_list_item(result, 0.into()).unwrap().unwrap();
+        let overflow = data.get_list_item(result, 1.into()).unwrap().unwrap();
+        let (divide, overflow) = (
+            data.get_string_for_data_at(divide).unwrap(),
+            data.get_string_for_data_at(overflow).unwrap(),
+        );
+        assert!(divide.starts_with("No result for Divide of Number and Number"));
+        assert!(overflow.starts_with("No result for Add of Number and Number"));
+    }
+
+    #[test]
+    fn unsupported_comparison_raises() {
+        let data = run_script("\"a\" < 1 !? $.message", |data| data.set_strict_mode(true));
+
+        let result = data.get_current_value().unwrap();
+        assert!(
+            data.get_string_for_data_at(result)
+                .unwrap()
+                .starts_with("Unsupported types for LessThan, CharList and Number")
+        );
+    }
+
+    #[test]
+    fn unsupported_comparison_is_false_without_strict_mode() {
+        let data = run_script("\"a\" < 1", |_| {});
+
+        let result = data.get_current_value().unwrap();
+        assert_eq!(data.get_data_type(result).unwrap(), GarnishDataType::False);
+    }
+}
@@ -1,3 +1,4 @@
+mod basic;
 mod runtime_impls;
 mod simple;
 
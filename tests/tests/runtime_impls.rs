@@ -17,7 +17,10 @@ impl<Data: GarnishData> SimpleGarnishRuntime<Data> {
         self.data
     }
 
-    pub fn execute_current_instruction(&mut self) -> Result<SimpleRuntimeInfo, RuntimeError<Data::Error>> {
+    pub fn execute_current_instruction(&mut self) -> Result<SimpleRuntimeInfo, RuntimeError<Data::Error>>
+    where
+        Data::Char: From<char>,
+    {
         execute_current_instruction(self.get_data_mut())
     }
 }
@@ -25,6 +28,7 @@ impl<Data: GarnishData> SimpleGarnishRuntime<Data> {
 impl<Data> GarnishRuntime<Data> for SimpleGarnishRuntime<Data>
 where
     Data: GarnishData,
+    Data::Char: From<char>,
 {
     fn get_data(&self) -> &Data {
         &self.data
@@ -212,6 +216,10 @@ where
         make_list(self.get_data_mut(), len)
     }
 
+    fn make_closure(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        make_closure(self.get_data_mut())
+    }
+
     fn access(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
         access(self.get_data_mut())
     }
@@ -284,6 +292,14 @@ where
         update_value(self.get_data_mut())
     }
 
+    fn put_local(&mut self, index: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        put_local(self.get_data_mut(), index)
+    }
+
+    fn update_local(&mut self, index: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        update_local(self.get_data_mut(), index)
+    }
+
     //
     // Side Effect
     //
@@ -296,6 +312,58 @@ where
         end_side_effect(self.get_data_mut())
     }
 
+    //
+    // Try
+    //
+
+    fn start_try(&mut self, index: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        start_try(self.get_data_mut(), index)
+    }
+
+    fn end_try(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        end_try(self.get_data_mut())
+    }
+
+    fn end_catch(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        end_catch(self.get_data_mut())
+    }
+
+    //
+    // Iteration
+    //
+
+    fn start_iteration(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        start_iteration(self.get_data_mut())
+    }
+
+    fn map(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        map(self.get_data_mut())
+    }
+
+    fn filter(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        filter(self.get_data_mut())
+    }
+
+    fn fold(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        fold(self.get_data_mut())
+    }
+
+    fn any(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        any(self.get_data_mut())
+    }
+
+    fn all(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        all(self.get_data_mut())
+    }
+
+    fn find(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        find(self.get_data_mut())
+    }
+
+    fn sort(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>> {
+        sort(self.get_data_mut())
+    }
+
     //
     // Resolve
     //
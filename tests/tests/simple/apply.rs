@@ -36,6 +36,62 @@ mod tests {
         assert_eq!(next.unwrap(), i1);
     }
 
+    #[test]
+    fn apply_closure() {
+        let mut runtime = create_simple_runtime();
+
+        let exp1 = runtime.get_data_mut().add_expression(0).unwrap();
+        let int1 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        let int2 = runtime.get_data_mut().add_number(20.into()).unwrap();
+        let list = runtime.get_data_mut().start_list(1).unwrap();
+        runtime.get_data_mut().add_to_list(list, int1).unwrap();
+        let captures = runtime.get_data_mut().end_list(list).unwrap();
+        let closure = runtime.get_data_mut().add_closure(exp1, captures).unwrap();
+
+        // 1
+        let i1 = runtime.get_data_mut().push_instruction(Instruction::PutLocal, Some(0)).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::PutValue, None).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::Add, None).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::EndExpression, None).unwrap();
+
+        // 5
+        runtime.get_data_mut().push_instruction(Instruction::Put, Some(closure)).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::Put, Some(int2)).unwrap();
+        let i2 = runtime.get_data_mut().push_instruction(Instruction::Apply, None).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::EndExpression, None).unwrap();
+
+        runtime.get_data_mut().push_to_jump_table(i1).unwrap();
+
+        runtime.get_data_mut().push_register(closure).unwrap();
+        runtime.get_data_mut().push_register(int2).unwrap();
+
+        runtime.get_data_mut().set_instruction_cursor(i2).unwrap();
+
+        let next = runtime.apply().unwrap();
+
+        assert_eq!(runtime.get_data_mut().get_value(0).unwrap(), int2);
+        assert_eq!(runtime.get_data_mut().get_local(0), Some(int1));
+        assert_eq!(next.unwrap(), i1);
+    }
+
+    #[test]
+    fn make_closure() {
+        let mut runtime = create_simple_runtime();
+
+        let exp1 = runtime.get_data_mut().add_expression(0).unwrap();
+        let list = runtime.get_data_mut().start_list(0).unwrap();
+        let captures = runtime.get_data_mut().end_list(list).unwrap();
+
+        runtime.get_data_mut().push_register(exp1).unwrap();
+        runtime.get_data_mut().push_register(captures).unwrap();
+
+        runtime.make_closure().unwrap();
+
+        let closure = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data_mut().get_data_type(closure).unwrap(), GarnishDataType::Closure);
+        assert_eq!(runtime.get_data_mut().get_closure(closure).unwrap(), (exp1, captures));
+    }
+
     #[test]
     fn empty_apply() {
         let mut runtime = create_simple_runtime();
@@ -132,8 +188,10 @@ mod tests {
 
 #[cfg(test)]
 mod slices {
-    use crate::simple::testing_utilities::{add_concatenation_with_start, add_list, add_range, create_simple_runtime};
-    use garnish_lang::{GarnishData, GarnishRuntime};
+    use crate::simple::testing_utilities::{
+        add_char_list, add_concatenation_with_start, add_list, add_range, add_stepped_range, create_simple_runtime,
+    };
+    use garnish_lang::{GarnishData, GarnishDataType, GarnishRuntime};
 
     #[test]
     fn create_with_list() {
@@ -255,4 +313,66 @@ mod slices {
         assert_eq!(runtime.get_data_mut().get_number(start).unwrap(), 6.into());
         assert_eq!(runtime.get_data_mut().get_number(end).unwrap(), 14.into());
     }
+
+    #[test]
+    fn stepped_range_collects_items_into_list() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_list(runtime.get_data_mut(), 10);
+        let d2 = add_stepped_range(runtime.get_data_mut(), 1, 7, 3);
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.apply().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        let data = runtime.get_data();
+        assert_eq!(data.get_data_type(i).unwrap(), GarnishDataType::List);
+        assert_eq!(data.get_list_len(i).unwrap(), 3);
+        for (index, expected) in [(0, 1), (1, 4), (2, 7)] {
+            assert_eq!(
+                data.get_list_item(i, index.into()).unwrap(),
+                data.get_list_item(d1, expected.into()).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn stepped_range_of_char_list_is_char_list() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_char_list(runtime.get_data_mut(), "abcdef");
+        let d2 = add_stepped_range(runtime.get_data_mut(), 5, 0, -2);
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.apply().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data().get_data().get(i).unwrap().as_char_list().unwrap(), "fdb");
+    }
+
+    #[test]
+    fn narrow_with_invalid_range() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_list(runtime.get_data_mut(), 10);
+        let d2 = add_range(runtime.get_data_mut(), 1, 5);
+        let d3 = runtime.get_data_mut().add_slice(d1, d2).unwrap();
+        let start = runtime.get_data_mut().add_char('a').unwrap();
+        let end = runtime.get_data_mut().add_char('c').unwrap();
+        let d4 = runtime.get_data_mut().add_range(start, end).unwrap();
+
+        runtime.get_data_mut().push_register(d3).unwrap();
+        runtime.get_data_mut().push_register(d4).unwrap();
+
+        let result = runtime.apply();
+
+        assert_eq!(
+            result.unwrap_err().get_message(),
+            "Attempting to create slice from slice with an invalid range. Slice range starting with Number. Range Char Char"
+        );
+    }
 }
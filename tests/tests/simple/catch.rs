@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use crate::simple::testing_utilities::create_simple_runtime;
+    use garnish_lang::simple::SimpleDataFactory;
+    use garnish_lang::simple::ops::catch_error;
+    use garnish_lang::{GarnishData, GarnishDataFactory, GarnishRuntime, Instruction, RuntimeError};
+
+    #[test]
+    fn end_try() {
+        let mut runtime = create_simple_runtime();
+
+        runtime.start_try(0).unwrap();
+        runtime.end_try().unwrap();
+
+        assert!(runtime.end_try().is_err());
+    }
+
+    #[test]
+    fn end_catch() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        runtime.get_data_mut().push_value_stack(d1).unwrap();
+
+        runtime.end_catch().unwrap();
+
+        assert_eq!(runtime.get_data_mut().get_current_value(), None);
+        assert!(runtime.end_catch().is_err());
+    }
+
+    #[test]
+    fn error_without_handler() {
+        let mut runtime = create_simple_runtime();
+
+        runtime.get_data_mut().push_instruction(Instruction::EndSideEffect, None).unwrap();
+
+        let err = runtime.execute_current_instruction().err().unwrap();
+
+        assert_eq!(err.get_message(), &"Could not pop value at end of side effect.".to_string());
+    }
+
+    #[test]
+    fn error_jumps_to_handler() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::StartTry, Some(0)).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::Put, Some(d1)).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::EndSideEffect, None).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::EndTry, None).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::EndExpression, None).unwrap();
+        let handler = runtime.get_data_mut().push_instruction(Instruction::PutValue, None).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::EndCatch, None).unwrap();
+        runtime.get_data_mut().push_to_jump_table(handler).unwrap();
+
+        for _ in 0..3 {
+            runtime.execute_current_instruction().unwrap();
+        }
+
+        assert_eq!(runtime.get_data().get_instruction_cursor(), handler);
+        assert_eq!(runtime.get_data().get_registers().len(), 0);
+        assert!(runtime.end_try().is_err());
+
+        let data = runtime.get_data();
+        let error = data.get_current_value().unwrap();
+        let field = |name: &str| {
+            let item = data
+                .get_list_item_with_symbol(error, SimpleDataFactory::parse_symbol(name).unwrap())
+                .unwrap()
+                .unwrap();
+            data.get_data().get(item).unwrap().clone()
+        };
+
+        assert_eq!(field("message").as_char_list().unwrap(), "Could not pop value at end of side effect.");
+        assert_eq!(field("type").as_symbol().unwrap(), SimpleDataFactory::parse_symbol("Unknown").unwrap());
+        assert_eq!(field("instruction").as_number().unwrap(), 2.into());
+    }
+
+    #[test]
+    fn caught_message_is_kept_verbatim() {
+        let mut runtime = create_simple_runtime();
+
+        let handler = runtime.get_data_mut().push_instruction(Instruction::PutValue, None).unwrap();
+        runtime.get_data_mut().push_to_jump_table(handler).unwrap();
+        runtime.start_try(0).unwrap();
+
+        let error = RuntimeError::new("Expected \"a\\b\"\n");
+        catch_error(runtime.get_data_mut(), error, 0).unwrap();
+
+        let data = runtime.get_data();
+        let value = data.get_current_value().unwrap();
+        let message = data
+            .get_list_item_with_symbol(value, SimpleDataFactory::parse_symbol("message").unwrap())
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(data.get_data().get(message).unwrap().as_char_list().unwrap(), "Expected \"a\\b\"\n");
+    }
+
+    #[test]
+    fn handler_restores_frames() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        runtime.get_data_mut().push_value_stack(d1).unwrap();
+        runtime.get_data_mut().push_handler(0).unwrap();
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_frame(5).unwrap();
+        runtime.get_data_mut().push_value_stack(d1).unwrap();
+
+        assert_eq!(runtime.get_data_mut().unwind_handler().unwrap(), Some(0));
+        assert_eq!(runtime.get_data().get_registers().len(), 0);
+        assert_eq!(runtime.get_data().get_value_stack_len(), 1);
+        assert_eq!(runtime.get_data_mut().pop_frame().unwrap(), None);
+    }
+}
@@ -573,6 +573,10 @@ mod test_data_impl {
             unimplemented!()
         }
 
+        fn cmp_symbol_names(&self, left: Self::Symbol, right: Self::Symbol) -> Result<std::cmp::Ordering, Self::Error> {
+            unimplemented!()
+        }
+
         fn get_expression(&self, addr: Self::Size) -> Result<Self::Size, Self::Error> {
             unimplemented!()
         }
@@ -601,6 +605,14 @@ mod test_data_impl {
             unimplemented!()
         }
 
+        fn get_closure(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error> {
+            unimplemented!()
+        }
+
+        fn get_error(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error> {
+            unimplemented!()
+        }
+
         fn get_list_len(&self, addr: Self::Size) -> Result<Self::Size, Self::Error> {
             unimplemented!()
         }
@@ -720,6 +732,22 @@ mod test_data_impl {
             unimplemented!()
         }
 
+        fn add_closure(&mut self, expression: Self::Size, captures: Self::Size) -> Result<Self::Size, Self::Error> {
+            unimplemented!()
+        }
+
+        fn add_error(&mut self, message: Self::Size, code: Self::Size) -> Result<Self::Size, Self::Error> {
+            unimplemented!()
+        }
+
+        fn add_char_list(&mut self, chars: Vec<Self::Char>) -> Result<Self::Size, Self::Error> {
+            unimplemented!()
+        }
+
+        fn add_byte_list(&mut self, bytes: Vec<Self::Byte>) -> Result<Self::Size, Self::Error> {
+            unimplemented!()
+        }
+
         fn merge_to_symbol_list(&mut self, first: Self::Size, second: Self::Size) -> Result<Self::Size, Self::Error> {
             unimplemented!()
         }
@@ -800,6 +828,26 @@ mod test_data_impl {
             unimplemented!()
         }
 
+        fn get_local(&self, index: Self::Size) -> Option<Self::Size> {
+            unimplemented!()
+        }
+
+        fn set_local(&mut self, index: Self::Size, addr: Self::Size) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn push_handler(&mut self, index: Self::Size) -> Result<(), Self::Error> {
+            unimplemented!()
+        }
+
+        fn pop_handler(&mut self) -> Result<Option<Self::Size>, Self::Error> {
+            unimplemented!()
+        }
+
+        fn unwind_handler(&mut self) -> Result<Option<Self::Size>, Self::Error> {
+            unimplemented!()
+        }
+
         fn add_char_list_from(&mut self, from: Self::Size) -> Result<Self::Size, Self::Error> {
             unimplemented!()
         }
@@ -0,0 +1,205 @@
+#[cfg(test)]
+mod tests {
+    use crate::SimpleGarnishRuntime;
+    use crate::simple::testing_utilities::{add_char_list, create_simple_runtime};
+    use garnish_lang::simple::{SimpleDataFactory, SimpleGarnishData};
+    use garnish_lang::{GarnishData, GarnishDataFactory, GarnishDataType, GarnishRuntime};
+
+    fn create_error_runtime() -> SimpleGarnishRuntime<SimpleGarnishData> {
+        let mut runtime = create_simple_runtime();
+        runtime.get_data_mut().set_error_values(true);
+        runtime
+    }
+
+    fn add_error(runtime: &mut SimpleGarnishRuntime<SimpleGarnishData>, message: &str, code: &str) -> usize {
+        let message = add_char_list(runtime.get_data_mut(), message);
+        let code = runtime.get_data_mut().parse_add_symbol(code).unwrap();
+        runtime.get_data_mut().add_error(message, code).unwrap()
+    }
+
+    fn char_list_string(runtime: &mut SimpleGarnishRuntime<SimpleGarnishData>, addr: usize) -> String {
+        let len = runtime.get_data_mut().get_char_list_len(addr).unwrap();
+        let mut chars = String::new();
+        for i in 0..len {
+            chars.push(runtime.get_data_mut().get_char_list_item(addr, i.into()).unwrap().unwrap());
+        }
+        chars
+    }
+
+    #[test]
+    fn failed_cast_is_unit_by_default() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_char_list(runtime.get_data_mut(), "abc");
+        let d2 = runtime.get_data_mut().add_type(GarnishDataType::Number).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.type_cast().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data_mut().get_data_type(i).unwrap(), GarnishDataType::Unit);
+    }
+
+    #[test]
+    fn failed_cast_is_error() {
+        let mut runtime = create_error_runtime();
+
+        let d1 = add_char_list(runtime.get_data_mut(), "abc");
+        let d2 = runtime.get_data_mut().add_type(GarnishDataType::Number).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.type_cast().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data_mut().get_data_type(i).unwrap(), GarnishDataType::Error);
+
+        let (message, code) = runtime.get_data_mut().get_error(i).unwrap();
+        assert_eq!(char_list_string(&mut runtime, message), "Could not cast CharList to Number");
+        assert_eq!(
+            runtime.get_data_mut().get_symbol(code).unwrap(),
+            SimpleDataFactory::parse_symbol("cast").unwrap()
+        );
+    }
+
+    #[test]
+    fn failed_access_is_error() {
+        let mut runtime = create_error_runtime();
+
+        let d1 = runtime.get_data_mut().add_true().unwrap();
+        let d2 = runtime.get_data_mut().add_number(1.into()).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.access().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        let (message, code) = runtime.get_data_mut().get_error(i).unwrap();
+        assert_eq!(char_list_string(&mut runtime, message), "Could not access True with Number");
+        assert_eq!(
+            runtime.get_data_mut().get_symbol(code).unwrap(),
+            SimpleDataFactory::parse_symbol("access").unwrap()
+        );
+    }
+
+    #[test]
+    fn type_of_error() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_error(&mut runtime, "failed", "cast");
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+
+        runtime.type_of().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data_mut().get_type(i).unwrap(), GarnishDataType::Error);
+    }
+
+    #[test]
+    fn propagates_through_arithmetic() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        let d2 = add_error(&mut runtime, "failed", "cast");
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.add().unwrap();
+
+        assert_eq!(runtime.get_data_mut().get_register(0), Some(d2));
+    }
+
+    #[test]
+    fn propagates_through_comparison() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_error(&mut runtime, "failed", "cast");
+        let d2 = runtime.get_data_mut().add_number(10.into()).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.less_than().unwrap();
+
+        assert_eq!(runtime.get_data_mut().get_register(0), Some(d1));
+    }
+
+    #[test]
+    fn propagates_through_cast() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_error(&mut runtime, "failed", "cast");
+        let d2 = runtime.get_data_mut().add_type(GarnishDataType::Number).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.type_cast().unwrap();
+
+        assert_eq!(runtime.get_data_mut().get_register(0), Some(d1));
+    }
+
+    #[test]
+    fn access_message_and_code() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_error(&mut runtime, "failed", "cast");
+        let (message, code) = runtime.get_data_mut().get_error(d1).unwrap();
+
+        for (key, expected) in [("message", message), ("code", code)] {
+            let sym = runtime.get_data_mut().parse_add_symbol(key).unwrap();
+
+            runtime.get_data_mut().push_register(d1).unwrap();
+            runtime.get_data_mut().push_register(sym).unwrap();
+
+            runtime.access().unwrap();
+
+            assert_eq!(runtime.get_data_mut().pop_register().unwrap(), Some(expected));
+        }
+    }
+
+    #[test]
+    fn access_internals() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_error(&mut runtime, "failed", "cast");
+        let (message, code) = runtime.get_data_mut().get_error(d1).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.access_left_internal().unwrap();
+        assert_eq!(runtime.get_data_mut().pop_register().unwrap(), Some(message));
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.access_right_internal().unwrap();
+        assert_eq!(runtime.get_data_mut().pop_register().unwrap(), Some(code));
+    }
+
+    #[test]
+    fn errors_equal() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_error(&mut runtime, "failed", "cast");
+        let d2 = add_error(&mut runtime, "failed", "cast");
+        let d3 = add_error(&mut runtime, "failed", "access");
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+        runtime.equal().unwrap();
+
+        let i = runtime.get_data_mut().pop_register().unwrap().unwrap();
+        assert_eq!(runtime.get_data_mut().get_data_type(i).unwrap(), GarnishDataType::True);
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d3).unwrap();
+        runtime.equal().unwrap();
+
+        let i = runtime.get_data_mut().pop_register().unwrap().unwrap();
+        assert_eq!(runtime.get_data_mut().get_data_type(i).unwrap(), GarnishDataType::False);
+    }
+}
@@ -0,0 +1,240 @@
+#[cfg(test)]
+mod tests {
+    use crate::SimpleGarnishRuntime;
+    use crate::simple::testing_utilities::{add_char_list, add_integer_list, add_range, create_simple_runtime, slice_of_char_list};
+    use garnish_lang::simple::SimpleGarnishData;
+    use garnish_lang::{GarnishData, GarnishDataType, GarnishRuntime, Instruction};
+
+    fn list_numbers(runtime: &SimpleGarnishRuntime<SimpleGarnishData>, list: usize) -> Vec<i64> {
+        let len = runtime.get_data().get_list_len(list).unwrap();
+        (0..len)
+            .map(|i| {
+                let item = runtime.get_data().get_list_item(list, (i as i32).into()).unwrap().unwrap();
+                runtime.get_data().get_number(item).unwrap().as_integer().unwrap()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn start_iteration_keeps_range_to_visit_lazily() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_range(runtime.get_data_mut(), 1, 4);
+        let d2 = runtime.get_data_mut().add_unit().unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.start_iteration().unwrap();
+
+        assert_eq!(runtime.get_data().get_register_len(), 5);
+        assert_eq!(runtime.get_data().get_register(0), Some(d1));
+        assert_eq!(runtime.get_data().get_register(1), Some(d2));
+    }
+
+    #[test]
+    fn map_range_visits_each_number() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_range(runtime.get_data_mut(), 1, 4);
+        let d2 = add_integer_list(runtime.get_data_mut(), 5);
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.start_iteration().unwrap();
+        runtime.map().unwrap();
+
+        let result = runtime.get_data().get_register(0).unwrap();
+        assert_eq!(list_numbers(&runtime, result), vec![20, 30, 40, 50]);
+    }
+
+    #[test]
+    fn start_iteration_collects_char_list_slice() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = slice_of_char_list(runtime.get_data_mut(), "abcde", 1, 3);
+        let d2 = runtime.get_data_mut().add_unit().unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.start_iteration().unwrap();
+
+        let items = runtime.get_data().get_register(0).unwrap();
+        assert_eq!(runtime.get_data().get_list_len(items).unwrap(), 3);
+        let first = runtime.get_data().get_list_item(items, 0.into()).unwrap().unwrap();
+        assert_eq!(runtime.get_data().get_char(first).unwrap(), 'b');
+    }
+
+    #[test]
+    fn start_iteration_skips_next_instruction_for_unsupported_value() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        let d2 = runtime.get_data_mut().add_unit().unwrap();
+
+        runtime.get_data_mut().push_instruction(Instruction::StartIteration, None).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::Map, None).unwrap();
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        let next = runtime.start_iteration().unwrap();
+
+        assert_eq!(next, Some(2));
+        let i = runtime.get_data().get_register(0).unwrap();
+        assert_eq!(runtime.get_data().get_data_type(i).unwrap(), GarnishDataType::Unit);
+    }
+
+    #[test]
+    fn map_with_list_applies_without_jumping() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = runtime.get_data_mut().add_number(0.into()).unwrap();
+        let d2 = runtime.get_data_mut().add_number(1.into()).unwrap();
+        let mut list = runtime.get_data_mut().start_list(2).unwrap();
+        list = runtime.get_data_mut().add_to_list(list, d2).unwrap();
+        list = runtime.get_data_mut().add_to_list(list, d1).unwrap();
+        let items = runtime.get_data_mut().end_list(list).unwrap();
+        let applied = add_integer_list(runtime.get_data_mut(), 2);
+
+        runtime.get_data_mut().push_register(items).unwrap();
+        runtime.get_data_mut().push_register(applied).unwrap();
+
+        runtime.start_iteration().unwrap();
+        let next = runtime.map().unwrap();
+
+        assert_eq!(next, None);
+        assert_eq!(runtime.get_data().get_register_len(), 1);
+        let result = runtime.get_data().get_register(0).unwrap();
+        assert_eq!(list_numbers(&runtime, result), vec![20, 10]);
+    }
+
+    #[test]
+    fn map_with_expression_returns_to_itself() {
+        let mut runtime = create_simple_runtime();
+
+        runtime.get_data_mut().push_instruction(Instruction::StartIteration, None).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::Map, None).unwrap();
+        let start = runtime.get_data_mut().push_instruction(Instruction::PutValue, None).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::EndExpression, None).unwrap();
+        runtime.get_data_mut().push_to_jump_table(start).unwrap();
+
+        let items = add_integer_list(runtime.get_data_mut(), 2);
+        let expression = runtime.get_data_mut().add_expression(0).unwrap();
+
+        runtime.get_data_mut().push_register(items).unwrap();
+        runtime.get_data_mut().push_register(expression).unwrap();
+
+        runtime.start_iteration().unwrap();
+        runtime.get_data_mut().set_instruction_cursor(1).unwrap();
+        let next = runtime.map().unwrap();
+
+        assert_eq!(next, Some(start));
+        assert_eq!(runtime.get_data_mut().pop_frame().unwrap(), Some(1));
+        let value = runtime.get_data().get_current_value().unwrap();
+        assert_eq!(runtime.get_data().get_number(value).unwrap(), 10.into());
+    }
+
+    #[test]
+    fn any_stops_at_first_truthy_result() {
+        let mut runtime = create_simple_runtime();
+
+        let items = add_integer_list(runtime.get_data_mut(), 3);
+        let f = runtime.get_data_mut().add_false().unwrap();
+        let t = runtime.get_data_mut().add_true().unwrap();
+        let mut list = runtime.get_data_mut().start_list(2).unwrap();
+        list = runtime.get_data_mut().add_to_list(list, f).unwrap();
+        list = runtime.get_data_mut().add_to_list(list, t).unwrap();
+        let applied = runtime.get_data_mut().end_list(list).unwrap();
+
+        // applying the list to an item accesses its index, items are 10, 20 and 30 so the lookups fail
+        runtime.get_data_mut().push_register(items).unwrap();
+        runtime.get_data_mut().push_register(applied).unwrap();
+
+        runtime.start_iteration().unwrap();
+        runtime.any().unwrap();
+
+        let result = runtime.get_data().get_register(0).unwrap();
+        assert_eq!(runtime.get_data().get_data_type(result).unwrap(), GarnishDataType::False);
+    }
+
+    #[test]
+    fn sort_without_comparator_uses_total_order() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = runtime.get_data_mut().add_number(30.into()).unwrap();
+        let d2 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        let d3 = runtime.get_data_mut().add_number(20.into()).unwrap();
+        let mut list = runtime.get_data_mut().start_list(3).unwrap();
+        list = runtime.get_data_mut().add_to_list(list, d1).unwrap();
+        list = runtime.get_data_mut().add_to_list(list, d2).unwrap();
+        list = runtime.get_data_mut().add_to_list(list, d3).unwrap();
+        let items = runtime.get_data_mut().end_list(list).unwrap();
+        let comparator = runtime.get_data_mut().add_unit().unwrap();
+
+        runtime.get_data_mut().push_register(items).unwrap();
+        runtime.get_data_mut().push_register(comparator).unwrap();
+
+        runtime.start_iteration().unwrap();
+        let next = runtime.sort().unwrap();
+
+        assert_eq!(next, None);
+        assert_eq!(runtime.get_data().get_register_len(), 1);
+        let result = runtime.get_data().get_register(0).unwrap();
+        assert_eq!(list_numbers(&runtime, result), vec![10, 20, 30]);
+    }
+
+    #[test]
+    fn sort_orders_types_before_values() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_char_list(runtime.get_data_mut(), "b");
+        let d2 = runtime.get_data_mut().add_number(5.into()).unwrap();
+        let d3 = add_char_list(runtime.get_data_mut(), "a");
+        let mut list = runtime.get_data_mut().start_list(3).unwrap();
+        list = runtime.get_data_mut().add_to_list(list, d1).unwrap();
+        list = runtime.get_data_mut().add_to_list(list, d2).unwrap();
+        list = runtime.get_data_mut().add_to_list(list, d3).unwrap();
+        let items = runtime.get_data_mut().end_list(list).unwrap();
+        let comparator = runtime.get_data_mut().add_unit().unwrap();
+
+        runtime.get_data_mut().push_register(items).unwrap();
+        runtime.get_data_mut().push_register(comparator).unwrap();
+
+        runtime.start_iteration().unwrap();
+        runtime.sort().unwrap();
+
+        let result = runtime.get_data().get_register(0).unwrap();
+        let sorted = (0..3)
+            .map(|i| runtime.get_data().get_list_item(result, i.into()).unwrap().unwrap())
+            .collect::<Vec<_>>();
+        assert_eq!(sorted, vec![d2, d3, d1]);
+    }
+
+    #[test]
+    fn sort_with_expression_returns_to_itself() {
+        let mut runtime = create_simple_runtime();
+
+        runtime.get_data_mut().push_instruction(Instruction::StartIteration, None).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::Sort, None).unwrap();
+        let start = runtime.get_data_mut().push_instruction(Instruction::PutValue, None).unwrap();
+        runtime.get_data_mut().push_instruction(Instruction::EndExpression, None).unwrap();
+        runtime.get_data_mut().push_to_jump_table(start).unwrap();
+
+        let items = add_integer_list(runtime.get_data_mut(), 2);
+        let expression = runtime.get_data_mut().add_expression(0).unwrap();
+
+        runtime.get_data_mut().push_register(items).unwrap();
+        runtime.get_data_mut().push_register(expression).unwrap();
+
+        runtime.start_iteration().unwrap();
+        runtime.get_data_mut().set_instruction_cursor(1).unwrap();
+        let next = runtime.sort().unwrap();
+
+        // second item is compared against the first, already placed, item
+        assert_eq!(next, Some(start));
+        let value = runtime.get_data().get_current_value().unwrap();
+        assert_eq!(list_numbers(&runtime, value), vec![20, 10]);
+    }
+}
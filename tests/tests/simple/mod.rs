@@ -3,11 +3,14 @@ mod apply;
 mod arithmetic;
 mod bitwise;
 mod casting;
+mod catch;
 mod clone;
 mod comparison;
 mod concat;
 mod equality;
+mod error;
 mod jumps;
+mod iteration;
 mod list;
 mod logical;
 mod pair;
@@ -15,6 +18,7 @@ mod put;
 mod range;
 mod resolve;
 mod sideeffect;
+mod strict;
 
 #[cfg(test)]
 pub mod testing_utilities {
@@ -85,6 +89,12 @@ pub mod testing_utilities {
         return d3;
     }
 
+    pub fn add_stepped_range(runtime: &mut SimpleGarnishData, start: i32, end: i32, step: i32) -> usize {
+        let d1 = add_range(runtime, start, end);
+        let d2 = runtime.add_number(step.into()).unwrap();
+        runtime.add_range(d1, d2).unwrap()
+    }
+
     pub fn add_concatenation_with_start(runtime: &mut SimpleGarnishData, count: usize, start: i32) -> usize {
         let v = start as i32;
         let mut left = add_pair(runtime, format!("val{}", v).as_str(), v);
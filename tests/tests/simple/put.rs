@@ -107,4 +107,65 @@ mod tests {
 
         assert!(runtime.update_value().is_err());
     }
+
+    #[test]
+    fn update_local() {
+        let mut runtime = create_simple_runtime();
+
+        let i1 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        runtime.get_data_mut().push_register(i1).unwrap();
+
+        runtime.update_local(1).unwrap();
+
+        assert_eq!(runtime.get_data_mut().get_register_len(), 0);
+        assert_eq!(runtime.get_data_mut().get_local(1), Some(i1));
+        let unit = runtime.get_data_mut().get_local(0).unwrap();
+        assert_eq!(runtime.get_data_mut().get_data_type(unit).unwrap(), GarnishDataType::Unit);
+    }
+
+    #[test]
+    fn update_local_no_register_is_err() {
+        let mut runtime = create_simple_runtime();
+
+        assert!(runtime.update_local(0).is_err());
+    }
+
+    #[test]
+    fn put_local() {
+        let mut runtime = create_simple_runtime();
+
+        let i1 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        runtime.get_data_mut().set_local(0, i1).unwrap();
+
+        runtime.put_local(0).unwrap();
+
+        assert_eq!(runtime.get_data_mut().get_register(0).unwrap(), i1);
+    }
+
+    #[test]
+    fn put_local_is_unit_if_not_set() {
+        let mut runtime = create_simple_runtime();
+
+        runtime.put_local(0).unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data_mut().get_data_type(i).unwrap(), GarnishDataType::Unit);
+    }
+
+    #[test]
+    fn locals_belong_to_frame() {
+        let mut runtime = create_simple_runtime();
+
+        let i1 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        let i2 = runtime.get_data_mut().add_number(20.into()).unwrap();
+        runtime.get_data_mut().set_local(0, i1).unwrap();
+
+        runtime.get_data_mut().push_frame(5).unwrap();
+        assert_eq!(runtime.get_data_mut().get_local(0), None);
+        runtime.get_data_mut().set_local(0, i2).unwrap();
+        assert_eq!(runtime.get_data_mut().get_local(0), Some(i2));
+
+        runtime.get_data_mut().pop_frame().unwrap();
+        assert_eq!(runtime.get_data_mut().get_local(0), Some(i1));
+    }
 }
@@ -1,7 +1,7 @@
 #[cfg(test)]
 mod tests {
-    use crate::simple::testing_utilities::create_simple_runtime;
-    use garnish_lang::{GarnishData, GarnishRuntime};
+    use crate::simple::testing_utilities::{add_range, add_stepped_range, create_simple_runtime};
+    use garnish_lang::{GarnishData, GarnishDataType, GarnishRuntime};
 
     #[test]
     fn range() {
@@ -74,4 +74,112 @@ mod tests {
         assert_eq!(runtime.get_data_mut().get_number(start).unwrap(), 11.into());
         assert_eq!(runtime.get_data_mut().get_number(end).unwrap(), 19.into());
     }
+
+    #[test]
+    fn stepped() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_range(runtime.get_data_mut(), 10, 20);
+        let d2 = runtime.get_data_mut().add_number(2.into()).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.make_range().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data().get_range(i).unwrap(), (d1, d2));
+    }
+
+    #[test]
+    fn stepped_with_zero_step_is_unit() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_range(runtime.get_data_mut(), 10, 20);
+        let d2 = runtime.get_data_mut().add_number(0.into()).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.make_range().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data().get_data_type(i).unwrap(), GarnishDataType::Unit);
+    }
+
+    #[test]
+    fn exclusive_step_is_unit() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_range(runtime.get_data_mut(), 10, 20);
+        let d2 = runtime.get_data_mut().add_number(2.into()).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.make_end_exclusive_range().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data().get_data_type(i).unwrap(), GarnishDataType::Unit);
+    }
+
+    #[test]
+    fn length_of_stepped_range() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_stepped_range(runtime.get_data_mut(), 10, 20, 3);
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+
+        runtime.access_length_internal().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data().get_number(i).unwrap(), 4.into());
+    }
+
+    #[test]
+    fn length_of_stepped_range_away_from_end() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_stepped_range(runtime.get_data_mut(), 10, 20, -3);
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+
+        runtime.access_length_internal().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data().get_number(i).unwrap(), 0.into());
+    }
+
+    #[test]
+    fn stepped_ranges_with_same_items_are_equal() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_stepped_range(runtime.get_data_mut(), 10, 20, 3);
+        let d2 = add_stepped_range(runtime.get_data_mut(), 10, 19, 3);
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.equal().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data().get_data_type(i).unwrap(), GarnishDataType::True);
+    }
+
+    #[test]
+    fn stepped_ranges_with_different_steps_are_not_equal() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = add_stepped_range(runtime.get_data_mut(), 10, 20, 3);
+        let d2 = add_stepped_range(runtime.get_data_mut(), 10, 20, 2);
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.equal().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data().get_data_type(i).unwrap(), GarnishDataType::False);
+    }
 }
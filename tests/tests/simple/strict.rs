@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use crate::SimpleGarnishRuntime;
+    use crate::simple::testing_utilities::{add_char_list, add_integer_list, add_list, create_simple_runtime};
+    use garnish_lang::simple::SimpleGarnishData;
+    use garnish_lang::{ErrorType, GarnishData, GarnishDataType, GarnishRuntime};
+
+    fn create_strict_runtime() -> SimpleGarnishRuntime<SimpleGarnishData> {
+        let mut runtime = create_simple_runtime();
+        runtime.get_data_mut().set_strict_mode(true);
+        runtime
+    }
+
+    #[test]
+    fn unsupported_types_are_unit_by_default() {
+        let mut runtime = create_simple_runtime();
+
+        let d1 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        let d2 = runtime.get_data_mut().add_symbol(1).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        runtime.add().unwrap();
+
+        let i = runtime.get_data_mut().get_register(0).unwrap();
+        assert_eq!(runtime.get_data_mut().get_data_type(i).unwrap(), GarnishDataType::Unit);
+    }
+
+    #[test]
+    fn unsupported_types_raise() {
+        let mut runtime = create_strict_runtime();
+
+        let d1 = runtime.get_data_mut().add_number(10.into()).unwrap();
+        let d2 = runtime.get_data_mut().add_symbol(1).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        let err = runtime.add().unwrap_err();
+
+        assert_eq!(err.get_type(), ErrorType::Strict);
+        assert_eq!(err.get_message(), "Unsupported types for Add, Number and Symbol at instruction 0");
+    }
+
+    #[test]
+    fn unsupported_unary_type_raises() {
+        let mut runtime = create_strict_runtime();
+
+        let d1 = runtime.get_data_mut().add_symbol(1).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().set_instruction_cursor(0).unwrap();
+
+        let err = runtime.opposite().unwrap_err();
+
+        assert_eq!(err.get_message(), "Unsupported types for Opposite, Symbol at instruction 0");
+    }
+
+    #[test]
+    fn out_of_range_access_raises() {
+        let mut runtime = create_strict_runtime();
+
+        let d1 = add_integer_list(runtime.get_data_mut(), 3);
+        let d2 = runtime.get_data_mut().add_number(5.into()).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        let err = runtime.access().unwrap_err();
+
+        assert_eq!(err.get_message(), "No value found for Access of List with Number at instruction 0");
+    }
+
+    #[test]
+    fn missing_key_in_apply_raises() {
+        let mut runtime = create_strict_runtime();
+
+        let d1 = add_list(runtime.get_data_mut(), 3);
+        let d2 = runtime.get_data_mut().parse_add_symbol("missing").unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        let err = runtime.apply().unwrap_err();
+
+        assert_eq!(err.get_message(), "No value found for Apply of List with Symbol at instruction 0");
+    }
+
+    #[test]
+    fn unresolved_symbol_raises() {
+        let mut runtime = create_strict_runtime();
+
+        let d1 = runtime.get_data_mut().add_symbol(1).unwrap();
+
+        let err = runtime.resolve(d1).unwrap_err();
+
+        assert_eq!(err.get_message(), "No value found for Resolve of Symbol 1 at instruction 0");
+    }
+
+    #[test]
+    fn failed_cast_raises_over_error_value() {
+        let mut runtime = create_strict_runtime();
+        runtime.get_data_mut().set_error_values(true);
+
+        let d1 = add_char_list(runtime.get_data_mut(), "abc");
+        let d2 = runtime.get_data_mut().add_type(GarnishDataType::Number).unwrap();
+
+        runtime.get_data_mut().push_register(d1).unwrap();
+        runtime.get_data_mut().push_register(d2).unwrap();
+
+        let err = runtime.type_cast().unwrap_err();
+
+        assert_eq!(err.get_message(), "Could not cast CharList to Number at instruction 0");
+    }
+}
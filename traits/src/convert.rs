@@ -0,0 +1,427 @@
+//! Conversions between Rust values and values stored in any [`GarnishData`] implementation.
+
+use crate::{Extents, GarnishData, GarnishDataFactory, GarnishDataType, RuntimeError, TypeConstants};
+
+/// Conversion of a Rust value into a value added to a [`GarnishData`] instance.
+///
+/// Can be derived with `#[derive(IntoGarnish)]` when the `derive` feature is enabled.
+pub trait IntoGarnish<Data>
+where
+    Data: GarnishData,
+{
+    fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>>;
+}
+
+/// Conversion of a value in a [`GarnishData`] instance into a Rust value.
+///
+/// Can be derived with `#[derive(FromGarnish)]` when the `derive` feature is enabled.
+pub trait FromGarnish<Data>: Sized
+where
+    Data: GarnishData,
+{
+    fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>>;
+
+    /// Value used when an associative list does not contain this field. `None` means the field is required.
+    fn from_missing() -> Option<Self> {
+        None
+    }
+}
+
+/// Wrapper converting bytes to and from a [`GarnishDataType::ByteList`], instead of a list of [`GarnishDataType::Byte`] values.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct ByteList(pub Vec<u8>);
+
+fn whole<Data: GarnishData>() -> Extents<Data::Number> {
+    Extents::new(Data::Number::zero(), Data::Number::max_value())
+}
+
+/// Returns an error if the value at `addr` is not of the `expected` type.
+pub fn expect_type<Data: GarnishData>(data: &Data, addr: Data::Size, expected: GarnishDataType) -> Result<(), RuntimeError<Data::Error>> {
+    let got = data.get_data_type(addr)?;
+    if got != expected {
+        return Err(RuntimeError::new_message(format!("Expected {:?}, got {:?}", expected, got)));
+    }
+    Ok(())
+}
+
+/// Adds a list containing the given items.
+pub fn add_list<Data: GarnishData>(data: &mut Data, items: Vec<Data::Size>) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    let mut len = Data::Size::zero();
+    for _ in items.iter() {
+        len += Data::Size::one();
+    }
+
+    let mut list = data.start_list(len)?;
+    for item in items {
+        list = data.add_to_list(list, item)?;
+    }
+    Ok(data.end_list(list)?)
+}
+
+/// Adds a `:name = value` pair, for use as an item of an associative list.
+pub fn add_field<Data: GarnishData>(data: &mut Data, name: &str, value: Data::Size) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    let key = data.parse_add_symbol(name)?;
+    Ok(data.add_pair((key, value))?)
+}
+
+/// Adds the symbol `name`, paired with `payload` if given.
+pub fn add_variant<Data: GarnishData>(data: &mut Data, name: &str, payload: Option<Data::Size>) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    let symbol = data.parse_add_symbol(name)?;
+    match payload {
+        Some(payload) => Ok(data.add_pair((symbol, payload))?),
+        None => Ok(symbol),
+    }
+}
+
+/// Returns the addresses of all items in the list at `addr`.
+pub fn read_list_items<Data: GarnishData>(data: &Data, addr: Data::Size) -> Result<Vec<Data::Size>, RuntimeError<Data::Error>> {
+    expect_type(data, addr.clone(), GarnishDataType::List)?;
+    Ok(data.get_list_item_iter(addr, whole::<Data>())?.collect())
+}
+
+/// Reads the item at `position` in the list at `addr`.
+pub fn read_item<Data, V>(data: &Data, addr: Data::Size, position: usize) -> Result<V, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    V: FromGarnish<Data>,
+{
+    match read_list_items(data, addr)?.get(position) {
+        Some(item) => V::from_garnish(data, item.clone()),
+        None => Err(RuntimeError::new_message(format!("Missing list item {}", position))),
+    }
+}
+
+/// Reads the value associated with `name` in the list at `addr`, using [`FromGarnish::from_missing`] when there is no association.
+pub fn read_field<Data, V>(data: &Data, addr: Data::Size, name: &str) -> Result<V, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    V: FromGarnish<Data>,
+{
+    expect_type(data, addr.clone(), GarnishDataType::List)?;
+    let symbol = Data::DataFactory::parse_symbol(name)?;
+    match data.get_list_item_with_symbol(addr, symbol)? {
+        Some(item) => V::from_garnish(data, item),
+        None => V::from_missing().ok_or_else(|| RuntimeError::new_message(format!("Missing field '{}'", name))),
+    }
+}
+
+/// Symbol and optional payload of an enum variant value.
+pub type Variant<Data> = (<Data as GarnishData>::Symbol, Option<<Data as GarnishData>::Size>);
+
+/// Returns the symbol and payload of a value added with [`add_variant`].
+pub fn read_variant<Data: GarnishData>(data: &Data, addr: Data::Size) -> Result<Variant<Data>, RuntimeError<Data::Error>> {
+    match data.get_data_type(addr.clone())? {
+        GarnishDataType::Symbol => Ok((data.get_symbol(addr)?, None)),
+        GarnishDataType::Pair => {
+            let (left, right) = data.get_pair(addr)?;
+            expect_type(data, left.clone(), GarnishDataType::Symbol)?;
+            Ok((data.get_symbol(left)?, Some(right)))
+        }
+        got => Err(RuntimeError::new_message(format!("Expected Symbol or Pair, got {:?}", got))),
+    }
+}
+
+/// Returns true if `symbol` is the symbol for `name`.
+pub fn is_variant<Data: GarnishData>(symbol: &Data::Symbol, name: &str) -> Result<bool, RuntimeError<Data::Error>> {
+    Ok(*symbol == Data::DataFactory::parse_symbol(name)?)
+}
+
+/// Returns the payload read by [`read_variant`], failing for variants without one.
+pub fn variant_payload<Data: GarnishData>(payload: Option<Data::Size>) -> Result<Data::Size, RuntimeError<Data::Error>> {
+    payload.ok_or_else(|| RuntimeError::new("Expected variant with data, got Symbol"))
+}
+
+/// Error for a symbol that does not match any variant.
+pub fn unknown_variant<Data: GarnishData>(symbol: &Data::Symbol) -> RuntimeError<Data::Error> {
+    RuntimeError::new_message(format!("Unknown variant {}", symbol))
+}
+
+impl<Data> IntoGarnish<Data> for ()
+where
+    Data: GarnishData,
+{
+    fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+        Ok(data.add_unit()?)
+    }
+}
+
+impl<Data> FromGarnish<Data> for ()
+where
+    Data: GarnishData,
+{
+    fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>> {
+        expect_type(data, addr, GarnishDataType::Unit)
+    }
+}
+
+impl<Data> IntoGarnish<Data> for bool
+where
+    Data: GarnishData,
+{
+    fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+        match self {
+            true => Ok(data.add_true()?),
+            false => Ok(data.add_false()?),
+        }
+    }
+}
+
+impl<Data> FromGarnish<Data> for bool
+where
+    Data: GarnishData,
+{
+    fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>> {
+        match data.get_data_type(addr)? {
+            GarnishDataType::True => Ok(true),
+            GarnishDataType::False => Ok(false),
+            got => Err(RuntimeError::new_message(format!("Expected True or False, got {:?}", got))),
+        }
+    }
+}
+
+macro_rules! number_conversions {
+    ($($t:ty),*) => {
+        $(
+            impl<Data> IntoGarnish<Data> for $t
+            where
+                Data: GarnishData,
+                Data::Number: From<$t>,
+            {
+                fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+                    Ok(data.add_number(Data::Number::from(self))?)
+                }
+            }
+
+            impl<Data> FromGarnish<Data> for $t
+            where
+                Data: GarnishData,
+                $t: From<Data::Number>,
+            {
+                fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>> {
+                    expect_type(data, addr.clone(), GarnishDataType::Number)?;
+                    Ok(<$t>::from(data.get_number(addr)?))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! integer_conversions {
+    ($($t:ty),*) => {
+        $(
+            impl<Data> IntoGarnish<Data> for $t
+            where
+                Data: GarnishData,
+                Data::Number: From<$t>,
+            {
+                fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+                    Ok(data.add_number(Data::Number::from(self))?)
+                }
+            }
+
+            impl<Data> FromGarnish<Data> for $t
+            where
+                Data: GarnishData,
+                i64: From<Data::Number>,
+                f64: From<Data::Number>,
+            {
+                fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>> {
+                    expect_type(data, addr.clone(), GarnishDataType::Number)?;
+                    let integer = checked_integer::<Data>(data.get_number(addr)?)?;
+                    <$t>::try_from(integer)
+                        .map_err(|_| RuntimeError::new_message(format!("Number {} is out of range for {}", integer, stringify!($t))))
+                }
+            }
+        )*
+    };
+}
+
+// reads a number as a whole i64, rejecting fractional values and values an i64 can't hold instead of truncating or wrapping
+fn checked_integer<Data>(number: Data::Number) -> Result<i64, RuntimeError<Data::Error>>
+where
+    Data: GarnishData,
+    i64: From<Data::Number>,
+    f64: From<Data::Number>,
+{
+    let float = f64::from(number.clone());
+    if float.fract() != 0.0 || float.is_nan() {
+        return Err(RuntimeError::new_message(format!("Expected integer Number, got {}", float)));
+    }
+
+    let integer = i64::from(number);
+    match integer as f64 == float {
+        true => Ok(integer),
+        false => Err(RuntimeError::new_message(format!("Number {} is out of range for i64", float))),
+    }
+}
+
+integer_conversions!(i8, i16, i32, i64, u16, u32, u64, isize, usize);
+number_conversions!(f32, f64);
+
+impl<Data> IntoGarnish<Data> for char
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
+    fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+        Ok(data.add_char(Data::Char::from(self))?)
+    }
+}
+
+impl<Data> FromGarnish<Data> for char
+where
+    Data: GarnishData,
+    char: From<Data::Char>,
+{
+    fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>> {
+        expect_type(data, addr.clone(), GarnishDataType::Char)?;
+        Ok(char::from(data.get_char(addr)?))
+    }
+}
+
+impl<Data> IntoGarnish<Data> for u8
+where
+    Data: GarnishData,
+    Data::Byte: From<u8>,
+{
+    fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+        Ok(data.add_byte(Data::Byte::from(self))?)
+    }
+}
+
+impl<Data> FromGarnish<Data> for u8
+where
+    Data: GarnishData,
+    u8: From<Data::Byte>,
+{
+    fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>> {
+        expect_type(data, addr.clone(), GarnishDataType::Byte)?;
+        Ok(u8::from(data.get_byte(addr)?))
+    }
+}
+
+impl<Data> IntoGarnish<Data> for String
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
+    fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+        self.as_str().into_garnish(data)
+    }
+}
+
+impl<Data> IntoGarnish<Data> for &str
+where
+    Data: GarnishData,
+    Data::Char: From<char>,
+{
+    fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+        Ok(data.add_char_list(self.chars().map(Data::Char::from).collect())?)
+    }
+}
+
+impl<Data> FromGarnish<Data> for String
+where
+    Data: GarnishData,
+    char: From<Data::Char>,
+{
+    fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>> {
+        expect_type(data, addr.clone(), GarnishDataType::CharList)?;
+        Ok(data.get_char_list_iter(addr, whole::<Data>())?.map(char::from).collect())
+    }
+}
+
+impl<Data> IntoGarnish<Data> for ByteList
+where
+    Data: GarnishData,
+    Data::Byte: From<u8>,
+{
+    fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+        Ok(data.add_byte_list(self.0.into_iter().map(Data::Byte::from).collect())?)
+    }
+}
+
+impl<Data> FromGarnish<Data> for ByteList
+where
+    Data: GarnishData,
+    u8: From<Data::Byte>,
+{
+    fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>> {
+        expect_type(data, addr.clone(), GarnishDataType::ByteList)?;
+        Ok(ByteList(data.get_byte_list_iter(addr, whole::<Data>())?.map(u8::from).collect()))
+    }
+}
+
+impl<Data, V> IntoGarnish<Data> for Option<V>
+where
+    Data: GarnishData,
+    V: IntoGarnish<Data>,
+{
+    fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+        match self {
+            Some(value) => value.into_garnish(data),
+            None => Ok(data.add_unit()?),
+        }
+    }
+}
+
+impl<Data, V> FromGarnish<Data> for Option<V>
+where
+    Data: GarnishData,
+    V: FromGarnish<Data>,
+{
+    fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>> {
+        match data.get_data_type(addr.clone())? {
+            GarnishDataType::Unit => Ok(None),
+            _ => Ok(Some(V::from_garnish(data, addr)?)),
+        }
+    }
+
+    fn from_missing() -> Option<Self> {
+        Some(None)
+    }
+}
+
+impl<Data, V> IntoGarnish<Data> for Vec<V>
+where
+    Data: GarnishData,
+    V: IntoGarnish<Data>,
+{
+    fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+        let items = self
+            .into_iter()
+            .map(|item| item.into_garnish(data))
+            .collect::<Result<Vec<Data::Size>, RuntimeError<Data::Error>>>()?;
+        add_list(data, items)
+    }
+}
+
+impl<Data, V> FromGarnish<Data> for Vec<V>
+where
+    Data: GarnishData,
+    V: FromGarnish<Data>,
+{
+    fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>> {
+        read_list_items(data, addr)?.into_iter().map(|item| V::from_garnish(data, item)).collect()
+    }
+}
+
+impl<Data, V> IntoGarnish<Data> for Box<V>
+where
+    Data: GarnishData,
+    V: IntoGarnish<Data>,
+{
+    fn into_garnish(self, data: &mut Data) -> Result<Data::Size, RuntimeError<Data::Error>> {
+        (*self).into_garnish(data)
+    }
+}
+
+impl<Data, V> FromGarnish<Data> for Box<V>
+where
+    Data: GarnishData,
+    V: FromGarnish<Data>,
+{
+    fn from_garnish(data: &Data, addr: Data::Size) -> Result<Self, RuntimeError<Data::Error>> {
+        Ok(Box::new(V::from_garnish(data, addr)?))
+    }
+}
@@ -1,6 +1,7 @@
 use crate::Instruction;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::fmt::{Debug, Display, Formatter};
 use std::ops::{Add, AddAssign, Sub, SubAssign};
 
@@ -29,6 +30,8 @@ pub enum GarnishDataType {
     True,
     False,
     Custom,
+    Closure,
+    Error,
 }
 
 impl Display for GarnishDataType {
@@ -71,6 +74,14 @@ pub enum SymbolListPart<A, B> {
     Number(B)
 }
 
+/// Addresses of the parts of a range, step is None for ranges created without one.
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct SteppedRange<T> {
+    pub start: T,
+    pub end: T,
+    pub step: Option<T>,
+}
+
 #[derive(Clone, PartialEq, PartialOrd, Debug)]
 pub struct Extents<T> where T: Clone + PartialOrd + PartialEq + Debug {
     start: T,
@@ -162,13 +173,32 @@ pub trait GarnishData {
     fn get_char(&self, addr: Self::Size) -> Result<Self::Char, Self::Error>;
     fn get_byte(&self, addr: Self::Size) -> Result<Self::Byte, Self::Error>;
     fn get_symbol(&self, addr: Self::Size) -> Result<Self::Symbol, Self::Error>;
+    // orders symbols by their names without adding data, symbols without a name come after named ones
+    fn cmp_symbol_names(&self, left: Self::Symbol, right: Self::Symbol) -> Result<Ordering, Self::Error>;
     fn get_expression(&self, addr: Self::Size) -> Result<Self::Size, Self::Error>;
     fn get_external(&self, addr: Self::Size) -> Result<Self::Size, Self::Error>;
     fn get_pair(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error>;
     fn get_concatenation(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error>;
     fn get_range(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error>;
+    // stepped ranges are stored as a range whose start is the contiguous range and whose end is the step
+    fn get_stepped_range(&self, addr: Self::Size) -> Result<SteppedRange<Self::Size>, Self::Error> {
+        let (start, end) = self.get_range(addr)?;
+        match self.get_data_type(start.clone())? {
+            GarnishDataType::Range => {
+                let (inner_start, inner_end) = self.get_range(start)?;
+                Ok(SteppedRange {
+                    start: inner_start,
+                    end: inner_end,
+                    step: Some(end),
+                })
+            }
+            _ => Ok(SteppedRange { start, end, step: None }),
+        }
+    }
     fn get_slice(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error>;
     fn get_partial(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error>;
+    fn get_closure(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error>;
+    fn get_error(&self, addr: Self::Size) -> Result<(Self::Size, Self::Size), Self::Error>;
 
     fn get_list_len(&self, addr: Self::Size) -> Result<Self::Size, Self::Error>;
     fn get_list_item(&self, list_addr: Self::Size, item_addr: Self::Number) -> Result<Option<Self::Size>, Self::Error>;
@@ -205,6 +235,12 @@ pub trait GarnishData {
     fn add_range(&mut self, start: Self::Size, end: Self::Size) -> Result<Self::Size, Self::Error>;
     fn add_slice(&mut self, list: Self::Size, range: Self::Size) -> Result<Self::Size, Self::Error>;
     fn add_partial(&mut self, reciever: Self::Size, input: Self::Size) -> Result<Self::Size, Self::Error>;
+    // captures is a list whose items are set to the expression's local slots in order when applied
+    fn add_closure(&mut self, expression: Self::Size, captures: Self::Size) -> Result<Self::Size, Self::Error>;
+    // message is a char list describing the failure, code is a symbol naming its kind
+    fn add_error(&mut self, message: Self::Size, code: Self::Size) -> Result<Self::Size, Self::Error>;
+    fn add_char_list(&mut self, chars: Vec<Self::Char>) -> Result<Self::Size, Self::Error>;
+    fn add_byte_list(&mut self, bytes: Vec<Self::Byte>) -> Result<Self::Size, Self::Error>;
 
     fn merge_to_symbol_list(&mut self, first: Self::Size, second: Self::Size) -> Result<Self::Size, Self::Error>;
 
@@ -224,6 +260,10 @@ pub trait GarnishData {
 
     fn get_instruction_cursor(&self) -> Self::Size;
     fn set_instruction_cursor(&mut self, addr: Self::Size) -> Result<(), Self::Error>;
+    // called by the runtime after moving to the next instruction, when it holds no data indexes, so data may be moved here (e.g. by a garbage collector)
+    fn safe_point(&mut self) -> Result<(), Self::Error> {
+        Ok(())
+    }
 
     fn get_jump_table_len(&self) -> Self::Size;
     fn push_to_jump_table(&mut self, index: Self::Size) -> Result<(), Self::Error>;
@@ -233,6 +273,16 @@ pub trait GarnishData {
     fn push_frame(&mut self, index: Self::Size) -> Result<(), Self::Error>;
     fn pop_frame(&mut self) -> Result<Option<Self::Size>, Self::Error>;
 
+    // local slots belong to the current frame, unset slots are None
+    fn get_local(&self, index: Self::Size) -> Option<Self::Size>;
+    fn set_local(&mut self, index: Self::Size, addr: Self::Size) -> Result<(), Self::Error>;
+
+    // handlers record the value stack, registers, frames and locals at the time they're pushed, index is the jump table index of the catch block
+    fn push_handler(&mut self, index: Self::Size) -> Result<(), Self::Error>;
+    fn pop_handler(&mut self) -> Result<Option<Self::Size>, Self::Error>;
+    // removes the latest handler after restoring the state it recorded
+    fn unwind_handler(&mut self) -> Result<Option<Self::Size>, Self::Error>;
+
     // mut conversions
     fn add_char_list_from(&mut self, from: Self::Size) -> Result<Self::Size, Self::Error>;
     fn add_byte_list_from(&mut self, from: Self::Size) -> Result<Self::Size, Self::Error>;
@@ -264,6 +314,59 @@ pub trait GarnishData {
 
     // Execution checks
 
+    /// Checked when a cast or access fails.
+    ///
+    /// Return true to have the runtime produce a [`GarnishDataType::Error`] describing the failure.
+    ///
+    /// Return false to produce [`GarnishDataType::Unit`] instead.
+    ///
+    fn error_values(&self) -> bool {
+        false
+    }
+
+    /// Checked when an operation would produce [`GarnishDataType::Unit`] because its operands are unsupported or have no value.
+    ///
+    /// Return true to have the runtime raise an error naming the operation, operand types and instruction address.
+    ///
+    fn strict_mode(&self) -> bool {
+        false
+    }
+
+    /// Called during arithmetic and bitwise instructions when both operands are [`GarnishDataType::Number`].
+    ///
+    /// Defaults to the matching [`GarnishNumber`] operation, override to apply settings of the data to number operations, such as how overflow is handled.
+    ///
+    /// Return None when the operation has no result.
+    ///
+    fn number_op(&self, operation: Instruction, left: Self::Number, right: Self::Number) -> Option<Self::Number> {
+        match operation {
+            Instruction::Add => left.plus(right),
+            Instruction::Subtract => left.subtract(right),
+            Instruction::Multiply => left.multiply(right),
+            Instruction::Divide => left.divide(right),
+            Instruction::IntegerDivide => left.integer_divide(right),
+            Instruction::Power => left.power(right),
+            Instruction::Remainder => left.remainder(right),
+            Instruction::BitwiseAnd => left.bitwise_and(right),
+            Instruction::BitwiseOr => left.bitwise_or(right),
+            Instruction::BitwiseXor => left.bitwise_xor(right),
+            Instruction::BitwiseShiftLeft => left.bitwise_shift_left(right),
+            Instruction::BitwiseShiftRight => left.bitwise_shift_right(right),
+            _ => None,
+        }
+    }
+
+    /// Called during unary arithmetic and bitwise instructions when the operand is a [`GarnishDataType::Number`]. See [`GarnishData::number_op`].
+    ///
+    fn unary_number_op(&self, operation: Instruction, value: Self::Number) -> Option<Self::Number> {
+        match operation {
+            Instruction::AbsoluteValue => value.absolute_value(),
+            Instruction::Opposite => value.opposite(),
+            Instruction::BitwiseNot => value.bitwise_not(),
+            _ => None,
+        }
+    }
+
     /// Called during a [`Instruction::Resolve`], to convert a [`GarnishDataType::Symbol`] to a value.
     ///
     /// Return Ok(true) to tell the runtime that the symbol was resolved
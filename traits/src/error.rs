@@ -9,6 +9,8 @@ pub enum ErrorType {
     Unknown,
     /// Code used to determine if an operation should be deferred to [`crate::GarnishContext`].
     UnsupportedOpTypes,
+    /// Raised in strict mode by operations that would otherwise produce Unit.
+    Strict,
 }
 
 /// Error implementation for [`crate::GarnishRuntime`] instruction methods.
@@ -44,6 +46,14 @@ impl<Source: 'static + std::error::Error> RuntimeError<Source> {
         }
     }
 
+    pub fn strict(message: String) -> Self {
+        RuntimeError {
+            code: ErrorType::Strict,
+            message,
+            source: None,
+        }
+    }
+
     pub fn get_message(&self) -> &String { &self.message }
 
     pub fn get_type(&self) -> ErrorType {
@@ -58,6 +58,8 @@ pub trait GarnishCloneHandler<Data: GarnishData> {
             GarnishDataType::True => self.clone_true(addr, from, to),
             GarnishDataType::False => self.clone_false(addr, from, to),
             GarnishDataType::Custom => self.clone_custom(addr, from, to),
+            GarnishDataType::Closure => self.clone_closure(addr, from, to),
+            GarnishDataType::Error => self.clone_error(addr, from, to),
         }
     }
 
@@ -151,6 +153,22 @@ pub trait GarnishCloneHandler<Data: GarnishData> {
         })
     }
 
+    fn clone_closure(&mut self, addr: Data::Size, from: &Data, to: &mut Data) -> Result<Data::Size, Data::Error> {
+        from.get_closure(addr.clone()).and_then(|(expression, captures)| {
+            let to_expression = self.clone_data(expression, from, to)?;
+            let to_captures = self.clone_data(captures, from, to)?;
+            to.add_closure(to_expression, to_captures)
+        })
+    }
+
+    fn clone_error(&mut self, addr: Data::Size, from: &Data, to: &mut Data) -> Result<Data::Size, Data::Error> {
+        from.get_error(addr.clone()).and_then(|(message, code)| {
+            let to_message = self.clone_data(message, from, to)?;
+            let to_code = self.clone_data(code, from, to)?;
+            to.add_error(to_message, to_code)
+        })
+    }
+
     fn clone_range(&mut self, addr: Data::Size, from: &Data, to: &mut Data) -> Result<Data::Size, Data::Error> {
         from.get_range(addr.clone()).and_then(|(left, right)| {
             let to_left = self.clone_data(left, from, to)?;
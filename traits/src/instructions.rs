@@ -61,4 +61,18 @@ pub enum Instruction {
     MakeEndExclusiveRange,
     MakeExclusiveRange,
     Concat,
+    PutLocal,
+    UpdateLocal,
+    MakeClosure,
+    StartTry,
+    EndTry,
+    EndCatch,
+    StartIteration,
+    Map,
+    Filter,
+    Fold,
+    Any,
+    All,
+    Find,
+    Sort,
 }
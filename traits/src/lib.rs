@@ -1,12 +1,16 @@
 //! This crate contains interfaces and helper functions used by the Garnish Core libraries.
 //!
 
+pub mod convert;
 mod data;
 mod error;
 pub mod helpers;
 mod instructions;
 mod runtime;
 
+pub use convert::{ByteList, FromGarnish, IntoGarnish};
+#[cfg(feature = "derive")]
+pub use garnish_lang_derive::{FromGarnish, IntoGarnish};
 pub use data::*;
 pub use error::*;
 pub use instructions::*;
@@ -52,6 +52,7 @@ pub trait GarnishRuntime<Data: GarnishData> {
     fn end_expression(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
 
     fn make_list(&mut self, len: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn make_closure(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
     fn access(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
     fn access_left_internal(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
     fn access_right_internal(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
@@ -70,9 +71,24 @@ pub trait GarnishRuntime<Data: GarnishData> {
     fn put_value(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
     fn push_value(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
     fn update_value(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn put_local(&mut self, index: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn update_local(&mut self, index: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
 
     fn start_side_effect(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
     fn end_side_effect(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
 
+    fn start_try(&mut self, index: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn end_try(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn end_catch(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+
+    fn start_iteration(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn map(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn filter(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn fold(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn any(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn all(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn find(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+    fn sort(&mut self) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
+
     fn resolve(&mut self, data: Data::Size) -> Result<Option<Data::Size>, RuntimeError<Data::Error>>;
 }